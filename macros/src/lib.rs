@@ -0,0 +1,132 @@
+//! Compile-time manifest inclusion.
+//!
+//! The expansion refers to `lib_plugin_manifest::StaticManifest`, so crates
+//! using `include_manifest!` depend on both crates.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use lib_plugin_manifest::PluginManifest;
+use quote::quote;
+use syn::{parse_macro_input, LitStr};
+
+/// Include a plugin.toml at compile time as a `StaticManifest`.
+///
+/// The path is relative to the crate root (`CARGO_MANIFEST_DIR`). The
+/// manifest is parsed and validated during compilation, so a broken
+/// manifest fails the build instead of the plugin at runtime.
+///
+/// ```ignore
+/// const MANIFEST: lib_plugin_manifest::StaticManifest =
+///     lib_plugin_manifest_macros::include_manifest!("plugin.toml");
+/// ```
+#[proc_macro]
+pub fn include_manifest(input: TokenStream) -> TokenStream {
+    let lit = parse_macro_input!(input as LitStr);
+
+    let root = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".to_string());
+    let path = std::path::Path::new(&root).join(lit.value());
+
+    let content = match std::fs::read_to_string(&path) {
+        Ok(c) => c,
+        Err(e) => {
+            let msg = format!("cannot read {}: {e}", path.display());
+            return syn::Error::new(lit.span(), msg).to_compile_error().into();
+        }
+    };
+
+    match expand(&path.to_string_lossy(), &content) {
+        Ok(tokens) => tokens.into(),
+        Err(msg) => syn::Error::new(lit.span(), msg).to_compile_error().into(),
+    }
+}
+
+/// Parse and validate a manifest, producing the `StaticManifest` expression.
+///
+/// The manifest goes through the same parser and validation as at runtime,
+/// so whatever `PluginManifest::from_toml` and `validate()` reject fails
+/// the build.
+fn expand(path: &str, content: &str) -> Result<TokenStream2, String> {
+    let doc: toml::Table = toml::from_str(content).map_err(|e| format!("invalid TOML: {e}"))?;
+    if !doc.contains_key("plugin") {
+        return Err("manifest has no [plugin] section (packages can't be included)".into());
+    }
+
+    let manifest = PluginManifest::from_toml(content).map_err(|e| e.to_string())?;
+    let errors: Vec<String> = manifest
+        .validate()
+        .iter()
+        .filter(|issue| issue.is_error())
+        .map(ToString::to_string)
+        .collect();
+    if !errors.is_empty() {
+        return Err(errors.join("; "));
+    }
+
+    let plugin = &manifest.plugin;
+    let (id, name, version) = (&plugin.id, &plugin.name, &plugin.version);
+    let (author, description) = (&plugin.author, &plugin.description);
+    let plugin_type = plugin.plugin_type.as_str();
+    let api_version = manifest.compatibility.api_version;
+
+    Ok(quote! {
+        {
+            // Rebuild when the manifest changes
+            const _: &str = include_str!(#path);
+            ::lib_plugin_manifest::StaticManifest {
+                id: #id,
+                name: #name,
+                version: #version,
+                plugin_type: #plugin_type,
+                author: #author,
+                description: #description,
+                api_version: #api_version,
+                toml: #content,
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOML: &str = r#"
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.0.0"
+type = "extension"
+
+[binary]
+name = "tool"
+"#;
+
+    #[test]
+    fn test_expand_valid() {
+        let toml = format!("{TOML}\n[compatibility]\napi_version = 3\n");
+        let tokens = expand("plugin.toml", &toml).unwrap().to_string();
+        assert!(tokens.contains("StaticManifest"));
+        assert!(tokens.contains("\"acme.tool\""));
+        assert!(tokens.contains("3u32"));
+    }
+
+    #[test]
+    fn test_expand_defaults_api_version() {
+        let tokens = expand("plugin.toml", TOML).unwrap().to_string();
+        let expected = format!("{}u32", lib_plugin_manifest::DEFAULT_API_VERSION);
+        assert!(tokens.contains(&expected));
+    }
+
+    #[test]
+    fn test_expand_rejects_invalid() {
+        assert!(expand("plugin.toml", "[package]\nid = \"a.b\"").is_err());
+        let bad_version = TOML.replace("1.0.0", "one");
+        assert!(expand("plugin.toml", &bad_version).is_err());
+        let bad_id = TOML.replace("acme.tool", "tool");
+        assert!(expand("plugin.toml", &bad_id).is_err());
+        let bad_id = TOML.replace("acme.tool", "acme..tool");
+        assert!(expand("plugin.toml", &bad_id).is_err());
+        let bad_type = TOML.replace("name = \"tool\"", "name = \"tool\"\nchecksums = 5");
+        assert!(expand("plugin.toml", &bad_type).is_err());
+    }
+}
@@ -0,0 +1,258 @@
+//! System ABI requirements of prebuilt binaries (`[binary.abi_requirements]`).
+//!
+//! A dylib built on a new system may not load on an older one. Plugins
+//! declare the oldest system their binaries support, and installers check
+//! it against the host before downloading anything:
+//!
+//! ```toml
+//! [binary.abi_requirements]
+//! glibc = "2.28"          # Linux: minimum glibc
+//! macos = "11.0"          # macOS: deployment target
+//! msvc_runtime = "14.30"  # Windows: minimum MSVC runtime (VS 2022)
+//! ```
+//!
+//! Each requirement only applies on its own OS. A host that doesn't report
+//! a value an applicable requirement needs (e.g., a musl system and
+//! `glibc`) fails the check.
+
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::platform::Platform;
+use crate::plugin::PluginManifest;
+use crate::validate::ValidationIssue;
+
+/// Minimum system library versions a binary needs.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct AbiRequirements {
+    /// Minimum glibc version on Linux (e.g., "2.28")
+    #[serde(default)]
+    pub glibc: Option<String>,
+
+    /// Minimum macOS version the binary was built for (e.g., "11.0")
+    #[serde(default)]
+    pub macos: Option<String>,
+
+    /// Minimum MSVC runtime version on Windows (e.g., "14.30")
+    #[serde(default)]
+    pub msvc_runtime: Option<String>,
+}
+
+/// What the host provides, reported by the host itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostEnvironment {
+    /// Host platform
+    pub platform: Platform,
+
+    /// glibc version (None on non-glibc systems)
+    pub glibc: Option<String>,
+
+    /// macOS version
+    pub macos: Option<String>,
+
+    /// Installed MSVC runtime version
+    pub msvc_runtime: Option<String>,
+}
+
+impl HostEnvironment {
+    /// A host on `platform` that reports no library versions.
+    pub fn new(platform: Platform) -> Self {
+        Self {
+            platform,
+            glibc: None,
+            macos: None,
+            msvc_runtime: None,
+        }
+    }
+
+    /// Builder-style glibc version.
+    pub fn glibc(mut self, version: impl Into<String>) -> Self {
+        self.glibc = Some(version.into());
+        self
+    }
+
+    /// Builder-style macOS version.
+    pub fn macos(mut self, version: impl Into<String>) -> Self {
+        self.macos = Some(version.into());
+        self
+    }
+
+    /// Builder-style MSVC runtime version.
+    pub fn msvc_runtime(mut self, version: impl Into<String>) -> Self {
+        self.msvc_runtime = Some(version.into());
+        self
+    }
+}
+
+/// An ABI requirement the host doesn't meet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AbiMismatch {
+    /// Requirement key (e.g., "glibc")
+    pub requirement: &'static str,
+
+    /// Minimum version required
+    pub required: String,
+
+    /// Version the host reported, if any
+    pub found: Option<String>,
+}
+
+impl fmt::Display for AbiMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.found {
+            Some(found) => write!(
+                f,
+                "{} {} or newer required, host has {found}",
+                self.requirement, self.required
+            ),
+            None => write!(
+                f,
+                "{} {} or newer required, host has none",
+                self.requirement, self.required
+            ),
+        }
+    }
+}
+
+impl AbiRequirements {
+    /// Requirements (key, OS, required, host value) applicable to `env`.
+    fn applicable<'a>(
+        &'a self,
+        env: &'a HostEnvironment,
+    ) -> impl Iterator<Item = (&'static str, &'a String, Option<&'a String>)> {
+        [
+            ("glibc", "linux", &self.glibc, &env.glibc),
+            ("macos", "darwin", &self.macos, &env.macos),
+            (
+                "msvc_runtime",
+                "windows",
+                &self.msvc_runtime,
+                &env.msvc_runtime,
+            ),
+        ]
+        .into_iter()
+        .filter(move |(_, os, _, _)| env.platform.os == *os)
+        .filter_map(|(key, _, required, found)| Some((key, required.as_ref()?, found.as_ref())))
+    }
+
+    /// Requirements `env` doesn't meet.
+    pub fn check(&self, env: &HostEnvironment) -> Vec<AbiMismatch> {
+        self.applicable(env)
+            .filter(|(_, required, found)| {
+                !found.is_some_and(|found| {
+                    compare_versions(found, required).is_some_and(|o| o != Ordering::Less)
+                })
+            })
+            .map(|(requirement, required, found)| AbiMismatch {
+                requirement,
+                required: required.clone(),
+                found: found.cloned(),
+            })
+            .collect()
+    }
+
+    /// Check that every declared version is a dotted number.
+    pub(crate) fn validate(&self) -> Vec<ValidationIssue> {
+        [
+            ("glibc", &self.glibc),
+            ("macos", &self.macos),
+            ("msvc_runtime", &self.msvc_runtime),
+        ]
+        .into_iter()
+        .filter_map(|(key, version)| {
+            let version = version.as_ref()?;
+            parse_dotted(version).is_none().then(|| {
+                ValidationIssue::error(
+                    format!("binary.abi_requirements.{key}"),
+                    format!("'{version}' is not a dotted version (e.g., \"2.28\")"),
+                )
+            })
+        })
+        .collect()
+    }
+}
+
+impl PluginManifest {
+    /// ABI requirements of the binary that `env` doesn't meet.
+    pub fn check_abi(&self, env: &HostEnvironment) -> Vec<AbiMismatch> {
+        self.binary
+            .abi_requirements
+            .as_ref()
+            .map(|abi| abi.check(env))
+            .unwrap_or_default()
+    }
+}
+
+fn parse_dotted(version: &str) -> Option<Vec<u64>> {
+    version
+        .trim()
+        .split('.')
+        .map(|part| part.parse().ok())
+        .collect()
+}
+
+/// Compare dotted versions numerically, padding with zeros ("2.28" <
+/// "2.28.1" < "2.30"). None if either isn't a dotted number.
+fn compare_versions(a: &str, b: &str) -> Option<Ordering> {
+    let (mut a, mut b) = (parse_dotted(a)?, parse_dotted(b)?);
+    let len = a.len().max(b.len());
+    a.resize(len, 0);
+    b.resize(len, 0);
+    Some(a.cmp(&b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_abi() {
+        let manifest = PluginManifest::from_toml(
+            r#"
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.0.0"
+type = "extension"
+
+[binary.abi_requirements]
+glibc = "2.28"
+macos = "11.0"
+"#,
+        )
+        .unwrap();
+        let linux = Platform::parse("linux-x86_64").unwrap();
+        let darwin = Platform::parse("darwin-aarch64").unwrap();
+
+        let old = HostEnvironment::new(linux.clone()).glibc("2.17");
+        let mismatches = manifest.check_abi(&old);
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(
+            mismatches[0].to_string(),
+            "glibc 2.28 or newer required, host has 2.17"
+        );
+        assert!(manifest
+            .check_abi(&HostEnvironment::new(linux.clone()).glibc("2.31"))
+            .is_empty());
+        assert_eq!(manifest.check_abi(&HostEnvironment::new(linux)).len(), 1);
+        assert!(manifest
+            .check_abi(&HostEnvironment::new(darwin).macos("14.2"))
+            .is_empty());
+        assert!(manifest.validate().iter().all(|i| !i.is_error()));
+    }
+
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(compare_versions("2.28", "2.28.0"), Some(Ordering::Equal));
+        assert_eq!(compare_versions("2.9", "2.28"), Some(Ordering::Less));
+        assert_eq!(compare_versions("2.x", "2.28"), None);
+        let abi = AbiRequirements {
+            glibc: Some("2.x".into()),
+            ..Default::default()
+        };
+        assert_eq!(abi.validate().len(), 1);
+    }
+}
@@ -0,0 +1,126 @@
+//! Plugin rename table (`aliases.toml`).
+//!
+//! When a plugin is renamed, manifests that still list the old ID in
+//! `depends_on` keep resolving through an alias table:
+//!
+//! ```toml
+//! [aliases]
+//! "acme.search-legacy" = "acme.search"
+//! "acme.idx" = "acme.index"
+//! ```
+//!
+//! Load it into a `DependencyResolver` with `with_aliases()`; the resulting
+//! `LoadPlan` lists every alias that was needed, so hosts can nag the
+//! authors of the manifests still using old IDs.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::error::ManifestError;
+use crate::validate::ValidationIssue;
+
+/// Conventional alias table name.
+pub const ALIASES_FILE_NAME: &str = "aliases.toml";
+
+/// Old plugin IDs mapped to the IDs that replaced them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AliasTable {
+    /// Old ID -> new ID
+    #[serde(default)]
+    pub aliases: BTreeMap<String, String>,
+}
+
+/// An alias used while resolving dependencies.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AliasUse {
+    /// Plugin whose `depends_on` named the old ID
+    pub plugin: String,
+
+    /// Old ID as written in the manifest
+    pub alias: String,
+
+    /// ID it resolved to
+    pub target: String,
+}
+
+impl AliasTable {
+    /// Create an empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder-style addition of an alias.
+    pub fn alias(mut self, old: impl Into<String>, new: impl Into<String>) -> Self {
+        self.aliases.insert(old.into(), new.into());
+        self
+    }
+
+    /// Follow aliases from `id` to the current ID.
+    ///
+    /// Returns None if `id` is not an alias or the chain loops.
+    pub fn resolve(&self, id: &str) -> Option<&str> {
+        let mut current = self.aliases.get(id)?;
+        for _ in 0..self.aliases.len() {
+            match self.aliases.get(current) {
+                Some(next) => current = next,
+                None => return Some(current),
+            }
+        }
+        None
+    }
+
+    /// Check for aliases that loop back on themselves.
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        self.aliases
+            .keys()
+            .filter(|old| self.resolve(old).is_none())
+            .map(|old| {
+                ValidationIssue::error(
+                    format!("aliases.{old}"),
+                    format!("Alias '{old}' resolves back to itself"),
+                )
+            })
+            .collect()
+    }
+
+    /// Parse from TOML string.
+    pub fn from_toml(content: &str) -> Result<Self, ManifestError> {
+        Ok(toml::from_str(content)?)
+    }
+
+    /// Parse from file.
+    pub fn from_file(path: &Path) -> Result<Self, ManifestError> {
+        let content = std::fs::read_to_string(path)?;
+        Self::from_toml(&content)
+    }
+
+    /// Serialize to TOML string.
+    pub fn to_toml(&self) -> Result<String, ManifestError> {
+        toml::to_string_pretty(self).map_err(|e| {
+            ManifestError::InvalidFormat(format!("Failed to serialize alias table: {e}"))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_chains() {
+        let table = AliasTable::from_toml(
+            "[aliases]\n\"acme.a\" = \"acme.b\"\n\"acme.b\" = \"acme.c\"\n\"acme.x\" = \"acme.y\"\n\"acme.y\" = \"acme.x\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(table.resolve("acme.a"), Some("acme.c"));
+        assert_eq!(table.resolve("acme.c"), None);
+        assert_eq!(table.resolve("acme.x"), None);
+        assert_eq!(table.validate().len(), 2);
+        assert_eq!(
+            AliasTable::from_toml(&table.to_toml().unwrap()).unwrap(),
+            table
+        );
+    }
+}
@@ -0,0 +1,251 @@
+//! Uniform view of downloadable artifacts.
+//!
+//! Binary details are spread over `[binary]`, `[binary.checksums]`,
+//! `[binary.sizes]` and `[binary.platform.*]` (or the package-level
+//! `[binary]` tables). `artifacts()` flattens them into one
+//! `ArtifactDescriptor` per platform, so installers iterate one list:
+//!
+//! ```no_run
+//! # let manifest = lib_plugin_manifest::PluginManifest::from_file(std::path::Path::new("plugin.toml")).unwrap();
+//! for artifact in manifest.artifacts() {
+//!     println!("{} {} {:?}", artifact.platform, artifact.name, artifact.checksum);
+//! }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+use crate::package::{PackageBinaryInfo, PackageManifest};
+use crate::plugin::{BinaryInfo, BinaryKind, PluginManifest};
+
+/// What an artifact contains.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ArtifactKind {
+    /// Native dynamic library
+    Dylib,
+    /// Native executable
+    Executable,
+    /// WebAssembly module
+    Wasm,
+    /// Interpreted script
+    Script,
+    /// Package archive containing several plugins
+    Archive,
+}
+
+impl From<BinaryKind> for ArtifactKind {
+    fn from(kind: BinaryKind) -> Self {
+        match kind {
+            BinaryKind::Native => ArtifactKind::Dylib,
+            BinaryKind::Executable => ArtifactKind::Executable,
+            BinaryKind::Wasm => ArtifactKind::Wasm,
+            BinaryKind::Script => ArtifactKind::Script,
+        }
+    }
+}
+
+/// One downloadable artifact for one platform.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ArtifactDescriptor {
+    /// File name (e.g., "libtool.so"); the package ID for archives
+    pub name: String,
+
+    /// Artifact kind
+    pub kind: ArtifactKind,
+
+    /// Platform identifier (e.g., "linux-x86_64")
+    pub platform: String,
+
+    /// Declared checksum
+    pub checksum: Option<String>,
+
+    /// Declared size in bytes
+    pub size: Option<u64>,
+
+    /// Download URL; manifests don't declare one, registries fill it in
+    pub url: Option<String>,
+}
+
+impl ArtifactDescriptor {
+    /// Builder-style download URL.
+    pub fn with_url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+}
+
+impl BinaryInfo {
+    /// The artifact for a platform.
+    pub fn artifact_for(&self, platform: &str) -> ArtifactDescriptor {
+        ArtifactDescriptor {
+            name: self.filename_for(platform),
+            kind: self.kind.into(),
+            platform: platform.to_string(),
+            checksum: self.checksum_for(platform).map(String::from),
+            size: self.size_for(platform),
+            url: None,
+        }
+    }
+
+    /// One artifact per platform with a checksum, size or override, sorted
+    /// by platform.
+    pub fn artifacts(&self) -> Vec<ArtifactDescriptor> {
+        let platforms: BTreeSet<&String> = self
+            .checksums
+            .keys()
+            .chain(self.sizes.keys())
+            .chain(self.platform.keys())
+            .collect();
+        platforms
+            .into_iter()
+            .map(|p| self.artifact_for(p))
+            .collect()
+    }
+}
+
+impl PackageBinaryInfo {
+    /// One archive artifact per platform with a checksum or size, sorted by
+    /// platform.
+    pub fn artifacts(&self, package_id: &str) -> Vec<ArtifactDescriptor> {
+        let platforms: BTreeSet<&String> = self.checksums.keys().chain(self.sizes.keys()).collect();
+        platforms
+            .into_iter()
+            .map(|platform| ArtifactDescriptor {
+                name: package_id.to_string(),
+                kind: ArtifactKind::Archive,
+                platform: platform.clone(),
+                checksum: self.checksums.get(platform).cloned(),
+                size: self.sizes.get(platform).copied(),
+                url: None,
+            })
+            .collect()
+    }
+}
+
+impl PluginManifest {
+    /// One artifact per declared or checksummed platform, sorted by platform.
+    pub fn artifacts(&self) -> Vec<ArtifactDescriptor> {
+        let mut platforms: BTreeSet<&String> = self
+            .binary
+            .checksums
+            .keys()
+            .chain(self.binary.sizes.keys())
+            .chain(self.binary.platform.keys())
+            .collect();
+        platforms.extend(self.compatibility.platforms.iter().filter(|p| *p != "all"));
+        platforms
+            .into_iter()
+            .map(|p| self.binary.artifact_for(p))
+            .collect()
+    }
+}
+
+impl PackageManifest {
+    /// The package archives, one per declared or checksummed platform.
+    pub fn artifacts(&self) -> Vec<ArtifactDescriptor> {
+        let mut artifacts = self.binary.artifacts(&self.package.id);
+        for platform in self.compatibility.platforms.iter().filter(|p| *p != "all") {
+            if !artifacts.iter().any(|a| a.platform == *platform) {
+                artifacts.push(ArtifactDescriptor {
+                    name: self.package.id.clone(),
+                    kind: ArtifactKind::Archive,
+                    platform: platform.clone(),
+                    checksum: None,
+                    size: None,
+                    url: None,
+                });
+            }
+        }
+        artifacts.sort_by(|a, b| a.platform.cmp(&b.platform));
+        artifacts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plugin_artifacts() {
+        let manifest = PluginManifest::from_toml(
+            r#"
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.0.0"
+type = "extension"
+
+[compatibility]
+platforms = ["linux-x86_64", "windows-x86_64", "darwin-aarch64"]
+
+[binary]
+name = "tool"
+kind = "executable"
+
+[binary.checksums]
+linux-x86_64 = "abc"
+
+[binary.sizes]
+linux-x86_64 = 1024
+
+[binary.platform.windows-x86_64]
+name = "tool_win"
+checksum = "def"
+"#,
+        )
+        .unwrap();
+
+        let artifacts = manifest.artifacts();
+        let platforms: Vec<_> = artifacts.iter().map(|a| a.platform.as_str()).collect();
+        assert_eq!(
+            platforms,
+            ["darwin-aarch64", "linux-x86_64", "windows-x86_64"]
+        );
+        assert_eq!(artifacts[0].checksum, None);
+        assert_eq!(artifacts[1].name, "tool");
+        assert_eq!(artifacts[1].size, Some(1024));
+        assert_eq!(artifacts[2].name, "tool_win.exe");
+        assert_eq!(artifacts[2].checksum.as_deref(), Some("def"));
+        assert!(artifacts.iter().all(|a| a.kind == ArtifactKind::Executable));
+    }
+
+    #[test]
+    fn test_package_artifacts() {
+        let package = PackageManifest::from_toml(
+            r#"
+[package]
+id = "acme.suite"
+name = "Suite"
+version = "1.0.0"
+
+[compatibility]
+platforms = ["linux-x86_64", "darwin-aarch64"]
+
+[[plugins]]
+id = "acme.tool"
+name = "Tool"
+type = "extension"
+binary = "tool"
+
+[binary.checksums]
+linux-x86_64 = "abc"
+"#,
+        )
+        .unwrap();
+
+        let artifacts = package.artifacts();
+        assert_eq!(artifacts.len(), 2);
+        assert_eq!(artifacts[0].platform, "darwin-aarch64");
+        assert_eq!(artifacts[1].checksum.as_deref(), Some("abc"));
+        assert_eq!(artifacts[1].kind, ArtifactKind::Archive);
+        let artifact = artifacts[1]
+            .clone()
+            .with_url("https://example.com/suite.tar.gz");
+        assert!(artifact.url.is_some());
+    }
+}
@@ -0,0 +1,214 @@
+//! Non-binary asset declarations (`[[assets]]`).
+//!
+//! Themes, fonts and icon packs ship files the host installs alongside (or
+//! instead of) the plugin binary:
+//!
+//! ```toml
+//! [[assets]]
+//! path = "themes/dark.json"
+//! kind = "theme"
+//!
+//! [[assets]]
+//! path = "fonts/AcmeMono-Regular.ttf"
+//! kind = "font"
+//! target = "fonts/AcmeMono.ttf"
+//! platforms = ["darwin-*", "linux-*"]
+//! checksum = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+//! ```
+//!
+//! In a package.toml, `assets` goes under each `[[plugins]]` entry.
+
+use serde::{Deserialize, Serialize};
+
+use crate::package::PluginDef;
+use crate::platform::{is_known_platform, Platform};
+use crate::plugin::PluginManifest;
+use crate::validate::ValidationIssue;
+
+/// What an asset is used for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum AssetKind {
+    /// Color theme or stylesheet
+    Theme,
+    /// Font file
+    Font,
+    /// Icon or icon pack
+    Icon,
+    /// Image (e.g., backgrounds, illustrations)
+    Image,
+    /// Anything else
+    Data,
+}
+
+/// A file shipped with a plugin.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Asset {
+    /// Path within the plugin archive
+    pub path: String,
+
+    /// Asset kind
+    pub kind: AssetKind,
+
+    /// Install path relative to the host's directory for the kind
+    /// (defaults to `path`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+
+    /// Platforms the asset applies to, as identifiers or patterns
+    /// (e.g., "linux-*"); empty means all platforms
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub platforms: Vec<String>,
+
+    /// SHA256 checksum of the file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
+}
+
+impl Asset {
+    /// Install path: `target`, or `path` when unset.
+    pub fn install_path(&self) -> &str {
+        self.target.as_deref().unwrap_or(&self.path)
+    }
+
+    /// Check whether the asset applies to a platform.
+    pub fn applies_to(&self, platform: &Platform) -> bool {
+        self.platforms.is_empty() || self.platforms.iter().any(|p| platform.matches(p))
+    }
+}
+
+/// Assets of a list that apply to a platform.
+fn assets_for<'a>(assets: &'a [Asset], platform: &Platform) -> Vec<&'a Asset> {
+    assets.iter().filter(|a| a.applies_to(platform)).collect()
+}
+
+impl PluginManifest {
+    /// Assets that apply to a platform.
+    pub fn assets_for(&self, platform: &Platform) -> Vec<&Asset> {
+        assets_for(&self.assets, platform)
+    }
+
+    /// Assets that apply to the current platform.
+    pub fn current_platform_assets(&self) -> Vec<&Asset> {
+        self.assets_for(&Platform::current())
+    }
+}
+
+impl PluginDef {
+    /// Assets that apply to a platform.
+    pub fn assets_for(&self, platform: &Platform) -> Vec<&Asset> {
+        assets_for(&self.assets, platform)
+    }
+
+    /// Assets that apply to the current platform.
+    pub fn current_platform_assets(&self) -> Vec<&Asset> {
+        self.assets_for(&Platform::current())
+    }
+}
+
+/// Check asset declarations; `prefix` is prepended to issue paths
+/// (e.g., "plugins[0].").
+pub(crate) fn check_assets(prefix: &str, assets: &[Asset]) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+    for (i, asset) in assets.iter().enumerate() {
+        for (field, path) in [
+            ("path", Some(&asset.path)),
+            ("target", asset.target.as_ref()),
+        ] {
+            let Some(path) = path else {
+                continue;
+            };
+            let escapes = path.starts_with(['/', '\\'])
+                || path.split(['/', '\\']).any(|segment| segment == "..");
+            if path.trim().is_empty() || escapes {
+                issues.push(ValidationIssue::error(
+                    format!("{prefix}assets[{i}].{field}"),
+                    format!("'{path}' must be a non-empty relative path"),
+                ));
+            }
+        }
+        if assets[..i]
+            .iter()
+            .any(|a| a.install_path() == asset.install_path() && a.platforms == asset.platforms)
+        {
+            issues.push(ValidationIssue::warning(
+                format!("{prefix}assets[{i}]"),
+                format!(
+                    "Another asset already installs to '{}'",
+                    asset.install_path()
+                ),
+            ));
+        }
+        for platform in asset.platforms.iter().filter(|p| !is_known_platform(p)) {
+            issues.push(ValidationIssue::warning(
+                format!("{prefix}assets[{i}].platforms"),
+                format!("Unknown platform '{platform}'"),
+            ));
+        }
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate::has_errors;
+
+    #[test]
+    fn test_assets_for_platform() {
+        let manifest = PluginManifest::from_toml(
+            r#"
+[plugin]
+id = "acme.fonts"
+name = "Fonts"
+version = "1.0.0"
+type = "font"
+
+[[assets]]
+path = "fonts/Mono.ttf"
+kind = "font"
+
+[[assets]]
+path = "fonts/Mono-win.ttf"
+kind = "font"
+target = "fonts/Mono.ttf"
+platforms = ["windows-*"]
+"#,
+        )
+        .unwrap();
+
+        let linux = Platform::parse("linux-x86_64").unwrap();
+        let windows = Platform::parse("x86_64-pc-windows-msvc").unwrap();
+        assert_eq!(manifest.assets_for(&linux).len(), 1);
+        let on_windows = manifest.assets_for(&windows);
+        assert_eq!(on_windows.len(), 2);
+        assert_eq!(on_windows[1].install_path(), "fonts/Mono.ttf");
+        assert!(!has_errors(&manifest.validate()));
+    }
+
+    #[test]
+    fn test_check_assets() {
+        let asset = |path: &str| Asset {
+            path: path.to_string(),
+            kind: AssetKind::Icon,
+            target: None,
+            platforms: Vec::new(),
+            checksum: None,
+        };
+        let issues = check_assets(
+            "plugins[0].",
+            &[
+                asset("icons/a.svg"),
+                asset("../a.svg"),
+                asset("icons/a.svg"),
+            ],
+        );
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].path, "plugins[0].assets[1].path");
+        assert!(!issues[1].is_error());
+    }
+}
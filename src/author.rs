@@ -0,0 +1,201 @@
+//! Structured `author` fields.
+//!
+//! `author` is a display string in the usual "Name <email> (url)" form,
+//! with each part optional:
+//!
+//! ```toml
+//! [plugin]
+//! author = "ADI Team <team@adi.dev> (https://adi.dev)"
+//! ```
+//!
+//! `Author` splits it into components, so registries can index plugins by
+//! contact email, and formats them back into the same form.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::ManifestError;
+use crate::package::PackageMeta;
+use crate::plugin::PluginMeta;
+
+/// An author parsed from a "Name <email> (url)" string.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Author {
+    /// Display name (may be empty when only an email is given)
+    pub name: String,
+
+    /// Contact email
+    pub email: Option<String>,
+
+    /// Homepage
+    pub url: Option<String>,
+}
+
+impl Author {
+    /// Create an author with just a name.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Builder-style email.
+    pub fn email(mut self, email: impl Into<String>) -> Self {
+        self.email = Some(email.into());
+        self
+    }
+
+    /// Builder-style homepage.
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    /// Parse a "Name <email> (url)" string. Returns None for a blank string.
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut rest = s.trim().to_string();
+        if rest.is_empty() {
+            return None;
+        }
+        let url = take_delimited(&mut rest, '(', ')');
+        let email = take_delimited(&mut rest, '<', '>');
+        Some(Self {
+            name: rest.trim().to_string(),
+            email,
+            url,
+        })
+    }
+}
+
+/// Remove the last `open ... close` group from `s` and return its trimmed
+/// contents (None if absent or empty).
+fn take_delimited(s: &mut String, open: char, close: char) -> Option<String> {
+    let end = s.rfind(close)?;
+    let start = s[..end].rfind(open)?;
+    let inner = s[start + open.len_utf8()..end].trim().to_string();
+    let rest = format!(
+        "{} {}",
+        s[..start].trim(),
+        s[end + close.len_utf8()..].trim()
+    );
+    *s = rest.trim().to_string();
+    (!inner.is_empty()).then_some(inner)
+}
+
+impl fmt::Display for Author {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::new();
+        if !self.name.is_empty() {
+            parts.push(self.name.clone());
+        }
+        if let Some(email) = &self.email {
+            parts.push(format!("<{email}>"));
+        }
+        if let Some(url) = &self.url {
+            parts.push(format!("({url})"));
+        }
+        f.write_str(&parts.join(" "))
+    }
+}
+
+impl FromStr for Author {
+    type Err = ManifestError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s).ok_or_else(|| ManifestError::MissingField("author".to_string()))
+    }
+}
+
+impl Serialize for Author {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Author {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl PluginMeta {
+    /// Parse `plugin.author`, if set.
+    pub fn parsed_author(&self) -> Option<Author> {
+        Author::parse(&self.author)
+    }
+
+    /// Contact email from `plugin.author`, if given.
+    pub fn author_email(&self) -> Option<String> {
+        self.parsed_author()?.email
+    }
+
+    /// Set `plugin.author` from its components.
+    pub fn set_author(&mut self, author: &Author) {
+        self.author = author.to_string();
+    }
+}
+
+impl PackageMeta {
+    /// Parse `package.author`, if set.
+    pub fn parsed_author(&self) -> Option<Author> {
+        Author::parse(&self.author)
+    }
+
+    /// Contact email from `package.author`, if given.
+    pub fn author_email(&self) -> Option<String> {
+        self.parsed_author()?.email
+    }
+
+    /// Set `package.author` from its components.
+    pub fn set_author(&mut self, author: &Author) {
+        self.author = author.to_string();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_author() {
+        let author = Author::parse("ADI Team <team@adi.dev> (https://adi.dev)").unwrap();
+        assert_eq!(author.name, "ADI Team");
+        assert_eq!(author.email.as_deref(), Some("team@adi.dev"));
+        assert_eq!(author.url.as_deref(), Some("https://adi.dev"));
+        assert_eq!(
+            author.to_string(),
+            "ADI Team <team@adi.dev> (https://adi.dev)"
+        );
+
+        assert_eq!(
+            Author::parse("Acme Corp").unwrap(),
+            Author::new("Acme Corp")
+        );
+        assert_eq!(
+            Author::parse("<dev@acme.dev>").unwrap(),
+            Author::new("").email("dev@acme.dev")
+        );
+        assert_eq!(Author::parse("  "), None);
+    }
+
+    #[test]
+    fn test_meta_author() {
+        let mut manifest = crate::plugin::PluginManifest::from_toml(
+            "[plugin]\nid = \"acme.tool\"\nname = \"Tool\"\nversion = \"1.0.0\"\ntype = \"extension\"\nauthor = \"Jo <jo@acme.dev>\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            manifest.plugin.author_email().as_deref(),
+            Some("jo@acme.dev")
+        );
+
+        manifest
+            .plugin
+            .set_author(&Author::new("Acme").url("https://acme.dev"));
+        assert_eq!(manifest.plugin.author, "Acme (https://acme.dev)");
+        assert_eq!(manifest.plugin.author_email(), None);
+    }
+}
@@ -1,13 +1,32 @@
 //! Generate plugin.toml from Cargo.toml `[package.metadata.plugin]`.
 //!
 //! Usage: manifest-gen --cargo-toml <path> [--output <path>]
+//!        manifest-gen init --type <type> [--id <id>] [--output <path>]
+//!        manifest-gen diff <old> <new>
+//!        manifest-gen schema [plugin|package] [--output <path>]  (feature "schema")
 
 use lib_plugin_manifest::cargo_extract::generate_manifest_from_cargo;
-use std::path::PathBuf;
+use lib_plugin_manifest::{ChangeLevel, Manifest, ManifestDiff, PluginManifest, PluginType};
+use semver::Version;
+use std::path::{Path, PathBuf};
 
 fn main() {
     let args: Vec<String> = std::env::args().collect();
 
+    if args.get(1).map(String::as_str) == Some("init") {
+        init(&args[2..]);
+        return;
+    }
+    if args.get(1).map(String::as_str) == Some("diff") {
+        diff(&args[2..]);
+        return;
+    }
+    #[cfg(feature = "schema")]
+    if args.get(1).map(String::as_str) == Some("schema") {
+        schema(&args[2..]);
+        return;
+    }
+
     let mut cargo_toml_path: Option<PathBuf> = None;
     let mut output_path: Option<PathBuf> = None;
 
@@ -24,6 +43,8 @@ fn main() {
             }
             "--help" | "-h" => {
                 eprintln!("Usage: manifest-gen --cargo-toml <path> [--output <path>]");
+                eprintln!("       manifest-gen init --type <type> [--id <id>] [--output <path>]");
+                eprintln!("       manifest-gen diff <old> <new>");
                 eprintln!();
                 eprintln!("Generate plugin.toml from Cargo.toml [package.metadata.plugin].");
                 eprintln!();
@@ -66,21 +87,198 @@ fn main() {
         }
     };
 
-    let toml_str = match manifest.to_toml() {
-        Ok(s) => s,
+    write_manifest(output_path, manifest);
+}
+
+/// `manifest-gen init`: print a plugin.toml skeleton for a plugin type.
+fn init(args: &[String]) {
+    let mut plugin_type: Option<String> = None;
+    let mut id: Option<String> = None;
+    let mut output_path: Option<PathBuf> = None;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--type" | "-t" => {
+                i += 1;
+                plugin_type = args.get(i).cloned();
+            }
+            "--id" => {
+                i += 1;
+                id = args.get(i).cloned();
+            }
+            "--output" | "-o" => {
+                i += 1;
+                output_path = args.get(i).map(PathBuf::from);
+            }
+            "--help" | "-h" => {
+                eprintln!("Usage: manifest-gen init --type <type> [--id <id>] [--output <path>]");
+                eprintln!();
+                eprintln!("Generate a plugin.toml skeleton for a plugin type.");
+                eprintln!();
+                eprintln!("Options:");
+                eprintln!("  --type, -t <type>    Plugin type (e.g., extension, theme, lang)");
+                eprintln!("  --id <id>            Plugin ID (default: placeholder)");
+                eprintln!("  --output, -o <path>  Output path (default: stdout)");
+                std::process::exit(0);
+            }
+            other => {
+                eprintln!("Unknown argument: {other}");
+                std::process::exit(1);
+            }
+        }
+        i += 1;
+    }
+
+    let plugin_type = match plugin_type {
+        Some(t) => t,
+        None => {
+            eprintln!("Error: --type <type> is required");
+            std::process::exit(1);
+        }
+    };
+
+    let mut manifest = PluginManifest::template_for(PluginType::parse(&plugin_type));
+    if let Some(id) = id {
+        manifest.plugin.id = id;
+    }
+
+    write_manifest(output_path, manifest);
+}
+
+/// `manifest-gen diff`: compare two manifests and check the version bump.
+///
+/// Exits with status 2 when the manifests differ and the version bump is
+/// smaller than the changes require.
+fn diff(args: &[String]) {
+    let mut paths: Vec<PathBuf> = Vec::new();
+    for arg in args {
+        match arg.as_str() {
+            "--help" | "-h" => {
+                eprintln!("Usage: manifest-gen diff <old> <new>");
+                eprintln!();
+                eprintln!("Compare two plugin.toml or package.toml files: changed fields,");
+                eprintln!("plugins, services, permissions, platforms and checksums, and whether");
+                eprintln!("the version bump is large enough (major/minor/patch).");
+                std::process::exit(0);
+            }
+            other => paths.push(PathBuf::from(other)),
+        }
+    }
+    let [old_path, new_path] = paths.as_slice() else {
+        eprintln!("Error: expected <old> and <new> manifest paths");
+        std::process::exit(1);
+    };
+
+    let old = load(old_path);
+    let new = load(new_path);
+
+    let diff = ManifestDiff::compute(&old, &new);
+    if diff.is_empty() {
+        println!("No changes");
+    }
+    for change in &diff.changes {
+        println!("{} ({})", change, change.level());
+    }
+    let required = diff.required_bump();
+
+    let actual = match (Version::parse(old.version()), Version::parse(new.version())) {
+        (Ok(o), Ok(n)) => ChangeLevel::between(&o, &n),
+        _ => None,
+    };
+    println!(
+        "Version: {} -> {} ({})",
+        old.version(),
+        new.version(),
+        actual.map_or("not a release bump", ChangeLevel::name)
+    );
+    println!("Required bump: {required}");
+    if !diff.is_empty() && actual.is_none_or(|actual| actual < required) {
+        eprintln!("Error: version bump is smaller than the changes require ({required})");
+        std::process::exit(2);
+    }
+}
+
+fn load(path: &Path) -> Manifest {
+    match Manifest::from_file(path) {
+        Ok(m) => m,
+        Err(e) => {
+            eprintln!("Error reading {}: {e}", path.display());
+            std::process::exit(1);
+        }
+    }
+}
+
+/// `manifest-gen schema`: print the JSON Schema for plugin.toml or package.toml.
+#[cfg(feature = "schema")]
+fn schema(args: &[String]) {
+    let mut kind = "plugin".to_string();
+    let mut output_path: Option<PathBuf> = None;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--output" | "-o" => {
+                i += 1;
+                output_path = args.get(i).map(PathBuf::from);
+            }
+            "--help" | "-h" => {
+                eprintln!("Usage: manifest-gen schema [plugin|package] [--output <path>]");
+                eprintln!();
+                eprintln!("Print the JSON Schema for plugin.toml (default) or package.toml.");
+                eprintln!();
+                eprintln!("Options:");
+                eprintln!("  --output, -o <path>  Output path (default: stdout)");
+                std::process::exit(0);
+            }
+            "plugin" | "package" => kind = args[i].clone(),
+            other => {
+                eprintln!("Unknown argument: {other}");
+                std::process::exit(1);
+            }
+        }
+        i += 1;
+    }
+
+    let schema = if kind == "package" {
+        lib_plugin_manifest::schema::package_schema()
+    } else {
+        lib_plugin_manifest::schema::plugin_schema()
+    };
+    let json = match serde_json::to_string_pretty(&schema) {
+        Ok(s) => s + "\n",
         Err(e) => {
-            eprintln!("Error serializing manifest: {e}");
+            eprintln!("Error serializing schema: {e}");
             std::process::exit(1);
         }
     };
 
+    write_output(output_path, &json);
+}
+
+/// Write a manifest in canonical form, keeping the header comment of an
+/// existing output file.
+fn write_manifest(output_path: Option<PathBuf>, manifest: PluginManifest) {
+    let manifest = Manifest::Single(manifest);
+    let result = match &output_path {
+        Some(path) => manifest.write_canonical(path),
+        None => manifest.to_canonical_toml(None).map(|s| print!("{s}")),
+    };
+    if let Err(e) = result {
+        eprintln!("Error writing manifest: {e}");
+        std::process::exit(1);
+    }
+}
+
+#[cfg(feature = "schema")]
+fn write_output(output_path: Option<PathBuf>, content: &str) {
     match output_path {
         Some(path) => {
-            if let Err(e) = std::fs::write(&path, &toml_str) {
+            if let Err(e) = std::fs::write(&path, content) {
                 eprintln!("Error writing to {}: {e}", path.display());
                 std::process::exit(1);
             }
         }
-        None => print!("{toml_str}"),
+        None => print!("{content}"),
     }
 }
@@ -0,0 +1,126 @@
+//! Build-script support for embedding the manifest into plugin binaries.
+//!
+//! In the plugin crate's `build.rs`:
+//!
+//! ```ignore
+//! fn main() {
+//!     lib_plugin_manifest::build_support::embed_manifest().unwrap();
+//! }
+//! ```
+//!
+//! and somewhere in the plugin's `lib.rs`:
+//!
+//! ```ignore
+//! include!(concat!(env!("OUT_DIR"), "/plugin_manifest.rs"));
+//! ```
+//!
+//! The manifest is generated from `[package.metadata.plugin]` on every
+//! build, so the binary and its manifest can't drift apart. It can be read
+//! back with `Manifest::from_binary()`.
+
+use std::path::{Path, PathBuf};
+
+use crate::cargo_extract::generate_manifest_from_cargo;
+use crate::embed::encode_embedded;
+use crate::error::ManifestError;
+
+/// Name of the generated file in `OUT_DIR`.
+pub const EMBED_FILE_NAME: &str = "plugin_manifest.rs";
+
+/// Generate and embed the manifest of the crate being built.
+///
+/// Reads `CARGO_MANIFEST_DIR` and `OUT_DIR` from the build-script
+/// environment and returns the path of the generated file.
+pub fn embed_manifest() -> Result<PathBuf, ManifestError> {
+    let manifest_dir = env_path("CARGO_MANIFEST_DIR")?;
+    let out_dir = env_path("OUT_DIR")?;
+    let cargo_toml = manifest_dir.join("Cargo.toml");
+
+    println!("cargo:rerun-if-changed={}", cargo_toml.display());
+    embed_manifest_from(&cargo_toml, &out_dir)
+}
+
+/// Generate the manifest from a Cargo.toml and write the embedding source
+/// file into `out_dir`.
+pub fn embed_manifest_from(cargo_toml: &Path, out_dir: &Path) -> Result<PathBuf, ManifestError> {
+    let manifest = generate_manifest_from_cargo(cargo_toml)?;
+    let source = embed_source(&manifest.to_toml()?);
+
+    let path = out_dir.join(EMBED_FILE_NAME);
+    std::fs::write(&path, source)?;
+    Ok(path)
+}
+
+/// Generate Rust source for a static holding the embedded manifest blob.
+///
+/// The static is kept alive with `#[used]` and placed in a dedicated
+/// link section so tools can find it without loading the binary.
+pub fn embed_source(manifest_toml: &str) -> String {
+    let blob = encode_embedded(manifest_toml);
+    let bytes = blob
+        .iter()
+        .map(|b| b.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!(
+        "// Generated by lib-plugin-manifest build_support. Do not edit.\n\
+         #[used]\n\
+         #[cfg_attr(target_vendor = \"apple\", link_section = \"__DATA,__adi_manifest\")]\n\
+         #[cfg_attr(target_os = \"windows\", link_section = \".adimf\")]\n\
+         #[cfg_attr(\n    not(any(target_vendor = \"apple\", target_os = \"windows\")),\n    link_section = \".adi_manifest\"\n)]\n\
+         #[doc(hidden)]\n\
+         pub static ADI_PLUGIN_MANIFEST: [u8; {}] = [{}];\n",
+        blob.len(),
+        bytes
+    )
+}
+
+fn env_path(name: &str) -> Result<PathBuf, ManifestError> {
+    std::env::var_os(name)
+        .map(PathBuf::from)
+        .ok_or_else(|| ManifestError::MissingField(format!("environment variable {name}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embed::find_embedded;
+
+    #[test]
+    fn test_embed_manifest_from_cargo() {
+        let dir = tempfile::tempdir().unwrap();
+        let cargo_toml = dir.path().join("Cargo.toml");
+        std::fs::write(
+            &cargo_toml,
+            r#"
+[package]
+name = "acme-tool"
+version = "0.3.0"
+authors = ["Acme"]
+
+[package.metadata.plugin]
+id = "acme.tool"
+name = "Tool"
+type = "extension"
+"#,
+        )
+        .unwrap();
+
+        let path = embed_manifest_from(&cargo_toml, dir.path()).unwrap();
+        let source = std::fs::read_to_string(path).unwrap();
+        assert!(source.contains("#[used]"));
+        assert!(source.contains("pub static ADI_PLUGIN_MANIFEST"));
+
+        // Decode the byte array literal and read the manifest back
+        let start = source.rfind('[').unwrap() + 1;
+        let end = source.rfind(']').unwrap();
+        let bytes: Vec<u8> = source[start..end]
+            .split(", ")
+            .map(|b| b.parse().unwrap())
+            .collect();
+        let manifest = crate::Manifest::from_toml(find_embedded(&bytes).unwrap()).unwrap();
+        assert_eq!(manifest.id(), "acme.tool");
+        assert_eq!(manifest.version(), "0.3.0");
+    }
+}
@@ -0,0 +1,381 @@
+//! Fluent builders for constructing manifests in code.
+//!
+//! ```
+//! use lib_plugin_manifest::PluginManifest;
+//!
+//! let manifest = PluginManifest::builder("acme.tool", "Tool", "1.0.0")
+//!     .description("Does things")
+//!     .min_host_version("0.8.0")
+//!     .binary("acme_tool")
+//!     .provides("acme.tool.api", "1.0.0")
+//!     .build()
+//!     .unwrap();
+//! assert!(manifest.to_toml().unwrap().contains("acme_tool"));
+//! ```
+
+use crate::error::ManifestError;
+use crate::migrate::CURRENT_MANIFEST_VERSION;
+use crate::options::ManifestOrigin;
+use crate::package::{PackageBinaryInfo, PackageManifest, PackageMeta, PluginDef};
+use crate::plugin::*;
+use crate::plugin_type::PluginType;
+use crate::validate::{ensure_valid, ValidationPolicy};
+
+/// Builder for `PluginManifest`.
+///
+/// Starts from the `extension` template, so every section has its default.
+#[derive(Debug, Clone)]
+pub struct PluginManifestBuilder {
+    manifest: PluginManifest,
+}
+
+impl PluginManifest {
+    /// Start building a manifest.
+    pub fn builder(id: &str, name: &str, version: &str) -> PluginManifestBuilder {
+        PluginManifestBuilder::new(id, name, version)
+    }
+}
+
+impl PluginManifestBuilder {
+    /// Create a builder for an `extension` plugin.
+    pub fn new(id: &str, name: &str, version: &str) -> Self {
+        let mut manifest = PluginManifest::template_for(PluginType::Extension);
+        manifest.plugin.id = id.to_string();
+        manifest.plugin.name = name.to_string();
+        manifest.plugin.version = version.to_string();
+        manifest.binary.name = id.replace(['.', '-'], "_");
+        Self { manifest }
+    }
+
+    /// Set the plugin kind, adding the sections it requires
+    /// (e.g., `[translation]`) from its template.
+    pub fn kind(mut self, kind: PluginType) -> Self {
+        let template = PluginManifest::template_for(kind);
+        let m = &mut self.manifest;
+        m.plugin.plugin_type = template.plugin.plugin_type;
+        m.translation = m.translation.take().or(template.translation);
+        m.language = m.language.take().or(template.language);
+        m.hive = m.hive.take().or(template.hive);
+        m.theme = m.theme.take().or(template.theme);
+        self
+    }
+
+    /// Set the description.
+    pub fn description(mut self, description: &str) -> Self {
+        self.manifest.plugin.description = description.to_string();
+        self
+    }
+
+    /// Set the author.
+    pub fn author(mut self, author: &str) -> Self {
+        self.manifest.plugin.author = author.to_string();
+        self
+    }
+
+    /// Set the SPDX license expression.
+    pub fn license(mut self, license: &str) -> Self {
+        self.manifest.plugin.license = Some(license.to_string());
+        self
+    }
+
+    /// Set the homepage URL.
+    pub fn homepage(mut self, url: &str) -> Self {
+        self.manifest.plugin.homepage = Some(url.to_string());
+        self
+    }
+
+    /// Set the repository URL.
+    pub fn repository(mut self, url: &str) -> Self {
+        self.manifest.plugin.repository = Some(url.to_string());
+        self
+    }
+
+    /// Set the plugin API version.
+    pub fn api_version(mut self, api_version: u32) -> Self {
+        self.manifest.compatibility.api_version = api_version;
+        self
+    }
+
+    /// Set the minimum host version.
+    pub fn min_host_version(mut self, version: &str) -> Self {
+        self.manifest.compatibility.min_host_version = Some(version.to_string());
+        self
+    }
+
+    /// Set the maximum host version.
+    pub fn max_host_version(mut self, version: &str) -> Self {
+        self.manifest.compatibility.max_host_version = Some(version.to_string());
+        self
+    }
+
+    /// Add a supported platform.
+    pub fn platform(mut self, platform: &str) -> Self {
+        self.manifest
+            .compatibility
+            .platforms
+            .push(platform.to_string());
+        self
+    }
+
+    /// Add a plugin that must be loaded first.
+    pub fn depends_on(mut self, id: &str) -> Self {
+        self.manifest.compatibility.depends_on.push(id.to_string());
+        self
+    }
+
+    /// Set the binary name (defaults to the ID with `.`/`-` replaced by `_`).
+    pub fn binary(mut self, name: &str) -> Self {
+        self.manifest.binary.name = name.to_string();
+        self
+    }
+
+    /// Set the binary checksum for a platform.
+    pub fn checksum(mut self, platform: &str, checksum: &str) -> Self {
+        self.manifest
+            .binary
+            .checksums
+            .insert(platform.to_string(), checksum.to_string());
+        self
+    }
+
+    /// Set the binary size for a platform.
+    pub fn size(mut self, platform: &str, bytes: u64) -> Self {
+        self.manifest
+            .binary
+            .sizes
+            .insert(platform.to_string(), bytes);
+        self
+    }
+
+    /// Add a provided service.
+    pub fn provides(mut self, id: &str, version: &str) -> Self {
+        self.manifest.provides.push(ServiceDeclaration {
+            id: id.to_string(),
+            version: version.to_string(),
+            description: String::new(),
+            deprecated: false,
+            replaced_by: None,
+        });
+        self
+    }
+
+    /// Add a required service.
+    pub fn requires(mut self, id: &str, min_version: Option<&str>) -> Self {
+        self.manifest.requires.push(ServiceRequirement {
+            id: id.to_string(),
+            min_version: min_version.map(String::from),
+            optional: false,
+            provider: ServiceSource::Plugin,
+        });
+        self
+    }
+
+    /// Add a capability.
+    pub fn capability(mut self, protocol: &str, version: &str) -> Self {
+        self.manifest.capabilities.push(CapabilityDeclaration {
+            protocol: protocol.to_string(),
+            version: version.to_string(),
+            description: String::new(),
+            implements: None,
+        });
+        self
+    }
+
+    /// Set a config default.
+    pub fn config_default(mut self, key: &str, value: impl Into<toml::Value>) -> Self {
+        self.manifest
+            .config
+            .defaults
+            .insert(key.to_string(), value.into());
+        self
+    }
+
+    /// Modify any other part of the manifest.
+    pub fn edit(mut self, f: impl FnOnce(&mut PluginManifest)) -> Self {
+        f(&mut self.manifest);
+        self
+    }
+
+    /// Validate with the default policy and return the manifest.
+    pub fn build(self) -> Result<PluginManifest, ManifestError> {
+        self.build_with(&ValidationPolicy::default())
+    }
+
+    /// Validate with a custom policy and return the manifest.
+    pub fn build_with(self, policy: &ValidationPolicy) -> Result<PluginManifest, ManifestError> {
+        ensure_valid(&self.manifest.validate_with(policy))?;
+        Ok(self.manifest)
+    }
+}
+
+/// Builder for `PackageManifest`.
+#[derive(Debug, Clone)]
+pub struct PackageManifestBuilder {
+    manifest: PackageManifest,
+}
+
+impl PackageManifest {
+    /// Start building a package manifest.
+    pub fn builder(id: &str, name: &str, version: &str) -> PackageManifestBuilder {
+        PackageManifestBuilder::new(id, name, version)
+    }
+}
+
+impl PackageManifestBuilder {
+    /// Create a builder for an empty package.
+    pub fn new(id: &str, name: &str, version: &str) -> Self {
+        Self {
+            manifest: PackageManifest {
+                manifest_version: CURRENT_MANIFEST_VERSION,
+                package: PackageMeta {
+                    id: id.to_string(),
+                    name: name.to_string(),
+                    version: version.to_string(),
+                    author: String::new(),
+                    description: String::new(),
+                    license: None,
+                    homepage: None,
+                    repository: None,
+                    license_file: None,
+                },
+                compatibility: CompatibilityInfo::default(),
+                plugins: Vec::new(),
+                binary: PackageBinaryInfo::default(),
+                signature: None,
+                origin: ManifestOrigin::default(),
+            },
+        }
+    }
+
+    /// Set the description.
+    pub fn description(mut self, description: &str) -> Self {
+        self.manifest.package.description = description.to_string();
+        self
+    }
+
+    /// Set the author.
+    pub fn author(mut self, author: &str) -> Self {
+        self.manifest.package.author = author.to_string();
+        self
+    }
+
+    /// Set the SPDX license expression.
+    pub fn license(mut self, license: &str) -> Self {
+        self.manifest.package.license = Some(license.to_string());
+        self
+    }
+
+    /// Set the plugin API version.
+    pub fn api_version(mut self, api_version: u32) -> Self {
+        self.manifest.compatibility.api_version = api_version;
+        self
+    }
+
+    /// Set the minimum host version.
+    pub fn min_host_version(mut self, version: &str) -> Self {
+        self.manifest.compatibility.min_host_version = Some(version.to_string());
+        self
+    }
+
+    /// Add a supported platform.
+    pub fn platform(mut self, platform: &str) -> Self {
+        self.manifest
+            .compatibility
+            .platforms
+            .push(platform.to_string());
+        self
+    }
+
+    /// Add a plugin with the given type and binary name.
+    pub fn plugin(self, id: &str, name: &str, plugin_type: &str, binary: &str) -> Self {
+        self.plugin_def(PluginDef {
+            id: id.to_string(),
+            name: name.to_string(),
+            plugin_type: PluginType::parse(plugin_type),
+            binary: binary.to_string(),
+            description: None,
+            depends_on: Vec::new(),
+            config: None,
+            provides: Vec::new(),
+            requires: Vec::new(),
+            assets: Vec::new(),
+            compatibility: None,
+        })
+    }
+
+    /// Add a fully specified plugin.
+    pub fn plugin_def(mut self, plugin: PluginDef) -> Self {
+        self.manifest.plugins.push(plugin);
+        self
+    }
+
+    /// Set the checksum of a binary for a platform.
+    pub fn checksum(mut self, platform: &str, checksum: &str) -> Self {
+        self.manifest
+            .binary
+            .checksums
+            .insert(platform.to_string(), checksum.to_string());
+        self
+    }
+
+    /// Modify any other part of the manifest.
+    pub fn edit(mut self, f: impl FnOnce(&mut PackageManifest)) -> Self {
+        f(&mut self.manifest);
+        self
+    }
+
+    /// Validate with the default policy and return the manifest.
+    pub fn build(self) -> Result<PackageManifest, ManifestError> {
+        self.build_with(&ValidationPolicy::default())
+    }
+
+    /// Validate with a custom policy and return the manifest.
+    pub fn build_with(self, policy: &ValidationPolicy) -> Result<PackageManifest, ManifestError> {
+        ensure_valid(&self.manifest.validate_with(policy))?;
+        Ok(self.manifest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plugin_builder() {
+        let manifest = PluginManifest::builder("acme.lang.zig", "Zig", "1.2.0")
+            .kind(PluginType::Lang)
+            .license("MIT")
+            .platform("linux-x86_64")
+            .requires("adi.lsp", Some("1.0.0"))
+            .config_default("fmt", true)
+            .build()
+            .unwrap();
+        assert_eq!(manifest.plugin.plugin_type, PluginType::Lang);
+        assert!(manifest.language.is_some());
+        assert_eq!(manifest.binary.name, "acme_lang_zig");
+
+        let reparsed = PluginManifest::from_toml(&manifest.to_toml().unwrap()).unwrap();
+        assert_eq!(reparsed.requires[0].min_version.as_deref(), Some("1.0.0"));
+        assert_eq!(reparsed.config.defaults["fmt"], toml::Value::Boolean(true));
+
+        let invalid = PluginManifest::builder("acme.tool", "Tool", "1.0").build();
+        assert!(matches!(invalid, Err(ManifestError::InvalidFormat(_))));
+    }
+
+    #[test]
+    fn test_package_builder() {
+        let package = PackageManifest::builder("acme.themes", "Themes", "2.0.0")
+            .plugin("acme.theme-dark", "Dark", "theme", "dark_theme")
+            .plugin("acme.theme-light", "Light", "theme", "light_theme")
+            .edit(|p| p.plugins[1].depends_on.push("acme.theme-dark".to_string()))
+            .build()
+            .unwrap();
+        assert_eq!(package.expand_plugins().len(), 2);
+
+        let dangling = PackageManifest::builder("acme.themes", "Themes", "2.0.0")
+            .plugin("acme.theme-dark", "Dark", "theme", "dark_theme")
+            .edit(|p| p.plugins[0].depends_on.push("acme.missing".to_string()))
+            .build();
+        assert!(dangling.is_err());
+    }
+}
@@ -0,0 +1,983 @@
+//! Plugin bundles (`.adiplugin` archives).
+//!
+//! A bundle is a gzip-compressed tar archive with a fixed layout:
+//!
+//! ```text
+//! plugin.toml | package.toml       manifest (canonical path)
+//! bin/<platform>/<binary file>     binaries
+//! <license_file>                   license text (if declared)
+//! <signature_file>                 signature (if declared)
+//! ...                              additional files (assets)
+//! ```
+//!
+//! The manifest is embedded as the document it was parsed from, so comments
+//! and the bytes its signature covers survive; manifests built or edited in
+//! code are embedded as `to_toml()` writes them.
+//!
+//! Entries are written in sorted order with zeroed timestamps and owners,
+//! so the same inputs always produce the same archive. `linearize()` lists
+//! the entries a manifest implies in that order, with their expected hashes.
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Component, Path, PathBuf};
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+
+use crate::checksum::{Checksum, ChecksumAlgorithm};
+use crate::error::ManifestError;
+use crate::platform::find_platform_key;
+use crate::plugin::BinaryInfo;
+use crate::Manifest;
+
+/// File extension of plugin bundles.
+pub const BUNDLE_EXTENSION: &str = "adiplugin";
+
+/// Directory containing per-platform binaries inside a bundle.
+pub const BINARY_DIR: &str = "bin";
+
+/// Get the archive path of a binary inside a bundle.
+///
+/// The file name follows the binary's kind and per-platform name override,
+/// as in an installed plugin.
+pub fn binary_entry_path(binary: &BinaryInfo, platform: &str) -> String {
+    format!("{BINARY_DIR}/{platform}/{}", binary.filename_for(platform))
+}
+
+/// Assembles a plugin bundle from a manifest and the files it references.
+#[derive(Debug, Clone)]
+pub struct BundleBuilder {
+    manifest: Manifest,
+    base_dir: PathBuf,
+    files: BTreeMap<String, PathBuf>,
+}
+
+impl BundleBuilder {
+    /// Create a builder.
+    ///
+    /// `base_dir` is the directory relative paths in the manifest
+    /// (license file, signature file) are resolved against.
+    pub fn new(manifest: Manifest, base_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            manifest,
+            base_dir: base_dir.into(),
+            files: BTreeMap::new(),
+        }
+    }
+
+    /// Add a compiled binary for a platform.
+    ///
+    /// The binary name must be declared in the manifest and the platform
+    /// must be supported by it.
+    pub fn binary(
+        mut self,
+        binary_name: &str,
+        platform: &str,
+        source: impl Into<PathBuf>,
+    ) -> Result<Self, ManifestError> {
+        let binaries = manifest_binaries(&self.manifest);
+        let binary = binaries
+            .iter()
+            .find(|b| b.name == binary_name)
+            .ok_or_else(|| {
+                ManifestError::Bundle(format!(
+                    "Binary '{binary_name}' is not declared in the manifest"
+                ))
+            })?;
+
+        let platforms = match &self.manifest {
+            Manifest::Single(m) => &m.compatibility.platforms,
+            Manifest::Package(m) => &m.compatibility.platforms,
+        };
+        if !platforms.is_empty() && !platforms.iter().any(|p| p == platform || p == "all") {
+            return Err(ManifestError::Bundle(format!(
+                "Platform '{platform}' is not supported by the manifest"
+            )));
+        }
+
+        let entry_path = binary_entry_path(binary, platform);
+        check_entry_path(&entry_path)?;
+        if binary_platform(&entry_path, "") != Some(platform) {
+            return Err(ManifestError::Bundle(format!(
+                "Invalid path '{entry_path}' in bundle"
+            )));
+        }
+        self.files.insert(entry_path, source.into());
+        Ok(self)
+    }
+
+    /// Add an additional file (e.g., an asset) at a path inside the bundle.
+    pub fn file(
+        mut self,
+        archive_path: &str,
+        source: impl Into<PathBuf>,
+    ) -> Result<Self, ManifestError> {
+        check_entry_path(archive_path)?;
+        if archive_path == self.manifest.file_name()
+            || archive_path.starts_with(&format!("{BINARY_DIR}/"))
+            || self.referenced_files().contains(&archive_path)
+        {
+            return Err(ManifestError::Bundle(format!(
+                "Path '{archive_path}' is reserved in the bundle layout"
+            )));
+        }
+        self.files.insert(archive_path.to_string(), source.into());
+        Ok(self)
+    }
+
+    /// Get the archive paths the bundle will contain, in archive order.
+    pub fn entries(&self) -> Result<Vec<String>, ManifestError> {
+        Ok(self.collect_files()?.into_keys().collect())
+    }
+
+    /// Get the entries the bundle will contain, in archive order, with the
+    /// sha256 of each file's content.
+    pub fn linearize(&self) -> Result<Vec<ArchiveEntry>, ManifestError> {
+        let manifest_toml = self.manifest.declared_toml()?;
+        self.collect_files()?
+            .into_iter()
+            .map(|(path, source)| {
+                let sha256 = match source {
+                    Some(source) => sha256_hex(&mut File::open(source)?)?,
+                    None => sha256_hex(&mut manifest_toml.as_bytes())?,
+                };
+                Ok(ArchiveEntry {
+                    kind: EntryKind::of(&self.manifest, &path),
+                    path,
+                    sha256: Some(format!("sha256:{sha256}")),
+                })
+            })
+            .collect()
+    }
+
+    /// Write the bundle to a file.
+    pub fn write(&self, path: &Path) -> Result<(), ManifestError> {
+        let file = File::create(path)?;
+        self.write_to(file)
+    }
+
+    /// Write the bundle to a writer.
+    pub fn write_to<W: Write>(&self, writer: W) -> Result<(), ManifestError> {
+        let files = self.collect_files()?;
+        let manifest_toml = self.manifest.declared_toml()?;
+
+        let mut archive = tar::Builder::new(GzEncoder::new(writer, Compression::default()));
+        for (entry_path, source) in &files {
+            match source {
+                Some(source) => {
+                    let content = std::fs::read(source)?;
+                    append(&mut archive, entry_path, &content)?;
+                }
+                None => append(&mut archive, entry_path, manifest_toml.as_bytes())?,
+            }
+        }
+        archive.into_inner()?.finish()?;
+        Ok(())
+    }
+
+    /// The declared license and signature files.
+    fn referenced_files(&self) -> Vec<&str> {
+        let (license_file, signature) = match &self.manifest {
+            Manifest::Single(m) => (m.plugin.license_file.as_ref(), m.signature.as_ref()),
+            Manifest::Package(m) => (m.package.license_file.as_ref(), m.signature.as_ref()),
+        };
+        license_file
+            .into_iter()
+            .chain(signature.map(|s| &s.signature_file))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Collect all entries; `None` marks the manifest itself.
+    fn collect_files(&self) -> Result<BTreeMap<String, Option<PathBuf>>, ManifestError> {
+        let mut files: BTreeMap<String, Option<PathBuf>> = self
+            .files
+            .iter()
+            .map(|(k, v)| (k.clone(), Some(v.clone())))
+            .collect();
+        files.insert(self.manifest.file_name().to_string(), None);
+
+        for relative in self.referenced_files() {
+            check_entry_path(relative)?;
+            let source = self.base_dir.join(relative);
+            if !source.is_file() {
+                return Err(ManifestError::Bundle(format!(
+                    "Referenced file '{relative}' not found"
+                )));
+            }
+            files.insert(relative.to_string(), Some(source));
+        }
+
+        Ok(files)
+    }
+}
+
+/// Role of an entry in a bundle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EntryKind {
+    /// The manifest itself
+    Manifest,
+    /// A binary for a platform
+    Binary {
+        /// Platform identifier
+        platform: String,
+    },
+    /// The declared license file
+    License,
+    /// The declared signature file
+    Signature,
+    /// Any other file
+    Asset,
+}
+
+impl EntryKind {
+    fn of(manifest: &Manifest, path: &str) -> Self {
+        let (license_file, signature) = match manifest {
+            Manifest::Single(m) => (m.plugin.license_file.as_ref(), m.signature.as_ref()),
+            Manifest::Package(m) => (m.package.license_file.as_ref(), m.signature.as_ref()),
+        };
+        if path == manifest.file_name() {
+            EntryKind::Manifest
+        } else if let Some(platform) = binary_platform(path, "") {
+            EntryKind::Binary {
+                platform: platform.to_string(),
+            }
+        } else if license_file.is_some_and(|l| l == path) {
+            EntryKind::License
+        } else if signature.is_some_and(|s| s.signature_file == path) {
+            EntryKind::Signature
+        } else {
+            EntryKind::Asset
+        }
+    }
+}
+
+/// An entry of a bundle in archive order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveEntry {
+    /// Path inside the archive
+    pub path: String,
+
+    /// Role of the entry
+    pub kind: EntryKind,
+
+    /// Expected content hash ("sha256:<hex>"), if known
+    pub sha256: Option<String>,
+}
+
+/// List the entries a bundle for `manifest` must contain, in archive order.
+///
+/// Covers the manifest, one binary per plugin and platform, the declared
+/// `[[assets]]` and the declared license and signature files. Platforms
+/// come from `compatibility.platforms`, or from the declared checksums if
+/// that list is empty. Hashes are known for the manifest (the document it
+/// was parsed from, as the builder embeds it), and for binaries and assets
+/// with sha256 checksums; package binaries carry the package checksums
+/// their expanded plugins inherit.
+pub fn linearize(manifest: &Manifest) -> Result<Vec<ArchiveEntry>, ManifestError> {
+    let manifest_toml = manifest.declared_toml()?;
+    let mut entries: BTreeMap<String, Option<String>> = BTreeMap::new();
+    entries.insert(
+        manifest.file_name().to_string(),
+        Some(format!(
+            "sha256:{}",
+            sha256_hex(&mut manifest_toml.as_bytes())?
+        )),
+    );
+
+    let (compatibility, checksums, license_file, signature, assets) = match manifest {
+        Manifest::Single(m) => (
+            &m.compatibility,
+            &m.binary.checksums,
+            &m.plugin.license_file,
+            &m.signature,
+            m.assets.iter().collect::<Vec<_>>(),
+        ),
+        Manifest::Package(m) => (
+            &m.compatibility,
+            &m.binary.checksums,
+            &m.package.license_file,
+            &m.signature,
+            m.plugins.iter().flat_map(|p| &p.assets).collect(),
+        ),
+    };
+    let mut platforms: Vec<&String> = compatibility
+        .platforms
+        .iter()
+        .filter(|p| *p != "all")
+        .collect();
+    if platforms.is_empty() {
+        platforms = checksums.keys().collect();
+    }
+
+    for binary in manifest_binaries(manifest) {
+        for platform in &platforms {
+            entries.insert(
+                binary_entry_path(&binary, platform),
+                sha256_of(binary.checksum_for(platform)),
+            );
+        }
+    }
+    for asset in assets {
+        check_entry_path(&asset.path)?;
+        entries.insert(asset.path.clone(), sha256_of(asset.checksum.as_deref()));
+    }
+    for path in license_file
+        .iter()
+        .chain(signature.as_ref().map(|s| &s.signature_file))
+    {
+        check_entry_path(path)?;
+        entries.insert(path.clone(), None);
+    }
+
+    Ok(entries
+        .into_iter()
+        .map(|(path, sha256)| ArchiveEntry {
+            kind: EntryKind::of(manifest, &path),
+            path,
+            sha256,
+        })
+        .collect())
+}
+
+/// A declared checksum as an entry hash, if it is a sha256 one.
+fn sha256_of(checksum: Option<&str>) -> Option<String> {
+    checksum
+        .filter(|c| c.starts_with("sha256:"))
+        .map(|c| c.to_ascii_lowercase())
+}
+
+/// The binary of every plugin in `manifest`.
+fn manifest_binaries(manifest: &Manifest) -> Vec<BinaryInfo> {
+    match manifest {
+        Manifest::Single(m) => vec![m.binary.clone()],
+        Manifest::Package(m) => m.expand_plugins().into_iter().map(|p| p.binary).collect(),
+    }
+}
+
+/// Check that an entry path is relative and stays inside the bundle.
+fn check_entry_path(path: &str) -> Result<(), ManifestError> {
+    let valid = !path.is_empty()
+        && Path::new(path)
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)));
+    if valid {
+        Ok(())
+    } else {
+        Err(ManifestError::Bundle(format!(
+            "Invalid path '{path}' in bundle"
+        )))
+    }
+}
+
+fn append<W: Write>(
+    archive: &mut tar::Builder<W>,
+    path: &str,
+    content: &[u8],
+) -> Result<(), ManifestError> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(content.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(0);
+    header.set_uid(0);
+    header.set_gid(0);
+    header.set_cksum();
+    archive.append_data(&mut header, path, content)?;
+    Ok(())
+}
+
+/// Manifest filenames recognized inside archives.
+const MANIFEST_NAMES: &[&str] = &["plugin.toml", "package.toml"];
+
+/// Largest manifest read from an archive, in bytes.
+pub const MAX_ARCHIVE_MANIFEST_SIZE: usize = 1024 * 1024;
+
+/// Read the manifest from a .zip or .tar.gz archive.
+///
+/// The manifest may sit at the archive root or inside a single top-level
+/// directory. With `verify`, every binary under `bin/<platform>/` is hashed
+/// and compared against the checksum the manifest declares for its
+/// platform. A binary without a checksum, a checksum without a binary or an
+/// unparsable checksum fails verification.
+pub(crate) fn read_archive(path: &Path, verify: bool) -> Result<Manifest, ManifestError> {
+    let mut file = File::open(path)?;
+    let mut magic = [0u8; 4];
+    let read = file.read(&mut magic)?;
+    file.seek(SeekFrom::Start(0))?;
+
+    let zip = match &magic[..read] {
+        [b'P', b'K', 3, 4] => true,
+        [0x1f, 0x8b, ..] => false,
+        _ => {
+            return Err(ManifestError::Bundle(format!(
+                "Unrecognized archive format: {}",
+                path.display()
+            )))
+        }
+    };
+    let (content, prefix) = if zip {
+        read_zip(file)?
+    } else {
+        read_tar_gz(file)?
+    };
+
+    let manifest = Manifest::from_toml(&content)?;
+    if verify {
+        verify_archive_checksums(&manifest, |visit| {
+            for_each_binary(path, zip, &prefix, visit)
+        })?;
+    }
+    Ok(manifest)
+}
+
+/// Find the manifest among archive entry names, returning its directory prefix.
+fn find_manifest(names: &[String]) -> Result<(String, String), ManifestError> {
+    names
+        .iter()
+        .filter_map(|name| {
+            let (prefix, file) = match name.rsplit_once('/') {
+                Some((dir, file)) if !dir.contains('/') => (format!("{dir}/"), file),
+                Some(_) => return None,
+                None => (String::new(), name.as_str()),
+            };
+            MANIFEST_NAMES
+                .contains(&file)
+                .then(|| (name.clone(), prefix))
+        })
+        .min_by_key(|(_, prefix)| prefix.len())
+        .ok_or_else(|| ManifestError::Bundle("No plugin.toml or package.toml in archive".into()))
+}
+
+/// Map an entry path to its platform if it is a binary (`bin/<platform>/<file>`).
+fn binary_platform<'a>(name: &'a str, prefix: &str) -> Option<&'a str> {
+    let rest = name
+        .strip_prefix(prefix)?
+        .strip_prefix(BINARY_DIR)?
+        .strip_prefix('/')?;
+    let (platform, file) = rest.split_once('/')?;
+    (!file.is_empty() && !file.contains('/')).then_some(platform)
+}
+
+/// Archive entry names of a zip archive.
+fn zip_names<R: Read + Seek>(archive: &zip::ZipArchive<R>) -> Result<Vec<String>, ManifestError> {
+    archive
+        .file_names()
+        .map(|name| name.map(|n| n.into_owned()))
+        .collect::<Result<Vec<String>, _>>()
+        .map_err(|e| ManifestError::Bundle(e.to_string()))
+}
+
+/// Read the manifest of a zip archive, returning it and its directory prefix.
+fn read_zip(file: File) -> Result<(String, String), ManifestError> {
+    let mut archive =
+        zip::ZipArchive::new(file).map_err(|e| ManifestError::Bundle(e.to_string()))?;
+    let (manifest_name, prefix) = find_manifest(&zip_names(&archive)?)?;
+
+    let entry = archive
+        .by_name(&manifest_name)
+        .map_err(|e| ManifestError::Bundle(e.to_string()))?;
+    Ok((read_manifest_entry(entry)?, prefix))
+}
+
+/// Read the manifest of a tar.gz archive, returning it and its directory
+/// prefix.
+fn read_tar_gz(file: File) -> Result<(String, String), ManifestError> {
+    // Tar can't seek to an entry, so keep the best manifest candidate of one
+    // scan in memory
+    let mut archive = tar::Archive::new(GzDecoder::new(file));
+    let mut best: Option<(String, String)> = None;
+
+    for entry in archive.entries()? {
+        let entry = entry?;
+        let name = entry.path()?.to_string_lossy().into_owned();
+        let Ok((_, prefix)) = find_manifest(std::slice::from_ref(&name)) else {
+            continue;
+        };
+        if best.as_ref().is_some_and(|(_, p)| p.len() <= prefix.len()) {
+            continue;
+        }
+        best = Some((read_manifest_entry(entry)?, prefix));
+    }
+
+    best.ok_or_else(|| ManifestError::Bundle("No plugin.toml or package.toml in archive".into()))
+}
+
+/// Read a manifest entry of at most `MAX_ARCHIVE_MANIFEST_SIZE` bytes.
+fn read_manifest_entry(entry: impl Read) -> Result<String, ManifestError> {
+    let mut content = String::new();
+    entry
+        .take(MAX_ARCHIVE_MANIFEST_SIZE as u64 + 1)
+        .read_to_string(&mut content)?;
+    if content.len() > MAX_ARCHIVE_MANIFEST_SIZE {
+        return Err(ManifestError::Bundle(format!(
+            "Manifest in archive exceeds {MAX_ARCHIVE_MANIFEST_SIZE} bytes"
+        )));
+    }
+    Ok(content)
+}
+
+/// Call `visit` with the platform and content of every binary below
+/// `prefix`.
+///
+/// The manifest decides which algorithm each binary is hashed with, and in
+/// a tar archive it may come after the binaries, so this is a second pass.
+fn for_each_binary(
+    path: &Path,
+    zip: bool,
+    prefix: &str,
+    visit: &mut dyn FnMut(&str, &mut dyn Read) -> Result<(), ManifestError>,
+) -> Result<(), ManifestError> {
+    let file = File::open(path)?;
+    if zip {
+        let mut archive =
+            zip::ZipArchive::new(file).map_err(|e| ManifestError::Bundle(e.to_string()))?;
+        for name in zip_names(&archive)? {
+            if let Some(platform) = binary_platform(&name, prefix) {
+                let mut entry = archive
+                    .by_name(&name)
+                    .map_err(|e| ManifestError::Bundle(e.to_string()))?;
+                visit(platform, &mut entry)?;
+            }
+        }
+    } else {
+        let mut archive = tar::Archive::new(GzDecoder::new(file));
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            let name = entry.path()?.to_string_lossy().into_owned();
+            if let Some(platform) = binary_platform(&name, prefix) {
+                visit(platform, &mut entry)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Sha256 of everything read from `reader`, as lowercase hex.
+fn sha256_hex(reader: &mut impl Read) -> Result<String, ManifestError> {
+    ChecksumAlgorithm::Sha256.digest(reader)
+}
+
+/// Check every binary `binaries` visits against the manifest checksums, and
+/// that every declared checksum has a binary.
+fn verify_archive_checksums(
+    manifest: &Manifest,
+    binaries: impl FnOnce(
+        &mut dyn FnMut(&str, &mut dyn Read) -> Result<(), ManifestError>,
+    ) -> Result<(), ManifestError>,
+) -> Result<(), ManifestError> {
+    // Package checksums cover the whole archive, not individual entries
+    let binary = match manifest {
+        Manifest::Single(m) => &m.binary,
+        Manifest::Package(_) => return Ok(()),
+    };
+
+    let mut verified: Vec<String> = Vec::new();
+    binaries(&mut |platform, mut content| {
+        let declared =
+            binary
+                .checksum_for(platform)
+                .ok_or_else(|| ManifestError::MissingChecksum {
+                    platform: platform.to_string(),
+                })?;
+        let expected = Checksum::parse(declared)?;
+        let actual = Checksum {
+            algorithm: expected.algorithm,
+            hex: expected.algorithm.digest(&mut content)?,
+        };
+        if actual != expected {
+            return Err(ManifestError::ChecksumMismatch {
+                platform: platform.to_string(),
+                expected: declared.to_string(),
+                actual: actual.to_string(),
+            });
+        }
+        verified.push(platform.to_string());
+        Ok(())
+    })?;
+
+    let declared = binary.checksums.keys().chain(
+        binary
+            .platform
+            .iter()
+            .filter(|(_, o)| o.checksum.is_some())
+            .map(|(platform, _)| platform),
+    );
+    for platform in declared {
+        if find_platform_key(&verified, platform).is_none() {
+            return Err(ManifestError::Bundle(format!(
+                "A checksum is declared for '{platform}', but the archive has no binary for it"
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::PluginManifest;
+
+    const TOML: &str = r#"
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.0.0"
+type = "extension"
+license_file = "LICENSE"
+
+[compatibility]
+platforms = ["linux-x86_64", "darwin-aarch64"]
+
+[binary]
+name = "tool"
+"#;
+
+    fn list_entries(path: &Path) -> Vec<String> {
+        let mut archive = tar::Archive::new(GzDecoder::new(File::open(path).unwrap()));
+        archive
+            .entries()
+            .unwrap()
+            .map(|e| e.unwrap().path().unwrap().to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn test_build_bundle() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("LICENSE"), "MIT").unwrap();
+        std::fs::write(dir.path().join("libtool.so"), b"\x7fELF").unwrap();
+        std::fs::write(dir.path().join("icon.png"), b"png").unwrap();
+
+        let manifest = Manifest::from_toml(TOML).unwrap();
+        let out = dir.path().join(format!("tool.{BUNDLE_EXTENSION}"));
+        BundleBuilder::new(manifest, dir.path())
+            .binary("tool", "linux-x86_64", dir.path().join("libtool.so"))
+            .unwrap()
+            .file("assets/icon.png", dir.path().join("icon.png"))
+            .unwrap()
+            .write(&out)
+            .unwrap();
+
+        assert_eq!(
+            list_entries(&out),
+            vec![
+                "LICENSE",
+                "assets/icon.png",
+                "bin/linux-x86_64/libtool.so",
+                "plugin.toml"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_linearize_matches_builder() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("LICENSE"), "MIT").unwrap();
+        std::fs::write(dir.path().join("libtool.so"), b"binary").unwrap();
+        std::fs::write(dir.path().join("libtool.dylib"), b"macho").unwrap();
+
+        let mut manifest = PluginManifest::from_toml(TOML).unwrap();
+        let checksum = format!("sha256:{}", sha256_hex(&mut &b"binary"[..]).unwrap());
+        manifest
+            .binary
+            .checksums
+            .insert("linux-x86_64".into(), checksum.clone());
+        let manifest = Manifest::Single(manifest);
+
+        let expected = linearize(&manifest).unwrap();
+        let paths: Vec<&str> = expected.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "LICENSE",
+                "bin/darwin-aarch64/libtool.dylib",
+                "bin/linux-x86_64/libtool.so",
+                "plugin.toml"
+            ]
+        );
+        assert_eq!(expected[1].sha256, None);
+        assert_eq!(expected[2].sha256.as_deref(), Some(checksum.as_str()));
+        assert_eq!(expected[3].kind, EntryKind::Manifest);
+
+        let actual = BundleBuilder::new(manifest, dir.path())
+            .binary("tool", "linux-x86_64", dir.path().join("libtool.so"))
+            .unwrap()
+            .binary("tool", "darwin-aarch64", dir.path().join("libtool.dylib"))
+            .unwrap()
+            .linearize()
+            .unwrap();
+        assert_eq!(actual.len(), expected.len());
+        for (actual, expected) in actual.iter().zip(&expected) {
+            assert_eq!(actual.path, expected.path);
+            assert_eq!(actual.kind, expected.kind);
+            if expected.sha256.is_some() {
+                assert_eq!(actual.sha256, expected.sha256);
+            }
+        }
+    }
+
+    #[test]
+    fn test_binary_entries_follow_kind_and_overrides() {
+        let toml = format!(
+            "{TOML}kind = \"wasm\"\n\n[binary.platform.darwin-aarch64]\nname = \"tool_macos\"\n"
+        );
+        let manifest = Manifest::from_toml(&toml).unwrap();
+        let paths: Vec<String> = linearize(&manifest)
+            .unwrap()
+            .into_iter()
+            .filter(|e| matches!(e.kind, EntryKind::Binary { .. }))
+            .map(|e| e.path)
+            .collect();
+        assert_eq!(
+            paths,
+            [
+                "bin/darwin-aarch64/tool_macos.wasm",
+                "bin/linux-x86_64/tool.wasm"
+            ]
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("LICENSE"), "MIT").unwrap();
+        let builder = BundleBuilder::new(manifest, dir.path())
+            .binary("tool", "darwin-aarch64", dir.path().join("LICENSE"))
+            .unwrap();
+        assert!(builder
+            .entries()
+            .unwrap()
+            .contains(&"bin/darwin-aarch64/tool_macos.wasm".to_string()));
+    }
+
+    #[test]
+    fn test_linearize_covers_assets_and_package_checksums() {
+        let toml = format!(
+            "{TOML}\n[[assets]]\npath = \"themes/dark.toml\"\nkind = \"theme\"\nchecksum = \"sha256:ABC\"\n"
+        );
+        let entries = linearize(&Manifest::from_toml(&toml).unwrap()).unwrap();
+        let asset = entries
+            .iter()
+            .find(|e| e.path == "themes/dark.toml")
+            .unwrap();
+        assert_eq!(asset.kind, EntryKind::Asset);
+        assert_eq!(asset.sha256.as_deref(), Some("sha256:abc"));
+
+        let package = r#"
+[package]
+id = "acme.suite"
+name = "Suite"
+version = "1.0.0"
+
+[compatibility]
+platforms = ["linux-x86_64"]
+
+[[plugins]]
+id = "acme.core"
+name = "Core"
+type = "extension"
+binary = "core"
+
+[[plugins.assets]]
+path = "fonts/mono.ttf"
+kind = "font"
+
+[binary.checksums]
+linux-x86_64 = "sha256:def"
+"#;
+        let entries = linearize(&Manifest::from_toml(package).unwrap()).unwrap();
+        let binary = entries
+            .iter()
+            .find(|e| e.path == "bin/linux-x86_64/libcore.so")
+            .unwrap();
+        assert_eq!(binary.sha256.as_deref(), Some("sha256:def"));
+        assert!(entries.iter().any(|e| e.path == "fonts/mono.ttf"));
+    }
+
+    #[test]
+    fn test_bundle_layout_enforced() {
+        let manifest = Manifest::from_toml(TOML).unwrap();
+        let builder = BundleBuilder::new(manifest, "/nonexistent");
+
+        assert!(builder
+            .clone()
+            .binary("other", "linux-x86_64", "x")
+            .is_err());
+        assert!(builder
+            .clone()
+            .binary("tool", "windows-x86_64", "x")
+            .is_err());
+        assert!(builder.clone().file("../escape", "x").is_err());
+        assert!(builder.clone().file("plugin.toml", "x").is_err());
+        assert!(builder.clone().file("bin/custom", "x").is_err());
+        assert!(builder.clone().file("LICENSE", "x").is_err());
+        assert!(builder.clone().binary("tool", "../x", "x").is_err());
+        assert!(builder.clone().binary("tool", "linux/x86_64", "x").is_err());
+
+        // Declared license file is missing
+        assert!(matches!(builder.entries(), Err(ManifestError::Bundle(_))));
+    }
+
+    #[test]
+    fn test_bundle_embeds_declared_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("LICENSE"), "MIT").unwrap();
+        let source = format!("# Maintained by acme\n{TOML}");
+        let builder = BundleBuilder::new(Manifest::from_toml(&source).unwrap(), dir.path());
+
+        assert_eq!(embedded_manifest(&builder), source);
+
+        // Edited manifests are embedded as written out
+        let mut manifest = PluginManifest::from_toml(&source).unwrap();
+        manifest.plugin.version = "1.0.1".into();
+        let builder = BundleBuilder::new(Manifest::Single(manifest), dir.path());
+        let manifest_toml = embedded_manifest(&builder);
+        assert!(!manifest_toml.contains("# Maintained by acme"));
+        assert!(manifest_toml.contains("1.0.1"));
+    }
+
+    /// Write the bundle and read back its manifest entry.
+    fn embedded_manifest(builder: &BundleBuilder) -> String {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("tool.adiplugin");
+        builder.write(&out).unwrap();
+        let mut archive = tar::Archive::new(GzDecoder::new(File::open(&out).unwrap()));
+        let mut content = String::new();
+        for entry in archive.entries().unwrap() {
+            let mut entry = entry.unwrap();
+            if entry.path().unwrap().to_str() == Some("plugin.toml") {
+                entry.read_to_string(&mut content).unwrap();
+            }
+        }
+        content
+    }
+
+    fn write_bundle(dir: &Path, checksum: &str) -> PathBuf {
+        write_bundle_with(dir, &[("linux-x86_64", checksum)])
+    }
+
+    /// Bundle with a linux-x86_64 binary and the given checksums.
+    fn write_bundle_with(dir: &Path, checksums: &[(&str, &str)]) -> PathBuf {
+        std::fs::write(dir.join("LICENSE"), "MIT").unwrap();
+        std::fs::write(dir.join("libtool.so"), b"binary").unwrap();
+        let mut manifest = PluginManifest::from_toml(TOML).unwrap();
+        for (platform, checksum) in checksums {
+            manifest
+                .binary
+                .checksums
+                .insert(platform.to_string(), checksum.to_string());
+        }
+
+        let out = dir.join("tool.adiplugin");
+        BundleBuilder::new(Manifest::Single(manifest), dir)
+            .binary("tool", "linux-x86_64", dir.join("libtool.so"))
+            .unwrap()
+            .write(&out)
+            .unwrap();
+        out
+    }
+
+    #[test]
+    fn test_read_tar_gz_archive() {
+        let dir = tempfile::tempdir().unwrap();
+        let good = format!("sha256:{}", sha256_hex(&mut &b"binary"[..]).unwrap());
+        let out = write_bundle(dir.path(), &good);
+
+        let manifest = Manifest::from_archive(&out).unwrap();
+        assert_eq!(manifest.id(), "acme.tool");
+        assert!(Manifest::from_archive_verified(&out).is_ok());
+
+        let out = write_bundle(dir.path(), "sha256:0000");
+        assert!(Manifest::from_archive(&out).is_ok());
+        assert!(matches!(
+            Manifest::from_archive_verified(&out),
+            Err(ManifestError::ChecksumMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_verification_covers_every_binary() {
+        let dir = tempfile::tempdir().unwrap();
+        let sha512 = ChecksumAlgorithm::Sha512
+            .digest(&mut &b"binary"[..])
+            .unwrap();
+        let out = write_bundle(dir.path(), &format!("sha512:{sha512}"));
+        assert!(Manifest::from_archive_verified(&out).is_ok());
+
+        let out = write_bundle(dir.path(), "md5:0000");
+        assert!(Manifest::from_archive_verified(&out).is_err());
+
+        // Binary without a checksum
+        let out = write_bundle_with(dir.path(), &[]);
+        assert!(matches!(
+            Manifest::from_archive_verified(&out),
+            Err(ManifestError::MissingChecksum { .. })
+        ));
+
+        // Checksum without a binary
+        let good = format!("sha256:{}", sha256_hex(&mut &b"binary"[..]).unwrap());
+        let out = write_bundle_with(
+            dir.path(),
+            &[("linux-x86_64", good.as_str()), ("darwin-aarch64", good.as_str())],
+        );
+        assert!(matches!(
+            Manifest::from_archive_verified(&out),
+            Err(ManifestError::Bundle(_))
+        ));
+    }
+
+    #[test]
+    fn test_read_zip_archive() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("tool.zip");
+        let manifest = format!(
+            "{TOML}\n[binary.checksums]\nlinux-x86_64 = \"sha256:{}\"\n",
+            sha256_hex(&mut &b"binary"[..]).unwrap()
+        );
+        let mut zip = zip::ZipWriter::new(File::create(&out).unwrap());
+        let options = zip::write::SimpleFileOptions::default();
+        zip.start_file("tool-1.0.0/README.md", options).unwrap();
+        zip.write_all(b"readme").unwrap();
+        zip.start_file("tool-1.0.0/plugin.toml", options).unwrap();
+        zip.write_all(manifest.as_bytes()).unwrap();
+        zip.start_file("tool-1.0.0/bin/linux-x86_64/libtool.so", options)
+            .unwrap();
+        zip.write_all(b"binary").unwrap();
+        zip.finish().unwrap();
+
+        let manifest = Manifest::from_archive_verified(&out).unwrap();
+        assert_eq!(manifest.id(), "acme.tool");
+    }
+
+    #[test]
+    fn test_oversized_archive_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("huge.adiplugin");
+        let mut archive = tar::Builder::new(GzEncoder::new(
+            File::create(&out).unwrap(),
+            Compression::default(),
+        ));
+        let content = format!("#{}\n{TOML}", "x".repeat(MAX_ARCHIVE_MANIFEST_SIZE));
+        append(&mut archive, "plugin.toml", content.as_bytes()).unwrap();
+        archive.into_inner().unwrap().finish().unwrap();
+
+        assert!(matches!(
+            Manifest::from_archive(&out),
+            Err(ManifestError::Bundle(_))
+        ));
+    }
+
+    #[test]
+    fn test_archive_without_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("empty.zip");
+        let mut zip = zip::ZipWriter::new(File::create(&out).unwrap());
+        zip.start_file("README.md", zip::write::SimpleFileOptions::default())
+            .unwrap();
+        zip.finish().unwrap();
+        assert!(matches!(
+            Manifest::from_archive(&out),
+            Err(ManifestError::Bundle(_))
+        ));
+    }
+}
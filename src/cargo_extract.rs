@@ -3,10 +3,21 @@
 use std::path::Path;
 
 use crate::error::ManifestError;
+use crate::migrate::CURRENT_MANIFEST_VERSION;
+use crate::options::{ManifestOrigin, ParseOptions, API_VERSION_FIELD};
 use crate::plugin::*;
+use crate::plugin_type::PluginType;
 
 /// Generate a `PluginManifest` from a Cargo.toml with `[package.metadata.plugin]`.
 pub fn generate_manifest_from_cargo(cargo_toml_path: &Path) -> Result<PluginManifest, ManifestError> {
+    generate_manifest_from_cargo_with(cargo_toml_path, &ParseOptions::default())
+}
+
+/// Like `generate_manifest_from_cargo()`, with custom parse options.
+pub fn generate_manifest_from_cargo_with(
+    cargo_toml_path: &Path,
+    options: &ParseOptions,
+) -> Result<PluginManifest, ManifestError> {
     let content = std::fs::read_to_string(cargo_toml_path)?;
     let doc: toml::Value = toml::from_str(&content).map_err(ManifestError::TomlParse)?;
 
@@ -42,11 +53,19 @@ pub fn generate_manifest_from_cargo(cargo_toml_path: &Path) -> Result<PluginMani
     let plugin_type = metadata_plugin
         .get("type")
         .and_then(|v| v.as_str())
-        .ok_or_else(|| ManifestError::MissingField("package.metadata.plugin.type".into()))?
-        .to_string();
+        .map(PluginType::parse)
+        .ok_or_else(|| ManifestError::MissingField("package.metadata.plugin.type".into()))?;
 
     // Compatibility
-    let compatibility = parse_compatibility(metadata_plugin);
+    let compatibility = parse_compatibility(metadata_plugin, options.default_api_version);
+    let mut defaulted_fields = Vec::new();
+    if metadata_plugin
+        .get("compatibility")
+        .and_then(|c| c.get("api_version"))
+        .is_none()
+    {
+        defaulted_fields.push(API_VERSION_FIELD.to_string());
+    }
 
     // CLI config
     let cli = parse_cli(metadata_plugin);
@@ -72,13 +91,112 @@ pub fn generate_manifest_from_cargo(cargo_toml_path: &Path) -> Result<PluginMani
     // Language
     let language = parse_language(metadata_plugin);
 
+    // Theme
+    let theme = parse_theme(metadata_plugin);
+
     // Requirements
     let requirements = parse_requirements(metadata_plugin);
 
     // Capabilities
     let capabilities = parse_capabilities(metadata_plugin);
 
+    // Hooks
+    let hooks = metadata_plugin.get("hooks").cloned().and_then(|v| v.try_into().ok());
+
+    // Uninstall cleanup
+    let uninstall = metadata_plugin
+        .get("uninstall")
+        .cloned()
+        .and_then(|v| v.try_into().ok());
+
+    // Post-install message
+    let post_install_message = metadata_plugin
+        .get("post_install_message")
+        .cloned()
+        .and_then(|v| v.try_into().ok());
+
+    // Migrations
+    let migrations = metadata_plugin
+        .get("migrations")
+        .cloned()
+        .and_then(|v| v.try_into().ok())
+        .unwrap_or_default();
+
+    // Storage
+    let storage = metadata_plugin
+        .get("storage")
+        .cloned()
+        .and_then(|v| v.try_into().ok());
+
+    // Permissions
+    let permissions = metadata_plugin
+        .get("permissions")
+        .cloned()
+        .and_then(|v| v.try_into().ok());
+
+    // Messaging
+    let messaging = metadata_plugin
+        .get("messaging")
+        .cloned()
+        .and_then(|v| v.try_into().ok())
+        .unwrap_or_default();
+
+    // Assets
+    let assets = metadata_plugin
+        .get("assets")
+        .cloned()
+        .and_then(|v| v.try_into().ok())
+        .unwrap_or_default();
+
+    // Resources
+    let resources = metadata_plugin
+        .get("resources")
+        .cloned()
+        .and_then(|v| v.try_into().ok());
+
+    // Health check
+    let health = metadata_plugin
+        .get("health")
+        .cloned()
+        .and_then(|v| v.try_into().ok());
+
+    // Observability
+    let observability = metadata_plugin
+        .get("observability")
+        .cloned()
+        .and_then(|v| v.try_into().ok());
+
+    // Logging
+    let logging = metadata_plugin
+        .get("logging")
+        .cloned()
+        .and_then(|v| v.try_into().ok());
+
+    // Conditional sections and localization
+    let target = metadata_plugin
+        .get("target")
+        .cloned()
+        .and_then(|v| v.try_into().ok())
+        .unwrap_or_default();
+    let features = metadata_plugin
+        .get("features")
+        .cloned()
+        .and_then(|v| v.try_into().ok())
+        .unwrap_or_default();
+    let locales = metadata_plugin
+        .get("locales")
+        .cloned()
+        .and_then(|v| v.try_into().ok())
+        .unwrap_or_default();
+
+    // Host API shims
+    let compat = metadata_plugin
+        .get("compat")
+        .cloned()
+        .and_then(|v| v.try_into().ok());
+
     Ok(PluginManifest {
+        manifest_version: CURRENT_MANIFEST_VERSION,
         plugin: PluginMeta {
             id,
             name,
@@ -88,6 +206,8 @@ pub fn generate_manifest_from_cargo(cargo_toml_path: &Path) -> Result<PluginMani
             description,
             license: None,
             homepage: None,
+            repository: None,
+            license_file: None,
         },
         compatibility,
         binary,
@@ -101,7 +221,28 @@ pub fn generate_manifest_from_cargo(cargo_toml_path: &Path) -> Result<PluginMani
         hive,
         translation,
         language,
+        theme,
         requirements,
+        hooks,
+        uninstall,
+        post_install_message,
+        migrations,
+        storage,
+        permissions,
+        messaging,
+        assets,
+        resources,
+        health,
+        observability,
+        logging,
+        target,
+        features,
+        locales,
+        compat,
+        origin: ManifestOrigin {
+            defaulted_fields,
+            ..Default::default()
+        },
     })
 }
 
@@ -165,17 +306,23 @@ fn resolve_author(package: &toml::Value) -> String {
         .to_string()
 }
 
-fn parse_compatibility(meta: &toml::Value) -> CompatibilityInfo {
+fn parse_compatibility(meta: &toml::Value, default_api_version: u32) -> CompatibilityInfo {
     let compat = match meta.get("compatibility") {
         Some(c) => c,
-        None => return CompatibilityInfo::default(),
+        None => {
+            return CompatibilityInfo {
+                api_version: default_api_version,
+                ..CompatibilityInfo::default()
+            }
+        }
     };
 
     CompatibilityInfo {
         api_version: compat
             .get("api_version")
             .and_then(|v| v.as_integer())
-            .unwrap_or(2) as u32,
+            .map(|v| v as u32)
+            .unwrap_or(default_api_version),
         min_host_version: compat
             .get("min_host_version")
             .and_then(|v| v.as_str())
@@ -194,6 +341,16 @@ fn parse_compatibility(meta: &toml::Value) -> CompatibilityInfo {
             .and_then(|v| v.as_array())
             .map(|a| a.iter().filter_map(|v| v.as_str().map(String::from)).collect())
             .unwrap_or_default(),
+        hosts: compat
+            .get("hosts")
+            .and_then(|v| v.as_array())
+            .map(|a| a.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+            .unwrap_or_default(),
+        host_versions: compat
+            .get("host_versions")
+            .cloned()
+            .and_then(|v| v.try_into().ok())
+            .unwrap_or_default(),
     }
 }
 
@@ -215,6 +372,16 @@ fn parse_cli(meta: &toml::Value) -> Option<CliConfig> {
             .get("dynamic_completions")
             .and_then(|v| v.as_bool())
             .unwrap_or(false),
+        args: cli
+            .get("args")
+            .cloned()
+            .and_then(|v| v.try_into().ok())
+            .unwrap_or_default(),
+        subcommands: cli
+            .get("subcommands")
+            .cloned()
+            .and_then(|v| v.try_into().ok())
+            .unwrap_or_default(),
     })
 }
 
@@ -236,6 +403,14 @@ fn parse_provides(meta: &toml::Value) -> Vec<ServiceDeclaration> {
                             .and_then(|v| v.as_str())
                             .unwrap_or("")
                             .to_string(),
+                        deprecated: item
+                            .get("deprecated")
+                            .and_then(|v| v.as_bool())
+                            .unwrap_or(false),
+                        replaced_by: item
+                            .get("replaced_by")
+                            .and_then(|v| v.as_str())
+                            .map(String::from),
                     })
                 })
                 .collect()
@@ -260,6 +435,11 @@ fn parse_requires(meta: &toml::Value) -> Vec<ServiceRequirement> {
                             .get("optional")
                             .and_then(|v| v.as_bool())
                             .unwrap_or(false),
+                        provider: item
+                            .get("provider")
+                            .cloned()
+                            .and_then(|v| v.try_into().ok())
+                            .unwrap_or_default(),
                     })
                 })
                 .collect()
@@ -276,6 +456,22 @@ fn parse_binary(meta: &toml::Value) -> BinaryInfo {
                 .unwrap_or("plugin")
                 .to_string(),
             checksums: Default::default(),
+            sizes: Default::default(),
+            path: b.get("path").and_then(|v| v.as_str()).map(String::from),
+            platform: b
+                .get("platform")
+                .cloned()
+                .and_then(|v| v.try_into().ok())
+                .unwrap_or_default(),
+            kind: b
+                .get("kind")
+                .cloned()
+                .and_then(|v| v.try_into().ok())
+                .unwrap_or_default(),
+            abi_requirements: b
+                .get("abi_requirements")
+                .cloned()
+                .and_then(|v| v.try_into().ok()),
         },
         None => BinaryInfo::default(),
     }
@@ -306,32 +502,18 @@ fn parse_hive(meta: &toml::Value) -> Option<HiveInfo> {
 }
 
 fn parse_translation(meta: &toml::Value) -> Option<TranslationInfo> {
-    let tr = meta.get("translation")?;
-    Some(TranslationInfo {
-        translates: tr.get("translates")?.as_str()?.to_string(),
-        language: tr.get("language")?.as_str()?.to_string(),
-        language_name: tr
-            .get("language_name")
-            .and_then(|v| v.as_str())
-            .unwrap_or("")
-            .to_string(),
-        namespace: tr
-            .get("namespace")
-            .and_then(|v| v.as_str())
-            .unwrap_or("")
-            .to_string(),
-    })
+    meta.get("translation").cloned()?.try_into().ok()
 }
 
 fn parse_language(meta: &toml::Value) -> Option<LanguageInfo> {
-    let lang = meta.get("language")?;
-    Some(LanguageInfo {
-        id: lang.get("id")?.as_str()?.to_string(),
-        extensions: lang
-            .get("extensions")
-            .and_then(|v| v.as_array())
-            .map(|a| a.iter().filter_map(|v| v.as_str().map(String::from)).collect())
-            .unwrap_or_default(),
+    meta.get("language").cloned()?.try_into().ok()
+}
+
+fn parse_theme(meta: &toml::Value) -> Option<ThemeInfo> {
+    let theme = meta.get("theme")?;
+    Some(ThemeInfo {
+        appearance: theme.get("appearance")?.as_str()?.to_string(),
+        extends: theme.get("extends").and_then(|v| v.as_str()).map(String::from),
     })
 }
 
@@ -349,21 +531,7 @@ fn parse_capabilities(meta: &toml::Value) -> Vec<CapabilityDeclaration> {
         .and_then(|v| v.as_array())
         .map(|arr| {
             arr.iter()
-                .filter_map(|item| {
-                    Some(CapabilityDeclaration {
-                        protocol: item.get("protocol")?.as_str()?.to_string(),
-                        version: item
-                            .get("version")
-                            .and_then(|v| v.as_str())
-                            .unwrap_or("1.0.0")
-                            .to_string(),
-                        description: item
-                            .get("description")
-                            .and_then(|v| v.as_str())
-                            .unwrap_or("")
-                            .to_string(),
-                    })
-                })
+                .filter_map(|item| item.clone().try_into().ok())
                 .collect()
         })
         .unwrap_or_default()
@@ -419,7 +587,7 @@ categories = ["tasks", "workflow"]
         assert_eq!(manifest.plugin.id, "adi.tasks");
         assert_eq!(manifest.plugin.name, "ADI Tasks");
         assert_eq!(manifest.plugin.version, "0.8.8");
-        assert_eq!(manifest.plugin.plugin_type, "core");
+        assert_eq!(manifest.plugin.plugin_type, PluginType::Core);
         assert_eq!(manifest.plugin.author, "ADI Team");
         assert_eq!(manifest.plugin.description, "Task management with dependency tracking");
         assert_eq!(manifest.compatibility.api_version, 3);
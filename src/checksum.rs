@@ -0,0 +1,221 @@
+//! Binary checksum computation and verification.
+//!
+//! Checksums in `[binary.checksums]` are written as `<algorithm>:<hex>`:
+//!
+//! ```toml
+//! [binary.checksums]
+//! darwin-aarch64 = "sha256:9f86d081884c7d65..."
+//! linux-x86_64 = "blake3:af1349b9f5f9a1a6..."
+//! ```
+//!
+//! Bare hex digests are read as sha256.
+
+use std::fmt;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use std::str::FromStr;
+
+use sha2::{Digest, Sha256, Sha512};
+
+use crate::error::ManifestError;
+use crate::platform::current_platform;
+use crate::plugin::PluginManifest;
+
+/// Supported checksum algorithms.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ChecksumAlgorithm {
+    /// SHA-256 (the default)
+    #[default]
+    Sha256,
+    /// SHA-512
+    Sha512,
+    /// BLAKE3
+    Blake3,
+}
+
+impl ChecksumAlgorithm {
+    /// Prefix used in checksum strings (e.g., "sha256").
+    pub fn name(self) -> &'static str {
+        match self {
+            ChecksumAlgorithm::Sha256 => "sha256",
+            ChecksumAlgorithm::Sha512 => "sha512",
+            ChecksumAlgorithm::Blake3 => "blake3",
+        }
+    }
+
+    /// Hash everything read from `reader`, returning the hex digest.
+    pub fn digest(self, reader: &mut impl Read) -> Result<String, ManifestError> {
+        let bytes = match self {
+            ChecksumAlgorithm::Sha256 => {
+                let mut hasher = Sha256::new();
+                std::io::copy(reader, &mut hasher)?;
+                hasher.finalize().to_vec()
+            }
+            ChecksumAlgorithm::Sha512 => {
+                let mut hasher = Sha512::new();
+                std::io::copy(reader, &mut hasher)?;
+                hasher.finalize().to_vec()
+            }
+            ChecksumAlgorithm::Blake3 => {
+                let mut hasher = blake3::Hasher::new();
+                std::io::copy(reader, &mut hasher)?;
+                hasher.finalize().as_bytes().to_vec()
+            }
+        };
+        Ok(bytes.iter().map(|b| format!("{b:02x}")).collect())
+    }
+}
+
+impl fmt::Display for ChecksumAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for ChecksumAlgorithm {
+    type Err = ManifestError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sha256" => Ok(ChecksumAlgorithm::Sha256),
+            "sha512" => Ok(ChecksumAlgorithm::Sha512),
+            "blake3" => Ok(ChecksumAlgorithm::Blake3),
+            other => Err(ManifestError::InvalidFormat(format!(
+                "Unknown checksum algorithm '{other}'"
+            ))),
+        }
+    }
+}
+
+/// A parsed `<algorithm>:<hex>` checksum.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Checksum {
+    /// Hash algorithm
+    pub algorithm: ChecksumAlgorithm,
+    /// Lowercase hex digest
+    pub hex: String,
+}
+
+impl Checksum {
+    /// Parse a checksum string; bare hex digests are sha256.
+    pub fn parse(s: &str) -> Result<Self, ManifestError> {
+        let (algorithm, hex) = match s.split_once(':') {
+            Some((algorithm, hex)) => (algorithm.parse()?, hex),
+            None => (ChecksumAlgorithm::Sha256, s),
+        };
+        if hex.is_empty() || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(ManifestError::InvalidFormat(format!(
+                "Invalid checksum '{s}'"
+            )));
+        }
+        Ok(Self {
+            algorithm,
+            hex: hex.to_ascii_lowercase(),
+        })
+    }
+}
+
+impl fmt::Display for Checksum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.algorithm, self.hex)
+    }
+}
+
+/// Compute the sha256 checksum of a file, as `sha256:<hex>`.
+pub fn compute_file_checksum(path: &Path) -> Result<String, ManifestError> {
+    compute_file_checksum_with(path, ChecksumAlgorithm::Sha256)
+}
+
+/// Compute a file checksum with a specific algorithm.
+pub fn compute_file_checksum_with(
+    path: &Path,
+    algorithm: ChecksumAlgorithm,
+) -> Result<String, ManifestError> {
+    let hex = algorithm.digest(&mut File::open(path)?)?;
+    Ok(Checksum { algorithm, hex }.to_string())
+}
+
+impl PluginManifest {
+    /// Verify a binary against the checksum declared for the current platform.
+    pub fn verify_binary(&self, path: &Path) -> Result<(), ManifestError> {
+        self.verify_binary_for(path, &current_platform())
+    }
+
+    /// Verify a binary against the checksum declared for `platform`.
+    pub fn verify_binary_for(&self, path: &Path, platform: &str) -> Result<(), ManifestError> {
+        let declared =
+            self.binary
+                .checksum_for(platform)
+                .ok_or_else(|| ManifestError::MissingChecksum {
+                    platform: platform.to_string(),
+                })?;
+        let expected = Checksum::parse(declared)?;
+        let actual = compute_file_checksum_with(path, expected.algorithm)?;
+        if actual == expected.to_string() {
+            Ok(())
+        } else {
+            Err(ManifestError::ChecksumMismatch {
+                platform: platform.to_string(),
+                expected: declared.to_string(),
+                actual,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compute_file_checksum() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("plugin.bin");
+        std::fs::write(&path, "test").unwrap();
+
+        assert_eq!(
+            compute_file_checksum(&path).unwrap(),
+            "sha256:9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+        );
+        let blake3 = compute_file_checksum_with(&path, ChecksumAlgorithm::Blake3).unwrap();
+        assert_eq!(
+            Checksum::parse(&blake3).unwrap().algorithm,
+            ChecksumAlgorithm::Blake3
+        );
+        assert!(Checksum::parse("md5:abc").is_err());
+        assert_eq!(Checksum::parse("ABC").unwrap().to_string(), "sha256:abc");
+    }
+
+    #[test]
+    fn test_verify_binary() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("plugin.bin");
+        std::fs::write(&path, "test").unwrap();
+        let sha512 = compute_file_checksum_with(&path, ChecksumAlgorithm::Sha512).unwrap();
+
+        let toml = format!(
+            r#"
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.0.0"
+type = "extension"
+
+[binary.checksums]
+linux-x86_64 = "{sha512}"
+darwin-aarch64 = "sha256:0000"
+"#
+        );
+        let manifest = PluginManifest::from_toml(&toml).unwrap();
+        manifest.verify_binary_for(&path, "linux-x86_64").unwrap();
+        assert!(matches!(
+            manifest.verify_binary_for(&path, "darwin-aarch64"),
+            Err(ManifestError::ChecksumMismatch { .. })
+        ));
+        assert!(matches!(
+            manifest.verify_binary_for(&path, "windows-x86_64"),
+            Err(ManifestError::MissingChecksum { .. })
+        ));
+    }
+}
@@ -0,0 +1,850 @@
+//! CLI command trees (`[[cli.subcommands]]`).
+//!
+//! Plugins with a `[cli]` section can describe their subcommands and
+//! arguments, so hosts build help output and shell completions without
+//! loading the plugin binary:
+//!
+//! ```toml
+//! [cli]
+//! command = "tasks"
+//! description = "Task management"
+//!
+//! [[cli.subcommands]]
+//! name = "add"
+//! description = "Add a task"
+//! aliases = ["a"]
+//!
+//! [[cli.subcommands.args]]
+//! name = "title"
+//! required = true
+//!
+//! [[cli.subcommands.args]]
+//! name = "priority"
+//! long = "priority"
+//! short = "p"
+//! choices = ["low", "normal", "high"]
+//! ```
+//!
+//! Arguments with `long` or `short` are options; the rest are positional,
+//! in declaration order.
+//!
+//! [`generate_completions`] turns the tree into a shell completion script.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::plugin::CliConfig;
+use crate::validate::ValidationIssue;
+
+/// A subcommand of a plugin command (e.g., `adi tasks add`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CliSubcommand {
+    /// Subcommand name (lowercase alphanumeric with hyphens)
+    pub name: String,
+
+    /// Human-readable description for --help output
+    #[serde(default)]
+    pub description: String,
+
+    /// Alternative names
+    #[serde(default)]
+    pub aliases: Vec<String>,
+
+    /// Arguments
+    #[serde(default)]
+    pub args: Vec<CliArg>,
+
+    /// Nested subcommands
+    #[serde(default)]
+    pub subcommands: Vec<CliSubcommand>,
+}
+
+impl CliSubcommand {
+    /// Check if `name` is this subcommand's name or one of its aliases.
+    pub fn is_named(&self, name: &str) -> bool {
+        self.name == name || self.aliases.iter().any(|a| a == name)
+    }
+}
+
+/// An argument or option of a command.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CliArg {
+    /// Argument name (shown as `<name>` in usage)
+    pub name: String,
+
+    /// Human-readable description
+    #[serde(default)]
+    pub description: String,
+
+    /// Value type
+    #[serde(default, rename = "type")]
+    pub arg_type: CliArgType,
+
+    /// Whether the argument must be given
+    #[serde(default)]
+    pub required: bool,
+
+    /// Long option name without dashes (e.g., "priority" for `--priority`)
+    #[serde(default)]
+    pub long: Option<String>,
+
+    /// Short option letter without the dash (e.g., "p" for `-p`)
+    #[serde(default)]
+    pub short: Option<String>,
+
+    /// Allowed values (empty = any)
+    #[serde(default)]
+    pub choices: Vec<String>,
+}
+
+impl CliArg {
+    /// Check if the argument is positional rather than an option.
+    pub fn is_positional(&self) -> bool {
+        self.long.is_none() && self.short.is_none()
+    }
+}
+
+/// Value type of a CLI argument.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum CliArgType {
+    /// Free-form text
+    #[default]
+    String,
+    /// File system path
+    Path,
+    /// Number
+    Number,
+    /// Switch without a value (options only)
+    Bool,
+}
+
+impl CliConfig {
+    /// Find a subcommand by its path below the plugin command, resolving
+    /// aliases (e.g., `["add"]` or `["a"]` for `adi tasks add`).
+    pub fn subcommand(&self, path: &[&str]) -> Option<&CliSubcommand> {
+        let (first, rest) = path.split_first()?;
+        let mut current = self.subcommands.iter().find(|s| s.is_named(first))?;
+        for name in rest {
+            current = current.subcommands.iter().find(|s| s.is_named(name))?;
+        }
+        Some(current)
+    }
+
+    /// Check subcommand and argument declarations.
+    pub(crate) fn check_tree(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        issues.extend(check_token("cli.command", &self.command));
+        for (i, alias) in self.aliases.iter().enumerate() {
+            issues.extend(check_token(&format!("cli.aliases[{i}]"), alias));
+        }
+        issues.extend(check_args("cli.args", &self.args));
+        check_subcommands("cli.subcommands", &self.subcommands, &mut issues);
+        issues
+    }
+}
+
+/// Name of the host binary plugin commands hang off (`adi <command>`).
+pub const HOST_COMMAND: &str = "adi";
+
+/// Shell to generate completions for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+    PowerShell,
+}
+
+impl Shell {
+    /// All supported shells.
+    pub const ALL: [Shell; 4] = [Shell::Bash, Shell::Zsh, Shell::Fish, Shell::PowerShell];
+
+    /// Shell name as accepted by `from_str()`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Shell::Bash => "bash",
+            Shell::Zsh => "zsh",
+            Shell::Fish => "fish",
+            Shell::PowerShell => "powershell",
+        }
+    }
+}
+
+impl fmt::Display for Shell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Shell {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pwsh" => Ok(Shell::PowerShell),
+            _ => Shell::ALL
+                .into_iter()
+                .find(|shell| shell.name() == s)
+                .ok_or_else(|| format!("Unknown shell '{s}'")),
+        }
+    }
+}
+
+/// Generate completions for `adi <command> ...`.
+///
+/// Fish completions are additive, so the fish script registers the command
+/// directly. For bash, zsh and PowerShell the host owns the completion of
+/// `adi`; the script defines `_adi_<command>`, which the host's completion
+/// calls once the plugin command (or an alias) is the first word:
+///
+/// - bash: with `COMP_WORDS`/`COMP_CWORD` set, fills `COMPREPLY`
+/// - zsh: with `words`/`CURRENT` set, adds matches
+/// - PowerShell: `_adi_<command> $words $current`, where `$words` are the
+///   words before the cursor; returns the candidates
+pub fn generate_completions(cli: &CliConfig, shell: Shell) -> String {
+    generate_completions_for(cli, shell, HOST_COMMAND)
+}
+
+/// Generate completions for a host binary other than `adi`.
+pub fn generate_completions_for(cli: &CliConfig, shell: Shell, host: &str) -> String {
+    let mut nodes = Vec::new();
+    collect_nodes(cli.command.clone(), &cli.args, &cli.subcommands, &mut nodes);
+    let function = format!("_{}_{}", ident(host), ident(&cli.command));
+    match shell {
+        Shell::Bash | Shell::Zsh => posix_completions(cli, &nodes, &function, shell),
+        Shell::Fish => fish_completions(cli, &nodes, &function, host),
+        Shell::PowerShell => powershell_completions(cli, &nodes, &function),
+    }
+}
+
+/// A command or subcommand, keyed by its space-separated path
+/// (e.g., "tasks add").
+struct Node<'a> {
+    key: String,
+    args: &'a [CliArg],
+    subcommands: &'a [CliSubcommand],
+}
+
+/// What to complete as the value of an option.
+enum ValueCompletion<'a> {
+    Choices(&'a [String]),
+    Files,
+    Nothing,
+}
+
+impl<'a> Node<'a> {
+    /// Subcommand names and aliases, with the key they lead to.
+    fn transitions(&self) -> Vec<(Vec<&'a str>, String)> {
+        self.subcommands
+            .iter()
+            .map(|sub| {
+                let names = std::iter::once(&sub.name)
+                    .chain(&sub.aliases)
+                    .map(String::as_str)
+                    .collect();
+                (names, format!("{} {}", self.key, sub.name))
+            })
+            .collect()
+    }
+
+    /// Words completed at this node: subcommands, options and positional
+    /// choices.
+    fn words(&self) -> Vec<String> {
+        let subcommands = self
+            .subcommands
+            .iter()
+            .flat_map(|s| std::iter::once(&s.name).chain(&s.aliases))
+            .cloned();
+        let options = self.args.iter().flat_map(option_flags);
+        let choices = self
+            .args
+            .iter()
+            .filter(|a| a.is_positional())
+            .flat_map(|a| a.choices.iter().cloned());
+        subcommands.chain(options).chain(choices).collect()
+    }
+
+    /// Whether a positional argument takes a path.
+    fn completes_files(&self) -> bool {
+        self.args
+            .iter()
+            .any(|a| a.is_positional() && a.arg_type == CliArgType::Path)
+    }
+
+    /// Options taking a value, with their flags and value completion.
+    fn value_options(&self) -> Vec<(Vec<String>, ValueCompletion<'a>)> {
+        self.args
+            .iter()
+            .filter(|a| !a.is_positional() && a.arg_type != CliArgType::Bool)
+            .map(|a| {
+                let value = if !a.choices.is_empty() {
+                    ValueCompletion::Choices(&a.choices)
+                } else if a.arg_type == CliArgType::Path {
+                    ValueCompletion::Files
+                } else {
+                    ValueCompletion::Nothing
+                };
+                (option_flags(a), value)
+            })
+            .collect()
+    }
+}
+
+fn collect_nodes<'a>(
+    key: String,
+    args: &'a [CliArg],
+    subcommands: &'a [CliSubcommand],
+    nodes: &mut Vec<Node<'a>>,
+) {
+    nodes.push(Node {
+        key: key.clone(),
+        args,
+        subcommands,
+    });
+    for sub in subcommands {
+        collect_nodes(
+            format!("{key} {}", sub.name),
+            &sub.args,
+            &sub.subcommands,
+            nodes,
+        );
+    }
+}
+
+/// `--long` and `-s` spellings of an option.
+fn option_flags(arg: &CliArg) -> Vec<String> {
+    let long = arg.long.as_ref().map(|l| format!("--{l}"));
+    let short = arg.short.as_ref().map(|s| format!("-{s}"));
+    long.into_iter().chain(short).collect()
+}
+
+/// Shell identifier from a command name ("hive-plugin" -> "hive_plugin").
+fn ident(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// Text safe to put on a comment line: control characters (newlines in
+/// particular) are dropped.
+fn comment(s: &str) -> String {
+    s.chars().filter(|c| !c.is_control()).collect()
+}
+
+/// Single-quote a word for `shell`.
+fn quote(s: &str, shell: Shell) -> String {
+    match shell {
+        Shell::Fish => format!("'{}'", s.replace('\\', "\\\\").replace('\'', "\\'")),
+        // PowerShell also ends single-quoted strings at the typographic
+        // single quotes; any of them is escaped by doubling it
+        Shell::PowerShell => {
+            let mut quoted = String::from("'");
+            for c in s.chars() {
+                if matches!(c, '\'' | '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}') {
+                    quoted.push(c);
+                }
+                quoted.push(c);
+            }
+            quoted.push('\'');
+            quoted
+        }
+        Shell::Bash | Shell::Zsh => format!("'{}'", s.replace('\'', r"'\''")),
+    }
+}
+
+fn posix_completions(cli: &CliConfig, nodes: &[Node], function: &str, shell: Shell) -> String {
+    let q = |s: &str| quote(s, shell);
+    let (prelude, word, first, words_to) = match shell {
+        Shell::Bash => (
+            r#"local cur="${COMP_WORDS[COMP_CWORD]}" prev="${COMP_WORDS[COMP_CWORD-1]}" w"#,
+            "${COMP_WORDS[i]}",
+            2,
+            "COMP_CWORD",
+        ),
+        _ => (
+            r#"local prev="${words[CURRENT-1]}""#,
+            "${words[i]}",
+            3,
+            "CURRENT",
+        ),
+    };
+    let complete = |words: &[String], files: bool| match shell {
+        // Candidates are a quoted word list filtered in bash itself;
+        // `compgen -W` would expand `$(...)` inside manifest strings
+        Shell::Bash => {
+            let mut reply = String::from("COMPREPLY=(); for w in");
+            for w in words {
+                reply.push(' ');
+                reply.push_str(&q(w));
+            }
+            reply.push_str("; do [[ $w == \"$cur\"* ]] && COMPREPLY+=(\"$w\"); done");
+            if files {
+                reply.push_str("; COMPREPLY+=($(compgen -f -- \"$cur\"))");
+            }
+            reply
+        }
+        _ => {
+            let mut add = String::from("compadd --");
+            for w in words {
+                add.push(' ');
+                add.push_str(&q(w));
+            }
+            if files {
+                add.push_str("; _files");
+            }
+            add
+        }
+    };
+
+    let mut out = format!(
+        "# {shell} completions for the `{}` plugin command\n{function}() {{\n    {prelude}\n    local node={} i\n    for ((i = {first}; i < {words_to}; i++)); do\n        case \"$node:{word}\" in\n",
+        comment(&cli.command),
+        q(&cli.command),
+    );
+    for node in nodes {
+        for (names, target) in node.transitions() {
+            let patterns: Vec<String> = names
+                .iter()
+                .map(|n| q(&format!("{}:{n}", node.key)))
+                .collect();
+            out.push_str(&format!(
+                "            {}) node={} ;;\n",
+                patterns.join("|"),
+                q(&target)
+            ));
+        }
+    }
+    out.push_str("        esac\n    done\n    case \"$node:$prev\" in\n");
+    for node in nodes {
+        for (flags, value) in node.value_options() {
+            let patterns: Vec<String> = flags
+                .iter()
+                .map(|f| q(&format!("{}:{f}", node.key)))
+                .collect();
+            let action = match value {
+                ValueCompletion::Choices(choices) => complete(choices, false),
+                ValueCompletion::Files => complete(&[], true),
+                ValueCompletion::Nothing => match shell {
+                    Shell::Bash => "COMPREPLY=()".to_string(),
+                    _ => ":".to_string(),
+                },
+            };
+            out.push_str(&format!(
+                "        {}) {action}; return ;;\n",
+                patterns.join("|")
+            ));
+        }
+    }
+    out.push_str("    esac\n    case \"$node\" in\n");
+    for node in nodes {
+        out.push_str(&format!(
+            "        {}) {} ;;\n",
+            q(&node.key),
+            complete(&node.words(), node.completes_files())
+        ));
+    }
+    out.push_str("    esac\n}\n");
+    out
+}
+
+fn fish_completions(cli: &CliConfig, nodes: &[Node], function: &str, host: &str) -> String {
+    let q = |s: &str| quote(s, Shell::Fish);
+    // fish expands `-a` at completion time, so each candidate is quoted
+    // again inside the quoted argument
+    let candidates = |words: &[&String]| {
+        let quoted: Vec<String> = words.iter().map(|w| q(w)).collect();
+        q(&quoted.join(" "))
+    };
+    let names: Vec<String> = std::iter::once(&cli.command)
+        .chain(&cli.aliases)
+        .map(|n| q(n))
+        .collect();
+    let mut out = format!(
+        "# fish completions for the `{}` plugin command\nfunction {function}_at\n    set -l words (commandline -opc)\n    contains -- \"$words[2]\" {}; or return 1\n    set -l node {}\n    for word in $words[3..-1]\n        switch \"$node:$word\"\n",
+        comment(&cli.command),
+        names.join(" "),
+        q(&cli.command)
+    );
+    for node in nodes {
+        for (names, target) in node.transitions() {
+            let patterns: Vec<String> = names
+                .iter()
+                .map(|n| q(&format!("{}:{n}", node.key)))
+                .collect();
+            out.push_str(&format!(
+                "            case {}\n                set node {}\n",
+                patterns.join(" "),
+                q(&target)
+            ));
+        }
+    }
+    out.push_str("        end\n    end\n    test \"$node\" = \"$argv[1]\"\nend\n\n");
+
+    let host = q(host);
+    let line =
+        |condition: &str, rest: String| format!("complete -c {host} -n {} {rest}\n", q(condition));
+    out.push_str(&line(
+        "__fish_use_subcommand",
+        format!(
+            "-f -a {} -d {}",
+            candidates(&[&cli.command]),
+            q(&cli.description)
+        ),
+    ));
+    for node in nodes {
+        let condition = format!("{function}_at {}", q(&node.key));
+        for sub in node.subcommands {
+            for name in std::iter::once(&sub.name).chain(&sub.aliases) {
+                out.push_str(&line(
+                    &condition,
+                    format!("-f -a {} -d {}", candidates(&[name]), q(&sub.description)),
+                ));
+            }
+        }
+        for arg in node.args {
+            let mut rest = if arg.is_positional() {
+                match arg.arg_type {
+                    CliArgType::Path => "-F".to_string(),
+                    _ if arg.choices.is_empty() => continue,
+                    _ => "-f".to_string(),
+                }
+            } else {
+                let mut rest = String::new();
+                if let Some(long) = &arg.long {
+                    rest.push_str(&format!("-l {} ", q(long)));
+                }
+                if let Some(short) = &arg.short {
+                    rest.push_str(&format!("-s {} ", q(short)));
+                }
+                rest.push_str(match arg.arg_type {
+                    CliArgType::Bool => "-f",
+                    CliArgType::Path => "-r -F",
+                    _ => "-r -f",
+                });
+                rest
+            };
+            if !arg.choices.is_empty() {
+                let choices: Vec<&String> = arg.choices.iter().collect();
+                rest.push_str(&format!(" -a {}", candidates(&choices)));
+            }
+            if !arg.description.is_empty() {
+                rest.push_str(&format!(" -d {}", q(&arg.description)));
+            }
+            out.push_str(&line(&condition, rest));
+        }
+    }
+    out
+}
+
+fn powershell_completions(cli: &CliConfig, nodes: &[Node], function: &str) -> String {
+    let q = |s: &str| quote(s, Shell::PowerShell);
+    let list = |words: &[String]| {
+        let quoted: Vec<String> = words.iter().map(|w| q(w)).collect();
+        format!("@({})", quoted.join(", "))
+    };
+    const FILES: &str = "@(Get-ChildItem -Path \"$Current*\" -Name)";
+
+    let mut out = format!(
+        "# PowerShell completions for the `{}` plugin command\nfunction {function}([string[]]$Words, [string]$Current) {{\n    $node = {}\n    for ($i = 2; $i -lt $Words.Count; $i++) {{\n        switch ($node + ':' + $Words[$i]) {{\n",
+        comment(&cli.command),
+        q(&cli.command)
+    );
+    for node in nodes {
+        for (names, target) in node.transitions() {
+            for name in names {
+                out.push_str(&format!(
+                    "            {} {{ $node = {} }}\n",
+                    q(&format!("{}:{name}", node.key)),
+                    q(&target)
+                ));
+            }
+        }
+    }
+    out.push_str("        }\n    }\n    $candidates = switch ($node + ':' + $Words[-1]) {\n");
+    for node in nodes {
+        for (flags, value) in node.value_options() {
+            let value = match value {
+                ValueCompletion::Choices(choices) => list(choices),
+                ValueCompletion::Files => FILES.to_string(),
+                ValueCompletion::Nothing => "return".to_string(),
+            };
+            for flag in flags {
+                out.push_str(&format!(
+                    "        {} {{ {value} }}\n",
+                    q(&format!("{}:{flag}", node.key))
+                ));
+            }
+        }
+    }
+    out.push_str("        default {\n            switch ($node) {\n");
+    for node in nodes {
+        let mut value = list(&node.words());
+        if node.completes_files() {
+            value = format!("{value} + {FILES}");
+        }
+        out.push_str(&format!("                {} {{ {value} }}\n", q(&node.key)));
+    }
+    out.push_str(
+        "            }\n        }\n    }\n    $candidates | Where-Object { $_ -like \"$Current*\" }\n}\n",
+    );
+    out
+}
+
+fn check_subcommands(path: &str, subcommands: &[CliSubcommand], issues: &mut Vec<ValidationIssue>) {
+    for (i, sub) in subcommands.iter().enumerate() {
+        let path = format!("{path}[{i}]");
+        let valid = !sub.name.is_empty()
+            && sub
+                .name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+        if !valid {
+            issues.push(ValidationIssue::error(
+                format!("{path}.name"),
+                format!("'{}' must be lowercase alphanumeric with hyphens", sub.name),
+            ));
+        }
+        for (j, alias) in sub.aliases.iter().enumerate() {
+            issues.extend(check_token(&format!("{path}.aliases[{j}]"), alias));
+        }
+        let names = std::iter::once(&sub.name).chain(&sub.aliases);
+        for name in names {
+            if subcommands[..i].iter().any(|s| s.is_named(name)) {
+                issues.push(ValidationIssue::error(
+                    format!("{path}.name"),
+                    format!("'{name}' is already used by another subcommand"),
+                ));
+            }
+        }
+        issues.extend(check_args(&format!("{path}.args"), &sub.args));
+        check_subcommands(&format!("{path}.subcommands"), &sub.subcommands, issues);
+    }
+}
+
+/// Names and values end up in generated shell scripts, so only allow
+/// characters no shell treats specially.
+fn check_token(path: &str, token: &str) -> Option<ValidationIssue> {
+    let safe = !token.is_empty()
+        && !token.starts_with('-')
+        && token
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':' | '+' | '@'));
+    (!safe).then(|| {
+        ValidationIssue::error(
+            path.to_string(),
+            format!("'{token}' may only contain letters, digits and '-_.:+@'"),
+        )
+    })
+}
+
+fn check_args(path: &str, args: &[CliArg]) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+    for (i, arg) in args.iter().enumerate() {
+        let path = format!("{path}[{i}]");
+        if args[..i].iter().any(|a| a.name == arg.name) {
+            issues.push(ValidationIssue::error(
+                format!("{path}.name"),
+                format!("Duplicate argument '{}'", arg.name),
+            ));
+        }
+        if let Some(short) = &arg.short {
+            if short.chars().count() != 1 || !short.chars().all(|c| c.is_ascii_alphanumeric()) {
+                issues.push(ValidationIssue::error(
+                    format!("{path}.short"),
+                    format!("'{short}' must be a single letter or digit"),
+                ));
+            }
+        }
+        if let Some(long) = &arg.long {
+            issues.extend(check_token(&format!("{path}.long"), long));
+        }
+        for (j, choice) in arg.choices.iter().enumerate() {
+            issues.extend(check_token(&format!("{path}.choices[{j}]"), choice));
+        }
+        if arg.arg_type == CliArgType::Bool && arg.is_positional() {
+            issues.push(ValidationIssue::error(
+                format!("{path}.type"),
+                "Bool arguments must be options (set `long` or `short`)",
+            ));
+        }
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::PluginManifest;
+
+    const MANIFEST: &str = r#"
+[plugin]
+id = "adi.tasks"
+name = "Tasks"
+version = "1.0.0"
+type = "extension"
+
+[cli]
+command = "tasks"
+description = "Task management"
+
+[[cli.subcommands]]
+name = "add"
+description = "Add a task"
+aliases = ["a"]
+
+[[cli.subcommands.args]]
+name = "title"
+required = true
+
+[[cli.subcommands.args]]
+name = "priority"
+long = "priority"
+short = "p"
+choices = ["low", "normal", "high"]
+
+[[cli.subcommands]]
+name = "list"
+
+[[cli.subcommands.subcommands]]
+name = "done"
+"#;
+
+    #[test]
+    fn test_subcommand_tree() {
+        let manifest = PluginManifest::from_toml(MANIFEST).unwrap();
+        let cli = manifest.cli.as_ref().unwrap();
+        let add = cli.subcommand(&["a"]).unwrap();
+        assert_eq!(add.name, "add");
+        assert!(add.args[0].is_positional());
+        assert!(!add.args[1].is_positional());
+        assert_eq!(cli.subcommand(&["list", "done"]).unwrap().name, "done");
+        assert!(cli.subcommand(&["list", "missing"]).is_none());
+        assert!(manifest.validate().is_empty());
+
+        let reparsed = PluginManifest::from_toml(&manifest.to_toml().unwrap()).unwrap();
+        assert_eq!(reparsed.cli, manifest.cli);
+    }
+
+    #[test]
+    fn test_invalid_tree() {
+        let mut manifest = PluginManifest::from_toml(MANIFEST).unwrap();
+        let cli = manifest.cli.as_mut().unwrap();
+        cli.subcommands[1].aliases.push("a".into());
+        cli.subcommands[0].args[1].short = Some("pr".into());
+        let paths: Vec<_> = manifest.validate().into_iter().map(|i| i.path).collect();
+        assert_eq!(
+            paths,
+            [
+                "cli.subcommands[0].args[1].short",
+                "cli.subcommands[1].name"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_shell_names() {
+        for shell in Shell::ALL {
+            assert_eq!(shell.name().parse::<Shell>().unwrap(), shell);
+        }
+        assert_eq!("pwsh".parse::<Shell>().unwrap(), Shell::PowerShell);
+        assert!("tcsh".parse::<Shell>().is_err());
+    }
+
+    #[test]
+    fn test_generate_completions() {
+        let manifest = PluginManifest::from_toml(MANIFEST).unwrap();
+        let cli = manifest.cli.as_ref().unwrap();
+
+        let bash = generate_completions(cli, Shell::Bash);
+        assert!(bash.contains("_adi_tasks() {"));
+        assert!(bash.contains("'tasks:add'|'tasks:a') node='tasks add' ;;"));
+        assert!(bash.contains(
+            r#"'tasks add:--priority'|'tasks add:-p') COMPREPLY=(); for w in 'low' 'normal' 'high'; do [[ $w == "$cur"* ]] && COMPREPLY+=("$w"); done; return ;;"#
+        ));
+        assert!(!bash.contains("compgen -W"));
+
+        let zsh = generate_completions(cli, Shell::Zsh);
+        assert!(zsh.contains("'tasks add') compadd -- '--priority' '-p' ;;"));
+
+        let fish = generate_completions(cli, Shell::Fish);
+        assert!(fish.contains(
+            r"complete -c 'adi' -n '_adi_tasks_at \'tasks add\'' -l 'priority' -s 'p' -r -f -a '\'low\' \'normal\' \'high\''"
+        ));
+
+        let ps = generate_completions(cli, Shell::PowerShell);
+        assert!(ps.contains("'tasks:a' { $node = 'tasks add' }"));
+        assert!(ps.contains("'tasks add:-p' { @('low', 'normal', 'high') }"));
+    }
+
+    #[test]
+    fn test_shell_metacharacters_rejected() {
+        let mut manifest = PluginManifest::from_toml(MANIFEST).unwrap();
+        let cli = manifest.cli.as_mut().unwrap();
+        cli.aliases.push("t;rm".into());
+        cli.subcommands[0].args[1].choices[0] = "$(touch /tmp/pwned)".into();
+        cli.subcommands[0].args[1].long = Some("prio`x`".into());
+        let paths: Vec<_> = manifest.validate().into_iter().map(|i| i.path).collect();
+        assert_eq!(
+            paths,
+            [
+                "cli.aliases[0]",
+                "cli.subcommands[0].args[1].long",
+                "cli.subcommands[0].args[1].choices[0]"
+            ]
+        );
+
+        // Even unvalidated input stays inside single quotes
+        let bash = generate_completions(manifest.cli.as_ref().unwrap(), Shell::Bash);
+        assert!(bash.contains("for w in '$(touch /tmp/pwned)' 'normal' 'high';"));
+    }
+
+    #[test]
+    fn test_shell_metacharacters_stay_quoted() {
+        let mut manifest = PluginManifest::from_toml(MANIFEST).unwrap();
+        let cli = manifest.cli.as_mut().unwrap();
+        cli.subcommands[0].name = "(touch /tmp/a)".into();
+        cli.subcommands[0].args[1].choices = vec!["$HOME".into(), "(id)".into(), "x'y".into()];
+        let cli = manifest.cli.as_ref().unwrap();
+
+        let zsh = generate_completions(cli, Shell::Zsh);
+        assert!(zsh.contains("compadd -- '(touch /tmp/a)' 'a' 'list' ;;"));
+        assert!(zsh.contains(r"compadd -- '$HOME' '(id)' 'x'\''y'; return ;;"));
+
+        // `-a` is expanded by fish, so the words are quoted twice
+        let fish = generate_completions(cli, Shell::Fish);
+        assert!(fish.contains(r"-f -a '\'(touch /tmp/a)\'' -d 'Add a task'"));
+        assert!(fish.contains(r"-a '\'$HOME\' \'(id)\' \'x\\\'y\''"));
+
+        let ps = generate_completions(cli, Shell::PowerShell);
+        assert!(ps.contains("@('$HOME', '(id)', 'x''y')"));
+    }
+
+    #[test]
+    fn test_unvalidated_command_stays_inert() {
+        let mut manifest = PluginManifest::from_toml(MANIFEST).unwrap();
+        let cli = manifest.cli.as_mut().unwrap();
+        cli.command = "t\necho PWNED\n#\u{2019}; Remove-Item x; \u{2019}".into();
+        let paths: Vec<_> = manifest.validate().into_iter().map(|i| i.path).collect();
+        assert_eq!(paths, ["cli.command"]);
+
+        let cli = manifest.cli.as_ref().unwrap();
+        for shell in Shell::ALL {
+            // The comment stays on one line and the function follows it
+            let script = generate_completions(cli, shell);
+            let mut lines = script.lines();
+            let header = lines.next().unwrap();
+            assert!(header.ends_with("plugin command"), "{shell}: {header}");
+            assert!(lines.next().unwrap().contains("_adi_t_echo_PWNED"), "{shell}");
+        }
+        let ps = generate_completions(cli, Shell::PowerShell);
+        assert!(ps.contains("#\u{2019}\u{2019}; Remove-Item x; \u{2019}\u{2019}'"));
+    }
+}
@@ -0,0 +1,273 @@
+//! Checksum coverage.
+//!
+//! Compares the platforms a manifest declares with the platforms it has
+//! checksums for. Releases often ship with a checksum missing for a newly
+//! added platform, or left over for a dropped one:
+//!
+//! ```toml
+//! [compatibility]
+//! platforms = ["linux-x86_64", "darwin-aarch64"]
+//!
+//! [binary.checksums]
+//! linux-x86_64 = "sha256:..."     # covered
+//! windows-x86_64 = "sha256:..."   # undeclared; darwin-aarch64 is missing
+//! ```
+//!
+//! `ManifestSet::platform_matrix()` tabulates the same information for every
+//! plugin in a set.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+
+use crate::package::PackageManifest;
+use crate::plugin::{CompatibilityInfo, PluginManifest};
+use crate::set::ManifestSet;
+
+/// Which declared platforms have checksums.
+///
+/// All lists are sorted. A manifest without `compatibility.platforms` (or
+/// with "all") declares no specific platforms, so every checksum counts as
+/// covered and nothing is missing.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ChecksumCoverage {
+    /// Declared platforms with a checksum
+    pub covered: Vec<String>,
+
+    /// Declared platforms without a checksum
+    pub missing: Vec<String>,
+
+    /// Checksums for platforms that aren't declared
+    pub undeclared: Vec<String>,
+}
+
+impl ChecksumCoverage {
+    fn compute(compatibility: &CompatibilityInfo, checksums: &HashMap<String, String>) -> Self {
+        let checksummed: BTreeSet<&String> = checksums.keys().collect();
+        let platforms = &compatibility.platforms;
+        if platforms.is_empty() || platforms.iter().any(|p| p == "all") {
+            return Self {
+                covered: checksummed.into_iter().cloned().collect(),
+                ..Self::default()
+            };
+        }
+
+        let declared: BTreeSet<&String> = platforms.iter().collect();
+        Self {
+            covered: declared
+                .intersection(&checksummed)
+                .map(|p| p.to_string())
+                .collect(),
+            missing: declared
+                .difference(&checksummed)
+                .map(|p| p.to_string())
+                .collect(),
+            undeclared: checksummed
+                .difference(&declared)
+                .map(|p| p.to_string())
+                .collect(),
+        }
+    }
+
+    /// Whether every declared platform has a checksum and every checksum
+    /// belongs to a declared platform.
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty() && self.undeclared.is_empty()
+    }
+}
+
+impl PluginManifest {
+    /// Compare `compatibility.platforms` with `[binary.checksums]`.
+    pub fn checksum_coverage(&self) -> ChecksumCoverage {
+        ChecksumCoverage::compute(&self.compatibility, &self.binary.checksums)
+    }
+}
+
+impl PackageManifest {
+    /// Compare `compatibility.platforms` with the package `[binary.checksums]`.
+    pub fn checksum_coverage(&self) -> ChecksumCoverage {
+        ChecksumCoverage::compute(&self.compatibility, &self.binary.checksums)
+    }
+}
+
+/// Support for one plugin on one platform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "snake_case")]
+pub enum PlatformSupport {
+    /// The platform isn't supported
+    Unsupported,
+    /// Supported, but no checksum is declared
+    NoChecksum,
+    /// Supported with a checksum
+    Checksummed,
+}
+
+/// One plugin's row in a `PlatformMatrix`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct PlatformRow {
+    /// Plugin ID
+    pub plugin: String,
+
+    /// Support per platform, in the order of `PlatformMatrix::platforms`
+    pub support: Vec<PlatformSupport>,
+}
+
+/// Platform support of every plugin in a set.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct PlatformMatrix {
+    /// Columns: every platform declared or checksummed by any plugin, sorted
+    pub platforms: Vec<String>,
+
+    /// One row per plugin, in set order
+    pub rows: Vec<PlatformRow>,
+}
+
+impl PlatformMatrix {
+    /// Support of a plugin on a platform (None if either is unknown).
+    pub fn support(&self, plugin: &str, platform: &str) -> Option<PlatformSupport> {
+        let column = self.platforms.iter().position(|p| p == platform)?;
+        let row = self.rows.iter().find(|r| r.plugin == plugin)?;
+        Some(row.support[column])
+    }
+
+    /// Plugins that block a release on `platform`: those that don't support
+    /// it or have no checksum for it.
+    pub fn blocking(&self, platform: &str) -> Vec<&str> {
+        let Some(column) = self.platforms.iter().position(|p| p == platform) else {
+            return self.rows.iter().map(|r| r.plugin.as_str()).collect();
+        };
+        self.rows
+            .iter()
+            .filter(|r| r.support[column] != PlatformSupport::Checksummed)
+            .map(|r| r.plugin.as_str())
+            .collect()
+    }
+}
+
+impl ManifestSet {
+    /// Tabulate supported platforms and checksum availability per plugin.
+    ///
+    /// Plugins without `compatibility.platforms` (or with "all") support
+    /// every column. Packages contribute one row per plugin, sharing the
+    /// package checksums.
+    pub fn platform_matrix(&self) -> PlatformMatrix {
+        let plugins: Vec<&PluginManifest> = self.plugins().collect();
+        let platforms: BTreeSet<&String> = plugins
+            .iter()
+            .flat_map(|p| {
+                p.compatibility
+                    .platforms
+                    .iter()
+                    .filter(|p| *p != "all")
+                    .chain(p.binary.checksums.keys())
+            })
+            .collect();
+
+        let rows = plugins
+            .iter()
+            .map(|plugin| {
+                let declared = &plugin.compatibility.platforms;
+                let everywhere = declared.is_empty() || declared.iter().any(|p| p == "all");
+                let support = platforms
+                    .iter()
+                    .map(|platform| {
+                        if !everywhere && !declared.contains(platform) {
+                            PlatformSupport::Unsupported
+                        } else if plugin.binary.checksums.contains_key(*platform) {
+                            PlatformSupport::Checksummed
+                        } else {
+                            PlatformSupport::NoChecksum
+                        }
+                    })
+                    .collect();
+                PlatformRow {
+                    plugin: plugin.plugin.id.clone(),
+                    support,
+                }
+            })
+            .collect();
+
+        PlatformMatrix {
+            platforms: platforms.into_iter().cloned().collect(),
+            rows,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checksum_coverage() {
+        let manifest = PluginManifest::from_toml(
+            r#"
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.0.0"
+type = "extension"
+
+[compatibility]
+platforms = ["linux-x86_64", "darwin-aarch64"]
+
+[binary]
+name = "tool"
+
+[binary.checksums]
+linux-x86_64 = "abc"
+windows-x86_64 = "def"
+"#,
+        )
+        .unwrap();
+
+        let coverage = manifest.checksum_coverage();
+        assert_eq!(coverage.covered, ["linux-x86_64"]);
+        assert_eq!(coverage.missing, ["darwin-aarch64"]);
+        assert_eq!(coverage.undeclared, ["windows-x86_64"]);
+        assert!(!coverage.is_complete());
+
+        let mut all = manifest.clone();
+        all.compatibility.platforms = vec!["all".to_string()];
+        let coverage = all.checksum_coverage();
+        assert_eq!(coverage.covered, ["linux-x86_64", "windows-x86_64"]);
+        assert!(coverage.is_complete());
+    }
+
+    #[test]
+    fn test_platform_matrix() {
+        let plugin = |id: &str, extra: &str| {
+            crate::Manifest::from_toml(&format!(
+                "[plugin]\nid = \"{id}\"\nname = \"{id}\"\nversion = \"1.0.0\"\ntype = \"extension\"\n{extra}"
+            ))
+            .unwrap()
+        };
+        let set = ManifestSet::new()
+            .with(plugin(
+                "acme.a",
+                "[compatibility]\nplatforms = [\"linux-x86_64\", \"darwin-aarch64\"]\n\n[binary]\nname = \"a\"\nchecksums = { linux-x86_64 = \"abc\", darwin-aarch64 = \"def\" }\n",
+            ))
+            .with(plugin(
+                "acme.b",
+                "[compatibility]\nplatforms = [\"linux-x86_64\"]\n\n[binary]\nname = \"b\"\nchecksums = { linux-x86_64 = \"abc\" }\n",
+            ))
+            .with(plugin("acme.c", ""));
+
+        let matrix = set.platform_matrix();
+        assert_eq!(matrix.platforms, ["darwin-aarch64", "linux-x86_64"]);
+        assert_eq!(
+            matrix.support("acme.b", "darwin-aarch64"),
+            Some(PlatformSupport::Unsupported)
+        );
+        assert_eq!(
+            matrix.support("acme.c", "linux-x86_64"),
+            Some(PlatformSupport::NoChecksum)
+        );
+        assert_eq!(matrix.blocking("darwin-aarch64"), ["acme.b", "acme.c"]);
+        assert_eq!(matrix.blocking("linux-x86_64"), ["acme.c"]);
+        assert_eq!(matrix.blocking("windows-x86_64").len(), 3);
+    }
+}
@@ -0,0 +1,926 @@
+//! Comparing two versions of a plugin manifest.
+//!
+//! `ServiceCompatibility` classifies service-level changes (provided and
+//! required services, permissions) by the version bump they require, so a
+//! registry can reject uploads that break consumers without a major bump.
+//! `field_changes()` lists every field that differs, and `ManifestDiff`
+//! combines both into typed entries for change logs.
+
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+
+use crate::error::ManifestError;
+use crate::plugin::PluginManifest;
+use crate::validate::ValidationIssue;
+use crate::Manifest;
+
+/// Semver bump level a change requires, ordered by severity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeLevel {
+    Patch,
+    Minor,
+    Major,
+}
+
+impl ChangeLevel {
+    /// The bump level between two versions, or None if `new` is not newer.
+    ///
+    /// Follows Cargo's convention for `0.x` versions: a minor bump counts as
+    /// major and a patch bump as minor.
+    pub fn between(old: &Version, new: &Version) -> Option<ChangeLevel> {
+        if new <= old {
+            return None;
+        }
+        let level = if new.major != old.major {
+            ChangeLevel::Major
+        } else if new.minor != old.minor {
+            ChangeLevel::Minor
+        } else {
+            ChangeLevel::Patch
+        };
+        if old.major == 0 && level < ChangeLevel::Major {
+            // 0.x: shift up one level
+            return Some(match level {
+                ChangeLevel::Minor => ChangeLevel::Major,
+                _ => ChangeLevel::Minor,
+            });
+        }
+        Some(level)
+    }
+
+    /// Level name.
+    pub fn name(self) -> &'static str {
+        match self {
+            ChangeLevel::Patch => "patch",
+            ChangeLevel::Minor => "minor",
+            ChangeLevel::Major => "major",
+        }
+    }
+}
+
+impl fmt::Display for ChangeLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A service-level change between two manifest versions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ServiceChange {
+    /// A provided service was removed
+    ProvideRemoved { service: String, version: String },
+    /// A service is now provided
+    ProvideAdded { service: String, version: String },
+    /// The version of a provided service changed
+    ProvideVersionChanged {
+        service: String,
+        old: String,
+        new: String,
+    },
+    /// A service is now required
+    RequirementAdded { service: String, optional: bool },
+    /// A required service is no longer required
+    RequirementRemoved { service: String },
+    /// An optional requirement became mandatory
+    RequirementMadeMandatory { service: String },
+    /// A requirement's minimum version was raised or added
+    RequirementNarrowed {
+        service: String,
+        old: Option<String>,
+        new: Option<String>,
+    },
+    /// A requirement's minimum version was lowered or dropped
+    RequirementWidened {
+        service: String,
+        old: Option<String>,
+        new: Option<String>,
+    },
+    /// A permission grant was added (e.g., "network: api.acme.dev:443")
+    PermissionExpanded { permission: String },
+}
+
+impl ServiceChange {
+    /// Version bump this change requires.
+    pub fn level(&self) -> ChangeLevel {
+        match self {
+            ServiceChange::ProvideRemoved { .. }
+            | ServiceChange::RequirementMadeMandatory { .. }
+            | ServiceChange::RequirementNarrowed { .. }
+            | ServiceChange::PermissionExpanded { .. } => ChangeLevel::Major,
+            ServiceChange::RequirementAdded { optional, .. } => {
+                if *optional {
+                    ChangeLevel::Minor
+                } else {
+                    ChangeLevel::Major
+                }
+            }
+            ServiceChange::ProvideVersionChanged { old, new, .. } => {
+                provide_version_level(old, new)
+            }
+            ServiceChange::ProvideAdded { .. }
+            | ServiceChange::RequirementRemoved { .. }
+            | ServiceChange::RequirementWidened { .. } => ChangeLevel::Minor,
+        }
+    }
+}
+
+/// Bump required when a provided service changes version: a compatible
+/// service bump is minor, anything else (including downgrades) major.
+fn provide_version_level(old: &str, new: &str) -> ChangeLevel {
+    match (Version::parse(old), Version::parse(new)) {
+        (Ok(old), Ok(new)) => match ChangeLevel::between(&old, &new) {
+            Some(ChangeLevel::Major) | None => ChangeLevel::Major,
+            Some(_) => ChangeLevel::Minor,
+        },
+        _ => ChangeLevel::Major,
+    }
+}
+
+impl fmt::Display for ServiceChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let or_any = |v: &Option<String>| v.clone().unwrap_or_else(|| "any".to_string());
+        match self {
+            ServiceChange::ProvideRemoved { service, version } => {
+                write!(f, "no longer provides {service} {version}")
+            }
+            ServiceChange::ProvideAdded { service, version } => {
+                write!(f, "now provides {service} {version}")
+            }
+            ServiceChange::ProvideVersionChanged { service, old, new } => {
+                write!(f, "provided {service} changed from {old} to {new}")
+            }
+            ServiceChange::RequirementAdded { service, optional } => {
+                let kind = if *optional {
+                    "optionally requires"
+                } else {
+                    "requires"
+                };
+                write!(f, "now {kind} {service}")
+            }
+            ServiceChange::RequirementRemoved { service } => {
+                write!(f, "no longer requires {service}")
+            }
+            ServiceChange::RequirementMadeMandatory { service } => {
+                write!(f, "requirement on {service} is no longer optional")
+            }
+            ServiceChange::RequirementNarrowed { service, old, new } => write!(
+                f,
+                "requirement on {service} narrowed from {} to {}",
+                or_any(old),
+                or_any(new)
+            ),
+            ServiceChange::RequirementWidened { service, old, new } => write!(
+                f,
+                "requirement on {service} widened from {} to {}",
+                or_any(old),
+                or_any(new)
+            ),
+            ServiceChange::PermissionExpanded { permission } => {
+                write!(f, "requests new permission {permission}")
+            }
+        }
+    }
+}
+
+/// Service compatibility analysis between two versions of a plugin.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ServiceCompatibility {
+    /// Detected changes
+    pub changes: Vec<ServiceChange>,
+}
+
+impl ServiceCompatibility {
+    /// Compare the services and permissions of two manifest versions.
+    pub fn compute(old: &PluginManifest, new: &PluginManifest) -> Self {
+        let mut changes = Vec::new();
+
+        for provided in &old.provides {
+            match new.provides.iter().find(|s| s.id == provided.id) {
+                None => changes.push(ServiceChange::ProvideRemoved {
+                    service: provided.id.clone(),
+                    version: provided.version.clone(),
+                }),
+                Some(s) if s.version != provided.version => {
+                    changes.push(ServiceChange::ProvideVersionChanged {
+                        service: provided.id.clone(),
+                        old: provided.version.clone(),
+                        new: s.version.clone(),
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+        for provided in &new.provides {
+            if !old.provides.iter().any(|s| s.id == provided.id) {
+                changes.push(ServiceChange::ProvideAdded {
+                    service: provided.id.clone(),
+                    version: provided.version.clone(),
+                });
+            }
+        }
+
+        for req in &new.requires {
+            let Some(before) = old.requires.iter().find(|r| r.id == req.id) else {
+                changes.push(ServiceChange::RequirementAdded {
+                    service: req.id.clone(),
+                    optional: req.optional,
+                });
+                continue;
+            };
+            if before.optional && !req.optional {
+                changes.push(ServiceChange::RequirementMadeMandatory {
+                    service: req.id.clone(),
+                });
+            }
+            let parse = |v: &Option<String>| v.as_deref().map(Version::parse);
+            let narrowed = match (parse(&before.min_version), parse(&req.min_version)) {
+                (None, None) => None,
+                (None, Some(_)) => Some(true),
+                (Some(_), None) => Some(false),
+                (Some(Ok(old)), Some(Ok(new))) if old == new => None,
+                (Some(Ok(old)), Some(Ok(new))) => Some(new > old),
+                // Unparsable bounds: only a textual change is known
+                _ => (before.min_version != req.min_version).then_some(true),
+            };
+            let (service, old, new) = (
+                req.id.clone(),
+                before.min_version.clone(),
+                req.min_version.clone(),
+            );
+            match narrowed {
+                Some(true) => {
+                    changes.push(ServiceChange::RequirementNarrowed { service, old, new })
+                }
+                Some(false) => {
+                    changes.push(ServiceChange::RequirementWidened { service, old, new })
+                }
+                None => {}
+            }
+        }
+        for req in &old.requires {
+            if !new.requires.iter().any(|r| r.id == req.id) {
+                changes.push(ServiceChange::RequirementRemoved {
+                    service: req.id.clone(),
+                });
+            }
+        }
+
+        let old_grants = permission_grants(old);
+        for permission in permission_grants(new) {
+            if !old_grants.contains(&permission) {
+                changes.push(ServiceChange::PermissionExpanded { permission });
+            }
+        }
+
+        Self { changes }
+    }
+
+    /// Minimum version bump the changes require.
+    pub fn required_bump(&self) -> ChangeLevel {
+        self.changes
+            .iter()
+            .map(ServiceChange::level)
+            .max()
+            .unwrap_or(ChangeLevel::Patch)
+    }
+
+    /// Check that the version change from `old` to `new` is large enough for
+    /// the detected changes.
+    pub fn check_version_bump(&self, old: &str, new: &str) -> Vec<ValidationIssue> {
+        let (Ok(old), Ok(new)) = (Version::parse(old), Version::parse(new)) else {
+            return vec![ValidationIssue::error(
+                "plugin.version",
+                format!("Cannot compare versions '{old}' and '{new}'"),
+            )];
+        };
+        let required = self.required_bump();
+        match ChangeLevel::between(&old, &new) {
+            Some(actual) if actual >= required => Vec::new(),
+            actual => {
+                // Changes the actual bump doesn't cover (None sorts below any level)
+                let reasons: Vec<String> = self
+                    .changes
+                    .iter()
+                    .filter(|c| Some(c.level()) > actual)
+                    .map(ToString::to_string)
+                    .collect();
+                vec![ValidationIssue::error(
+                    "plugin.version",
+                    format!(
+                        "{old} -> {new} requires a {required} version bump: {}",
+                        reasons.join("; ")
+                    ),
+                )]
+            }
+        }
+    }
+}
+
+/// Whether a field was added, removed or changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "snake_case")]
+pub enum FieldChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// A field that differs between two manifests.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct FieldChange {
+    /// Dotted field path (e.g., "compatibility.platforms")
+    pub path: String,
+
+    /// Kind of change
+    pub kind: FieldChangeKind,
+
+    /// Old value as a TOML literal (None if added)
+    pub old: Option<String>,
+
+    /// New value as a TOML literal (None if removed)
+    pub new: Option<String>,
+}
+
+impl fmt::Display for FieldChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = |v: &Option<String>| v.clone().unwrap_or_default();
+        match self.kind {
+            FieldChangeKind::Added => write!(f, "added {} = {}", self.path, value(&self.new)),
+            FieldChangeKind::Removed => {
+                write!(f, "removed {} (was {})", self.path, value(&self.old))
+            }
+            FieldChangeKind::Changed => write!(
+                f,
+                "changed {}: {} -> {}",
+                self.path,
+                value(&self.old),
+                value(&self.new)
+            ),
+        }
+    }
+}
+
+/// List the fields that differ between two manifests, sorted by path.
+///
+/// Tables are compared key by key; arrays (e.g., `provides`) are compared
+/// as a whole.
+pub fn field_changes(old: &Manifest, new: &Manifest) -> Result<Vec<FieldChange>, ManifestError> {
+    let mut changes = Vec::new();
+    diff_values(
+        "",
+        Some(&to_value(old)?),
+        Some(&to_value(new)?),
+        &mut changes,
+    );
+    Ok(changes)
+}
+
+fn to_value(manifest: &Manifest) -> Result<toml::Value, ManifestError> {
+    let value = match manifest {
+        Manifest::Single(m) => toml::Value::try_from(m),
+        Manifest::Package(m) => toml::Value::try_from(m),
+    };
+    value.map_err(|e| ManifestError::InvalidFormat(format!("Failed to serialize manifest: {e}")))
+}
+
+fn diff_values(
+    path: &str,
+    old: Option<&toml::Value>,
+    new: Option<&toml::Value>,
+    changes: &mut Vec<FieldChange>,
+) {
+    match (old, new) {
+        (Some(toml::Value::Table(old)), Some(toml::Value::Table(new))) => {
+            let keys: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+            for key in keys {
+                let path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                diff_values(&path, old.get(key), new.get(key), changes);
+            }
+        }
+        (old, new) if old == new => {}
+        (old, new) => changes.push(FieldChange {
+            path: path.to_string(),
+            kind: match (old, new) {
+                (None, _) => FieldChangeKind::Added,
+                (_, None) => FieldChangeKind::Removed,
+                _ => FieldChangeKind::Changed,
+            },
+            old: old.map(ToString::to_string),
+            new: new.map(ToString::to_string),
+        }),
+    }
+}
+
+/// Field paths reported as typed `ManifestChange`s instead of `FieldChanged`.
+const TYPED_FIELDS: &[&str] = &[
+    "provides",
+    "requires",
+    "compatibility.platforms",
+    "binary.checksums",
+];
+
+/// A change between two manifests.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ManifestChange {
+    /// A field without a more specific entry changed
+    FieldChanged(FieldChange),
+    /// A plugin was added (packages)
+    PluginAdded { plugin: String },
+    /// A plugin was removed (packages)
+    PluginRemoved { plugin: String },
+    /// A plugin now provides a service
+    ServiceAdded {
+        plugin: String,
+        service: String,
+        version: String,
+    },
+    /// A plugin no longer provides a service
+    ServiceRemoved {
+        plugin: String,
+        service: String,
+        version: String,
+    },
+    /// The version of a provided service changed
+    ServiceVersionChanged {
+        plugin: String,
+        service: String,
+        old: String,
+        new: String,
+    },
+    /// A service requirement was added, removed or changed
+    RequirementChanged {
+        plugin: String,
+        change: ServiceChange,
+    },
+    /// A permission grant was added
+    PermissionExpanded { plugin: String, permission: String },
+    /// A platform is now supported
+    PlatformAdded { platform: String },
+    /// A platform is no longer supported
+    PlatformDropped { platform: String },
+    /// A binary checksum was added, removed or changed
+    ChecksumChanged {
+        platform: String,
+        old: Option<String>,
+        new: Option<String>,
+    },
+}
+
+impl ManifestChange {
+    /// Version bump this change requires.
+    pub fn level(&self) -> ChangeLevel {
+        match self {
+            ManifestChange::FieldChanged(_) | ManifestChange::ChecksumChanged { .. } => {
+                ChangeLevel::Patch
+            }
+            ManifestChange::PluginAdded { .. }
+            | ManifestChange::ServiceAdded { .. }
+            | ManifestChange::PlatformAdded { .. } => ChangeLevel::Minor,
+            ManifestChange::PluginRemoved { .. }
+            | ManifestChange::ServiceRemoved { .. }
+            | ManifestChange::PermissionExpanded { .. }
+            | ManifestChange::PlatformDropped { .. } => ChangeLevel::Major,
+            ManifestChange::ServiceVersionChanged { old, new, .. } => {
+                provide_version_level(old, new)
+            }
+            ManifestChange::RequirementChanged { change, .. } => change.level(),
+        }
+    }
+
+    /// Typed entry for a service change of `plugin`.
+    fn from_service_change(plugin: &str, change: ServiceChange) -> Self {
+        let plugin = plugin.to_string();
+        match change {
+            ServiceChange::ProvideAdded { service, version } => ManifestChange::ServiceAdded {
+                plugin,
+                service,
+                version,
+            },
+            ServiceChange::ProvideRemoved { service, version } => ManifestChange::ServiceRemoved {
+                plugin,
+                service,
+                version,
+            },
+            ServiceChange::ProvideVersionChanged { service, old, new } => {
+                ManifestChange::ServiceVersionChanged {
+                    plugin,
+                    service,
+                    old,
+                    new,
+                }
+            }
+            ServiceChange::PermissionExpanded { permission } => {
+                ManifestChange::PermissionExpanded { plugin, permission }
+            }
+            change => ManifestChange::RequirementChanged { plugin, change },
+        }
+    }
+}
+
+impl fmt::Display for ManifestChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let or_none = |v: &Option<String>| v.clone().unwrap_or_else(|| "none".to_string());
+        match self {
+            ManifestChange::FieldChanged(change) => write!(f, "{change}"),
+            ManifestChange::PluginAdded { plugin } => write!(f, "added plugin {plugin}"),
+            ManifestChange::PluginRemoved { plugin } => write!(f, "removed plugin {plugin}"),
+            ManifestChange::ServiceAdded {
+                plugin,
+                service,
+                version,
+            } => write!(f, "{plugin} now provides {service} {version}"),
+            ManifestChange::ServiceRemoved {
+                plugin,
+                service,
+                version,
+            } => write!(f, "{plugin} no longer provides {service} {version}"),
+            ManifestChange::ServiceVersionChanged {
+                plugin,
+                service,
+                old,
+                new,
+            } => write!(
+                f,
+                "{plugin}: provided {service} changed from {old} to {new}"
+            ),
+            ManifestChange::RequirementChanged { plugin, change } => {
+                write!(f, "{plugin}: {change}")
+            }
+            ManifestChange::PermissionExpanded { plugin, permission } => {
+                write!(f, "{plugin} requests new permission {permission}")
+            }
+            ManifestChange::PlatformAdded { platform } => write!(f, "added platform {platform}"),
+            ManifestChange::PlatformDropped { platform } => {
+                write!(f, "dropped platform {platform}")
+            }
+            ManifestChange::ChecksumChanged { platform, old, new } => write!(
+                f,
+                "checksum for {platform} changed from {} to {}",
+                or_none(old),
+                or_none(new)
+            ),
+        }
+    }
+}
+
+/// Typed changes between two manifests, e.g. for a registry change log.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ManifestDiff {
+    /// Detected changes: plugins, services and permissions (per plugin,
+    /// with packages expanded), then platforms, checksums and other fields
+    pub changes: Vec<ManifestChange>,
+}
+
+impl ManifestDiff {
+    /// Compare two manifests (plugins or packages).
+    ///
+    /// `provides`, `requires`, `compatibility.platforms` and
+    /// `binary.checksums` are reported as typed entries; every other field
+    /// that differs is a `FieldChanged` entry. A package's `plugins` list
+    /// is compared as a whole.
+    pub fn compute(old: &Manifest, new: &Manifest) -> Self {
+        let mut changes = Vec::new();
+
+        let old_plugins = expand(old);
+        let new_plugins = expand(new);
+        for plugin in &new_plugins {
+            let id = &plugin.plugin.id;
+            match old_plugins.iter().find(|p| &p.plugin.id == id) {
+                Some(before) => changes.extend(
+                    ServiceCompatibility::compute(before, plugin)
+                        .changes
+                        .into_iter()
+                        .map(|c| ManifestChange::from_service_change(id, c)),
+                ),
+                None => changes.push(ManifestChange::PluginAdded { plugin: id.clone() }),
+            }
+        }
+        for plugin in &old_plugins {
+            if !new_plugins.iter().any(|p| p.plugin.id == plugin.plugin.id) {
+                changes.push(ManifestChange::PluginRemoved {
+                    plugin: plugin.plugin.id.clone(),
+                });
+            }
+        }
+
+        let (old_platforms, new_platforms) = (platforms(old), platforms(new));
+        for platform in old_platforms.difference(&new_platforms) {
+            changes.push(ManifestChange::PlatformDropped {
+                platform: platform.clone(),
+            });
+        }
+        for platform in new_platforms.difference(&old_platforms) {
+            changes.push(ManifestChange::PlatformAdded {
+                platform: platform.clone(),
+            });
+        }
+
+        let (old_sums, new_sums) = (checksums(old), checksums(new));
+        let keys: BTreeSet<&String> = old_sums.keys().chain(new_sums.keys()).collect();
+        for platform in keys {
+            let (old, new) = (old_sums.get(platform), new_sums.get(platform));
+            if old != new {
+                changes.push(ManifestChange::ChecksumChanged {
+                    platform: platform.clone(),
+                    old: old.cloned(),
+                    new: new.cloned(),
+                });
+            }
+        }
+
+        // Serialization only fails for values TOML can't represent, which
+        // parsed manifests don't contain
+        let fields = field_changes(old, new).unwrap_or_default();
+        changes.extend(
+            fields
+                .into_iter()
+                .filter(|c| {
+                    !TYPED_FIELDS
+                        .iter()
+                        .any(|typed| c.path == *typed || c.path.starts_with(&format!("{typed}.")))
+                })
+                .map(ManifestChange::FieldChanged),
+        );
+
+        Self { changes }
+    }
+
+    /// Check if the manifests are identical.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Minimum version bump the changes require.
+    pub fn required_bump(&self) -> ChangeLevel {
+        self.changes
+            .iter()
+            .map(ManifestChange::level)
+            .max()
+            .unwrap_or(ChangeLevel::Patch)
+    }
+}
+
+/// Plugins of a manifest, with packages expanded.
+fn expand(manifest: &Manifest) -> Vec<PluginManifest> {
+    match manifest {
+        Manifest::Single(m) => vec![m.clone()],
+        Manifest::Package(m) => m.expand_plugins(),
+    }
+}
+
+fn platforms(manifest: &Manifest) -> BTreeSet<String> {
+    let compatibility = match manifest {
+        Manifest::Single(m) => &m.compatibility,
+        Manifest::Package(m) => &m.compatibility,
+    };
+    compatibility.platforms.iter().cloned().collect()
+}
+
+fn checksums(manifest: &Manifest) -> &HashMap<String, String> {
+    match manifest {
+        Manifest::Single(m) => &m.binary.checksums,
+        Manifest::Package(m) => &m.binary.checksums,
+    }
+}
+
+/// Permission grants as comparable strings.
+fn permission_grants(manifest: &PluginManifest) -> Vec<String> {
+    let Some(permissions) = &manifest.permissions else {
+        return Vec::new();
+    };
+    let mut grants = Vec::new();
+    if let Some(network) = &permissions.network {
+        grants.extend(network.allow.iter().map(|a| format!("network: {a}")));
+    }
+    if let Some(fs) = &permissions.fs {
+        grants.extend(fs.read.iter().map(|p| format!("fs read: {p}")));
+        grants.extend(fs.write.iter().map(|p| format!("fs write: {p}")));
+    }
+    if let Some(env) = &permissions.env {
+        grants.extend(env.allow.iter().map(|v| format!("env: {v}")));
+    }
+    if let Some(subprocess) = &permissions.subprocess {
+        grants.extend(subprocess.allow.iter().map(|c| format!("subprocess: {c}")));
+    }
+    grants
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(version: &str, extra: &str) -> PluginManifest {
+        PluginManifest::from_toml(&format!(
+            "[plugin]\nid = \"acme.tool\"\nname = \"Tool\"\nversion = \"{version}\"\ntype = \"extension\"\n{extra}"
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_service_changes() {
+        let old = manifest(
+            "1.2.0",
+            r#"
+[[provides]]
+id = "acme.search"
+version = "1.0.0"
+
+[[provides]]
+id = "acme.index"
+version = "1.0.0"
+
+[[requires]]
+id = "adi.storage"
+min_version = "1.0.0"
+"#,
+        );
+        let new = manifest(
+            "1.3.0",
+            r#"
+[[provides]]
+id = "acme.search"
+version = "1.1.0"
+
+[[requires]]
+id = "adi.storage"
+min_version = "2.0.0"
+
+[[requires]]
+id = "adi.events"
+optional = true
+
+[permissions.network]
+allow = ["api.acme.dev:443"]
+"#,
+        );
+
+        let compat = ServiceCompatibility::compute(&old, &new);
+        let levels: Vec<(String, ChangeLevel)> = compat
+            .changes
+            .iter()
+            .map(|c| (c.to_string(), c.level()))
+            .collect();
+        assert_eq!(
+            levels,
+            [
+                (
+                    "provided acme.search changed from 1.0.0 to 1.1.0".to_string(),
+                    ChangeLevel::Minor
+                ),
+                (
+                    "no longer provides acme.index 1.0.0".to_string(),
+                    ChangeLevel::Major
+                ),
+                (
+                    "requirement on adi.storage narrowed from 1.0.0 to 2.0.0".to_string(),
+                    ChangeLevel::Major
+                ),
+                (
+                    "now optionally requires adi.events".to_string(),
+                    ChangeLevel::Minor
+                ),
+                (
+                    "requests new permission network: api.acme.dev:443".to_string(),
+                    ChangeLevel::Major
+                ),
+            ]
+        );
+        assert_eq!(compat.required_bump(), ChangeLevel::Major);
+        assert_eq!(compat.check_version_bump("1.2.0", "1.3.0").len(), 1);
+        assert!(compat.check_version_bump("1.2.0", "2.0.0").is_empty());
+    }
+
+    #[test]
+    fn test_change_level_between() {
+        let v = |s: &str| Version::parse(s).unwrap();
+        assert_eq!(
+            ChangeLevel::between(&v("1.2.3"), &v("1.2.4")),
+            Some(ChangeLevel::Patch)
+        );
+        assert_eq!(
+            ChangeLevel::between(&v("1.2.3"), &v("1.3.0")),
+            Some(ChangeLevel::Minor)
+        );
+        assert_eq!(
+            ChangeLevel::between(&v("0.2.3"), &v("0.3.0")),
+            Some(ChangeLevel::Major)
+        );
+        assert_eq!(
+            ChangeLevel::between(&v("0.2.3"), &v("0.2.4")),
+            Some(ChangeLevel::Minor)
+        );
+        assert_eq!(ChangeLevel::between(&v("1.2.3"), &v("1.2.3")), None);
+    }
+
+    #[test]
+    fn test_field_changes() {
+        let old = Manifest::Single(manifest(
+            "1.0.0",
+            "[compatibility]\nplatforms = [\"linux-x86_64\"]\n\n[cli]\ncommand = \"tool\"\ndescription = \"Tool\"\n",
+        ));
+        let new = Manifest::Single(manifest(
+            "1.1.0",
+            "[compatibility]\nplatforms = [\"linux-x86_64\", \"darwin-aarch64\"]\n\n[tags]\ncategories = [\"dev\"]\n",
+        ));
+
+        let changes = field_changes(&old, &new).unwrap();
+        let summary: Vec<_> = changes.iter().map(|c| (c.path.as_str(), c.kind)).collect();
+        assert_eq!(
+            summary,
+            [
+                ("cli", FieldChangeKind::Removed),
+                ("compatibility.platforms", FieldChangeKind::Changed),
+                ("plugin.version", FieldChangeKind::Changed),
+                ("tags", FieldChangeKind::Added),
+            ]
+        );
+        assert_eq!(
+            changes[2].to_string(),
+            "changed plugin.version: \"1.0.0\" -> \"1.1.0\""
+        );
+        assert!(field_changes(&old, &old).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_manifest_diff() {
+        let old = Manifest::Single(manifest(
+            "1.0.0",
+            r#"
+[compatibility]
+platforms = ["linux-x86_64", "windows-x86_64"]
+
+[binary]
+name = "tool"
+
+[binary.checksums]
+linux-x86_64 = "aaa"
+
+[[provides]]
+id = "acme.search"
+version = "1.0.0"
+"#,
+        ));
+        let new = Manifest::Single(manifest(
+            "1.1.0",
+            r#"
+[compatibility]
+platforms = ["linux-x86_64"]
+
+[binary]
+name = "tool"
+
+[binary.checksums]
+linux-x86_64 = "bbb"
+
+[[provides]]
+id = "acme.search"
+version = "1.1.0"
+"#,
+        ));
+
+        let diff = ManifestDiff::compute(&old, &new);
+        assert_eq!(
+            diff.changes,
+            [
+                ManifestChange::ServiceVersionChanged {
+                    plugin: "acme.tool".to_string(),
+                    service: "acme.search".to_string(),
+                    old: "1.0.0".to_string(),
+                    new: "1.1.0".to_string(),
+                },
+                ManifestChange::PlatformDropped {
+                    platform: "windows-x86_64".to_string(),
+                },
+                ManifestChange::ChecksumChanged {
+                    platform: "linux-x86_64".to_string(),
+                    old: Some("aaa".to_string()),
+                    new: Some("bbb".to_string()),
+                },
+                ManifestChange::FieldChanged(FieldChange {
+                    path: "plugin.version".to_string(),
+                    kind: FieldChangeKind::Changed,
+                    old: Some("\"1.0.0\"".to_string()),
+                    new: Some("\"1.1.0\"".to_string()),
+                }),
+            ]
+        );
+        assert_eq!(diff.required_bump(), ChangeLevel::Major);
+        assert!(ManifestDiff::compute(&old, &old).is_empty());
+    }
+}
@@ -0,0 +1,174 @@
+//! Discovery of installed manifests.
+//!
+//! `discover_manifests()` walks a plugins directory, parses every
+//! plugin.toml and package.toml it finds, and reports broken files next to
+//! the good ones instead of giving up on the first error:
+//!
+//! ```no_run
+//! use lib_plugin_manifest::{discover_manifests, DiscoverOptions, ManifestSet};
+//! use std::path::Path;
+//!
+//! let mut set = ManifestSet::new();
+//! for found in discover_manifests(Path::new("plugins"), &DiscoverOptions::default()) {
+//!     match found.manifest {
+//!         Ok(manifest) => set.add_with_source(manifest, &found.path),
+//!         Err(e) => eprintln!("skipping {}: {e}", found.path.display()),
+//!     }
+//! }
+//! ```
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::ManifestError;
+use crate::options::ParseOptions;
+use crate::Manifest;
+
+/// File names discovery, migration and indexing treat as manifests.
+const MANIFEST_FILE_NAMES: [&str; 2] = ["plugin.toml", "package.toml"];
+
+/// Options for `discover_manifests()`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiscoverOptions {
+    /// Options used to parse each manifest
+    pub parse: ParseOptions,
+
+    /// How many directory levels below `root` to search (None for no limit;
+    /// 0 only looks at `root` itself)
+    pub max_depth: Option<usize>,
+
+    /// Also search directories whose name starts with '.'
+    pub include_hidden: bool,
+}
+
+/// A manifest file found by `discover_manifests()`.
+#[derive(Debug)]
+pub struct DiscoveredManifest {
+    /// Manifest path, or the directory that couldn't be read
+    pub path: PathBuf,
+
+    /// The parsed manifest, or why it couldn't be read or parsed
+    pub manifest: Result<Manifest, ManifestError>,
+}
+
+/// Find and parse every plugin.toml and package.toml under `root`, sorted
+/// by path.
+///
+/// Unreadable directories and files that fail to parse are reported as
+/// entries with an error; the rest of the tree is still scanned.
+pub fn discover_manifests(root: &Path, options: &DiscoverOptions) -> Vec<DiscoveredManifest> {
+    find_manifest_files(root, options.max_depth, options.include_hidden)
+        .into_iter()
+        .map(|found| match found {
+            Ok(path) => {
+                let manifest = Manifest::from_file_with(&path, &options.parse);
+                DiscoveredManifest { path, manifest }
+            }
+            Err((path, e)) => DiscoveredManifest {
+                path,
+                manifest: Err(e),
+            },
+        })
+        .collect()
+}
+
+/// Find the manifest files under `root`, sorted by path, together with the
+/// directories that couldn't be read (and why).
+///
+/// `max_depth` limits how many levels below `root` are searched; hidden
+/// directories are skipped unless `include_hidden` is set.
+pub(crate) fn find_manifest_files(
+    root: &Path,
+    max_depth: Option<usize>,
+    include_hidden: bool,
+) -> Vec<Result<PathBuf, (PathBuf, ManifestError)>> {
+    let mut found = Vec::new();
+    let mut pending = vec![(root.to_path_buf(), 0)];
+    while let Some((dir, depth)) = pending.pop() {
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                found.push(Err((dir, e.into())));
+                continue;
+            }
+        };
+        for entry in entries {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => {
+                    found.push(Err((dir.clone(), e.into())));
+                    continue;
+                }
+            };
+            let path = entry.path();
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            if path.is_dir() {
+                let visible = include_hidden || !name.starts_with('.');
+                if visible && max_depth.is_none_or(|max| depth < max) {
+                    pending.push((path, depth + 1));
+                }
+            } else if MANIFEST_FILE_NAMES.contains(&name.as_ref()) {
+                found.push(Ok(path));
+            }
+        }
+    }
+    found.sort_by(|a, b| found_path(a).cmp(found_path(b)));
+    found
+}
+
+/// Collect plugin.toml and package.toml paths under `root`, sorted and
+/// skipping hidden directories. Fails on the first unreadable directory.
+pub(crate) fn collect_manifests(root: &Path) -> Result<Vec<PathBuf>, ManifestError> {
+    find_manifest_files(root, None, false)
+        .into_iter()
+        .map(|found| found.map_err(|(_, e)| e))
+        .collect()
+}
+
+fn found_path(found: &Result<PathBuf, (PathBuf, ManifestError)>) -> &Path {
+    match found {
+        Ok(path) | Err((path, _)) => path,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discover_manifests() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |rel: &str, content: &str| {
+            let path = dir.path().join(rel);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        };
+        let plugin = |id: &str| {
+            format!("[plugin]\nid = \"{id}\"\nname = \"{id}\"\nversion = \"1.0.0\"\ntype = \"extension\"\n")
+        };
+        write("acme.tool/plugin.toml", &plugin("acme.tool"));
+        write("vendor/acme.deep/plugin.toml", &plugin("acme.deep"));
+        write("broken/plugin.toml", "[plugin\n");
+        write(".cache/acme.hidden/plugin.toml", &plugin("acme.hidden"));
+        write("acme.tool/README.md", "not a manifest");
+
+        let found = discover_manifests(dir.path(), &DiscoverOptions::default());
+        let ids: Vec<Option<&str>> = found
+            .iter()
+            .map(|f| f.manifest.as_ref().ok().map(Manifest::id))
+            .collect();
+        assert_eq!(ids, [Some("acme.tool"), None, Some("acme.deep")]);
+        assert!(found[1].path.ends_with("broken/plugin.toml"));
+
+        let options = DiscoverOptions {
+            max_depth: Some(1),
+            include_hidden: true,
+            ..Default::default()
+        };
+        assert_eq!(discover_manifests(dir.path(), &options).len(), 2);
+
+        let missing = discover_manifests(&dir.path().join("missing"), &options);
+        assert!(matches!(missing[0].manifest, Err(ManifestError::Io(_))));
+    }
+}
@@ -0,0 +1,438 @@
+//! Registry wire types.
+//!
+//! Flattened, API-friendly views of manifests shared by the registry HTTP
+//! service and its clients. They are derived from the core types and are
+//! not meant to be turned back into manifests.
+//!
+//! The publish pipeline builds index entries with
+//! `RegistryEntry::from_manifest()`, which combines a manifest with the
+//! uploaded artifacts.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::error::ManifestError;
+use crate::package::PackageManifest;
+use crate::plugin::PluginManifest;
+use crate::Manifest;
+
+/// Manifest kind reported for single plugins.
+pub const KIND_PLUGIN: &str = "plugin";
+
+/// Manifest kind reported for packages.
+pub const KIND_PACKAGE: &str = "package";
+
+/// Summary of a manifest for listings and search results.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ManifestSummaryDto {
+    /// "plugin" or "package"
+    pub kind: String,
+
+    /// Plugin or package ID
+    pub id: String,
+
+    /// Human-readable name
+    pub name: String,
+
+    /// Version string (semver)
+    pub version: String,
+
+    /// Plugin types (one for a plugin, deduplicated for a package)
+    pub plugin_types: Vec<String>,
+
+    /// Author
+    pub author: String,
+
+    /// Description
+    pub description: String,
+
+    /// License identifier (SPDX)
+    pub license: Option<String>,
+
+    /// Homepage URL
+    pub homepage: Option<String>,
+
+    /// Source repository URL
+    pub repository: Option<String>,
+
+    /// Category tags
+    pub categories: Vec<String>,
+
+    /// Supported platforms (empty = all platforms)
+    pub platforms: Vec<String>,
+
+    /// Plugin API version
+    pub api_version: u32,
+}
+
+/// Full registry entry for a published manifest version.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct RegistryEntryDto {
+    /// Summary fields
+    #[serde(flatten)]
+    pub summary: ManifestSummaryDto,
+
+    /// Minimum host version required
+    pub min_host_version: Option<String>,
+
+    /// Maximum host version
+    pub max_host_version: Option<String>,
+
+    /// Plugin dependencies
+    pub depends_on: Vec<String>,
+
+    /// Provided service IDs
+    pub provides: Vec<String>,
+
+    /// Required service IDs
+    pub requires: Vec<String>,
+
+    /// IDs of the plugins contained (the plugin itself for single plugins)
+    pub plugins: Vec<String>,
+
+    /// SHA256 checksums per platform
+    pub checksums: HashMap<String, String>,
+
+    /// Artifact sizes in bytes per platform
+    pub sizes: HashMap<String, u64>,
+
+    /// Whether the manifest declares a signature
+    pub signed: bool,
+}
+
+impl From<&PluginManifest> for ManifestSummaryDto {
+    fn from(m: &PluginManifest) -> Self {
+        Self {
+            kind: KIND_PLUGIN.to_string(),
+            id: m.plugin.id.clone(),
+            name: m.plugin.name.clone(),
+            version: m.plugin.version.clone(),
+            plugin_types: vec![m.plugin.plugin_type.to_string()],
+            author: m.plugin.author.clone(),
+            description: m.plugin.description.clone(),
+            license: m.plugin.license.clone(),
+            homepage: m.plugin.homepage.clone(),
+            repository: m.plugin.repository.clone(),
+            categories: m
+                .tags
+                .as_ref()
+                .map(|t| t.categories.clone())
+                .unwrap_or_default(),
+            platforms: m.compatibility.platforms.clone(),
+            api_version: m.compatibility.api_version,
+        }
+    }
+}
+
+impl From<&PackageManifest> for ManifestSummaryDto {
+    fn from(m: &PackageManifest) -> Self {
+        let mut plugin_types: Vec<String> = Vec::new();
+        for plugin in &m.plugins {
+            let plugin_type = plugin.plugin_type.as_str();
+            if !plugin_types.iter().any(|t| t == plugin_type) {
+                plugin_types.push(plugin_type.to_string());
+            }
+        }
+
+        Self {
+            kind: KIND_PACKAGE.to_string(),
+            id: m.package.id.clone(),
+            name: m.package.name.clone(),
+            version: m.package.version.clone(),
+            plugin_types,
+            author: m.package.author.clone(),
+            description: m.package.description.clone(),
+            license: m.package.license.clone(),
+            homepage: m.package.homepage.clone(),
+            repository: m.package.repository.clone(),
+            categories: Vec::new(),
+            platforms: m.compatibility.platforms.clone(),
+            api_version: m.compatibility.api_version,
+        }
+    }
+}
+
+impl From<&Manifest> for ManifestSummaryDto {
+    fn from(m: &Manifest) -> Self {
+        match m {
+            Manifest::Single(m) => m.into(),
+            Manifest::Package(m) => m.into(),
+        }
+    }
+}
+
+impl From<&PluginManifest> for RegistryEntryDto {
+    fn from(m: &PluginManifest) -> Self {
+        Self {
+            summary: m.into(),
+            min_host_version: m.compatibility.min_host_version.clone(),
+            max_host_version: m.compatibility.max_host_version.clone(),
+            depends_on: m.compatibility.depends_on.clone(),
+            provides: m.provides.iter().map(|s| s.id.clone()).collect(),
+            requires: m.requires.iter().map(|s| s.id.clone()).collect(),
+            plugins: vec![m.plugin.id.clone()],
+            checksums: m.binary.checksums.clone(),
+            sizes: m.binary.sizes.clone(),
+            signed: m.signature.is_some(),
+        }
+    }
+}
+
+impl From<&PackageManifest> for RegistryEntryDto {
+    fn from(m: &PackageManifest) -> Self {
+        let mut provides = Vec::new();
+        let mut requires = Vec::new();
+        for plugin in &m.plugins {
+            provides.extend(plugin.provides.iter().map(|s| s.id.clone()));
+            requires.extend(plugin.requires.iter().map(|s| s.id.clone()));
+        }
+
+        Self {
+            summary: m.into(),
+            min_host_version: m.compatibility.min_host_version.clone(),
+            max_host_version: m.compatibility.max_host_version.clone(),
+            depends_on: m.compatibility.depends_on.clone(),
+            provides,
+            requires,
+            plugins: m.plugins.iter().map(|p| p.id.clone()).collect(),
+            checksums: m.binary.checksums.clone(),
+            sizes: m.binary.sizes.clone(),
+            signed: m.signature.is_some(),
+        }
+    }
+}
+
+impl From<&Manifest> for RegistryEntryDto {
+    fn from(m: &Manifest) -> Self {
+        match m {
+            Manifest::Single(m) => m.into(),
+            Manifest::Package(m) => m.into(),
+        }
+    }
+}
+
+/// An uploaded build artifact for one platform.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ArtifactInfo {
+    /// Platform identifier (e.g., "linux-x86_64")
+    pub platform: String,
+
+    /// Download URL
+    pub url: String,
+
+    /// Checksum of the artifact (falls back to the manifest's)
+    pub checksum: Option<String>,
+
+    /// Size in bytes (falls back to the manifest's)
+    pub size: Option<u64>,
+}
+
+/// A complete registry index entry: manifest fields plus downloads.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct RegistryEntry {
+    /// Manifest fields
+    #[serde(flatten)]
+    pub entry: RegistryEntryDto,
+
+    /// Downloads, sorted by platform
+    pub artifacts: Vec<ArtifactInfo>,
+
+    /// Publish timestamp (RFC 3339); None until the registry records it
+    pub published_at: Option<String>,
+}
+
+impl RegistryEntry {
+    /// Build the index entry for a manifest and its uploaded artifacts.
+    ///
+    /// Artifact checksums and sizes default to the ones declared in the
+    /// manifest and are copied into the entry's checksum and size maps.
+    /// Fails if an artifact disagrees with the manifest, targets a platform
+    /// the manifest doesn't support, or is listed twice.
+    pub fn from_manifest(
+        manifest: &Manifest,
+        artifacts: &[ArtifactInfo],
+    ) -> Result<Self, ManifestError> {
+        let mut entry = RegistryEntryDto::from(manifest);
+        let platforms = &entry.summary.platforms;
+        let mut resolved: Vec<ArtifactInfo> = Vec::with_capacity(artifacts.len());
+
+        for artifact in artifacts {
+            let platform = &artifact.platform;
+            if !platforms.is_empty() && !platforms.iter().any(|p| p == platform || p == "all") {
+                return Err(ManifestError::InvalidFormat(format!(
+                    "Artifact for {platform} but {} does not support it",
+                    entry.summary.id
+                )));
+            }
+            if resolved.iter().any(|a| &a.platform == platform) {
+                return Err(ManifestError::InvalidFormat(format!(
+                    "Duplicate artifact for {platform}"
+                )));
+            }
+
+            let declared = entry.checksums.get(platform);
+            let checksum = match (declared, &artifact.checksum) {
+                (Some(expected), Some(actual)) if expected != actual => {
+                    return Err(ManifestError::ChecksumMismatch {
+                        platform: platform.clone(),
+                        expected: expected.clone(),
+                        actual: actual.clone(),
+                    })
+                }
+                (declared, actual) => actual.as_ref().or(declared).cloned(),
+            };
+            let size = match (entry.sizes.get(platform), artifact.size) {
+                (Some(&expected), Some(actual)) if expected != actual => {
+                    return Err(ManifestError::SizeMismatch {
+                        platform: platform.clone(),
+                        expected,
+                        actual,
+                    })
+                }
+                (declared, actual) => actual.or(declared.copied()),
+            };
+
+            resolved.push(ArtifactInfo {
+                platform: platform.clone(),
+                url: artifact.url.clone(),
+                checksum,
+                size,
+            });
+        }
+
+        for artifact in &resolved {
+            if let Some(checksum) = &artifact.checksum {
+                entry
+                    .checksums
+                    .insert(artifact.platform.clone(), checksum.clone());
+            }
+            if let Some(size) = artifact.size {
+                entry.sizes.insert(artifact.platform.clone(), size);
+            }
+        }
+        resolved.sort_by(|a, b| a.platform.cmp(&b.platform));
+
+        Ok(Self {
+            entry,
+            artifacts: resolved,
+            published_at: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plugin_entry() {
+        let toml = r#"
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.0.0"
+type = "extension"
+license = "MIT"
+
+[binary]
+checksums = { linux-x86_64 = "abc" }
+
+[[provides]]
+id = "acme.search"
+version = "1.0.0"
+
+[tags]
+categories = ["search"]
+"#;
+        let manifest = PluginManifest::from_toml(toml).unwrap();
+        let entry = RegistryEntryDto::from(&manifest);
+
+        assert_eq!(entry.summary.kind, KIND_PLUGIN);
+        assert_eq!(entry.summary.plugin_types, vec!["extension"]);
+        assert_eq!(entry.summary.categories, vec!["search"]);
+        assert_eq!(entry.provides, vec!["acme.search"]);
+        assert_eq!(entry.plugins, vec!["acme.tool"]);
+        assert!(!entry.signed);
+    }
+
+    #[test]
+    fn test_package_summary() {
+        let toml = r#"
+[package]
+id = "acme.themes"
+name = "Themes"
+version = "2.0.0"
+
+[[plugins]]
+id = "acme.theme-dark"
+name = "Dark"
+type = "theme"
+binary = "dark"
+
+[[plugins]]
+id = "acme.theme-light"
+name = "Light"
+type = "theme"
+binary = "light"
+"#;
+        let manifest = Manifest::from_toml(toml).unwrap();
+        let entry = RegistryEntryDto::from(&manifest);
+
+        assert_eq!(entry.summary.kind, KIND_PACKAGE);
+        assert_eq!(entry.summary.plugin_types, vec!["theme"]);
+        assert_eq!(entry.plugins.len(), 2);
+    }
+
+    #[test]
+    fn test_registry_entry_from_manifest() {
+        let manifest = Manifest::from_toml(
+            r#"
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.0.0"
+type = "extension"
+
+[compatibility]
+platforms = ["linux-x86_64", "darwin-aarch64"]
+
+[binary]
+name = "tool"
+checksums = { linux-x86_64 = "abc" }
+"#,
+        )
+        .unwrap();
+        let artifact = |platform: &str, checksum: Option<&str>| ArtifactInfo {
+            platform: platform.to_string(),
+            url: format!("https://example.com/tool-{platform}.tar.gz"),
+            checksum: checksum.map(String::from),
+            size: Some(42),
+        };
+
+        let entry = RegistryEntry::from_manifest(
+            &manifest,
+            &[
+                artifact("linux-x86_64", None),
+                artifact("darwin-aarch64", Some("def")),
+            ],
+        )
+        .unwrap();
+        assert_eq!(entry.artifacts[0].platform, "darwin-aarch64");
+        assert_eq!(entry.artifacts[1].checksum.as_deref(), Some("abc"));
+        assert_eq!(entry.entry.checksums["darwin-aarch64"], "def");
+        assert_eq!(entry.entry.sizes["linux-x86_64"], 42);
+        assert!(entry.published_at.is_none());
+
+        assert!(matches!(
+            RegistryEntry::from_manifest(&manifest, &[artifact("linux-x86_64", Some("xyz"))]),
+            Err(ManifestError::ChecksumMismatch { .. })
+        ));
+        assert!(
+            RegistryEntry::from_manifest(&manifest, &[artifact("windows-x86_64", None)]).is_err()
+        );
+    }
+}
@@ -0,0 +1,146 @@
+//! Ed25519 manifest signatures.
+//!
+//! The `[signature]` table names the base64 public key and a signature file
+//! relative to the manifest. The file holds the base64 Ed25519 signature
+//! over the manifest's signing payload; binaries are covered through their
+//! checksums, which are part of the payload.
+//!
+//! ```toml
+//! [signature]
+//! public_key = "GsD1ckq2Qbx0qW1t5tgUeHkzP0p9RN9jJt5Ql0Mwqq8="
+//! signature_file = "plugin.sig"
+//! ```
+
+use std::path::Path;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+
+use crate::error::ManifestError;
+use crate::package::PackageManifest;
+use crate::plugin::{PluginManifest, SignatureInfo};
+use crate::signing::SignatureVerifier;
+use crate::Manifest;
+
+/// `SignatureVerifier` for Ed25519 keys and signatures.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Ed25519Verifier;
+
+impl SignatureVerifier for Ed25519Verifier {
+    fn verify(&self, public_key: &str, message: &[u8], signature: &[u8]) -> bool {
+        let Some(key) = STANDARD
+            .decode(public_key)
+            .ok()
+            .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+            .and_then(|bytes| VerifyingKey::from_bytes(&bytes).ok())
+        else {
+            return false;
+        };
+        Signature::from_slice(signature)
+            .is_ok_and(|signature| key.verify_strict(message, &signature).is_ok())
+    }
+}
+
+/// Base64 public key for a secret key, as written in `[signature]`.
+pub fn public_key(secret_key: &[u8; 32]) -> String {
+    STANDARD.encode(
+        SigningKey::from_bytes(secret_key)
+            .verifying_key()
+            .as_bytes(),
+    )
+}
+
+/// Sign a manifest, returning the base64 signature file content.
+///
+/// Fill in `[binary.checksums]` before signing; changing the manifest
+/// afterwards (other than `[signature]`) invalidates the signature.
+pub fn sign_manifest(manifest: &Manifest, secret_key: &[u8; 32]) -> Result<String, ManifestError> {
+    let payload = match manifest {
+        Manifest::Single(m) => m.signing_payload()?,
+        Manifest::Package(m) => m.signing_payload()?,
+    };
+    let signature = SigningKey::from_bytes(secret_key).sign(&payload);
+    Ok(STANDARD.encode(signature.to_bytes()))
+}
+
+/// Read the signature file named in `[signature]`.
+fn read_signature(info: Option<&SignatureInfo>, base_dir: &Path) -> Result<Vec<u8>, ManifestError> {
+    let info = info.ok_or_else(|| ManifestError::Signature("manifest is not signed".into()))?;
+    let content = std::fs::read_to_string(base_dir.join(&info.signature_file))?;
+    STANDARD
+        .decode(content.trim())
+        .map_err(|e| ManifestError::Signature(format!("invalid signature file: {e}")))
+}
+
+impl PluginManifest {
+    /// Verify the Ed25519 signature in `base_dir` and the binary for the
+    /// current platform against its signed checksum.
+    pub fn verify_signature(&self, base_dir: &Path) -> Result<(), ManifestError> {
+        let signature = read_signature(self.signature.as_ref(), base_dir)?;
+        self.verify_signature_with(&signature, &Ed25519Verifier)?;
+        self.verify_binary(&base_dir.join(self.binary_filename()))
+    }
+}
+
+impl PackageManifest {
+    /// Verify the Ed25519 signature in `base_dir`.
+    pub fn verify_signature(&self, base_dir: &Path) -> Result<(), ManifestError> {
+        let signature = read_signature(self.signature.as_ref(), base_dir)?;
+        self.verify_signature_with(&signature, &Ed25519Verifier)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checksum::compute_file_checksum;
+    use crate::platform::current_platform;
+
+    const KEY: [u8; 32] = [7; 32];
+
+    #[test]
+    fn test_sign_and_verify() {
+        let dir = tempfile::tempdir().unwrap();
+        let unsigned = PluginManifest::builder("acme.tool", "Tool", "1.0.0")
+            .build()
+            .unwrap();
+        let binary = dir.path().join(unsigned.binary_filename());
+        std::fs::write(&binary, b"plugin code").unwrap();
+
+        let mut manifest = PluginManifest::builder("acme.tool", "Tool", "1.0.0")
+            .checksum(
+                &current_platform(),
+                &compute_file_checksum(&binary).unwrap(),
+            )
+            .build()
+            .unwrap();
+        manifest.signature = Some(SignatureInfo {
+            public_key: public_key(&KEY),
+            signature_file: "plugin.sig".to_string(),
+        });
+        let signature = sign_manifest(&Manifest::Single(manifest.clone()), &KEY).unwrap();
+        std::fs::write(dir.path().join("plugin.sig"), signature).unwrap();
+        manifest.verify_signature(dir.path()).unwrap();
+
+        let mut tampered = manifest.clone();
+        tampered.plugin.name = "Evil".to_string();
+        assert!(matches!(
+            tampered.verify_signature(dir.path()),
+            Err(ManifestError::Signature(_))
+        ));
+
+        std::fs::write(&binary, b"other code").unwrap();
+        assert!(matches!(
+            manifest.verify_signature(dir.path()),
+            Err(ManifestError::ChecksumMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_verifier_rejects_bad_input() {
+        let verifier = Ed25519Verifier;
+        assert!(!verifier.verify("not base64!", b"msg", &[0; 64]));
+        assert!(!verifier.verify(&public_key(&KEY), b"msg", &[0; 10]));
+    }
+}
@@ -0,0 +1,230 @@
+//! In-place editing of manifest files.
+//!
+//! `ManifestEditor` changes individual values of a plugin.toml or
+//! package.toml and leaves everything else (comments, key order,
+//! whitespace) as the author wrote it, so CI can bump versions and record
+//! checksums without rewriting the file:
+//!
+//! ```
+//! use lib_plugin_manifest::ManifestEditor;
+//!
+//! let mut editor = ManifestEditor::parse(
+//!     "[plugin]\nid = \"acme.tool\"\nversion = \"1.0.0\" # bumped by CI\n",
+//! )
+//! .unwrap();
+//! editor.set_version("1.1.0").unwrap();
+//! assert!(editor.to_string().contains("version = \"1.1.0\" # bumped by CI"));
+//! ```
+
+use std::fmt;
+use std::path::Path;
+use toml_edit::{value, Array, DocumentMut, Item, Table, TableLike, Value};
+
+use crate::error::ManifestError;
+use crate::version::Version;
+use crate::Manifest;
+
+/// Editor for an existing manifest document.
+#[derive(Debug, Clone)]
+pub struct ManifestEditor {
+    doc: DocumentMut,
+}
+
+impl ManifestEditor {
+    /// Parse a plugin.toml or package.toml for editing.
+    pub fn parse(content: &str) -> Result<Self, ManifestError> {
+        let doc: DocumentMut = content
+            .parse()
+            .map_err(|e| ManifestError::InvalidFormat(format!("Failed to parse manifest: {e}")))?;
+        let editor = Self { doc };
+        editor.section()?;
+        Ok(editor)
+    }
+
+    /// Read a manifest file for editing.
+    pub fn open(path: &Path) -> Result<Self, ManifestError> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// Write the edited document.
+    pub fn save(&self, path: &Path) -> Result<(), ManifestError> {
+        std::fs::write(path, self.doc.to_string())?;
+        Ok(())
+    }
+
+    /// Parse the edited document as a manifest.
+    pub fn to_manifest(&self) -> Result<Manifest, ManifestError> {
+        Manifest::from_toml(&self.doc.to_string())
+    }
+
+    /// Whether the document is a package.toml.
+    pub fn is_package(&self) -> bool {
+        self.doc.contains_key("package")
+    }
+
+    /// Set `plugin.version` (or `package.version`).
+    pub fn set_version(&mut self, version: &str) -> Result<(), ManifestError> {
+        Version::parse_field("version", version)?;
+        let section = self.section()?;
+        let table = self.doc[section]
+            .as_table_like_mut()
+            .ok_or_else(|| ManifestError::InvalidFormat(format!("'{section}' must be a table")))?;
+        set_preserving_decor(table, "version", version.into());
+        Ok(())
+    }
+
+    /// Set the binary checksum for a platform, creating `[binary.checksums]`
+    /// if needed.
+    pub fn add_checksum(&mut self, platform: &str, hash: &str) -> Result<(), ManifestError> {
+        let checksums = table_mut(self.doc.as_table_mut(), "binary")?;
+        let checksums = table_mut(checksums, "checksums")?;
+        set_preserving_decor(checksums, platform, hash.into());
+        Ok(())
+    }
+
+    /// Remove the binary checksum for a platform. Returns whether one was
+    /// declared.
+    pub fn remove_checksum(&mut self, platform: &str) -> bool {
+        self.doc
+            .get_mut("binary")
+            .and_then(|b| b.get_mut("checksums"))
+            .and_then(Item::as_table_like_mut)
+            .is_some_and(|c| c.remove(platform).is_some())
+    }
+
+    /// Add a plugin to `compatibility.depends_on` (no-op if already listed).
+    ///
+    /// Packages declare dependencies per plugin, so this fails on a
+    /// package.toml.
+    pub fn add_dependency(&mut self, id: &str) -> Result<(), ManifestError> {
+        if self.is_package() {
+            return Err(ManifestError::InvalidFormat(
+                "Package dependencies are declared per plugin in [[plugins]]".to_string(),
+            ));
+        }
+        let compatibility = table_mut(self.doc.as_table_mut(), "compatibility")?;
+        let depends_on = compatibility
+            .entry("depends_on")
+            .or_insert_with(|| value(Array::new()))
+            .as_array_mut()
+            .ok_or_else(|| {
+                ManifestError::InvalidFormat("compatibility.depends_on must be an array".into())
+            })?;
+        if !depends_on.iter().any(|v| v.as_str() == Some(id)) {
+            depends_on.push(id);
+        }
+        Ok(())
+    }
+
+    /// Remove a plugin from `compatibility.depends_on`. Returns whether it
+    /// was listed.
+    pub fn remove_dependency(&mut self, id: &str) -> bool {
+        let Some(depends_on) = self
+            .doc
+            .get_mut("compatibility")
+            .and_then(|c| c.get_mut("depends_on"))
+            .and_then(Item::as_array_mut)
+        else {
+            return false;
+        };
+        let before = depends_on.len();
+        depends_on.retain(|v| v.as_str() != Some(id));
+        depends_on.len() != before
+    }
+
+    /// "plugin" or "package".
+    fn section(&self) -> Result<&'static str, ManifestError> {
+        match (
+            self.doc.contains_key("plugin"),
+            self.doc.contains_key("package"),
+        ) {
+            (true, true) => Err(ManifestError::AmbiguousType),
+            (true, false) => Ok("plugin"),
+            (false, true) => Ok("package"),
+            (false, false) => Err(ManifestError::InvalidFormat(
+                "Manifest must contain either [plugin] or [package] section".to_string(),
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ManifestEditor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.doc)
+    }
+}
+
+/// Get or create the subtable `key`.
+fn table_mut<'a>(parent: &'a mut Table, key: &str) -> Result<&'a mut Table, ManifestError> {
+    parent
+        .entry(key)
+        .or_insert_with(toml_edit::table)
+        .as_table_mut()
+        .ok_or_else(|| ManifestError::InvalidFormat(format!("'{key}' must be a table")))
+}
+
+/// Set `key` in a table, keeping the comments around an existing value.
+fn set_preserving_decor(table: &mut dyn TableLike, key: &str, mut new: Value) {
+    if let Some(old) = table.get(key).and_then(Item::as_value) {
+        *new.decor_mut() = old.decor().clone();
+    }
+    table.insert(key, Item::Value(new));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLUGIN: &str = r#"# Acme tool manifest
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.0.0"  # bumped by CI
+type = "extension"
+
+[compatibility]
+# Core must load first
+depends_on = ["acme.core"]
+"#;
+
+    #[test]
+    fn test_edit_preserves_comments() {
+        let mut editor = ManifestEditor::parse(PLUGIN).unwrap();
+        editor.set_version("1.1.0").unwrap();
+        editor.add_checksum("linux-x86_64", "abc").unwrap();
+        editor.add_dependency("acme.storage").unwrap();
+        editor.add_dependency("acme.core").unwrap();
+
+        let edited = editor.to_string();
+        assert!(edited.starts_with("# Acme tool manifest\n"));
+        assert!(edited.contains("version = \"1.1.0\"  # bumped by CI\n"));
+        assert!(edited.contains("# Core must load first\n"));
+
+        let Manifest::Single(manifest) = editor.to_manifest().unwrap() else {
+            panic!("expected a plugin manifest");
+        };
+        assert_eq!(manifest.plugin.version, "1.1.0");
+        assert_eq!(
+            manifest.compatibility.depends_on,
+            ["acme.core", "acme.storage"]
+        );
+        assert_eq!(manifest.binary.checksums["linux-x86_64"], "abc");
+
+        assert!(editor.remove_dependency("acme.core"));
+        assert!(editor.remove_checksum("linux-x86_64"));
+        assert!(!editor.remove_checksum("linux-x86_64"));
+        assert!(editor.set_version("not a version").is_err());
+    }
+
+    #[test]
+    fn test_edit_package() {
+        let mut editor = ManifestEditor::parse(
+            "[package]\nid = \"acme.suite\"\nname = \"Suite\"\nversion = \"1.0.0\"\n",
+        )
+        .unwrap();
+        editor.set_version("2.0.0").unwrap();
+        assert!(editor.to_string().contains("version = \"2.0.0\""));
+        assert!(editor.add_dependency("acme.core").is_err());
+        assert!(ManifestEditor::parse("[other]\n").is_err());
+    }
+}
@@ -0,0 +1,252 @@
+//! Effective manifest computation.
+//!
+//! A manifest can carry platform-conditional sections, optional features,
+//! config profiles and localized metadata. `PluginManifest::effective()`
+//! applies them in one step:
+//!
+//! ```toml
+//! [target.darwin-aarch64]
+//! binary = { name = "tool_macos" }
+//!
+//! [features.gpu]
+//! requirements = { notes = "Needs a Metal or Vulkan GPU" }
+//!
+//! [config.profiles.dev]
+//! log_level = "debug"
+//!
+//! [locales.de]
+//! name = "Werkzeug"
+//! description = "Ein Werkzeug"
+//! ```
+//!
+//! Target and feature tables are deep-merged into the manifest: tables merge
+//! key by key, arrays and values replace.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::ManifestError;
+use crate::plugin::PluginManifest;
+use crate::validate::ValidationIssue;
+
+/// Localized plugin metadata.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct LocalizedMeta {
+    /// Localized name
+    #[serde(default)]
+    pub name: Option<String>,
+
+    /// Localized description
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Fields an override table must not change.
+const IMMUTABLE_FIELDS: [&str; 2] = ["id", "version"];
+
+impl PluginManifest {
+    /// Compute the manifest a host should use for a platform, a set of
+    /// enabled features, a config profile and a locale.
+    ///
+    /// Applies `[target.<platform>]`, then each feature in the given order,
+    /// then merges the profile into the config defaults and the locale into
+    /// the plugin name and description. The locale falls back from `de-AT`
+    /// to `de`; an unknown locale keeps the default text. Unknown features
+    /// or profiles are an error. The result has no conditional sections left.
+    pub fn effective(
+        &self,
+        platform: &str,
+        features: &[&str],
+        profile: Option<&str>,
+        locale: Option<&str>,
+    ) -> Result<PluginManifest, ManifestError> {
+        let mut overrides = Vec::new();
+        if let Some(table) = self.target.get(platform) {
+            overrides.push(table);
+        }
+        for feature in features {
+            let table = self.features.get(*feature).ok_or_else(|| {
+                ManifestError::InvalidFormat(format!("Unknown feature '{feature}'"))
+            })?;
+            overrides.push(table);
+        }
+
+        let mut effective = if overrides.is_empty() {
+            self.clone()
+        } else {
+            let mut document = toml::Table::try_from(self).map_err(|e| {
+                ManifestError::InvalidFormat(format!("Failed to serialize manifest: {e}"))
+            })?;
+            for table in overrides {
+                merge(&mut document, table);
+            }
+            document.try_into().map_err(ManifestError::TomlParse)?
+        };
+
+        if let Some(profile) = profile {
+            let values = self.config.profiles.get(profile).ok_or_else(|| {
+                ManifestError::InvalidFormat(format!("Unknown config profile '{profile}'"))
+            })?;
+            for (key, value) in values {
+                effective.config.defaults.insert(key.clone(), value.clone());
+            }
+        }
+
+        if let Some(localized) = locale.and_then(|l| self.localized(l)) {
+            if let Some(name) = &localized.name {
+                effective.plugin.name = name.clone();
+            }
+            if let Some(description) = &localized.description {
+                effective.plugin.description = description.clone();
+            }
+        }
+
+        effective.target.clear();
+        effective.features.clear();
+        effective.locales.clear();
+        effective.config.profiles.clear();
+        Ok(effective)
+    }
+
+    /// Localized metadata for a locale, falling back to its language.
+    pub fn localized(&self, locale: &str) -> Option<&LocalizedMeta> {
+        self.locales.get(locale).or_else(|| {
+            let language = locale.split(['-', '_']).next()?;
+            self.locales.get(language)
+        })
+    }
+
+    /// Check conditional sections.
+    pub(crate) fn check_effective(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        let tables = self
+            .target
+            .iter()
+            .map(|(k, t)| (format!("target.{k}"), t))
+            .chain(
+                self.features
+                    .iter()
+                    .map(|(k, t)| (format!("features.{k}"), t)),
+            );
+
+        for (path, table) in tables {
+            if table.contains_key("target") || table.contains_key("features") {
+                issues.push(ValidationIssue::error(
+                    path.clone(),
+                    "Conditional sections cannot be nested",
+                ));
+            }
+            let plugin = table.get("plugin").and_then(|p| p.as_table());
+            for field in IMMUTABLE_FIELDS {
+                if plugin.is_some_and(|p| p.contains_key(field)) {
+                    issues.push(ValidationIssue::error(
+                        format!("{path}.plugin.{field}"),
+                        format!("plugin.{field} cannot be overridden"),
+                    ));
+                }
+            }
+        }
+
+        let platforms = &self.compatibility.platforms;
+        for platform in self.target.keys() {
+            if !platforms.is_empty() && !platforms.iter().any(|p| p == platform || p == "all") {
+                issues.push(ValidationIssue::warning(
+                    format!("target.{platform}"),
+                    format!("'{platform}' is not in compatibility.platforms"),
+                ));
+            }
+        }
+        issues
+    }
+}
+
+/// Deep-merge `overlay` into `base`.
+fn merge(base: &mut toml::Table, overlay: &toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(key), value) {
+            (Some(toml::Value::Table(base)), toml::Value::Table(overlay)) => merge(base, overlay),
+            _ => {
+                base.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate::has_errors;
+
+    const MANIFEST: &str = r#"
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.0.0"
+type = "extension"
+description = "A tool"
+
+[binary]
+name = "tool"
+
+[config.defaults]
+log_level = "info"
+threads = 4
+
+[config.profiles.dev]
+log_level = "debug"
+
+[target.darwin-aarch64]
+binary = { name = "tool_macos" }
+
+[features.gpu]
+requirements = { notes = "Needs a GPU" }
+
+[locales.de]
+name = "Werkzeug"
+"#;
+
+    #[test]
+    fn test_effective() {
+        let manifest = PluginManifest::from_toml(MANIFEST).unwrap();
+        assert!(manifest.validate().is_empty());
+
+        let effective = manifest
+            .effective("darwin-aarch64", &["gpu"], Some("dev"), Some("de-AT"))
+            .unwrap();
+        assert_eq!(effective.binary.name, "tool_macos");
+        assert_eq!(
+            effective.requirements.unwrap().notes.as_deref(),
+            Some("Needs a GPU")
+        );
+        assert_eq!(
+            effective.config.defaults["log_level"].as_str(),
+            Some("debug")
+        );
+        assert_eq!(effective.config.defaults["threads"].as_integer(), Some(4));
+        assert_eq!(effective.plugin.name, "Werkzeug");
+        assert_eq!(effective.plugin.description, "A tool");
+        assert!(effective.target.is_empty() && effective.locales.is_empty());
+
+        let plain = manifest
+            .effective("linux-x86_64", &[], None, Some("fr"))
+            .unwrap();
+        assert_eq!(plain.binary.name, "tool");
+        assert_eq!(plain.plugin.name, "Tool");
+
+        assert!(manifest
+            .effective("linux-x86_64", &["missing"], None, None)
+            .is_err());
+        assert!(manifest
+            .effective("linux-x86_64", &[], Some("prod"), None)
+            .is_err());
+    }
+
+    #[test]
+    fn test_immutable_override() {
+        let toml =
+            format!("{MANIFEST}\n[target.linux-x86_64]\nplugin = {{ id = \"acme.other\" }}\n");
+        let manifest = PluginManifest::from_toml(&toml).unwrap();
+        assert!(has_errors(&manifest.validate()));
+    }
+}
@@ -0,0 +1,114 @@
+//! Manifests embedded in compiled plugin binaries.
+//!
+//! A manifest is embedded as a marked blob anywhere in the binary (a
+//! dedicated linker section or a trailing footer):
+//!
+//! ```text
+//! EMBED_MAGIC | u32 little-endian length | manifest TOML
+//! ```
+//!
+//! Because the blob is located by scanning for the magic marker, the same
+//! reader works for dylibs, executables and wasm modules.
+
+use std::path::Path;
+
+use crate::error::ManifestError;
+use crate::Manifest;
+
+/// Marker preceding an embedded manifest.
+pub const EMBED_MAGIC: &[u8; 16] = b"\0ADI-MANIFEST-V1";
+
+/// Encode a manifest as an embeddable blob.
+pub fn encode_embedded(manifest_toml: &str) -> Vec<u8> {
+    let mut blob = Vec::with_capacity(EMBED_MAGIC.len() + 4 + manifest_toml.len());
+    blob.extend_from_slice(EMBED_MAGIC);
+    blob.extend_from_slice(&(manifest_toml.len() as u32).to_le_bytes());
+    blob.extend_from_slice(manifest_toml.as_bytes());
+    blob
+}
+
+/// Find an embedded manifest in binary data.
+///
+/// Returns the manifest TOML, or None if no valid blob is present.
+pub fn find_embedded(data: &[u8]) -> Option<&str> {
+    let mut offset = 0;
+    while let Some(pos) = find(&data[offset..], EMBED_MAGIC) {
+        let start = offset + pos + EMBED_MAGIC.len();
+        if let Some(content) = read_blob(&data[start..]) {
+            return Some(content);
+        }
+        // The marker can also appear in the reader's own code; keep looking
+        offset = start;
+    }
+    None
+}
+
+fn read_blob(data: &[u8]) -> Option<&str> {
+    let len = u32::from_le_bytes(data.get(..4)?.try_into().ok()?) as usize;
+    let content = data.get(4..4 + len)?;
+    std::str::from_utf8(content).ok()
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+impl Manifest {
+    /// Read a manifest embedded in a compiled plugin binary.
+    pub fn from_binary(path: &Path) -> Result<Self, ManifestError> {
+        let data = std::fs::read(path)?;
+        let content = find_embedded(&data).ok_or_else(|| {
+            ManifestError::InvalidFormat(format!(
+                "No embedded manifest found in {}",
+                path.display()
+            ))
+        })?;
+        Self::from_toml(content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOML: &str = r#"
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.0.0"
+type = "extension"
+"#;
+
+    #[test]
+    fn test_encode_and_find() {
+        let mut data = b"\x7fELF some code".to_vec();
+        data.extend_from_slice(&encode_embedded(TOML));
+        data.extend_from_slice(b"trailing data");
+        assert_eq!(find_embedded(&data), Some(TOML));
+        assert_eq!(find_embedded(b"no manifest here"), None);
+    }
+
+    #[test]
+    fn test_skips_truncated_marker() {
+        // A bare marker without a valid length (e.g., in string tables)
+        let mut data = EMBED_MAGIC.to_vec();
+        data.extend_from_slice(&[0xff, 0xff, 0xff, 0xff]);
+        data.extend_from_slice(&encode_embedded(TOML));
+        assert_eq!(find_embedded(&data), Some(TOML));
+    }
+
+    #[test]
+    fn test_from_binary() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("libtool.so");
+        let mut data = vec![0u8; 64];
+        data.extend_from_slice(&encode_embedded(TOML));
+        std::fs::write(&path, data).unwrap();
+
+        let manifest = Manifest::from_binary(&path).unwrap();
+        assert_eq!(manifest.id(), "acme.tool");
+
+        std::fs::write(&path, b"plain").unwrap();
+        assert!(Manifest::from_binary(&path).is_err());
+    }
+}
@@ -13,6 +13,11 @@ pub enum ManifestError {
     #[error("TOML parse error: {0}")]
     TomlParse(#[from] toml::de::Error),
 
+    /// JSON parsing or serialization error
+    #[cfg(feature = "json")]
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
     /// Invalid manifest format
     #[error("Invalid manifest format: {0}")]
     InvalidFormat(String),
@@ -25,7 +30,60 @@ pub enum ManifestError {
     #[error("Invalid version: {0}")]
     InvalidVersion(String),
 
+    /// Manifest has both `[plugin]` and `[package]` sections
+    #[error("Ambiguous manifest type: both [plugin] and [package] sections are present")]
+    AmbiguousType,
+
     /// Circular dependency detected
     #[error("Circular dependency detected: {0}")]
     CircularDependency(String),
+
+    /// Plugin or package ID not found
+    #[error("Unknown plugin: {0}")]
+    UnknownPlugin(String),
+
+    /// A required plugin or service is not available
+    #[error("Missing dependency: {0}")]
+    MissingDependency(String),
+
+    /// Dependency resolution across manifests failed
+    #[error("Unresolved dependencies: {}", crate::resolver::describe_problems(.0))]
+    UnresolvedDependencies(Vec<crate::resolver::DependencyProblem>),
+
+    /// Binary size doesn't match the size declared in the manifest
+    #[error("Size mismatch for {platform}: expected {expected} bytes, got {actual}")]
+    SizeMismatch {
+        /// Platform identifier
+        platform: String,
+        /// Declared size in bytes
+        expected: u64,
+        /// Actual size in bytes
+        actual: u64,
+    },
+
+    /// Signature is missing, doesn't match, or doesn't cover the manifest
+    #[error("Signature verification failed: {0}")]
+    Signature(String),
+
+    /// Plugin bundle could not be built or read
+    #[error("Bundle error: {0}")]
+    Bundle(String),
+
+    /// No checksum is declared for the platform
+    #[error("No checksum declared for {platform}")]
+    MissingChecksum {
+        /// Platform identifier
+        platform: String,
+    },
+
+    /// Binary checksum doesn't match the checksum declared in the manifest
+    #[error("Checksum mismatch for {platform}: expected {expected}, got {actual}")]
+    ChecksumMismatch {
+        /// Platform identifier
+        platform: String,
+        /// Declared checksum
+        expected: String,
+        /// Computed checksum
+        actual: String,
+    },
 }
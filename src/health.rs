@@ -0,0 +1,160 @@
+//! Health check declarations (`[health]`).
+//!
+//! ```toml
+//! [health]
+//! probe = "service_call"
+//! target = "acme.tool.health"
+//! interval_secs = 30
+//! failure_threshold = 3
+//! restart = "on_failure"
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::plugin::PluginManifest;
+use crate::validate::ValidationIssue;
+
+/// How the host probes the plugin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ProbeKind {
+    /// Call a service provided by the plugin; `target` is the service ID
+    ServiceCall,
+    /// Run a bundled command; `target` is its path in the plugin directory
+    Command,
+}
+
+/// What the host does after the failure threshold is reached.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum RestartPolicy {
+    /// Mark the plugin unhealthy but leave it running
+    Never,
+    /// Restart the plugin
+    #[default]
+    OnFailure,
+}
+
+/// Health check for long-running plugins.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct HealthInfo {
+    /// Probe kind
+    pub probe: ProbeKind,
+
+    /// Service ID or command path, depending on the probe kind
+    pub target: String,
+
+    /// Seconds between probes
+    #[serde(default = "default_interval_secs")]
+    pub interval_secs: u64,
+
+    /// Consecutive failures before the plugin is considered unhealthy
+    #[serde(default = "default_failure_threshold")]
+    pub failure_threshold: u32,
+
+    /// Restart policy
+    #[serde(default)]
+    pub restart: RestartPolicy,
+}
+
+fn default_interval_secs() -> u64 {
+    30
+}
+
+fn default_failure_threshold() -> u32 {
+    3
+}
+
+impl HealthInfo {
+    /// Time between probes.
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs(self.interval_secs)
+    }
+}
+
+impl PluginManifest {
+    /// Check the health check declaration.
+    pub(crate) fn check_health(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        let Some(health) = &self.health else {
+            return issues;
+        };
+
+        let (script, service) = match health.probe {
+            ProbeKind::Command => (Some(health.target.as_str()), None),
+            ProbeKind::ServiceCall => (None, Some(health.target.as_str())),
+        };
+        issues.extend(self.check_action(
+            "health.target".to_string(),
+            "Health probe",
+            script,
+            service,
+        ));
+        if health.interval_secs == 0 {
+            issues.push(ValidationIssue::error(
+                "health.interval_secs",
+                "Probe interval must be greater than zero",
+            ));
+        }
+        if health.failure_threshold == 0 {
+            issues.push(ValidationIssue::error(
+                "health.failure_threshold",
+                "Failure threshold must be greater than zero",
+            ));
+        }
+
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate::has_errors;
+
+    const BASE: &str = r#"
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.0.0"
+type = "extension"
+
+[[provides]]
+id = "acme.tool.health"
+version = "1.0.0"
+"#;
+
+    #[test]
+    fn test_parse_health() {
+        let toml =
+            format!("{BASE}\n[health]\nprobe = \"service_call\"\ntarget = \"acme.tool.health\"\n");
+        let manifest = PluginManifest::from_toml(&toml).unwrap();
+        let health = manifest.health.as_ref().unwrap();
+
+        assert_eq!(health.probe, ProbeKind::ServiceCall);
+        assert_eq!(health.interval(), Duration::from_secs(30));
+        assert_eq!(health.failure_threshold, 3);
+        assert_eq!(health.restart, RestartPolicy::OnFailure);
+        assert!(manifest.validate().is_empty());
+    }
+
+    #[test]
+    fn test_invalid_health() {
+        for health in [
+            "probe = \"service_call\"\ntarget = \"acme.other\"",
+            "probe = \"command\"\ntarget = \"../probe.sh\"",
+            "probe = \"command\"\ntarget = \"probe.sh\"\ninterval_secs = 0",
+        ] {
+            let toml = format!("{BASE}\n[health]\n{health}\n");
+            let manifest = PluginManifest::from_toml(&toml).unwrap();
+            assert!(has_errors(&manifest.validate()), "accepted: {health}");
+        }
+    }
+}
@@ -0,0 +1,225 @@
+//! Lifecycle hook declarations (`[hooks]`).
+//!
+//! ```toml
+//! [hooks.post_install]
+//! script = "scripts/setup.sh"
+//!
+//! [hooks.pre_uninstall]
+//! service = "acme.tool.cleanup"
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::plugin::PluginManifest;
+use crate::validate::{is_contained_path, ValidationIssue};
+
+/// Installer lifecycle events a plugin can hook into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HookEvent {
+    /// Before the plugin files are installed
+    PreInstall,
+    /// After a successful install
+    PostInstall,
+    /// Before an installed plugin is replaced by a new version
+    PreUpdate,
+    /// Before the plugin is removed
+    PreUninstall,
+}
+
+impl HookEvent {
+    /// All events, in lifecycle order.
+    pub const ALL: [HookEvent; 4] = [
+        HookEvent::PreInstall,
+        HookEvent::PostInstall,
+        HookEvent::PreUpdate,
+        HookEvent::PreUninstall,
+    ];
+
+    /// Key used in the `[hooks]` section.
+    pub fn name(&self) -> &'static str {
+        match self {
+            HookEvent::PreInstall => "pre_install",
+            HookEvent::PostInstall => "post_install",
+            HookEvent::PreUpdate => "pre_update",
+            HookEvent::PreUninstall => "pre_uninstall",
+        }
+    }
+}
+
+impl fmt::Display for HookEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Action run for a lifecycle event.
+///
+/// Exactly one of `script` or `service` must be set.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct HookAction {
+    /// Bundled script to run (relative to the plugin directory)
+    #[serde(default)]
+    pub script: Option<String>,
+
+    /// ID of a service provided by this plugin to invoke
+    #[serde(default)]
+    pub service: Option<String>,
+}
+
+/// Lifecycle hooks.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct HooksInfo {
+    /// Run before install
+    #[serde(default)]
+    pub pre_install: Option<HookAction>,
+
+    /// Run after a successful install
+    #[serde(default)]
+    pub post_install: Option<HookAction>,
+
+    /// Run before an update
+    #[serde(default)]
+    pub pre_update: Option<HookAction>,
+
+    /// Run before uninstall
+    #[serde(default)]
+    pub pre_uninstall: Option<HookAction>,
+}
+
+impl HooksInfo {
+    /// Get the action for an event.
+    pub fn get(&self, event: HookEvent) -> Option<&HookAction> {
+        match event {
+            HookEvent::PreInstall => self.pre_install.as_ref(),
+            HookEvent::PostInstall => self.post_install.as_ref(),
+            HookEvent::PreUpdate => self.pre_update.as_ref(),
+            HookEvent::PreUninstall => self.pre_uninstall.as_ref(),
+        }
+    }
+
+    /// Iterate over declared hooks in lifecycle order.
+    pub fn iter(&self) -> impl Iterator<Item = (HookEvent, &HookAction)> {
+        HookEvent::ALL
+            .into_iter()
+            .filter_map(|event| self.get(event).map(|action| (event, action)))
+    }
+}
+
+impl PluginManifest {
+    /// Get the hook declared for a lifecycle event.
+    pub fn hook(&self, event: HookEvent) -> Option<&HookAction> {
+        self.hooks.as_ref().and_then(|h| h.get(event))
+    }
+
+    /// Check hook declarations.
+    ///
+    /// Scripts must stay inside the plugin directory and services must be
+    /// ones this plugin provides.
+    pub(crate) fn check_hooks(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        let Some(hooks) = &self.hooks else {
+            return issues;
+        };
+
+        for (event, action) in hooks.iter() {
+            issues.extend(self.check_action(
+                format!("hooks.{event}"),
+                "Hook",
+                action.script.as_deref(),
+                action.service.as_deref(),
+            ));
+        }
+
+        issues
+    }
+
+    /// Check a script-or-service action declared by this plugin.
+    pub(crate) fn check_action(
+        &self,
+        path: String,
+        what: &str,
+        script: Option<&str>,
+        service: Option<&str>,
+    ) -> Option<ValidationIssue> {
+        match (script, service) {
+            (Some(script), None) if !is_contained_path(script) => Some(ValidationIssue::error(
+                path,
+                format!("{what} script '{script}' must be a relative path inside the plugin"),
+            )),
+            (None, Some(service)) if !self.provides.iter().any(|s| s.id == service) => {
+                Some(ValidationIssue::error(
+                    path,
+                    format!("{what} service '{service}' is not provided by this plugin"),
+                ))
+            }
+            (Some(_), None) | (None, Some(_)) => None,
+            _ => Some(ValidationIssue::error(
+                path,
+                format!("{what} must declare exactly one of 'script' or 'service'"),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate::has_errors;
+
+    const BASE: &str = r#"
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.0.0"
+type = "extension"
+
+[[provides]]
+id = "acme.tool.cleanup"
+version = "1.0.0"
+"#;
+
+    #[test]
+    fn test_parse_hooks() {
+        let toml = format!(
+            "{BASE}\n[hooks.post_install]\nscript = \"scripts/setup.sh\"\n\n[hooks.pre_uninstall]\nservice = \"acme.tool.cleanup\"\n"
+        );
+        let manifest = PluginManifest::from_toml(&toml).unwrap();
+
+        let post = manifest.hook(HookEvent::PostInstall).unwrap();
+        assert_eq!(post.script.as_deref(), Some("scripts/setup.sh"));
+        assert!(manifest.hook(HookEvent::PreInstall).is_none());
+
+        let events: Vec<_> = manifest
+            .hooks
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|(e, _)| e)
+            .collect();
+        assert_eq!(
+            events,
+            vec![HookEvent::PostInstall, HookEvent::PreUninstall]
+        );
+        assert!(manifest.validate().is_empty());
+    }
+
+    #[test]
+    fn test_invalid_hooks() {
+        for hook in [
+            "script = \"../outside.sh\"",
+            "script = \"/bin/sh\"",
+            "service = \"acme.other\"",
+            "script = \"a.sh\"\nservice = \"acme.tool.cleanup\"",
+            "",
+        ] {
+            let toml = format!("{BASE}\n[hooks.pre_install]\n{hook}\n");
+            let manifest = PluginManifest::from_toml(&toml).unwrap();
+            assert!(has_errors(&manifest.validate()), "accepted: {hook}");
+        }
+    }
+}
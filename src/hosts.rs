@@ -0,0 +1,162 @@
+//! Host application targeting (`compatibility.hosts`).
+//!
+//! Several host applications load plugins described by this format. A
+//! plugin lists the hosts it was built for, and can give each host its own
+//! version range, since hosts are versioned independently:
+//!
+//! ```toml
+//! [compatibility]
+//! hosts = ["adi", "adi-server"]
+//! min_host_version = "1.2.0"      # default range, used by "adi"
+//!
+//! [compatibility.host_versions.adi-server]
+//! min_host_version = "0.4.0"
+//! max_host_version = "0.9.0"
+//! ```
+//!
+//! An empty `hosts` list means any host. A `host_versions` entry replaces
+//! the default range for that host.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::ManifestError;
+use crate::plugin::{CompatibilityInfo, PluginManifest};
+use crate::validate::ValidationIssue;
+
+/// Host version range for one host application.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct HostVersionRange {
+    /// Minimum host version required
+    #[serde(default)]
+    pub min_host_version: Option<String>,
+
+    /// Maximum host version (optional)
+    #[serde(default)]
+    pub max_host_version: Option<String>,
+}
+
+impl CompatibilityInfo {
+    /// Check if the plugin targets the host application `host`.
+    pub fn targets_host(&self, host: &str) -> bool {
+        self.hosts.is_empty() || self.hosts.iter().any(|h| h == host)
+    }
+
+    /// Compatibility as seen by the host application `host`, with its
+    /// `host_versions` range applied.
+    ///
+    /// Returns None if the plugin doesn't target `host`.
+    pub fn for_host(&self, host: &str) -> Option<CompatibilityInfo> {
+        if !self.targets_host(host) {
+            return None;
+        }
+        let mut compat = self.clone();
+        if let Some(range) = self.host_versions.get(host) {
+            compat.min_host_version = range.min_host_version.clone();
+            compat.max_host_version = range.max_host_version.clone();
+        }
+        Some(compat)
+    }
+
+    /// Check whether version `host_version` of the host application `host`
+    /// can load the plugin.
+    pub fn satisfies_named_host(
+        &self,
+        host: &str,
+        host_version: &str,
+    ) -> Result<bool, ManifestError> {
+        match self.for_host(host) {
+            Some(compat) => compat.satisfies_host(host_version),
+            None => Ok(false),
+        }
+    }
+
+    /// Check that `host_versions` only names targeted hosts.
+    pub(crate) fn check_hosts(&self) -> Vec<ValidationIssue> {
+        self.host_versions
+            .keys()
+            .filter(|host| !self.targets_host(host))
+            .map(|host| {
+                ValidationIssue::warning(
+                    format!("compatibility.host_versions.{host}"),
+                    format!("'{host}' is not listed in compatibility.hosts"),
+                )
+            })
+            .collect()
+    }
+}
+
+impl PluginManifest {
+    /// Check whether version `host_version` of the host application `host`
+    /// can load the plugin; see `CompatibilityInfo::satisfies_named_host()`.
+    pub fn satisfies_named_host(
+        &self,
+        host: &str,
+        host_version: &str,
+    ) -> Result<bool, ManifestError> {
+        self.compatibility.satisfies_named_host(host, host_version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_satisfies_named_host() {
+        let manifest = PluginManifest::from_toml(
+            r#"
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.0.0"
+type = "extension"
+
+[compatibility]
+hosts = ["adi", "adi-server"]
+min_host_version = "1.2.0"
+
+[compatibility.host_versions.adi-server]
+min_host_version = "0.4.0"
+max_host_version = "0.9.0"
+"#,
+        )
+        .unwrap();
+        assert!(manifest.satisfies_named_host("adi", "1.3.0").unwrap());
+        assert!(!manifest.satisfies_named_host("adi", "1.0.0").unwrap());
+        assert!(manifest
+            .satisfies_named_host("adi-server", "0.5.0")
+            .unwrap());
+        assert!(!manifest
+            .satisfies_named_host("adi-server", "1.3.0")
+            .unwrap());
+        assert!(!manifest
+            .satisfies_named_host("adi-mobile", "1.3.0")
+            .unwrap());
+        assert!(manifest.validate().is_empty());
+
+        let reparsed = PluginManifest::from_toml(&manifest.to_toml().unwrap()).unwrap();
+        assert_eq!(reparsed.compatibility.hosts, manifest.compatibility.hosts);
+        assert_eq!(
+            reparsed.compatibility.host_versions,
+            manifest.compatibility.host_versions
+        );
+    }
+
+    #[test]
+    fn test_any_host() {
+        let mut compat = CompatibilityInfo::default();
+        assert!(compat.targets_host("anything"));
+        compat.host_versions.insert(
+            "adi".into(),
+            HostVersionRange {
+                min_host_version: Some("2.0".into()),
+                ..Default::default()
+            },
+        );
+        assert!(compat.check_hosts().is_empty());
+        compat.hosts.push("adi-server".into());
+        assert_eq!(compat.check_hosts().len(), 1);
+    }
+}
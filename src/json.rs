@@ -0,0 +1,128 @@
+//! JSON serialization.
+//!
+//! The manifest structs round-trip through JSON with the same field names
+//! as their TOML form, for registries and other services that speak JSON.
+//!
+//! ```json
+//! {
+//!   "plugin": {
+//!     "id": "vendor.plugin-name",
+//!     "name": "Human Readable Name",
+//!     "version": "1.0.0",
+//!     "type": "extension"
+//!   }
+//! }
+//! ```
+
+use crate::error::ManifestError;
+use crate::package::PackageManifest;
+use crate::plugin::PluginManifest;
+use crate::Manifest;
+
+impl PluginManifest {
+    /// Parse from a JSON string.
+    pub fn from_json(content: &str) -> Result<Self, ManifestError> {
+        Ok(serde_json::from_str(content)?)
+    }
+
+    /// Serialize to a pretty-printed JSON string.
+    pub fn to_json(&self) -> Result<String, ManifestError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+impl PackageManifest {
+    /// Parse from a JSON string.
+    pub fn from_json(content: &str) -> Result<Self, ManifestError> {
+        Ok(serde_json::from_str(content)?)
+    }
+
+    /// Serialize to a pretty-printed JSON string.
+    pub fn to_json(&self) -> Result<String, ManifestError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+impl Manifest {
+    /// Parse a manifest from a JSON string, detecting the type from the
+    /// top-level `plugin` or `package` key.
+    pub fn from_json(content: &str) -> Result<Self, ManifestError> {
+        let value: serde_json::Value = serde_json::from_str(content)?;
+        match (
+            value.get("plugin").is_some(),
+            value.get("package").is_some(),
+        ) {
+            (true, true) => Err(ManifestError::AmbiguousType),
+            (false, true) => Ok(Manifest::Package(serde_json::from_value(value)?)),
+            (true, false) => Ok(Manifest::Single(serde_json::from_value(value)?)),
+            (false, false) => Err(ManifestError::InvalidFormat(
+                "Manifest must contain either plugin or package object".to_string(),
+            )),
+        }
+    }
+
+    /// Serialize to a pretty-printed JSON string.
+    pub fn to_json(&self) -> Result<String, ManifestError> {
+        match self {
+            Manifest::Single(m) => m.to_json(),
+            Manifest::Package(m) => m.to_json(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plugin_roundtrip() {
+        let toml = r#"
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.0.0"
+type = "extension"
+
+[binary]
+name = "tool"
+
+[binary.checksums]
+linux-x86_64 = "sha256:abc"
+
+[config.defaults]
+level = 3
+"#;
+        let manifest = PluginManifest::from_toml(toml).unwrap();
+        let json = manifest.to_json().unwrap();
+        assert!(json.contains("\"id\": \"acme.tool\""));
+
+        let reparsed = PluginManifest::from_json(&json).unwrap();
+        assert_eq!(reparsed.to_toml().unwrap(), manifest.to_toml().unwrap());
+    }
+
+    #[test]
+    fn test_manifest_detects_type() {
+        let toml = r#"
+[package]
+id = "acme.pack"
+name = "Pack"
+version = "1.0.0"
+
+[[plugins]]
+id = "acme.one"
+name = "One"
+type = "extension"
+binary = "one"
+"#;
+        let manifest = Manifest::from_toml(toml).unwrap();
+        let reparsed = Manifest::from_json(&manifest.to_json().unwrap()).unwrap();
+        assert!(reparsed.is_package());
+        assert_eq!(reparsed.id(), "acme.pack");
+
+        assert!(Manifest::from_json("{}").is_err());
+        assert!(matches!(
+            Manifest::from_json("not json"),
+            Err(ManifestError::Json(_))
+        ));
+    }
+}
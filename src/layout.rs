@@ -0,0 +1,160 @@
+//! Install layout resolution.
+//!
+//! Hosts lay out installed plugins differently. A `LayoutPolicy` maps a
+//! plugin to its install directory; the manifest then resolves the binary
+//! location inside it (honoring `binary.path`).
+
+use std::path::{Path, PathBuf};
+
+use crate::error::ManifestError;
+use crate::platform::current_platform;
+use crate::plugin::PluginManifest;
+use crate::validate::{is_contained_path, is_file_name};
+
+/// Maps plugins to their install directories.
+pub trait LayoutPolicy {
+    /// Directory a plugin version is installed into.
+    fn plugin_dir(&self, plugin_id: &str, version: &str) -> PathBuf;
+}
+
+/// Default layout: `<root>/<plugin_id>/<version>/`.
+#[derive(Debug, Clone)]
+pub struct DefaultLayout {
+    root: PathBuf,
+}
+
+impl DefaultLayout {
+    /// Create a layout rooted at the given plugins directory.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Get the plugins root directory.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+}
+
+impl LayoutPolicy for DefaultLayout {
+    fn plugin_dir(&self, plugin_id: &str, version: &str) -> PathBuf {
+        self.root.join(plugin_id).join(version)
+    }
+}
+
+/// Flat layout without version directories: `<root>/<plugin_id>/`.
+#[derive(Debug, Clone)]
+pub struct FlatLayout {
+    root: PathBuf,
+}
+
+impl FlatLayout {
+    /// Create a layout rooted at the given plugins directory.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+impl LayoutPolicy for FlatLayout {
+    fn plugin_dir(&self, plugin_id: &str, _version: &str) -> PathBuf {
+        self.root.join(plugin_id)
+    }
+}
+
+impl PluginManifest {
+    /// Get the install directory of this plugin under a layout.
+    pub fn install_dir(&self, layout: &dyn LayoutPolicy) -> PathBuf {
+        layout.plugin_dir(&self.plugin.id, &self.plugin.version)
+    }
+
+    /// Resolve the binary for the current platform to a full path under a layout.
+    ///
+    /// Fails if `binary.path` or the binary name would leave the install
+    /// directory, even for manifests that were never validated.
+    pub fn resolve_binary_path(&self, layout: &dyn LayoutPolicy) -> Result<PathBuf, ManifestError> {
+        let mut path = self.install_dir(layout);
+        if let Some(dir) = self.binary.path_for(&current_platform()) {
+            if !is_contained_path(dir) {
+                return Err(ManifestError::InvalidFormat(format!(
+                    "Binary path '{dir}' must be a relative path inside the plugin"
+                )));
+            }
+            path.push(dir);
+        }
+        let filename = self.binary_filename();
+        if !is_file_name(&filename) {
+            return Err(ManifestError::InvalidFormat(format!(
+                "Binary name '{filename}' must be a file name without a directory"
+            )));
+        }
+        path.push(filename);
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platform::library_filename;
+    use crate::plugin::BinaryKind;
+
+    const TOML: &str = r#"
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.2.0"
+type = "extension"
+
+[binary]
+name = "tool"
+path = "lib"
+"#;
+
+    #[test]
+    fn test_default_layout() {
+        let manifest = PluginManifest::from_toml(TOML).unwrap();
+        let layout = DefaultLayout::new("/opt/adi/plugins");
+        assert_eq!(
+            manifest.resolve_binary_path(&layout).unwrap(),
+            PathBuf::from("/opt/adi/plugins/acme.tool/1.2.0/lib").join(library_filename("tool"))
+        );
+    }
+
+    #[test]
+    fn test_custom_layout() {
+        struct VendorLayout;
+        impl LayoutPolicy for VendorLayout {
+            fn plugin_dir(&self, plugin_id: &str, version: &str) -> PathBuf {
+                let (vendor, name) = plugin_id.split_once('.').unwrap();
+                PathBuf::from("/plugins")
+                    .join(vendor)
+                    .join(format!("{name}-{version}"))
+            }
+        }
+
+        let mut manifest = PluginManifest::from_toml(TOML).unwrap();
+        manifest.binary.path = None;
+        assert_eq!(
+            manifest.resolve_binary_path(&VendorLayout).unwrap(),
+            PathBuf::from("/plugins/acme/tool-1.2.0").join(library_filename("tool"))
+        );
+        assert_eq!(
+            manifest.install_dir(&FlatLayout::new("/p")),
+            PathBuf::from("/p/acme.tool")
+        );
+    }
+
+    #[test]
+    fn test_escaping_binary_path_rejected() {
+        let layout = DefaultLayout::new("/opt/adi/plugins");
+        for dir in ["/usr/lib", "../../other", "lib/../.."] {
+            let mut manifest = PluginManifest::from_toml(TOML).unwrap();
+            manifest.binary.path = Some(dir.to_string());
+            assert!(manifest.resolve_binary_path(&layout).is_err(), "{dir}");
+        }
+
+        let mut manifest = PluginManifest::from_toml(TOML).unwrap();
+        manifest.binary.name = "../../bin/sh".to_string();
+        manifest.binary.kind = BinaryKind::Script;
+        assert!(manifest.resolve_binary_path(&layout).is_err());
+    }
+}
@@ -35,21 +35,152 @@
 //! binary = "dark_theme"
 //! ```
 
+mod abi;
+mod aliases;
+mod artifact;
+mod assets;
+mod author;
+#[cfg(feature = "bundle")]
+pub mod bundle;
+pub mod build_support;
+mod builder;
 pub mod cargo_extract;
+#[cfg(feature = "checksum")]
+mod checksum;
+mod cli;
+mod coverage;
+mod diff;
+mod discover;
+mod dto;
+mod edit;
+#[cfg(feature = "signature")]
+mod ed25519;
+mod effective;
+mod embed;
 mod error;
+mod health;
+mod hooks;
+mod hosts;
+#[cfg(feature = "json")]
+mod json;
+mod layout;
+mod license;
+mod lint;
+mod lockfile;
+mod logging;
+mod messaging;
+mod options;
+mod migrate;
+mod migrations;
+mod narrow;
+mod observability;
 mod package;
+mod permissions;
+mod plan;
 mod platform;
 mod plugin;
+mod plugin_type;
+mod post_install;
+mod preflight;
+mod redact;
+mod registry;
+mod resolver;
+mod resources;
+mod rewrite;
+#[cfg(feature = "schema")]
+pub mod schema;
+mod serialize;
+mod services;
+mod set;
+mod shims;
+mod signing;
+mod source;
+#[cfg(feature = "proto")]
+pub mod proto;
+mod static_manifest;
+mod stats;
+mod storage;
+mod substitute;
+mod template;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "testing")]
+pub mod testkit;
+#[cfg(feature = "typescript")]
+mod typescript;
+mod uninstall;
+mod validate;
+mod vendor;
+mod version;
 
+pub use abi::*;
+pub use aliases::*;
+pub use artifact::*;
+pub use assets::*;
+pub use author::*;
+pub use builder::*;
+#[cfg(feature = "checksum")]
+pub use checksum::*;
+pub use cli::*;
+pub use coverage::*;
+pub use diff::*;
+pub use discover::*;
+pub use dto::*;
+pub use edit::*;
+#[cfg(feature = "signature")]
+pub use ed25519::*;
+pub use effective::*;
+pub use embed::*;
 pub use error::*;
+pub use health::*;
+pub use hooks::*;
+pub use hosts::*;
+pub use layout::*;
+pub use license::*;
+pub use lint::*;
+pub use lockfile::*;
+pub use logging::*;
+pub use messaging::*;
+pub use options::{ManifestOrigin, ParseOptions, API_VERSION_FIELD, DEFAULT_API_VERSION};
+pub use migrate::{MigrationNote, CURRENT_MANIFEST_VERSION};
+pub use migrations::*;
+pub use narrow::*;
+pub use observability::*;
 pub use package::*;
+pub use permissions::*;
+pub use plan::*;
 pub use platform::*;
 pub use plugin::*;
+pub use plugin_type::*;
+pub use post_install::*;
+pub use preflight::*;
+pub use redact::*;
+pub use registry::*;
+pub use resolver::*;
+pub use resources::*;
+pub use rewrite::*;
+pub use serialize::{header_comment, SerializeProfile};
+pub use services::*;
+pub use set::*;
+pub use shims::*;
+pub use signing::*;
+pub use source::*;
+pub use static_manifest::*;
+pub use stats::*;
+pub use storage::*;
+pub use substitute::*;
+#[cfg(feature = "typescript")]
+pub use typescript::*;
+pub use uninstall::*;
+pub use validate::*;
+pub use vendor::*;
+pub use version::*;
 
 use std::path::Path;
 
 /// Unified manifest type that can be either a single plugin or a package.
 #[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
 pub enum Manifest {
     /// A single plugin manifest
     Single(PluginManifest),
@@ -60,22 +191,73 @@ pub enum Manifest {
 impl Manifest {
     /// Parse a manifest from a TOML string, auto-detecting the type.
     pub fn from_toml(content: &str) -> Result<Self, ManifestError> {
-        // Try to detect the type by checking for [plugin] vs [package]
-        if content.contains("[package]") {
-            Ok(Manifest::Package(PackageManifest::from_toml(content)?))
-        } else if content.contains("[plugin]") {
-            Ok(Manifest::Single(PluginManifest::from_toml(content)?))
-        } else {
-            Err(ManifestError::InvalidFormat(
+        Self::from_toml_with(content, &ParseOptions::default())
+    }
+
+    /// Parse a manifest from a TOML string with custom options,
+    /// auto-detecting the type.
+    pub fn from_toml_with(content: &str, options: &ParseOptions) -> Result<Self, ManifestError> {
+        // Detect the type from the root tables
+        options.check_size(content)?;
+        let root: toml::Table = toml::from_str(content)?;
+        match (root.contains_key("plugin"), root.contains_key("package")) {
+            (true, true) => Err(ManifestError::AmbiguousType),
+            (false, true) => Ok(Manifest::Package(PackageManifest::from_toml_with(
+                content, options,
+            )?)),
+            (true, false) => Ok(Manifest::Single(PluginManifest::from_toml_with(
+                content, options,
+            )?)),
+            (false, false) => Err(ManifestError::InvalidFormat(
                 "Manifest must contain either [plugin] or [package] section".to_string(),
-            ))
+            )),
+        }
+    }
+
+    /// Serialize to TOML string.
+    pub fn to_toml(&self) -> Result<String, ManifestError> {
+        match self {
+            Manifest::Single(m) => m.to_toml(),
+            Manifest::Package(m) => m.to_toml(),
+        }
+    }
+
+    /// Get the canonical manifest filename ("plugin.toml" or "package.toml").
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            Manifest::Single(_) => "plugin.toml",
+            Manifest::Package(_) => "package.toml",
         }
     }
 
     /// Parse a manifest from a file, auto-detecting the type.
     pub fn from_file(path: &Path) -> Result<Self, ManifestError> {
+        Self::from_file_with(path, &ParseOptions::default())
+    }
+
+    /// Parse a manifest from a file with custom options, auto-detecting
+    /// the type.
+    pub fn from_file_with(path: &Path, options: &ParseOptions) -> Result<Self, ManifestError> {
         let content = std::fs::read_to_string(path)?;
-        Self::from_toml(&content)
+        Self::from_toml_with(&content, options)
+    }
+
+    /// Read the manifest from a plugin archive (.zip, .tar.gz or .adiplugin)
+    /// without extracting it.
+    #[cfg(feature = "bundle")]
+    pub fn from_archive(path: &Path) -> Result<Self, ManifestError> {
+        bundle::read_archive(path, false)
+    }
+
+    /// Like `from_archive()`, but also verifies binaries inside the archive
+    /// against the checksums declared in the manifest.
+    ///
+    /// Every binary needs a checksum and every checksum needs a binary.
+    /// Package checksums cover the whole archive, so package archives are
+    /// not verified entry by entry.
+    #[cfg(feature = "bundle")]
+    pub fn from_archive_verified(path: &Path) -> Result<Self, ManifestError> {
+        bundle::read_archive(path, true)
     }
 
     /// Get all plugin IDs contained in this manifest.
@@ -103,6 +285,19 @@ impl Manifest {
         }
     }
 
+    /// Validate the manifest with the default policy.
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        self.validate_with(&ValidationPolicy::default())
+    }
+
+    /// Validate the manifest with a custom policy.
+    pub fn validate_with(&self, policy: &ValidationPolicy) -> Vec<ValidationIssue> {
+        match self {
+            Manifest::Single(m) => m.validate_with(policy),
+            Manifest::Package(m) => m.validate_with(policy),
+        }
+    }
+
     /// Check if this is a package (multi-plugin).
     pub fn is_package(&self) -> bool {
         matches!(self, Manifest::Package(_))
@@ -118,3 +313,35 @@ impl Manifest {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_type_from_root_tables() {
+        let toml = r#"
+# Unlike a [package], this manifest describes a single plugin.
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.0.0"
+type = "extension"
+description = "Works with [package] files"
+"#;
+        let manifest = Manifest::from_toml(toml).unwrap();
+        assert!(!manifest.is_package());
+
+        let both = format!("{toml}\n[package]\nid = \"acme.pack\"\n");
+        assert!(matches!(
+            Manifest::from_toml(&both),
+            Err(ManifestError::AmbiguousType)
+        ));
+
+        let neither = "# [plugin]\n[binary]\nname = \"tool\"\n";
+        assert!(matches!(
+            Manifest::from_toml(neither),
+            Err(ManifestError::InvalidFormat(_))
+        ));
+    }
+}
@@ -0,0 +1,207 @@
+//! License expression validation.
+//!
+//! With the `spdx` feature, license strings are parsed as SPDX expressions
+//! (e.g., "MIT OR Apache-2.0") and non-SPDX strings produce warnings.
+//! Without it, expressions are split on operators and checked as plain
+//! identifiers.
+
+use std::path::Path;
+
+use crate::package::PackageManifest;
+use crate::plugin::PluginManifest;
+use crate::validate::{is_contained_path, ValidationIssue};
+
+/// License allow-list policy, e.g., for enterprises that restrict which
+/// licenses may be installed.
+#[derive(Debug, Clone, Default)]
+pub struct LicensePolicy {
+    /// Allowed license identifiers (None = any license is allowed)
+    pub allowed: Option<Vec<String>>,
+}
+
+impl LicensePolicy {
+    /// Only allow the given license identifiers.
+    pub fn allow_only(licenses: &[&str]) -> Self {
+        Self {
+            allowed: Some(licenses.iter().map(|l| l.to_string()).collect()),
+        }
+    }
+
+    /// Check if a single license identifier is allowed.
+    pub fn is_allowed(&self, license: &str) -> bool {
+        match &self.allowed {
+            Some(allowed) => allowed.iter().any(|a| a.eq_ignore_ascii_case(license)),
+            None => true,
+        }
+    }
+
+    /// Check a license expression against the policy.
+    pub fn check(&self, path: &str, expression: &str) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+
+        if expression.trim().is_empty() {
+            issues.push(ValidationIssue::warning(path, "License is empty"));
+            return issues;
+        }
+
+        #[cfg(feature = "spdx")]
+        match spdx::Expression::parse(expression) {
+            Ok(expr) => {
+                if self.allowed.is_some()
+                    && !expr.evaluate(|req| match req.license.id() {
+                        Some(id) => self.is_allowed(id.name),
+                        None => false,
+                    })
+                {
+                    issues.push(ValidationIssue::error(
+                        path,
+                        format!("License '{expression}' is not allowed by policy"),
+                    ));
+                }
+                return issues;
+            }
+            Err(e) => {
+                issues.push(ValidationIssue::warning(
+                    path,
+                    format!("License '{expression}' is not a valid SPDX expression: {e}"),
+                ));
+            }
+        }
+
+        if let Some(license) = license_ids(expression)
+            .into_iter()
+            .find(|l| !self.is_allowed(l))
+        {
+            issues.push(ValidationIssue::error(
+                path,
+                format!("License '{license}' is not allowed by policy"),
+            ));
+        }
+
+        issues
+    }
+}
+
+/// Split a license expression into its license identifiers, ignoring
+/// operators and parentheses.
+pub fn license_ids(expression: &str) -> Vec<String> {
+    expression
+        .split(|c: char| c.is_whitespace() || c == '(' || c == ')')
+        .filter(|t| !t.is_empty())
+        .filter(|t| !matches!(*t, "AND" | "OR" | "WITH" | "and" | "or" | "with"))
+        .map(|t| t.trim_end_matches('+').to_string())
+        .collect()
+}
+
+/// Check that a referenced license file stays inside `base_dir` and exists.
+pub fn check_license_file(path: &str, license_file: &str, base_dir: &Path) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+    if !is_contained_path(license_file) {
+        issues.push(ValidationIssue::error(
+            path,
+            format!("License file '{license_file}' must be a relative path inside the plugin"),
+        ));
+        return issues;
+    }
+
+    if !base_dir.join(license_file).is_file() {
+        issues.push(ValidationIssue::error(
+            path,
+            format!("License file '{license_file}' not found"),
+        ));
+    }
+
+    issues
+}
+
+impl PluginManifest {
+    /// Check that the referenced license file exists.
+    ///
+    /// `base_dir` is the directory containing plugin.toml.
+    pub fn check_license_file(&self, base_dir: &Path) -> Vec<ValidationIssue> {
+        match &self.plugin.license_file {
+            Some(file) => check_license_file("plugin.license_file", file, base_dir),
+            None => Vec::new(),
+        }
+    }
+}
+
+impl PackageManifest {
+    /// Check that the referenced license file exists.
+    ///
+    /// `base_dir` is the package root (the directory containing package.toml).
+    pub fn check_license_file(&self, base_dir: &Path) -> Vec<ValidationIssue> {
+        match &self.package.license_file {
+            Some(file) => check_license_file("package.license_file", file, base_dir),
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate::has_errors;
+
+    #[test]
+    fn test_license_ids() {
+        assert_eq!(license_ids("MIT"), vec!["MIT"]);
+        assert_eq!(
+            license_ids("(MIT OR Apache-2.0) AND BSD-3-Clause"),
+            vec!["MIT", "Apache-2.0", "BSD-3-Clause"]
+        );
+        assert_eq!(license_ids("GPL-2.0+"), vec!["GPL-2.0"]);
+    }
+
+    #[test]
+    fn test_license_policy() {
+        let policy = LicensePolicy::allow_only(&["MIT", "Apache-2.0"]);
+        assert!(policy.check("plugin.license", "MIT").is_empty());
+        assert!(has_errors(&policy.check("plugin.license", "GPL-3.0-only")));
+        assert!(LicensePolicy::default()
+            .check("plugin.license", "GPL-3.0-only")
+            .is_empty());
+    }
+
+    #[test]
+    fn test_license_file_presence() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("LICENSE"), "MIT License").unwrap();
+
+        let toml = r#"
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.0.0"
+type = "extension"
+license = "MIT"
+license_file = "LICENSE"
+"#;
+        let mut manifest = PluginManifest::from_toml(toml).unwrap();
+        assert_eq!(manifest.plugin.license_file.as_deref(), Some("LICENSE"));
+        assert!(manifest.check_license_file(dir.path()).is_empty());
+
+        manifest.plugin.license_file = Some("COPYING".into());
+        assert!(has_errors(&manifest.check_license_file(dir.path())));
+
+        manifest.plugin.license_file = Some("../LICENSE".into());
+        assert!(has_errors(&manifest.check_license_file(dir.path())));
+    }
+
+    #[cfg(feature = "spdx")]
+    #[test]
+    fn test_spdx_expressions() {
+        let policy = LicensePolicy::allow_only(&["MIT"]);
+        // OR only needs one allowed alternative
+        assert!(policy
+            .check("plugin.license", "MIT OR GPL-3.0-only")
+            .is_empty());
+        assert!(has_errors(
+            &policy.check("plugin.license", "MIT AND GPL-3.0-only")
+        ));
+
+        let issues = LicensePolicy::default().check("plugin.license", "Proprietary stuff");
+        assert_eq!(issues.len(), 1);
+        assert!(!issues[0].is_error());
+    }
+}
@@ -0,0 +1,274 @@
+//! Quality lint rules.
+//!
+//! Unlike validation, lints don't make a manifest unusable. They flag
+//! quality problems registries may want to enforce before publishing.
+
+use std::fmt;
+
+use crate::plugin::{CompatibilityInfo, PluginManifest};
+use crate::validate::Severity;
+use crate::Manifest;
+
+/// A quality lint rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LintRule {
+    /// No description given
+    MissingDescription,
+    /// No license given
+    MissingLicense,
+    /// No binary checksums given
+    NoChecksums,
+    /// No `max_host_version`, so the plugin claims to work with every future host
+    UnboundedHostCompatibility,
+    /// Permissions granted with a catch-all wildcard
+    WildcardPermissions,
+}
+
+impl LintRule {
+    /// All lint rules.
+    pub const ALL: &'static [LintRule] = &[
+        LintRule::MissingDescription,
+        LintRule::MissingLicense,
+        LintRule::NoChecksums,
+        LintRule::UnboundedHostCompatibility,
+        LintRule::WildcardPermissions,
+    ];
+
+    /// Stable rule name (e.g., for configuration files).
+    pub fn name(&self) -> &'static str {
+        match self {
+            LintRule::MissingDescription => "missing-description",
+            LintRule::MissingLicense => "missing-license",
+            LintRule::NoChecksums => "no-checksums",
+            LintRule::UnboundedHostCompatibility => "unbounded-host-compatibility",
+            LintRule::WildcardPermissions => "wildcard-permissions",
+        }
+    }
+
+    /// Default severity of the rule.
+    pub fn default_severity(&self) -> Severity {
+        match self {
+            LintRule::NoChecksums => Severity::Error,
+            _ => Severity::Warning,
+        }
+    }
+}
+
+impl fmt::Display for LintRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Set of enabled lint rules with their severities.
+#[derive(Debug, Clone)]
+pub struct LintRules {
+    rules: Vec<(LintRule, Severity)>,
+}
+
+impl Default for LintRules {
+    fn default() -> Self {
+        Self {
+            rules: LintRule::ALL
+                .iter()
+                .map(|r| (*r, r.default_severity()))
+                .collect(),
+        }
+    }
+}
+
+impl LintRules {
+    /// Create an empty rule set.
+    pub fn none() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    /// Enable a rule with the given severity (replacing any previous setting).
+    pub fn with(mut self, rule: LintRule, severity: Severity) -> Self {
+        self.rules.retain(|(r, _)| *r != rule);
+        self.rules.push((rule, severity));
+        self
+    }
+
+    /// Disable a rule.
+    pub fn without(mut self, rule: LintRule) -> Self {
+        self.rules.retain(|(r, _)| *r != rule);
+        self
+    }
+
+    /// Get the severity of a rule, if enabled.
+    pub fn severity(&self, rule: LintRule) -> Option<Severity> {
+        self.rules.iter().find(|(r, _)| *r == rule).map(|(_, s)| *s)
+    }
+}
+
+/// A lint finding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintFinding {
+    /// Rule that produced the finding
+    pub rule: LintRule,
+
+    /// Severity configured for the rule
+    pub severity: Severity,
+
+    /// Human-readable description
+    pub message: String,
+}
+
+impl fmt::Display for LintFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} [{}]: {}", self.severity, self.rule, self.message)
+    }
+}
+
+/// Run lint rules against a manifest.
+pub fn lint(manifest: &Manifest, rules: &LintRules) -> Vec<LintFinding> {
+    match manifest {
+        Manifest::Single(m) => m.lint(rules),
+        Manifest::Package(m) => lint_fields(
+            &LintSubject {
+                description: &m.package.description,
+                license: m.package.license.as_deref(),
+                has_checksums: !m.binary.checksums.is_empty(),
+                compatibility: &m.compatibility,
+                wildcard_permissions: Vec::new(),
+            },
+            rules,
+        ),
+    }
+}
+
+impl PluginManifest {
+    /// Run lint rules against this plugin manifest.
+    pub fn lint(&self, rules: &LintRules) -> Vec<LintFinding> {
+        lint_fields(
+            &LintSubject {
+                description: &self.plugin.description,
+                license: self.plugin.license.as_deref(),
+                has_checksums: !self.binary.checksums.is_empty(),
+                compatibility: &self.compatibility,
+                wildcard_permissions: self
+                    .permissions
+                    .as_ref()
+                    .map(|p| p.wildcards())
+                    .unwrap_or_default(),
+            },
+            rules,
+        )
+    }
+}
+
+/// Fields shared by plugin and package manifests that lints look at.
+struct LintSubject<'a> {
+    description: &'a str,
+    license: Option<&'a str>,
+    has_checksums: bool,
+    compatibility: &'a CompatibilityInfo,
+    wildcard_permissions: Vec<String>,
+}
+
+fn lint_fields(subject: &LintSubject<'_>, rules: &LintRules) -> Vec<LintFinding> {
+    let mut findings = Vec::new();
+    let mut report = |rule: LintRule, message: &str| {
+        if let Some(severity) = rules.severity(rule) {
+            findings.push(LintFinding {
+                rule,
+                severity,
+                message: message.to_string(),
+            });
+        }
+    };
+
+    if subject.description.trim().is_empty() {
+        report(LintRule::MissingDescription, "Manifest has no description");
+    }
+    if subject.license.is_none_or(|l| l.trim().is_empty()) {
+        report(LintRule::MissingLicense, "Manifest has no license");
+    }
+    if !subject.has_checksums {
+        report(LintRule::NoChecksums, "No binary checksums declared");
+    }
+    if subject.compatibility.max_host_version.is_none() {
+        report(
+            LintRule::UnboundedHostCompatibility,
+            "No max_host_version declared",
+        );
+    }
+    for grant in &subject.wildcard_permissions {
+        report(
+            LintRule::WildcardPermissions,
+            &format!("Wildcard permission requested ({grant})"),
+        );
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BARE: &str = r#"
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.0.0"
+type = "extension"
+"#;
+
+    #[test]
+    fn test_lint_default_rules() {
+        let manifest = Manifest::from_toml(BARE).unwrap();
+        let findings = lint(&manifest, &LintRules::default());
+        assert_eq!(findings.len(), 4);
+        let checksums = findings
+            .iter()
+            .find(|f| f.rule == LintRule::NoChecksums)
+            .unwrap();
+        assert_eq!(checksums.severity, Severity::Error);
+    }
+
+    #[test]
+    fn test_lint_clean_manifest() {
+        let toml = r#"
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.0.0"
+type = "extension"
+description = "Does things"
+license = "MIT"
+
+[compatibility]
+max_host_version = "1.0.0"
+
+[binary.checksums]
+linux-x86_64 = "sha256:abc"
+"#;
+        let manifest = PluginManifest::from_toml(toml).unwrap();
+        assert!(manifest.lint(&LintRules::default()).is_empty());
+    }
+
+    #[test]
+    fn test_lint_rule_configuration() {
+        let manifest = Manifest::from_toml(BARE).unwrap();
+        let rules = LintRules::none().with(LintRule::MissingLicense, Severity::Error);
+        let findings = lint(&manifest, &rules);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Error);
+
+        let rules = LintRules::default().without(LintRule::NoChecksums);
+        assert_eq!(lint(&manifest, &rules).len(), 3);
+    }
+
+    #[test]
+    fn test_lint_wildcard_permissions() {
+        let toml =
+            format!("{BARE}\n[permissions.network]\nallow = [\"*:443\", \"api.acme.dev\"]\n");
+        let manifest = PluginManifest::from_toml(&toml).unwrap();
+        let rules = LintRules::none().with(LintRule::WildcardPermissions, Severity::Warning);
+        let findings = manifest.lint(&rules);
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].message.contains("*:443"));
+    }
+}
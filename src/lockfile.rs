@@ -0,0 +1,246 @@
+//! Package lockfiles (`package.lock.toml`).
+//!
+//! A lockfile snapshots a resolved package: exact versions, the install
+//! order, per-platform checksums and sizes, and fingerprints of the
+//! signing key and signed payload. Hosts install from the lockfile instead
+//! of recomputing the order and checksums on every run, and regenerate it
+//! when `is_current()` says the package changed.
+//!
+//! ```toml
+//! lockfile_version = 1
+//!
+//! [package]
+//! id = "vendor.theme-pack"
+//! version = "2.0.0"
+//! payload_fingerprint = "fnv1a64:8c6f2e5b1d0a4c37"
+//!
+//! [package.checksums]
+//! darwin-aarch64 = "sha256:..."
+//!
+//! [[plugins]]
+//! id = "vendor.theme-dark"
+//! version = "2.0.0"
+//! type = "theme"
+//! binary = "dark_theme"
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::error::ManifestError;
+use crate::package::PackageManifest;
+use crate::source::fingerprint;
+
+/// Conventional lockfile name, next to package.toml.
+pub const LOCKFILE_NAME: &str = "package.lock.toml";
+
+/// Lockfile format version written by this crate.
+pub const LOCKFILE_VERSION: u32 = 1;
+
+/// Snapshot of a resolved package.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lockfile {
+    /// Lockfile format version
+    pub lockfile_version: u32,
+
+    /// The locked package
+    pub package: LockedPackage,
+
+    /// Plugins in install order
+    #[serde(default)]
+    pub plugins: Vec<LockedPlugin>,
+
+    /// Signing key, if the package is signed
+    #[serde(default)]
+    pub signature: Option<LockedSignature>,
+}
+
+/// Package-level entries of a lockfile.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedPackage {
+    /// Package ID
+    pub id: String,
+
+    /// Exact package version
+    pub version: String,
+
+    /// Fingerprint of the package's signing payload (see `signing_payload()`)
+    pub payload_fingerprint: String,
+
+    /// Per-platform checksums
+    #[serde(default)]
+    pub checksums: BTreeMap<String, String>,
+
+    /// Per-platform sizes in bytes
+    #[serde(default)]
+    pub sizes: BTreeMap<String, u64>,
+}
+
+/// A plugin entry of a lockfile.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedPlugin {
+    /// Plugin ID
+    pub id: String,
+
+    /// Exact plugin version
+    pub version: String,
+
+    /// Plugin type
+    #[serde(rename = "type")]
+    pub plugin_type: String,
+
+    /// Binary name
+    pub binary: String,
+
+    /// Plugins in the package this one depends on
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+}
+
+/// Signing key recorded in a lockfile.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedSignature {
+    /// Fingerprint of the public key
+    pub key_fingerprint: String,
+
+    /// Signature file path (relative to the manifest)
+    pub signature_file: String,
+}
+
+impl PackageManifest {
+    /// Resolve the package into a lockfile.
+    ///
+    /// Fails if the plugins have circular dependencies.
+    pub fn lock(&self) -> Result<Lockfile, ManifestError> {
+        let plugins = self
+            .install_order()?
+            .into_iter()
+            .map(|p| LockedPlugin {
+                id: p.id.clone(),
+                version: self.package.version.clone(),
+                plugin_type: p.plugin_type.to_string(),
+                binary: p.binary.clone(),
+                depends_on: p.depends_on.clone(),
+            })
+            .collect();
+        let payload = self.signing_payload()?;
+        Ok(Lockfile {
+            lockfile_version: LOCKFILE_VERSION,
+            package: LockedPackage {
+                id: self.package.id.clone(),
+                version: self.package.version.clone(),
+                payload_fingerprint: fingerprint(&String::from_utf8_lossy(&payload)),
+                checksums: self.binary.checksums.clone().into_iter().collect(),
+                sizes: self.binary.sizes.clone().into_iter().collect(),
+            },
+            plugins,
+            signature: self.signature.as_ref().map(|s| LockedSignature {
+                key_fingerprint: fingerprint(&s.public_key),
+                signature_file: s.signature_file.clone(),
+            }),
+        })
+    }
+}
+
+impl Lockfile {
+    /// Parse from TOML string.
+    pub fn from_toml(content: &str) -> Result<Self, ManifestError> {
+        let lockfile: Self = toml::from_str(content)?;
+        if lockfile.lockfile_version > LOCKFILE_VERSION {
+            return Err(ManifestError::InvalidFormat(format!(
+                "Lockfile version {} is newer than supported version {LOCKFILE_VERSION}",
+                lockfile.lockfile_version
+            )));
+        }
+        Ok(lockfile)
+    }
+
+    /// Parse from file.
+    pub fn from_file(path: &Path) -> Result<Self, ManifestError> {
+        let content = std::fs::read_to_string(path)?;
+        Self::from_toml(&content)
+    }
+
+    /// Serialize to TOML string.
+    pub fn to_toml(&self) -> Result<String, ManifestError> {
+        toml::to_string_pretty(self)
+            .map_err(|e| ManifestError::InvalidFormat(format!("Failed to serialize lockfile: {e}")))
+    }
+
+    /// Check whether the lockfile still matches a package.
+    pub fn is_current(&self, package: &PackageManifest) -> bool {
+        package.lock().is_ok_and(|lock| lock == *self)
+    }
+
+    /// Plugin IDs in install order.
+    pub fn install_order(&self) -> Vec<&str> {
+        self.plugins.iter().map(|p| p.id.as_str()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PACKAGE: &str = r#"
+[package]
+id = "acme.themes"
+name = "Themes"
+version = "2.0.0"
+
+[[plugins]]
+id = "acme.theme-light"
+name = "Light"
+type = "theme"
+binary = "light_theme"
+depends_on = ["acme.theme-base"]
+
+[[plugins]]
+id = "acme.theme-base"
+name = "Base"
+type = "theme"
+binary = "base_theme"
+
+[binary.checksums]
+linux-x86_64 = "sha256:aaa"
+darwin-aarch64 = "sha256:bbb"
+
+[signature]
+public_key = "key"
+signature_file = "package.sig"
+"#;
+
+    #[test]
+    fn test_lock_round_trip() {
+        let package = PackageManifest::from_toml(PACKAGE).unwrap();
+        let lock = package.lock().unwrap();
+        assert_eq!(
+            lock.install_order(),
+            ["acme.theme-base", "acme.theme-light"]
+        );
+        assert_eq!(lock.package.checksums.len(), 2);
+        assert!(lock.signature.is_some());
+
+        let toml = lock.to_toml().unwrap();
+        let reparsed = Lockfile::from_toml(&toml).unwrap();
+        assert_eq!(reparsed, lock);
+        assert!(reparsed.is_current(&package));
+    }
+
+    #[test]
+    fn test_lock_detects_changes() {
+        let package = PackageManifest::from_toml(PACKAGE).unwrap();
+        let lock = package.lock().unwrap();
+
+        let changed =
+            PackageManifest::from_toml(&PACKAGE.replace("sha256:aaa", "sha256:ccc")).unwrap();
+        assert!(!lock.is_current(&changed));
+
+        let future = lock
+            .to_toml()
+            .unwrap()
+            .replace("lockfile_version = 1", "lockfile_version = 99");
+        assert!(Lockfile::from_toml(&future).is_err());
+    }
+}
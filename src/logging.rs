@@ -0,0 +1,170 @@
+//! Logging configuration (`[logging]`).
+//!
+//! ```toml
+//! [logging]
+//! level = "info"
+//! user_content = false
+//!
+//! [logging.targets]
+//! "acme_tool::sync" = "debug"
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::plugin::PluginManifest;
+use crate::validate::ValidationIssue;
+
+/// Log level, ordered from least to most verbose.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum LogLevel {
+    Error,
+    Warn,
+    #[default]
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    /// Level name as written in the manifest.
+    pub fn name(self) -> &'static str {
+        match self {
+            LogLevel::Error => "error",
+            LogLevel::Warn => "warn",
+            LogLevel::Info => "info",
+            LogLevel::Debug => "debug",
+            LogLevel::Trace => "trace",
+        }
+    }
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Logging configuration applied by the host log router.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct LoggingInfo {
+    /// Default level for all targets
+    #[serde(default)]
+    pub level: LogLevel,
+
+    /// Per-target level overrides
+    #[serde(default)]
+    pub targets: BTreeMap<String, LogLevel>,
+
+    /// Whether logs may contain user content (requires redaction)
+    #[serde(default)]
+    pub user_content: bool,
+}
+
+impl LoggingInfo {
+    /// Level for a log target.
+    ///
+    /// The most specific declared target wins; `a::b` also covers `a::b::c`.
+    pub fn level_for(&self, target: &str) -> LogLevel {
+        self.targets
+            .iter()
+            .filter(|(name, _)| {
+                target == name.as_str()
+                    || target
+                        .strip_prefix(name.as_str())
+                        .is_some_and(|rest| rest.starts_with("::"))
+            })
+            .max_by_key(|(name, _)| name.len())
+            .map(|(_, level)| *level)
+            .unwrap_or(self.level)
+    }
+}
+
+impl PluginManifest {
+    /// Default log level (info when `[logging]` is absent).
+    pub fn log_level(&self) -> LogLevel {
+        self.logging.as_ref().map(|l| l.level).unwrap_or_default()
+    }
+
+    /// Log level for a target.
+    pub fn log_level_for(&self, target: &str) -> LogLevel {
+        self.logging
+            .as_ref()
+            .map(|l| l.level_for(target))
+            .unwrap_or_default()
+    }
+
+    /// Whether the plugin's logs may contain user content.
+    pub fn logs_user_content(&self) -> bool {
+        self.logging.as_ref().is_some_and(|l| l.user_content)
+    }
+
+    /// Check logging declarations.
+    pub(crate) fn check_logging(&self) -> Vec<ValidationIssue> {
+        let Some(logging) = &self.logging else {
+            return Vec::new();
+        };
+        logging
+            .targets
+            .keys()
+            .filter(|name| name.is_empty() || name.chars().any(char::is_whitespace))
+            .map(|name| {
+                ValidationIssue::error(
+                    format!("logging.targets.{name}"),
+                    format!("'{name}' is not a valid log target"),
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate::has_errors;
+
+    const BASE: &str = r#"
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.0.0"
+type = "extension"
+"#;
+
+    #[test]
+    fn test_logging() {
+        let toml = format!(
+            "{BASE}\n[logging]\nlevel = \"warn\"\nuser_content = true\n\n[logging.targets]\n\"acme_tool\" = \"info\"\n\"acme_tool::sync\" = \"trace\"\n"
+        );
+        let manifest = PluginManifest::from_toml(&toml).unwrap();
+
+        assert_eq!(manifest.log_level(), LogLevel::Warn);
+        assert_eq!(
+            manifest.log_level_for("acme_tool::sync::remote"),
+            LogLevel::Trace
+        );
+        assert_eq!(manifest.log_level_for("acme_tool::db"), LogLevel::Info);
+        assert_eq!(manifest.log_level_for("acme_toolkit"), LogLevel::Warn);
+        assert!(manifest.logs_user_content());
+        assert!(manifest.validate().is_empty());
+
+        let bare = PluginManifest::from_toml(BASE).unwrap();
+        assert_eq!(bare.log_level(), LogLevel::Info);
+        assert!(!bare.logs_user_content());
+    }
+
+    #[test]
+    fn test_invalid_target() {
+        let toml = format!("{BASE}\n[logging.targets]\n\"acme tool\" = \"debug\"\n");
+        let manifest = PluginManifest::from_toml(&toml).unwrap();
+        assert!(has_errors(&manifest.validate()));
+    }
+}
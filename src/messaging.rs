@@ -0,0 +1,202 @@
+//! Inter-plugin pub/sub topic declarations (`[[messaging]]`).
+//!
+//! ```toml
+//! [[messaging]]
+//! topic = "acme.build.finished"
+//! schema_version = 2
+//! direction = "publish"
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::plugin::PluginManifest;
+use crate::set::ManifestSet;
+use crate::validate::ValidationIssue;
+
+/// Whether a plugin publishes or subscribes to a topic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum TopicDirection {
+    /// Sends messages on the topic
+    Publish,
+    /// Receives messages from the topic
+    Subscribe,
+    /// Both sends and receives
+    Both,
+}
+
+impl TopicDirection {
+    /// Check if this direction includes publishing.
+    pub fn publishes(&self) -> bool {
+        matches!(self, TopicDirection::Publish | TopicDirection::Both)
+    }
+
+    /// Check if this direction includes subscribing.
+    pub fn subscribes(&self) -> bool {
+        matches!(self, TopicDirection::Subscribe | TopicDirection::Both)
+    }
+}
+
+/// A pub/sub topic declaration.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct MessagingTopic {
+    /// Topic ID (e.g., "acme.build.finished")
+    pub topic: String,
+
+    /// Message schema version; publishers and subscribers must agree
+    #[serde(default = "default_schema_version")]
+    pub schema_version: u32,
+
+    /// Publish, subscribe or both
+    pub direction: TopicDirection,
+}
+
+fn default_schema_version() -> u32 {
+    1
+}
+
+impl PluginManifest {
+    /// Topics this plugin publishes.
+    pub fn published_topics(&self) -> impl Iterator<Item = &MessagingTopic> {
+        self.messaging.iter().filter(|t| t.direction.publishes())
+    }
+
+    /// Topics this plugin subscribes to.
+    pub fn subscribed_topics(&self) -> impl Iterator<Item = &MessagingTopic> {
+        self.messaging.iter().filter(|t| t.direction.subscribes())
+    }
+
+    /// Check messaging declarations.
+    pub(crate) fn check_messaging(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        for (i, topic) in self.messaging.iter().enumerate() {
+            if topic.topic.trim().is_empty() {
+                issues.push(ValidationIssue::error(
+                    format!("messaging[{i}].topic"),
+                    "Topic is empty",
+                ));
+            } else if self.messaging[..i].iter().any(|t| t.topic == topic.topic) {
+                issues.push(ValidationIssue::warning(
+                    format!("messaging[{i}].topic"),
+                    format!("Topic '{}' is declared more than once", topic.topic),
+                ));
+            }
+        }
+        issues
+    }
+}
+
+impl ManifestSet {
+    /// Check that every subscribed topic has a publisher in the set with
+    /// the same schema version.
+    pub fn check_messaging(&self) -> Vec<ValidationIssue> {
+        // Publishers in the same plugin count, including a "both" entry
+        // publishing what it subscribes to
+        let mut publishers: HashMap<&str, Vec<&MessagingTopic>> = HashMap::new();
+        for topic in self.plugins().flat_map(|p| p.published_topics()) {
+            publishers
+                .entry(topic.topic.as_str())
+                .or_default()
+                .push(topic);
+        }
+        let mut issues = Vec::new();
+
+        for plugin in self.plugins() {
+            for (i, sub) in plugin.messaging.iter().enumerate() {
+                if !sub.direction.subscribes() {
+                    continue;
+                }
+                let publishers = publishers
+                    .get(sub.topic.as_str())
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+
+                let path = format!("{}.messaging[{i}]", plugin.plugin.id);
+                if publishers.is_empty() {
+                    issues.push(ValidationIssue::error(
+                        path,
+                        format!("No installed plugin publishes topic '{}'", sub.topic),
+                    ));
+                } else if !publishers
+                    .iter()
+                    .any(|p| p.schema_version == sub.schema_version)
+                {
+                    issues.push(ValidationIssue::error(
+                        path,
+                        format!(
+                            "No publisher of topic '{}' uses schema version {}",
+                            sub.topic, sub.schema_version
+                        ),
+                    ));
+                }
+            }
+        }
+
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Manifest;
+
+    fn plugin(id: &str, messaging: &str) -> Manifest {
+        Manifest::from_toml(&format!(
+            "[plugin]\nid = \"{id}\"\nname = \"P\"\nversion = \"1.0.0\"\ntype = \"extension\"\n{messaging}"
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_topic_publishers() {
+        let publisher = plugin(
+            "acme.build",
+            "[[messaging]]\ntopic = \"acme.build.finished\"\nschema_version = 2\ndirection = \"publish\"\n",
+        );
+        let subscriber = plugin(
+            "acme.notify",
+            "[[messaging]]\ntopic = \"acme.build.finished\"\nschema_version = 2\ndirection = \"subscribe\"\n",
+        );
+        let stale = plugin(
+            "acme.old",
+            "[[messaging]]\ntopic = \"acme.build.finished\"\ndirection = \"subscribe\"\n",
+        );
+
+        let set = ManifestSet::new()
+            .with(publisher.clone())
+            .with(subscriber.clone());
+        assert!(set.check_messaging().is_empty());
+
+        let issues = ManifestSet::new().with(subscriber).check_messaging();
+        assert_eq!(issues.len(), 1);
+
+        let issues = ManifestSet::new()
+            .with(publisher)
+            .with(stale)
+            .check_messaging();
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].message.contains("schema version 1"));
+    }
+
+    #[test]
+    fn test_self_published_topics() {
+        let both = plugin(
+            "acme.cache",
+            "[[messaging]]\ntopic = \"acme.cache.invalidate\"\ndirection = \"both\"\n",
+        );
+        assert!(ManifestSet::new().with(both).check_messaging().is_empty());
+
+        let split = plugin(
+            "acme.cache",
+            "[[messaging]]\ntopic = \"acme.cache.invalidate\"\ndirection = \"publish\"\n\n\
+             [[messaging]]\ntopic = \"acme.cache.invalidate\"\ndirection = \"subscribe\"\n",
+        );
+        assert!(ManifestSet::new().with(split).check_messaging().is_empty());
+    }
+}
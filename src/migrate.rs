@@ -0,0 +1,209 @@
+//! Manifest format versions and migration of older layouts.
+//!
+//! Manifests declare the format they were written for at the top level:
+//!
+//! ```toml
+//! manifest_version = 3
+//!
+//! [plugin]
+//! id = "acme.tool"
+//! ```
+//!
+//! A manifest without `manifest_version` is treated as version 1. On parse,
+//! older layouts are rewritten to the current one step by step, and each
+//! change is recorded as a `MigrationNote` on the parsed manifest:
+//!
+//! - 1 -> 2: `capabilities = ["tasks", "llm.chat@2.0.0"]` becomes
+//!   `[[capabilities]]` tables (version "1.0.0" when omitted)
+//! - 2 -> 3: `plugin.cli_command`, `plugin.cli_description` and
+//!   `plugin.cli_aliases` move to `[cli]`
+//!
+//! Steps only touch documents that still use the old layout, so current
+//! manifests without the field parse unchanged.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::error::ManifestError;
+
+/// Manifest format version written by this crate.
+pub const CURRENT_MANIFEST_VERSION: u32 = 3;
+
+/// Format version assumed when `manifest_version` is absent.
+const LEGACY_MANIFEST_VERSION: u32 = 1;
+
+/// Serde default for `manifest_version` (documents parsed without
+/// migration, e.g. JSON, are in the current format).
+pub(crate) fn current_manifest_version() -> u32 {
+    CURRENT_MANIFEST_VERSION
+}
+
+/// A change made while migrating a manifest to the current format.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct MigrationNote {
+    /// Format version the step migrated from
+    pub from_version: u32,
+
+    /// Format version the step migrated to
+    pub to_version: u32,
+
+    /// What was changed
+    pub message: String,
+}
+
+impl fmt::Display for MigrationNote {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "v{} -> v{}: {}",
+            self.from_version, self.to_version, self.message
+        )
+    }
+}
+
+/// A migration step from version `n` to `n + 1`, at index `n - 1`.
+type Step = fn(&mut toml::Table) -> Vec<String>;
+
+const STEPS: [Step; 2] = [capability_tables, cli_section];
+
+/// Migrate a parsed TOML document to the current format in place.
+///
+/// Sets `manifest_version` to the current version and returns the changes
+/// made. Fails for versions newer than this crate understands.
+pub(crate) fn migrate_document(
+    table: &mut toml::Table,
+) -> Result<Vec<MigrationNote>, ManifestError> {
+    let version = match table.get("manifest_version") {
+        None => LEGACY_MANIFEST_VERSION,
+        Some(toml::Value::Integer(v)) if *v >= 1 => u32::try_from(*v).unwrap_or(u32::MAX),
+        Some(v) => {
+            return Err(ManifestError::InvalidFormat(format!(
+                "manifest_version must be a positive integer, got {v}"
+            )))
+        }
+    };
+    if version > CURRENT_MANIFEST_VERSION {
+        return Err(ManifestError::InvalidFormat(format!(
+            "manifest_version {version} is newer than the supported version {CURRENT_MANIFEST_VERSION}"
+        )));
+    }
+
+    let mut notes = Vec::new();
+    // Package documents have no legacy layouts yet; only plugins migrate
+    if table.contains_key("plugin") {
+        for from_version in version..CURRENT_MANIFEST_VERSION {
+            let step = STEPS[(from_version - 1) as usize];
+            notes.extend(step(table).into_iter().map(|message| MigrationNote {
+                from_version,
+                to_version: from_version + 1,
+                message,
+            }));
+        }
+    }
+
+    table.insert(
+        "manifest_version".to_string(),
+        toml::Value::Integer(CURRENT_MANIFEST_VERSION.into()),
+    );
+    Ok(notes)
+}
+
+/// 1 -> 2: capability names become `[[capabilities]]` tables.
+fn capability_tables(table: &mut toml::Table) -> Vec<String> {
+    let Some(toml::Value::Array(capabilities)) = table.get_mut("capabilities") else {
+        return Vec::new();
+    };
+    let mut notes = Vec::new();
+    for capability in capabilities.iter_mut() {
+        let Some(name) = capability.as_str() else {
+            continue;
+        };
+        let (protocol, version) = name.split_once('@').unwrap_or((name, "1.0.0"));
+        notes.push(format!(
+            "capability '{name}' converted to {{ protocol = \"{protocol}\", version = \"{version}\" }}"
+        ));
+        let mut converted = toml::Table::new();
+        converted.insert("protocol".to_string(), protocol.into());
+        converted.insert("version".to_string(), version.into());
+        *capability = toml::Value::Table(converted);
+    }
+    notes
+}
+
+/// 2 -> 3: `plugin.cli_*` keys move to `[cli]`.
+fn cli_section(table: &mut toml::Table) -> Vec<String> {
+    let Some(plugin) = table.get_mut("plugin").and_then(|p| p.as_table_mut()) else {
+        return Vec::new();
+    };
+    let Some(command) = plugin.remove("cli_command") else {
+        return Vec::new();
+    };
+    let mut cli = toml::Table::new();
+    cli.insert("command".to_string(), command);
+    let description = plugin
+        .remove("cli_description")
+        .or_else(|| plugin.get("description").cloned())
+        .unwrap_or_else(|| toml::Value::String(String::new()));
+    cli.insert("description".to_string(), description);
+    if let Some(aliases) = plugin.remove("cli_aliases") {
+        cli.insert("aliases".to_string(), aliases);
+    }
+
+    if table.contains_key("cli") {
+        return vec!["plugin.cli_* keys dropped; [cli] is already declared".to_string()];
+    }
+    table.insert("cli".to_string(), toml::Value::Table(cli));
+    vec!["plugin.cli_* keys moved to [cli]".to_string()]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ManifestError, PluginManifest, CURRENT_MANIFEST_VERSION};
+
+    #[test]
+    fn test_migrate_legacy_manifest() {
+        let manifest = PluginManifest::from_toml(
+            r#"
+capabilities = ["tasks", "llm.chat@2.0.0"]
+
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.0.0"
+type = "extension"
+description = "A tool"
+cli_command = "tool"
+cli_aliases = ["t"]
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(manifest.manifest_version, CURRENT_MANIFEST_VERSION);
+        assert_eq!(manifest.capabilities[1].protocol, "llm.chat");
+        assert_eq!(manifest.capabilities[1].version, "2.0.0");
+        let cli = manifest.cli.as_ref().unwrap();
+        assert_eq!(cli.command, "tool");
+        assert_eq!(cli.description, "A tool");
+        assert_eq!(cli.aliases, ["t"]);
+        assert_eq!(manifest.origin.migration_notes.len(), 3);
+        assert_eq!(manifest.origin.migration_notes[2].to_version, 3);
+
+        let reparsed = PluginManifest::from_toml(&manifest.to_toml().unwrap()).unwrap();
+        assert!(reparsed.origin.migration_notes.is_empty());
+    }
+
+    #[test]
+    fn test_current_and_newer_versions() {
+        let current = PluginManifest::from_toml(
+            "[plugin]\nid = \"acme.tool\"\nname = \"Tool\"\nversion = \"1.0.0\"\ntype = \"extension\"\n",
+        )
+        .unwrap();
+        assert!(current.origin.migration_notes.is_empty());
+
+        let newer = PluginManifest::from_toml(
+            "manifest_version = 99\n\n[plugin]\nid = \"acme.tool\"\nname = \"Tool\"\nversion = \"1.0.0\"\ntype = \"extension\"\n",
+        );
+        assert!(matches!(newer, Err(ManifestError::InvalidFormat(_))));
+    }
+}
@@ -0,0 +1,149 @@
+//! Data migration declarations (`[[migrations]]`).
+//!
+//! ```toml
+//! [[migrations]]
+//! from_version = "<2.0.0"
+//! script = "migrations/v2.sh"
+//! description = "Move project index to the v2 layout"
+//!
+//! [[migrations]]
+//! from_version = ">=2.0.0, <2.3.0"
+//! service = "acme.tool.migrate"
+//! ```
+//!
+//! On update, the host runs every migration whose `from_version` matches the
+//! installed version, in declaration order.
+
+use semver::{Version, VersionReq};
+use serde::{Deserialize, Serialize};
+
+use crate::error::ManifestError;
+use crate::plugin::PluginManifest;
+use crate::validate::ValidationIssue;
+
+/// A data migration step.
+///
+/// Exactly one of `script` or `service` must be set.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Migration {
+    /// Installed versions this migration applies to (semver requirement)
+    pub from_version: String,
+
+    /// Bundled migration script or binary (relative to the plugin directory)
+    #[serde(default)]
+    pub script: Option<String>,
+
+    /// ID of a service provided by this plugin to invoke
+    #[serde(default)]
+    pub service: Option<String>,
+
+    /// Human-readable description
+    #[serde(default)]
+    pub description: String,
+}
+
+impl Migration {
+    /// Check whether this migration applies when upgrading from `installed`.
+    pub fn applies_to(&self, installed: &Version) -> Result<bool, ManifestError> {
+        let req = VersionReq::parse(&self.from_version)
+            .map_err(|e| ManifestError::InvalidVersion(format!("{}: {e}", self.from_version)))?;
+        Ok(req.matches(installed))
+    }
+}
+
+impl PluginManifest {
+    /// Migrations to run when upgrading from `installed`, in order.
+    pub fn migrations_from(&self, installed: &Version) -> Result<Vec<&Migration>, ManifestError> {
+        let mut steps = Vec::new();
+        for migration in &self.migrations {
+            if migration.applies_to(installed)? {
+                steps.push(migration);
+            }
+        }
+        Ok(steps)
+    }
+
+    /// Check migration declarations.
+    pub(crate) fn check_migrations(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+
+        for (i, migration) in self.migrations.iter().enumerate() {
+            if let Err(e) = VersionReq::parse(&migration.from_version) {
+                issues.push(ValidationIssue::error(
+                    format!("migrations[{i}].from_version"),
+                    format!(
+                        "'{}' is not a valid version requirement: {e}",
+                        migration.from_version
+                    ),
+                ));
+            }
+            issues.extend(self.check_action(
+                format!("migrations[{i}]"),
+                "Migration",
+                migration.script.as_deref(),
+                migration.service.as_deref(),
+            ));
+        }
+
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate::has_errors;
+
+    const TOML: &str = r#"
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "2.3.0"
+type = "extension"
+
+[[provides]]
+id = "acme.tool.migrate"
+version = "1.0.0"
+
+[[migrations]]
+from_version = "<2.0.0"
+script = "migrations/v2.sh"
+
+[[migrations]]
+from_version = ">=1.5.0, <2.3.0"
+service = "acme.tool.migrate"
+"#;
+
+    #[test]
+    fn test_migrations_from() {
+        let manifest = PluginManifest::from_toml(TOML).unwrap();
+        assert!(manifest.validate().is_empty());
+
+        let steps = manifest
+            .migrations_from(&Version::parse("1.6.0").unwrap())
+            .unwrap();
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[0].script.as_deref(), Some("migrations/v2.sh"));
+
+        let steps = manifest
+            .migrations_from(&Version::parse("2.1.0").unwrap())
+            .unwrap();
+        assert_eq!(steps.len(), 1);
+        assert!(manifest
+            .migrations_from(&Version::parse("2.3.0").unwrap())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_invalid_migration() {
+        let mut manifest = PluginManifest::from_toml(TOML).unwrap();
+        manifest.migrations[0].from_version = "before two".into();
+        assert!(has_errors(&manifest.validate()));
+        assert!(manifest
+            .migrations_from(&Version::parse("1.0.0").unwrap())
+            .is_err());
+    }
+}
@@ -0,0 +1,209 @@
+//! Per-plugin compatibility overrides and package-wide narrowing.
+//!
+//! A plugin in a package can only narrow the package's compatibility,
+//! never widen it:
+//!
+//! ```toml
+//! [compatibility]
+//! min_host_version = "1.0.0"
+//! platforms = ["linux-x86_64", "darwin-aarch64", "windows-x86_64"]
+//!
+//! [[plugins]]
+//! id = "acme.metal"
+//! name = "Metal Renderer"
+//! type = "extension"
+//! binary = "acme_metal"
+//!
+//! [plugins.compatibility]
+//! min_host_version = "1.4.0"
+//! platforms = ["darwin-aarch64"]
+//! ```
+//!
+//! [`PackageManifest::effective_compatibility`] intersects the package
+//! section with every override, answering "can the whole package run
+//! here?" without expanding plugins.
+
+use serde::{Deserialize, Serialize};
+
+use crate::package::PackageManifest;
+use crate::plugin::CompatibilityInfo;
+use crate::validate::ValidationIssue;
+use crate::version::Version;
+
+/// Compatibility constraints a package plugin adds to the package's own.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CompatibilityOverride {
+    /// Minimum host version (only raises the package minimum)
+    #[serde(default)]
+    pub min_host_version: Option<String>,
+
+    /// Maximum host version (only lowers the package maximum)
+    #[serde(default)]
+    pub max_host_version: Option<String>,
+
+    /// Supported platforms (a subset of the package platforms; empty = inherit)
+    #[serde(default)]
+    pub platforms: Vec<String>,
+}
+
+impl CompatibilityOverride {
+    /// Check that the override narrows `base` to something a host can meet.
+    pub(crate) fn validate(&self, path: &str, base: &CompatibilityInfo) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        for platform in &self.platforms {
+            if !base.platforms.is_empty() && !base.platforms.contains(platform) {
+                issues.push(ValidationIssue::error(
+                    format!("{path}.platforms"),
+                    format!("'{platform}' is not a package platform"),
+                ));
+            }
+        }
+        if issues.is_empty() && base.narrowed(self).is_none() {
+            issues.push(ValidationIssue::error(
+                path.to_string(),
+                "Minimum host version is above the maximum",
+            ));
+        }
+        issues
+    }
+}
+
+impl CompatibilityInfo {
+    /// The stricter of `self` and `other`, or None if no host could satisfy
+    /// both (disjoint platforms, or a minimum above the maximum).
+    ///
+    /// Unparsable host versions are left as they are; validation reports
+    /// them.
+    pub fn narrowed(&self, other: &CompatibilityOverride) -> Option<CompatibilityInfo> {
+        let platforms = match (self.platforms.is_empty(), other.platforms.is_empty()) {
+            (_, true) => self.platforms.clone(),
+            (true, false) => other.platforms.clone(),
+            (false, false) => {
+                let common: Vec<String> = self
+                    .platforms
+                    .iter()
+                    .filter(|p| other.platforms.contains(p))
+                    .cloned()
+                    .collect();
+                if common.is_empty() {
+                    return None;
+                }
+                common
+            }
+        };
+        let min_host_version = stricter(&self.min_host_version, &other.min_host_version, |a, b| {
+            a > b
+        });
+        let max_host_version = stricter(&self.max_host_version, &other.max_host_version, |a, b| {
+            a < b
+        });
+        if let (Some(min), Some(max)) = (&min_host_version, &max_host_version) {
+            if let (Ok(min), Ok(max)) = (Version::parse(min), Version::parse(max)) {
+                if min > max {
+                    return None;
+                }
+            }
+        }
+        Some(CompatibilityInfo {
+            min_host_version,
+            max_host_version,
+            platforms,
+            ..self.clone()
+        })
+    }
+}
+
+impl PackageManifest {
+    /// Compatibility of the package as a whole: the package section
+    /// narrowed by every plugin override.
+    ///
+    /// Returns None if the overrides leave no host that can run every
+    /// plugin.
+    pub fn effective_compatibility(&self) -> Option<CompatibilityInfo> {
+        self.plugins
+            .iter()
+            .filter_map(|plugin| plugin.compatibility.as_ref())
+            .try_fold(self.compatibility.clone(), |compat, o| compat.narrowed(o))
+    }
+}
+
+/// Pick `b` over `a` if `b` is set and `prefer(b, a)` holds for parsed versions.
+fn stricter(
+    a: &Option<String>,
+    b: &Option<String>,
+    prefer: impl Fn(&Version, &Version) -> bool,
+) -> Option<String> {
+    match (a, b) {
+        (Some(a), Some(b)) => match (Version::parse(a), Version::parse(b)) {
+            (Ok(va), Ok(vb)) if prefer(&vb, &va) => Some(b.clone()),
+            _ => Some(a.clone()),
+        },
+        (a, b) => a.clone().or_else(|| b.clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PACKAGE: &str = r#"
+[package]
+id = "acme.suite"
+name = "Suite"
+version = "1.0.0"
+
+[compatibility]
+min_host_version = "1.0.0"
+max_host_version = "3.0.0"
+platforms = ["linux-x86_64", "darwin-aarch64"]
+
+[[plugins]]
+id = "acme.core"
+name = "Core"
+type = "extension"
+binary = "acme_core"
+
+[[plugins]]
+id = "acme.metal"
+name = "Metal"
+type = "extension"
+binary = "acme_metal"
+
+[plugins.compatibility]
+min_host_version = "1.4.0"
+platforms = ["darwin-aarch64"]
+"#;
+
+    #[test]
+    fn test_effective_compatibility() {
+        let package = PackageManifest::from_toml(PACKAGE).unwrap();
+        let effective = package.effective_compatibility().unwrap();
+        assert_eq!(effective.min_host_version.as_deref(), Some("1.4.0"));
+        assert_eq!(effective.max_host_version.as_deref(), Some("3.0.0"));
+        assert_eq!(effective.platforms, ["darwin-aarch64"]);
+
+        let plugins = package.expand_plugins();
+        assert_eq!(plugins[0].compatibility.platforms.len(), 2);
+        assert_eq!(plugins[1].compatibility.platforms, ["darwin-aarch64"]);
+        assert!(package.validate().iter().all(|i| !i.is_error()));
+    }
+
+    #[test]
+    fn test_unsatisfiable_override() {
+        let mut package = PackageManifest::from_toml(PACKAGE).unwrap();
+        package.plugins[1].compatibility = Some(CompatibilityOverride {
+            min_host_version: Some("4.0.0".into()),
+            ..Default::default()
+        });
+        assert!(package.effective_compatibility().is_none());
+        assert!(package.validate().iter().any(|i| i.is_error()));
+
+        package.plugins[1].compatibility = Some(CompatibilityOverride {
+            platforms: vec!["windows-x86_64".into()],
+            ..Default::default()
+        });
+        assert!(package.effective_compatibility().is_none());
+    }
+}
@@ -0,0 +1,114 @@
+//! Observability metadata (`[observability]`).
+//!
+//! ```toml
+//! [observability]
+//! metrics = ["acme_tool"]
+//! tracing = true
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::plugin::PluginManifest;
+use crate::validate::ValidationIssue;
+
+/// Metrics and tracing exported by the plugin.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ObservabilityInfo {
+    /// Metric namespaces (name prefixes) the plugin exports
+    #[serde(default)]
+    pub metrics: Vec<String>,
+
+    /// Whether the plugin emits tracing spans compatible with the host collector
+    #[serde(default)]
+    pub tracing: bool,
+}
+
+impl ObservabilityInfo {
+    /// Check whether a metric name belongs to one of the declared namespaces.
+    pub fn owns_metric(&self, metric: &str) -> bool {
+        self.metrics.iter().any(|ns| {
+            metric == ns
+                || metric
+                    .strip_prefix(ns.as_str())
+                    .is_some_and(|rest| rest.starts_with('_'))
+        })
+    }
+}
+
+/// Check if a string is a valid metric namespace (`[a-zA-Z_][a-zA-Z0-9_]*`).
+fn is_valid_namespace(ns: &str) -> bool {
+    let mut chars = ns.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+impl PluginManifest {
+    /// Metric namespaces exported by the plugin.
+    pub fn metric_namespaces(&self) -> &[String] {
+        self.observability
+            .as_ref()
+            .map(|o| o.metrics.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Whether the plugin emits host-compatible tracing spans.
+    pub fn emits_traces(&self) -> bool {
+        self.observability.as_ref().is_some_and(|o| o.tracing)
+    }
+
+    /// Check observability declarations.
+    pub(crate) fn check_observability(&self) -> Vec<ValidationIssue> {
+        self.metric_namespaces()
+            .iter()
+            .enumerate()
+            .filter(|(_, ns)| !is_valid_namespace(ns))
+            .map(|(i, ns)| {
+                ValidationIssue::error(
+                    format!("observability.metrics[{i}]"),
+                    format!("'{ns}' is not a valid metric namespace"),
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate::has_errors;
+
+    const BASE: &str = r#"
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.0.0"
+type = "extension"
+"#;
+
+    #[test]
+    fn test_observability() {
+        let toml = format!("{BASE}\n[observability]\nmetrics = [\"acme_tool\"]\ntracing = true\n");
+        let manifest = PluginManifest::from_toml(&toml).unwrap();
+        let observability = manifest.observability.as_ref().unwrap();
+
+        assert!(manifest.emits_traces());
+        assert!(observability.owns_metric("acme_tool_requests_total"));
+        assert!(!observability.owns_metric("acme_toolbox_requests"));
+        assert!(manifest.validate().is_empty());
+
+        let bare = PluginManifest::from_toml(BASE).unwrap();
+        assert!(!bare.emits_traces());
+        assert!(bare.metric_namespaces().is_empty());
+    }
+
+    #[test]
+    fn test_invalid_namespace() {
+        let toml = format!("{BASE}\n[observability]\nmetrics = [\"acme.tool\"]\n");
+        let manifest = PluginManifest::from_toml(&toml).unwrap();
+        assert!(has_errors(&manifest.validate()));
+    }
+}
@@ -0,0 +1,316 @@
+//! Parse options.
+//!
+//! Defaults the crate used to hardcode (e.g., the plugin API version) are
+//! configurable through `ParseOptions`. Fields filled in from a default are
+//! recorded on the parsed manifest, so hosts can tell them apart from
+//! explicitly declared values.
+//!
+//! The same options carry the other parse-time knobs: strict validation,
+//! size limits, and the locale and platform used to resolve a plugin's
+//! conditional sections.
+//!
+//! ```
+//! use lib_plugin_manifest::{ParseOptions, PluginManifest};
+//!
+//! let options = ParseOptions::new()
+//!     .strict(true)
+//!     .max_size(64 * 1024)
+//!     .locale("de");
+//! let manifest = PluginManifest::from_toml_with(
+//!     r#"
+//! [plugin]
+//! id = "acme.tool"
+//! name = "Tool"
+//! version = "1.0.0"
+//! type = "extension"
+//!
+//! [locales.de]
+//! name = "Werkzeug"
+//! "#,
+//!     &options,
+//! )
+//! .unwrap();
+//! assert_eq!(manifest.plugin.name, "Werkzeug");
+//! ```
+
+/// Plugin API version assumed when a manifest doesn't declare one.
+pub const DEFAULT_API_VERSION: u32 = 2; // Match PLUGIN_API_VERSION in lib-plugin-abi
+
+/// Field path recorded when `compatibility.api_version` was defaulted.
+pub const API_VERSION_FIELD: &str = "compatibility.api_version";
+
+use std::sync::Arc;
+
+use crate::migrate::{migrate_document, MigrationNote};
+use crate::package::PackageManifest;
+use crate::plugin_type::PluginTypeRegistry;
+use crate::signing::DeclaredDocument;
+use crate::validate::{ensure_valid, ValidationIssue, ValidationPolicy};
+use crate::ManifestError;
+
+/// Options for the `*_with` parse entry points.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// API version used when `compatibility.api_version` is absent
+    pub default_api_version: u32,
+
+    /// Reject manifests that have validation errors or unknown plugin types
+    pub strict: bool,
+
+    /// Plugin types accepted when parsing strictly
+    pub plugin_types: PluginTypeRegistry,
+
+    /// Maximum size of the manifest source in bytes
+    pub max_size: Option<usize>,
+
+    /// Maximum number of plugins in a package
+    pub max_plugins: Option<usize>,
+
+    /// Locale used for the plugin name and description
+    pub locale: Option<String>,
+
+    /// Platform whose `[target.<platform>]` overrides are applied
+    /// (defaults to the current platform when a locale is set)
+    pub platform: Option<String>,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            default_api_version: DEFAULT_API_VERSION,
+            strict: false,
+            plugin_types: PluginTypeRegistry::builtin(),
+            max_size: None,
+            max_plugins: None,
+            locale: None,
+            platform: None,
+        }
+    }
+}
+
+impl ParseOptions {
+    /// Create options with the crate defaults.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder-style setter for `default_api_version`.
+    pub fn default_api_version(mut self, api_version: u32) -> Self {
+        self.default_api_version = api_version;
+        self
+    }
+
+    /// Builder-style setter for `strict`.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Accept a custom plugin type when parsing strictly.
+    pub fn plugin_type(mut self, plugin_type: impl Into<String>) -> Self {
+        self.plugin_types.register(plugin_type);
+        self
+    }
+
+    /// Builder-style setter for `max_size`.
+    pub fn max_size(mut self, bytes: usize) -> Self {
+        self.max_size = Some(bytes);
+        self
+    }
+
+    /// Builder-style setter for `max_plugins`.
+    pub fn max_plugins(mut self, count: usize) -> Self {
+        self.max_plugins = Some(count);
+        self
+    }
+
+    /// Builder-style setter for `locale`.
+    pub fn locale(mut self, locale: impl Into<String>) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+
+    /// Builder-style setter for `platform`.
+    pub fn platform(mut self, platform: impl Into<String>) -> Self {
+        self.platform = Some(platform.into());
+        self
+    }
+
+    /// Check whether a plugin manifest should be resolved with
+    /// `PluginManifest::effective()` after parsing.
+    pub(crate) fn resolves_sections(&self) -> bool {
+        self.locale.is_some() || self.platform.is_some()
+    }
+
+    /// Fail if the manifest source exceeds `max_size`.
+    pub(crate) fn check_size(&self, content: &str) -> Result<(), ManifestError> {
+        match self.max_size {
+            Some(limit) if content.len() > limit => Err(ManifestError::InvalidFormat(format!(
+                "Manifest is {} bytes, limit is {limit}",
+                content.len()
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Fail with the validation errors when parsing strictly. Strict
+    /// parsing rejects plugin types missing from `plugin_types`.
+    pub(crate) fn enforce(
+        &self,
+        validate: impl FnOnce(&ValidationPolicy) -> Vec<ValidationIssue>,
+    ) -> Result<(), ManifestError> {
+        if !self.strict {
+            return Ok(());
+        }
+        ensure_valid(&validate(&ValidationPolicy {
+            plugin_types: self.plugin_types.clone(),
+            reject_unknown_types: true,
+            ..ValidationPolicy::default()
+        }))
+    }
+}
+
+/// Where a manifest came from: what parsing filled in or changed, and the
+/// document or package it was read from.
+///
+/// Never serialized; manifests built in code have an empty origin.
+#[derive(Debug, Clone, Default)]
+pub struct ManifestOrigin {
+    /// Fields filled in from `ParseOptions` defaults rather than declared
+    pub defaulted_fields: Vec<String>,
+
+    /// Changes made while migrating from an older manifest format
+    pub migration_notes: Vec<MigrationNote>,
+
+    /// Package this manifest was expanded from (its signature covers the package)
+    pub source_package: Option<Arc<PackageManifest>>,
+
+    /// Document this manifest was parsed from (what its signature covers)
+    pub(crate) declared: Option<Arc<DeclaredDocument>>,
+}
+
+/// Parse a TOML document, migrating older layouts and applying defaults
+/// from `options`.
+///
+/// Returns the document and its origin: the fields that were defaulted,
+/// the migration notes and the declared source.
+pub(crate) fn parse_document<T: serde::de::DeserializeOwned>(
+    content: &str,
+    options: &ParseOptions,
+) -> Result<(T, ManifestOrigin), ManifestError> {
+    options.check_size(content)?;
+    let mut table: toml::Table = toml::from_str(content)?;
+    let notes = migrate_document(&mut table)?;
+    let mut defaulted = Vec::new();
+
+    let compatibility = table
+        .entry("compatibility")
+        .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+    if let Some(compatibility) = compatibility.as_table_mut() {
+        if !compatibility.contains_key("api_version") {
+            compatibility.insert(
+                "api_version".to_string(),
+                toml::Value::Integer(options.default_api_version.into()),
+            );
+            defaulted.push(API_VERSION_FIELD.to_string());
+        }
+    }
+
+    let origin = ManifestOrigin {
+        defaulted_fields: defaulted,
+        migration_notes: notes,
+        source_package: None,
+        declared: Some(DeclaredDocument::new(content, options)),
+    };
+    Ok((table.try_into()?, origin))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Manifest, ParseOptions, PluginManifest, API_VERSION_FIELD};
+
+    const TOML: &str = r#"
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.0.0"
+type = "extension"
+"#;
+
+    #[test]
+    fn test_default_api_version() {
+        let manifest = PluginManifest::from_toml(TOML).unwrap();
+        assert_eq!(manifest.compatibility.api_version, 2);
+        assert!(manifest.is_defaulted(API_VERSION_FIELD));
+
+        let options = ParseOptions::new().default_api_version(3);
+        let manifest = PluginManifest::from_toml_with(TOML, &options).unwrap();
+        assert_eq!(manifest.compatibility.api_version, 3);
+
+        let explicit = format!("{TOML}\n[compatibility]\napi_version = 1\n");
+        let manifest = Manifest::from_toml_with(&explicit, &options).unwrap();
+        let Manifest::Single(manifest) = manifest else {
+            panic!("expected a single plugin");
+        };
+        assert_eq!(manifest.compatibility.api_version, 1);
+        assert!(!manifest.is_defaulted(API_VERSION_FIELD));
+    }
+
+    #[test]
+    fn test_package_default_propagates() {
+        let toml = r#"
+[package]
+id = "acme.pack"
+name = "Pack"
+version = "1.0.0"
+
+[[plugins]]
+id = "acme.one"
+name = "One"
+type = "extension"
+binary = "one"
+"#;
+        let options = ParseOptions::new().default_api_version(3);
+        let package = crate::PackageManifest::from_toml_with(toml, &options).unwrap();
+        let plugins = package.expand_plugins();
+        assert_eq!(plugins[0].compatibility.api_version, 3);
+        assert!(plugins[0].is_defaulted(API_VERSION_FIELD));
+
+        let options = ParseOptions::new().max_plugins(0);
+        assert!(crate::PackageManifest::from_toml_with(toml, &options).is_err());
+    }
+
+    #[test]
+    fn test_strict_and_limits() {
+        let invalid = TOML.replace("1.0.0", "1.0");
+        assert!(PluginManifest::from_toml(&invalid).is_ok());
+        let strict = ParseOptions::new().strict(true);
+        assert!(PluginManifest::from_toml_with(TOML, &strict).is_ok());
+        assert!(PluginManifest::from_toml_with(&invalid, &strict).is_err());
+
+        let custom = TOML.replace("extension", "widget");
+        assert!(PluginManifest::from_toml(&custom).is_ok());
+        assert!(PluginManifest::from_toml_with(&custom, &strict).is_err());
+        let strict = strict.plugin_type("widget");
+        assert!(PluginManifest::from_toml_with(&custom, &strict).is_ok());
+
+        let limited = ParseOptions::new().max_size(16);
+        assert!(Manifest::from_toml_with(TOML, &limited).is_err());
+    }
+
+    #[test]
+    fn test_platform_override() {
+        let toml = format!(
+            "{TOML}\n[binary]\nname = \"tool\"\n\n[target.windows-x86_64.binary]\nname = \"tool_win\"\n"
+        );
+        let options = ParseOptions::new().platform("windows-x86_64");
+        let manifest = PluginManifest::from_toml_with(&toml, &options).unwrap();
+        assert_eq!(manifest.binary.name, "tool_win");
+        assert!(manifest.target.is_empty());
+        assert!(manifest.is_defaulted(API_VERSION_FIELD));
+
+        let options = ParseOptions::new().platform("linux-x86_64");
+        let manifest = PluginManifest::from_toml_with(&toml, &options).unwrap();
+        assert_eq!(manifest.binary.name, "tool");
+    }
+}
@@ -1,19 +1,31 @@
 //! Multi-plugin package manifest (package.toml).
 
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::Path;
+use std::sync::Arc;
 
+use crate::assets::Asset;
 use crate::error::ManifestError;
-use crate::platform::{current_platform, library_filename};
+use crate::migrate::current_manifest_version;
+use crate::narrow::CompatibilityOverride;
+use crate::options::{parse_document, ManifestOrigin, ParseOptions};
+use crate::platform::{current_platform, find_platform_key, library_filename, Platform};
 use crate::plugin::{
-    BinaryInfo, CompatibilityInfo, ConfigInfo, PluginManifest, PluginMeta, ServiceDeclaration,
-    ServiceRequirement, SignatureInfo,
+    verify_size, BinaryInfo, BinaryKind, CompatibilityInfo, ConfigInfo, PluginManifest, PluginMeta,
+    ServiceDeclaration, ServiceRequirement, SignatureInfo,
 };
+use crate::plugin_type::PluginType;
 
 /// A multi-plugin package manifest parsed from package.toml.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PackageManifest {
+    /// Manifest format version
+    #[serde(default = "current_manifest_version")]
+    pub manifest_version: u32,
+
     /// Package metadata
     pub package: PackageMeta,
 
@@ -31,25 +43,50 @@ pub struct PackageManifest {
     /// Signature information (optional)
     #[serde(default)]
     pub signature: Option<SignatureInfo>,
+
+    /// Where the manifest was parsed from (not part of the document)
+    #[serde(skip)]
+    pub origin: ManifestOrigin,
 }
 
 impl PackageManifest {
     /// Parse from TOML string.
     pub fn from_toml(content: &str) -> Result<Self, ManifestError> {
-        toml::from_str(content).map_err(ManifestError::TomlParse)
+        Self::from_toml_with(content, &ParseOptions::default())
+    }
+
+    /// Parse from TOML string with custom options.
+    pub fn from_toml_with(content: &str, options: &ParseOptions) -> Result<Self, ManifestError> {
+        let (mut manifest, origin): (Self, _) = parse_document(content, options)?;
+        if let Some(limit) = options.max_plugins.filter(|l| manifest.plugins.len() > *l) {
+            return Err(ManifestError::InvalidFormat(format!(
+                "Package has {} plugins, limit is {limit}",
+                manifest.plugins.len()
+            )));
+        }
+        options.enforce(|policy| manifest.validate_with(policy))?;
+        manifest.origin = origin;
+        Ok(manifest)
     }
 
     /// Parse from file.
     pub fn from_file(path: &Path) -> Result<Self, ManifestError> {
+        Self::from_file_with(path, &ParseOptions::default())
+    }
+
+    /// Parse from file with custom options.
+    pub fn from_file_with(path: &Path, options: &ParseOptions) -> Result<Self, ManifestError> {
         let content = std::fs::read_to_string(path)?;
-        Self::from_toml(&content)
+        Self::from_toml_with(&content, options)
     }
 
     /// Expand package into individual PluginManifest instances.
     ///
     /// Each plugin in the package gets its own manifest with inherited
-    /// compatibility and signature information.
+    /// compatibility and signature information, and a reference to the
+    /// package the signature covers.
     pub fn expand_plugins(&self) -> Vec<PluginManifest> {
+        let source = Arc::new(self.clone());
         self.plugins
             .iter()
             .map(|plugin_def| {
@@ -64,8 +101,16 @@ impl PackageManifest {
                 if !plugin_def.depends_on.is_empty() {
                     compatibility.depends_on = plugin_def.depends_on.clone();
                 }
+                if let Some(narrowed) = plugin_def
+                    .compatibility
+                    .as_ref()
+                    .and_then(|o| compatibility.narrowed(o))
+                {
+                    compatibility = narrowed;
+                }
 
                 PluginManifest {
+                    manifest_version: self.manifest_version,
                     plugin: PluginMeta {
                         id: plugin_def.id.clone(),
                         name: plugin_def.name.clone(),
@@ -78,25 +123,34 @@ impl PackageManifest {
                             .unwrap_or_else(|| self.package.description.clone()),
                         license: self.package.license.clone(),
                         homepage: self.package.homepage.clone(),
+                        repository: self.package.repository.clone(),
+                        license_file: self.package.license_file.clone(),
                     },
                     compatibility,
                     binary: BinaryInfo {
                         name: plugin_def.binary.clone(),
                         checksums,
+                        // Package sizes are those of the whole archive
+                        sizes: HashMap::new(),
+                        path: None,
+                        platform: BTreeMap::new(),
+                        kind: BinaryKind::Native,
+                        abi_requirements: None,
                     },
                     signature: self.signature.clone(),
                     config: plugin_def.config.clone().unwrap_or_default(),
                     provides: plugin_def.provides.clone(),
                     requires: plugin_def.requires.clone(),
-                    // Packages don't support CLI commands - only single plugins do
-                    cli: None,
-                    // Packages don't support capabilities - only single plugins do
-                    capabilities: Vec::new(),
-                    tags: None,
-                    hive: None,
-                    translation: None,
-                    language: None,
-                    requirements: None,
+                    assets: plugin_def.assets.clone(),
+                    origin: ManifestOrigin {
+                        defaulted_fields: self.origin.defaulted_fields.clone(),
+                        migration_notes: self.origin.migration_notes.clone(),
+                        source_package: Some(Arc::clone(&source)),
+                        declared: None,
+                    },
+                    // Packages don't support CLI commands or capabilities -
+                    // only single plugins do
+                    ..Default::default()
                 }
             })
             .collect()
@@ -105,54 +159,63 @@ impl PackageManifest {
     /// Get the installation order of plugins, respecting dependencies.
     ///
     /// Returns plugins sorted so that dependencies come before dependents.
+    /// Among plugins whose dependencies are already installed, the one
+    /// declared first in the manifest comes first, so the order is stable
+    /// across runs. Dependencies outside the package are ignored.
     /// Returns an error if there are circular dependencies.
     pub fn install_order(&self) -> Result<Vec<&PluginDef>, ManifestError> {
-        let mut result = Vec::new();
-        let mut visited = HashSet::new();
-        let mut in_progress = HashSet::new();
-
-        // Build a map of plugin id -> plugin def
-        let plugin_map: HashMap<&str, &PluginDef> =
-            self.plugins.iter().map(|p| (p.id.as_str(), p)).collect();
-
-        fn visit<'a>(
-            plugin_id: &str,
-            plugin_map: &HashMap<&str, &'a PluginDef>,
-            visited: &mut HashSet<String>,
-            in_progress: &mut HashSet<String>,
-            result: &mut Vec<&'a PluginDef>,
-        ) -> Result<(), ManifestError> {
-            if visited.contains(plugin_id) {
-                return Ok(());
-            }
-
-            if in_progress.contains(plugin_id) {
-                return Err(ManifestError::CircularDependency(plugin_id.to_string()));
-            }
-
-            in_progress.insert(plugin_id.to_string());
+        self.install_order_of(|_| true)
+    }
 
-            if let Some(plugin) = plugin_map.get(plugin_id) {
-                for dep in &plugin.depends_on {
-                    visit(dep, plugin_map, visited, in_progress, result)?;
+    /// `install_order()` restricted to the plugins `include` accepts, so
+    /// cycles among the other plugins don't matter. Dependencies outside
+    /// the selection are treated as installed.
+    pub(crate) fn install_order_of(
+        &self,
+        include: impl Fn(&PluginDef) -> bool,
+    ) -> Result<Vec<&PluginDef>, ManifestError> {
+        let index: HashMap<&str, usize> = self
+            .plugins
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (p.id.as_str(), i))
+            .collect();
+        let deps: Vec<Vec<usize>> = self
+            .plugins
+            .iter()
+            .map(|p| {
+                p.depends_on
+                    .iter()
+                    .filter_map(|d| index.get(d.as_str()).copied())
+                    .collect()
+            })
+            .collect();
+
+        let mut installed: Vec<bool> = self.plugins.iter().map(|p| !include(p)).collect();
+        let count = installed.iter().filter(|done| !**done).count();
+        let mut result = Vec::with_capacity(count);
+
+        while result.len() < count {
+            let next = (0..self.plugins.len())
+                .find(|&i| !installed[i] && deps[i].iter().all(|&d| installed[d]));
+            match next {
+                Some(i) => {
+                    installed[i] = true;
+                    result.push(&self.plugins[i]);
+                }
+                None => {
+                    // Every remaining plugin waits on another remaining one;
+                    // follow the chain until it loops back.
+                    let mut seen = HashSet::new();
+                    let mut current = installed.iter().position(|done| !done).unwrap();
+                    while seen.insert(current) {
+                        current = *deps[current].iter().find(|&&d| !installed[d]).unwrap();
+                    }
+                    return Err(ManifestError::CircularDependency(
+                        self.plugins[current].id.clone(),
+                    ));
                 }
-
-                in_progress.remove(plugin_id);
-                visited.insert(plugin_id.to_string());
-                result.push(plugin);
             }
-
-            Ok(())
-        }
-
-        for plugin in &self.plugins {
-            visit(
-                &plugin.id,
-                &plugin_map,
-                &mut visited,
-                &mut in_progress,
-                &mut result,
-            )?;
         }
 
         Ok(result)
@@ -160,10 +223,8 @@ impl PackageManifest {
 
     /// Get the checksum for the current platform (if available).
     pub fn checksum_for_current_platform(&self) -> Option<&str> {
-        self.binary
-            .checksums
-            .get(&current_platform())
-            .map(|s| s.as_str())
+        let checksums = &self.binary.checksums;
+        find_platform_key(checksums.keys(), &current_platform()).map(|key| checksums[key].as_str())
     }
 
     /// Check if the current platform is supported.
@@ -171,16 +232,18 @@ impl PackageManifest {
         if self.compatibility.platforms.is_empty() {
             return true;
         }
-        let current = current_platform();
+        let current = Platform::current();
         self.compatibility
             .platforms
             .iter()
-            .any(|p| p == &current || p == "all")
+            .any(|p| current.matches(p))
     }
 }
 
 /// Package metadata.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PackageMeta {
     /// Unique identifier (e.g., "vendor.theme-pack")
     pub id: String,
@@ -206,10 +269,20 @@ pub struct PackageMeta {
     /// Homepage URL
     #[serde(default)]
     pub homepage: Option<String>,
+
+    /// Source repository URL
+    #[serde(default)]
+    pub repository: Option<String>,
+
+    /// License text file (relative to the package root)
+    #[serde(default)]
+    pub license_file: Option<String>,
 }
 
 /// Plugin definition within a package.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PluginDef {
     /// Unique identifier
     pub id: String,
@@ -219,7 +292,9 @@ pub struct PluginDef {
 
     /// Plugin type
     #[serde(rename = "type")]
-    pub plugin_type: String,
+    #[cfg_attr(feature = "typescript", ts(type = "string"))]
+    #[cfg_attr(feature = "schema", schemars(with = "String"))]
+    pub plugin_type: PluginType,
 
     /// Binary name (without lib prefix and extension)
     pub binary: String,
@@ -243,6 +318,14 @@ pub struct PluginDef {
     /// Services this plugin requires
     #[serde(default)]
     pub requires: Vec<ServiceRequirement>,
+
+    /// Non-binary files (themes, fonts, icons)
+    #[serde(default)]
+    pub assets: Vec<Asset>,
+
+    /// Narrower compatibility than the package's (`[plugins.compatibility]`)
+    #[serde(default)]
+    pub compatibility: Option<CompatibilityOverride>,
 }
 
 impl PluginDef {
@@ -254,10 +337,33 @@ impl PluginDef {
 
 /// Package binary information.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PackageBinaryInfo {
     /// SHA256 checksums per platform (for the whole package archive)
     #[serde(default)]
     pub checksums: HashMap<String, String>,
+
+    /// Expected archive size in bytes per platform
+    #[serde(default)]
+    pub sizes: HashMap<String, u64>,
+}
+
+impl PackageBinaryInfo {
+    /// Check a downloaded archive's size against the declared size.
+    ///
+    /// Passes if no size is declared for the platform.
+    pub fn verify_size(&self, platform: &str, actual: u64) -> Result<(), ManifestError> {
+        verify_size(&self.sizes, platform, actual)
+    }
+}
+
+impl PackageManifest {
+    /// Serialize to TOML string.
+    pub fn to_toml(&self) -> Result<String, ManifestError> {
+        toml::to_string_pretty(self)
+            .map_err(|e| ManifestError::InvalidFormat(format!("Failed to serialize manifest: {e}")))
+    }
 }
 
 #[cfg(test)]
@@ -326,6 +432,9 @@ id = "vendor.plugin-b"
 name = "Plugin B"
 type = "theme"
 binary = "plugin_b"
+
+[binary.sizes]
+linux-x86_64 = 4096
 "#;
 
         let manifest = PackageManifest::from_toml(toml).unwrap();
@@ -337,6 +446,8 @@ binary = "plugin_b"
         // All inherit package version
         assert_eq!(expanded[0].plugin.version, "1.0.0");
         assert_eq!(expanded[1].plugin.version, "1.0.0");
+        // The archive size says nothing about a single plugin's binary
+        assert_eq!(expanded[0].binary.size_for("linux-x86_64"), None);
     }
 
     #[test]
@@ -383,6 +494,64 @@ depends_on = ["vendor.plugin-a"]
         assert!(pos_b < pos_c, "B should come before C");
     }
 
+    #[test]
+    fn test_install_order_is_deterministic() {
+        let toml = r#"
+[package]
+id = "vendor.pack"
+name = "Test Pack"
+version = "1.0.0"
+
+[[plugins]]
+id = "vendor.plugin-d"
+name = "Plugin D"
+type = "extension"
+binary = "plugin_d"
+depends_on = ["vendor.plugin-c", "vendor.plugin-b"]
+
+[[plugins]]
+id = "vendor.plugin-b"
+name = "Plugin B"
+type = "extension"
+binary = "plugin_b"
+
+[[plugins]]
+id = "vendor.plugin-c"
+name = "Plugin C"
+type = "extension"
+binary = "plugin_c"
+depends_on = ["vendor.external"]
+
+[[plugins]]
+id = "vendor.plugin-a"
+name = "Plugin A"
+type = "extension"
+binary = "plugin_a"
+"#;
+
+        let manifest = PackageManifest::from_toml(toml).unwrap();
+        let ids = |m: &PackageManifest| -> Vec<String> {
+            m.install_order()
+                .unwrap()
+                .iter()
+                .map(|p| p.id.clone())
+                .collect()
+        };
+
+        // Siblings follow declaration order, not depends_on order
+        let expected = [
+            "vendor.plugin-b",
+            "vendor.plugin-c",
+            "vendor.plugin-d",
+            "vendor.plugin-a",
+        ];
+        for _ in 0..10 {
+            assert_eq!(ids(&manifest), expected);
+            let reparsed = PackageManifest::from_toml(toml).unwrap();
+            assert_eq!(ids(&reparsed), expected);
+        }
+    }
+
     #[test]
     fn test_circular_dependency_detection() {
         let toml = r#"
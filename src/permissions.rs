@@ -0,0 +1,924 @@
+//! Sandbox permission declarations (`[permissions]`).
+//!
+//! ```toml
+//! [permissions.network]
+//! allow = ["api.acme.dev:443", "*.cdn.acme.dev", "localhost:8000-8100"]
+//!
+//! [permissions.fs]
+//! read = ["$WORKSPACE/**"]
+//! write = ["$WORKSPACE/build/**", "$CACHE/**"]
+//!
+//! [permissions.env]
+//! allow = ["ACME_*", "HOME"]
+//!
+//! [permissions.subprocess]
+//! allow = ["git", "docker"]
+//! ```
+//!
+//! Anything not declared is denied. `Permissions::is_subset_of()` compares
+//! two declarations, e.g. to check that an update asks for nothing new.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::error::ManifestError;
+
+use crate::plugin::PluginManifest;
+use crate::validate::ValidationIssue;
+
+/// Permissions requested by a plugin.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Permissions {
+    /// Network access
+    #[serde(default)]
+    pub network: Option<NetworkPermissions>,
+
+    /// Filesystem access
+    #[serde(default)]
+    pub fs: Option<FsPermissions>,
+
+    /// Environment variable access
+    #[serde(default)]
+    pub env: Option<EnvPermissions>,
+
+    /// Subprocess spawning
+    #[serde(default)]
+    pub subprocess: Option<SubprocessPermissions>,
+}
+
+impl Permissions {
+    /// Check whether every grant here is also granted by `other`.
+    ///
+    /// Comparison is by pattern: `*.acme.dev` is covered by `*` and
+    /// `$WORKSPACE/build/**` by `$WORKSPACE/**`, and read access is covered
+    /// by write access. Entries that fail to parse are only covered by an
+    /// identical entry.
+    pub fn is_subset_of(&self, other: &Permissions) -> bool {
+        let [network, read, write, env, subprocess] = self.grant_lists();
+        let [o_network, o_read, o_write, o_env, o_subprocess] = other.grant_lists();
+        let covered = |mine: &[String], theirs: &[&[String]], covers: fn(&str, &str) -> bool| {
+            mine.iter()
+                .all(|m| theirs.iter().flat_map(|t| t.iter()).any(|o| covers(o, m)))
+        };
+
+        covered(network, &[o_network], endpoint_covers)
+            && covered(read, &[o_read, o_write], fs_covers)
+            && covered(write, &[o_write], fs_covers)
+            && covered(env, &[o_env], name_covers)
+            && covered(subprocess, &[o_subprocess], name_covers)
+    }
+
+    /// Network, fs read, fs write, env and subprocess entries.
+    fn grant_lists(&self) -> [&[String]; 5] {
+        [
+            self.network.as_ref().map_or(&[], |n| &n.allow),
+            self.fs.as_ref().map_or(&[], |f| &f.read),
+            self.fs.as_ref().map_or(&[], |f| &f.write),
+            self.env.as_ref().map_or(&[], |e| &e.allow),
+            self.subprocess.as_ref().map_or(&[], |s| &s.allow),
+        ]
+    }
+
+    /// Describe grants that use a catch-all wildcard (e.g., any host).
+    pub fn wildcards(&self) -> Vec<String> {
+        let mut wildcards = Vec::new();
+        if let Some(network) = &self.network {
+            for entry in &network.allow {
+                if let Ok(pattern) = EndpointPattern::parse(entry) {
+                    if pattern.host == HostPattern::Any {
+                        wildcards.push(format!("network: {entry}"));
+                    }
+                }
+            }
+        }
+        if let Some(fs) = &self.fs {
+            for pattern in fs.read.iter().chain(&fs.write) {
+                if let Ok(compiled) = FsPattern::parse(pattern) {
+                    if compiled.root == FsRoot::Home && compiled.segments == ["**"] {
+                        wildcards.push(format!("fs: {pattern}"));
+                    }
+                }
+            }
+        }
+        if let Some(env) = &self.env {
+            if env.allow.iter().any(|v| v == "*") {
+                wildcards.push("env: *".to_string());
+            }
+        }
+        if let Some(subprocess) = &self.subprocess {
+            if subprocess.allow.iter().any(|c| c == "*") {
+                wildcards.push("subprocess: *".to_string());
+            }
+        }
+        wildcards
+    }
+}
+
+/// Network endpoint allow-list.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct NetworkPermissions {
+    /// Allowed endpoints as `host[:port]`, where host may be `*` or
+    /// `*.domain` and port may be a range (`8000-8100`) or `*`
+    #[serde(default)]
+    pub allow: Vec<String>,
+}
+
+impl NetworkPermissions {
+    /// Parse all allow-list entries.
+    pub fn patterns(&self) -> Result<Vec<EndpointPattern>, String> {
+        self.allow
+            .iter()
+            .map(|e| EndpointPattern::parse(e))
+            .collect()
+    }
+
+    /// Check whether a connection to `host:port` is allowed.
+    ///
+    /// Entries that fail to parse never match.
+    pub fn is_endpoint_allowed(&self, host: &str, port: u16) -> bool {
+        self.allow
+            .iter()
+            .filter_map(|e| EndpointPattern::parse(e).ok())
+            .any(|p| p.matches(host, port))
+    }
+}
+
+/// Host part of an endpoint pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HostPattern {
+    /// `*`: any host
+    Any,
+    /// Exact host name or IP address (lowercase)
+    Exact(String),
+    /// `*.domain`: any subdomain of the domain (not the domain itself)
+    Subdomains(String),
+}
+
+impl HostPattern {
+    /// Check whether a host matches.
+    pub fn matches(&self, host: &str) -> bool {
+        let host = normalize_host(host);
+        match self {
+            HostPattern::Any => true,
+            HostPattern::Exact(h) => *h == host,
+            HostPattern::Subdomains(domain) => host
+                .strip_suffix(domain.as_str())
+                .is_some_and(|prefix| prefix.len() > 1 && prefix.ends_with('.')),
+        }
+    }
+}
+
+/// A parsed allow-list entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EndpointPattern {
+    /// Host pattern
+    pub host: HostPattern,
+
+    /// Allowed port range, inclusive (None = any port)
+    pub ports: Option<(u16, u16)>,
+}
+
+impl EndpointPattern {
+    /// Parse a `host[:port]` entry.
+    pub fn parse(entry: &str) -> Result<Self, String> {
+        let entry = entry.trim();
+        let (host, port) = if let Some(rest) = entry.strip_prefix('[') {
+            // IPv6: [addr]:port
+            let (addr, rest) = rest
+                .split_once(']')
+                .ok_or_else(|| format!("'{entry}' has an unterminated IPv6 address"))?;
+            let port = match rest {
+                "" => None,
+                _ => Some(rest.strip_prefix(':').ok_or_else(|| {
+                    format!("'{entry}' has unexpected characters after the address")
+                })?),
+            };
+            (addr, port)
+        } else {
+            match entry.rsplit_once(':') {
+                Some((host, port)) => (host, Some(port)),
+                None => (entry, None),
+            }
+        };
+
+        let host = normalize_host(host);
+        let host = if host == "*" {
+            HostPattern::Any
+        } else if let Some(domain) = host.strip_prefix("*.") {
+            if domain.is_empty() || domain.contains('*') {
+                return Err(format!("'{entry}' has an invalid wildcard"));
+            }
+            HostPattern::Subdomains(domain.to_string())
+        } else if host.is_empty() || host.contains('*') {
+            return Err(format!("'{entry}' has an invalid host"));
+        } else {
+            HostPattern::Exact(host)
+        };
+
+        let ports = match port {
+            None | Some("*") => None,
+            Some(port) => {
+                let parse = |p: &str| {
+                    p.parse::<u16>()
+                        .map_err(|_| format!("'{entry}' has an invalid port '{p}'"))
+                };
+                let (lo, hi) = match port.split_once('-') {
+                    Some((lo, hi)) => (parse(lo)?, parse(hi)?),
+                    None => (parse(port)?, parse(port)?),
+                };
+                if lo > hi {
+                    return Err(format!("'{entry}' has an empty port range"));
+                }
+                Some((lo, hi))
+            }
+        };
+
+        Ok(Self { host, ports })
+    }
+
+    /// Check whether `host:port` matches.
+    pub fn matches(&self, host: &str, port: u16) -> bool {
+        self.host.matches(host) && self.ports.is_none_or(|(lo, hi)| (lo..=hi).contains(&port))
+    }
+}
+
+/// Filesystem path patterns.
+///
+/// Patterns start with a well-known root (e.g., `$WORKSPACE/build/**`).
+/// `*` and `?` match within a path segment, `**` matches any number of
+/// segments. Write access implies read access.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct FsPermissions {
+    /// Readable path patterns
+    #[serde(default)]
+    pub read: Vec<String>,
+
+    /// Writable path patterns
+    #[serde(default)]
+    pub write: Vec<String>,
+}
+
+impl FsPermissions {
+    /// Compile the patterns into a matcher.
+    pub fn compile(&self) -> Result<FsMatcher, ManifestError> {
+        let parse = |patterns: &[String]| {
+            patterns
+                .iter()
+                .map(|p| FsPattern::parse(p).map_err(ManifestError::InvalidFormat))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        Ok(FsMatcher {
+            read: parse(&self.read)?,
+            write: parse(&self.write)?,
+        })
+    }
+}
+
+/// Environment variable allow-list.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EnvPermissions {
+    /// Readable variable names; a trailing `*` matches a prefix
+    /// (e.g., "ACME_*"), and `*` alone matches every variable
+    #[serde(default)]
+    pub allow: Vec<String>,
+}
+
+impl EnvPermissions {
+    /// Check whether a variable may be read.
+    pub fn is_allowed(&self, name: &str) -> bool {
+        self.allow.iter().any(|p| name_matches(p, name))
+    }
+}
+
+/// Subprocess allow-list.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SubprocessPermissions {
+    /// Executables that may be spawned, by name (e.g., "git"); `*` allows
+    /// any executable
+    #[serde(default)]
+    pub allow: Vec<String>,
+}
+
+impl SubprocessPermissions {
+    /// Check whether a command may be spawned.
+    ///
+    /// Only the file name of `command` is compared, so "/usr/bin/git"
+    /// matches "git".
+    pub fn is_allowed(&self, command: &str) -> bool {
+        let name = command.rsplit(['/', '\\']).next().unwrap_or(command);
+        let name = name.strip_suffix(".exe").unwrap_or(name);
+        self.allow.iter().any(|p| name_matches(p, name))
+    }
+}
+
+/// Well-known filesystem roots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FsRoot {
+    /// The workspace the host has open
+    Workspace,
+    /// The user's home directory
+    Home,
+    /// The plugin's install directory
+    Plugin,
+    /// The plugin's data directory
+    Data,
+    /// The plugin's cache directory
+    Cache,
+    /// The plugin's state directory
+    State,
+    /// The system temporary directory
+    Temp,
+}
+
+impl FsRoot {
+    /// All roots.
+    pub const ALL: [FsRoot; 7] = [
+        FsRoot::Workspace,
+        FsRoot::Home,
+        FsRoot::Plugin,
+        FsRoot::Data,
+        FsRoot::Cache,
+        FsRoot::State,
+        FsRoot::Temp,
+    ];
+
+    /// Variable name used in patterns (e.g., `$WORKSPACE`).
+    pub fn variable(&self) -> &'static str {
+        match self {
+            FsRoot::Workspace => "$WORKSPACE",
+            FsRoot::Home => "$HOME",
+            FsRoot::Plugin => "$PLUGIN",
+            FsRoot::Data => "$DATA",
+            FsRoot::Cache => "$CACHE",
+            FsRoot::State => "$STATE",
+            FsRoot::Temp => "$TEMP",
+        }
+    }
+
+    /// Parse a root variable.
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|r| r.variable() == s)
+    }
+}
+
+impl fmt::Display for FsRoot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.variable())
+    }
+}
+
+/// Kind of filesystem access.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FsAccess {
+    /// Read files and list directories
+    Read,
+    /// Create, modify and delete files
+    Write,
+}
+
+/// Maximum number of `*` and `?` characters in a filesystem path pattern.
+pub const MAX_FS_WILDCARDS: usize = 16;
+
+/// A parsed filesystem path pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FsPattern {
+    /// Root the pattern is relative to
+    pub root: FsRoot,
+
+    /// Path segments below the root (may contain wildcards)
+    pub segments: Vec<String>,
+}
+
+impl FsPattern {
+    /// Parse a `$ROOT/path/pattern` string.
+    pub fn parse(pattern: &str) -> Result<Self, String> {
+        let (root, segments) = split_path(pattern)
+            .ok_or_else(|| format!("Path pattern '{pattern}' must be inside a plugin root"))?;
+        let root = FsRoot::parse(root)
+            .ok_or_else(|| format!("Path pattern '{pattern}' has an unknown root '{root}'"))?;
+        if segments.iter().any(|s| s.contains("**") && *s != "**") {
+            return Err(format!(
+                "Path pattern '{pattern}' uses '**' inside a segment"
+            ));
+        }
+        let wildcards = segments
+            .iter()
+            .map(|s| s.matches(['*', '?']).count())
+            .sum::<usize>();
+        if wildcards > MAX_FS_WILDCARDS {
+            return Err(format!(
+                "Path pattern '{pattern}' has {wildcards} wildcards, limit is {MAX_FS_WILDCARDS}"
+            ));
+        }
+
+        Ok(Self {
+            root,
+            segments: segments.into_iter().map(String::from).collect(),
+        })
+    }
+
+    /// Check whether a path (relative to the pattern's root) matches.
+    pub fn matches(&self, root: FsRoot, relative: &[&str]) -> bool {
+        root == self.root && match_segments(&self.segments, relative)
+    }
+}
+
+/// Compiled filesystem permissions.
+#[derive(Debug, Clone, Default)]
+pub struct FsMatcher {
+    read: Vec<FsPattern>,
+    write: Vec<FsPattern>,
+}
+
+impl FsMatcher {
+    /// Check access to a path such as `$WORKSPACE/build/out.txt`.
+    ///
+    /// Paths outside the known roots or containing `..` are denied.
+    pub fn is_allowed(&self, access: FsAccess, path: &str) -> bool {
+        let Some((root, segments)) = split_path(path) else {
+            return false;
+        };
+        match FsRoot::parse(root) {
+            Some(root) => self.is_allowed_in(access, root, &segments),
+            None => false,
+        }
+    }
+
+    /// Check access to a path given as root and relative segments.
+    pub fn is_allowed_in(&self, access: FsAccess, root: FsRoot, relative: &[&str]) -> bool {
+        let write = self.write.iter().any(|p| p.matches(root, relative));
+        match access {
+            FsAccess::Write => write,
+            FsAccess::Read => write || self.read.iter().any(|p| p.matches(root, relative)),
+        }
+    }
+}
+
+/// Split `$ROOT/a/b` into the root and its normalized segments.
+///
+/// Returns None if the path escapes its root.
+fn split_path(path: &str) -> Option<(&str, Vec<&str>)> {
+    let mut parts = path.trim().split(['/', '\\']);
+    let root = parts.next()?;
+    let segments: Vec<&str> = parts.filter(|s| !s.is_empty() && *s != ".").collect();
+    if segments.contains(&"..") {
+        return None;
+    }
+    Some((root, segments))
+}
+
+fn match_segments(pattern: &[String], path: &[&str]) -> bool {
+    let mut memo = vec![None; (pattern.len() + 1) * (path.len() + 1)];
+    match_segments_at(pattern, path, 0, 0, &mut memo)
+}
+
+/// Match `pattern[p..]` against `path[s..]`, memoizing by `(p, s)` so
+/// several `**` segments don't retry the same split.
+fn match_segments_at(
+    pattern: &[String],
+    path: &[&str],
+    p: usize,
+    s: usize,
+    memo: &mut [Option<bool>],
+) -> bool {
+    let index = p * (path.len() + 1) + s;
+    if let Some(matched) = memo[index] {
+        return matched;
+    }
+    let matched = match pattern.get(p) {
+        None => s == path.len(),
+        Some(first) if first == "**" => {
+            (s..=path.len()).any(|skip| match_segments_at(pattern, path, p + 1, skip, memo))
+        }
+        Some(first) => {
+            s < path.len()
+                && match_segment(first.as_bytes(), path[s].as_bytes())
+                && match_segments_at(pattern, path, p + 1, s + 1, memo)
+        }
+    };
+    memo[index] = Some(matched);
+    matched
+}
+
+/// Match a single segment against a pattern with `*` and `?`.
+///
+/// On a mismatch only the last `*` absorbs one more byte, which keeps the
+/// match linear in the pattern and segment lengths.
+fn match_segment(pattern: &[u8], segment: &[u8]) -> bool {
+    let (mut p, mut s) = (0, 0);
+    // Position of the last `*` and of the segment byte it matched up to
+    let mut star = None;
+    while s < segment.len() {
+        match pattern.get(p) {
+            Some(b'*') => {
+                star = Some((p, s));
+                p += 1;
+            }
+            Some(&c) if c == b'?' || c == segment[s] => {
+                p += 1;
+                s += 1;
+            }
+            _ => match star {
+                Some((star_p, star_s)) => {
+                    star = Some((star_p, star_s + 1));
+                    p = star_p + 1;
+                    s = star_s + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == b'*')
+}
+
+fn normalize_host(host: &str) -> String {
+    host.trim().trim_end_matches('.').to_ascii_lowercase()
+}
+
+/// Match a name against `*`, `PREFIX*` or an exact name.
+fn name_matches(pattern: &str, name: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => name.starts_with(prefix),
+        None => pattern == name,
+    }
+}
+
+/// Check that a name pattern is `*`, `PREFIX*` or a plain name.
+fn check_name_pattern(pattern: &str) -> Result<(), String> {
+    let name = pattern.strip_suffix('*').unwrap_or(pattern);
+    if pattern.is_empty() || name.contains('*') || name.contains(char::is_whitespace) {
+        return Err(format!(
+            "'{pattern}' must be a name, optionally ending in '*'"
+        ));
+    }
+    Ok(())
+}
+
+/// Whether name pattern `outer` grants everything `inner` does.
+fn name_covers(outer: &str, inner: &str) -> bool {
+    match outer.strip_suffix('*') {
+        Some(prefix) => inner.starts_with(prefix),
+        None => outer == inner,
+    }
+}
+
+/// Whether endpoint entry `outer` grants everything `inner` does.
+fn endpoint_covers(outer: &str, inner: &str) -> bool {
+    let (Ok(o), Ok(i)) = (EndpointPattern::parse(outer), EndpointPattern::parse(inner)) else {
+        return outer == inner;
+    };
+    let host = match (&o.host, &i.host) {
+        (HostPattern::Any, _) => true,
+        (_, HostPattern::Any) => false,
+        (HostPattern::Exact(o), HostPattern::Exact(i)) => o == i,
+        (HostPattern::Subdomains(_), HostPattern::Exact(i)) => o.host.matches(i),
+        (HostPattern::Subdomains(o), HostPattern::Subdomains(i)) => {
+            i == o || i.strip_suffix(o.as_str()).is_some_and(|p| p.ends_with('.'))
+        }
+        (HostPattern::Exact(_), HostPattern::Subdomains(_)) => false,
+    };
+    let ports = match (o.ports, i.ports) {
+        (None, _) => true,
+        (Some(_), None) => false,
+        (Some((olo, ohi)), Some((ilo, ihi))) => olo <= ilo && ihi <= ohi,
+    };
+    host && ports
+}
+
+/// Whether filesystem pattern `outer` grants everything `inner` does.
+fn fs_covers(outer: &str, inner: &str) -> bool {
+    let (Ok(o), Ok(i)) = (FsPattern::parse(outer), FsPattern::parse(inner)) else {
+        return outer == inner;
+    };
+    o.root == i.root && segments_cover(&o.segments, &i.segments)
+}
+
+/// Like `match_segments`, but the inner side may contain wildcards, which
+/// are only covered by equal or broader wildcards.
+fn segments_cover(outer: &[String], inner: &[String]) -> bool {
+    let mut memo = vec![None; (outer.len() + 1) * (inner.len() + 1)];
+    segments_cover_at(outer, inner, 0, 0, &mut memo)
+}
+
+/// Whether `outer[o..]` covers `inner[i..]`, memoized by `(o, i)` like
+/// `match_segments_at`.
+fn segments_cover_at(
+    outer: &[String],
+    inner: &[String],
+    o: usize,
+    i: usize,
+    memo: &mut [Option<bool>],
+) -> bool {
+    let index = o * (inner.len() + 1) + i;
+    if let Some(covered) = memo[index] {
+        return covered;
+    }
+    let covered = match outer.get(o) {
+        None => i == inner.len(),
+        Some(first) if first == "**" => {
+            (i..=inner.len()).any(|skip| segments_cover_at(outer, inner, o + 1, skip, memo))
+        }
+        Some(first) => match inner.get(i) {
+            Some(segment) if segment != "**" => {
+                let covered = if segment.contains(['*', '?']) {
+                    first == segment || first == "*"
+                } else {
+                    match_segment(first.as_bytes(), segment.as_bytes())
+                };
+                covered && segments_cover_at(outer, inner, o + 1, i + 1, memo)
+            }
+            _ => false,
+        },
+    };
+    memo[index] = Some(covered);
+    covered
+}
+
+impl PluginManifest {
+    /// Check whether the plugin may connect to `host:port`.
+    ///
+    /// Plugins without `[permissions.network]` have no network access.
+    pub fn is_endpoint_allowed(&self, host: &str, port: u16) -> bool {
+        self.permissions
+            .as_ref()
+            .and_then(|p| p.network.as_ref())
+            .is_some_and(|n| n.is_endpoint_allowed(host, port))
+    }
+
+    /// Check whether the plugin may read an environment variable.
+    ///
+    /// Plugins without `[permissions.env]` can't read any.
+    pub fn is_env_allowed(&self, name: &str) -> bool {
+        self.permissions
+            .as_ref()
+            .and_then(|p| p.env.as_ref())
+            .is_some_and(|e| e.is_allowed(name))
+    }
+
+    /// Check whether the plugin may spawn a command.
+    ///
+    /// Plugins without `[permissions.subprocess]` can't spawn processes.
+    pub fn is_command_allowed(&self, command: &str) -> bool {
+        self.permissions
+            .as_ref()
+            .and_then(|p| p.subprocess.as_ref())
+            .is_some_and(|s| s.is_allowed(command))
+    }
+
+    /// Compile the plugin's filesystem permissions.
+    ///
+    /// Plugins without `[permissions.fs]` get a matcher that denies everything.
+    pub fn fs_matcher(&self) -> Result<FsMatcher, ManifestError> {
+        match self.permissions.as_ref().and_then(|p| p.fs.as_ref()) {
+            Some(fs) => fs.compile(),
+            None => Ok(FsMatcher::default()),
+        }
+    }
+
+    /// Check permission declarations.
+    pub(crate) fn check_permissions(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        let Some(permissions) = &self.permissions else {
+            return issues;
+        };
+
+        if let Some(network) = &permissions.network {
+            for (i, entry) in network.allow.iter().enumerate() {
+                if let Err(e) = EndpointPattern::parse(entry) {
+                    issues.push(ValidationIssue::error(
+                        format!("permissions.network.allow[{i}]"),
+                        e,
+                    ));
+                }
+            }
+        }
+
+        if let Some(fs) = &permissions.fs {
+            for (kind, patterns) in [("read", &fs.read), ("write", &fs.write)] {
+                for (i, pattern) in patterns.iter().enumerate() {
+                    if let Err(e) = FsPattern::parse(pattern) {
+                        issues.push(ValidationIssue::error(
+                            format!("permissions.fs.{kind}[{i}]"),
+                            e,
+                        ));
+                    }
+                }
+            }
+        }
+
+        let names = [
+            ("env", permissions.env.as_ref().map(|e| &e.allow)),
+            (
+                "subprocess",
+                permissions.subprocess.as_ref().map(|s| &s.allow),
+            ),
+        ];
+        for (kind, allow) in names {
+            for (i, pattern) in allow.into_iter().flatten().enumerate() {
+                if let Err(e) = check_name_pattern(pattern) {
+                    issues.push(ValidationIssue::error(
+                        format!("permissions.{kind}.allow[{i}]"),
+                        e,
+                    ));
+                }
+            }
+        }
+
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate::has_errors;
+
+    #[test]
+    fn test_endpoint_patterns() {
+        let network = NetworkPermissions {
+            allow: vec![
+                "api.acme.dev:443".into(),
+                "*.cdn.acme.dev".into(),
+                "localhost:8000-8100".into(),
+                "[::1]:9000".into(),
+            ],
+        };
+
+        assert!(network.is_endpoint_allowed("api.acme.dev", 443));
+        assert!(network.is_endpoint_allowed("API.acme.dev.", 443));
+        assert!(!network.is_endpoint_allowed("api.acme.dev", 80));
+        assert!(network.is_endpoint_allowed("eu.cdn.acme.dev", 80));
+        assert!(!network.is_endpoint_allowed("cdn.acme.dev", 80));
+        assert!(!network.is_endpoint_allowed("evilcdn.acme.dev", 80));
+        assert!(network.is_endpoint_allowed("localhost", 8050));
+        assert!(!network.is_endpoint_allowed("localhost", 8101));
+        assert!(network.is_endpoint_allowed("::1", 9000));
+    }
+
+    #[test]
+    fn test_invalid_patterns() {
+        for entry in ["", "host:http", "host:90-80", "a.*.com", "*.*.com", "[::1"] {
+            assert!(EndpointPattern::parse(entry).is_err(), "accepted: {entry}");
+        }
+    }
+
+    #[test]
+    fn test_manifest_permissions() {
+        let toml = r#"
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.0.0"
+type = "extension"
+
+[permissions.network]
+allow = ["api.acme.dev:443", "*:70000"]
+"#;
+        let manifest = PluginManifest::from_toml(toml).unwrap();
+        assert!(manifest.is_endpoint_allowed("api.acme.dev", 443));
+        assert!(!manifest.is_endpoint_allowed("example.com", 443));
+        assert!(has_errors(&manifest.validate()));
+
+        let mut bare = manifest.clone();
+        bare.permissions = None;
+        assert!(!bare.is_endpoint_allowed("api.acme.dev", 443));
+    }
+
+    #[test]
+    fn test_fs_matcher() {
+        let fs = FsPermissions {
+            read: vec!["$WORKSPACE/**".into(), "$HOME/.config/acme/*.toml".into()],
+            write: vec!["$WORKSPACE/build/**".into()],
+        };
+        let matcher = fs.compile().unwrap();
+
+        assert!(matcher.is_allowed(FsAccess::Write, "$WORKSPACE/build"));
+        assert!(matcher.is_allowed(FsAccess::Write, "$WORKSPACE/build/a/b.o"));
+        assert!(!matcher.is_allowed(FsAccess::Write, "$WORKSPACE/src/main.rs"));
+        assert!(matcher.is_allowed(FsAccess::Read, "$WORKSPACE/src/main.rs"));
+        assert!(matcher.is_allowed(FsAccess::Read, "$HOME/.config/acme/tool.toml"));
+        assert!(!matcher.is_allowed(FsAccess::Read, "$HOME/.config/acme/sub/tool.toml"));
+        assert!(!matcher.is_allowed(FsAccess::Read, "$HOME/.ssh/id_rsa"));
+        assert!(!matcher.is_allowed(FsAccess::Write, "$WORKSPACE/build/../src"));
+        assert!(!matcher.is_allowed(FsAccess::Read, "/etc/passwd"));
+    }
+
+    #[test]
+    fn test_invalid_fs_patterns() {
+        let too_wild = format!("$WORKSPACE/{}", "*".repeat(MAX_FS_WILDCARDS + 1));
+        let invalid = [
+            "/etc/**",
+            "$NOPE/x",
+            "$WORKSPACE/../x",
+            "$WORKSPACE/a**",
+            too_wild.as_str(),
+        ];
+        for pattern in invalid {
+            assert!(FsPattern::parse(pattern).is_err(), "accepted: {pattern}");
+        }
+    }
+
+    #[test]
+    fn test_fs_pattern_matching_is_not_exponential() {
+        let pattern = FsPattern::parse(&format!("$WORKSPACE/{}b", "*a".repeat(12))).unwrap();
+        let segment = "a".repeat(40);
+        assert!(!pattern.matches(FsRoot::Workspace, &[&segment]));
+        let segment = format!("{segment}b");
+        assert!(pattern.matches(FsRoot::Workspace, &[&segment]));
+
+        let pattern = FsPattern::parse("$WORKSPACE/**/**/**/**/**/**/**/**/x").unwrap();
+        let path = vec!["a"; 40];
+        assert!(!pattern.matches(FsRoot::Workspace, &path));
+
+        let started = std::time::Instant::now();
+        let outer = format!("$WORKSPACE/{}x", "**/".repeat(MAX_FS_WILDCARDS / 2));
+        let inner = format!("$WORKSPACE/{}", vec!["a"; 40].join("/"));
+        assert!(FsPattern::parse(&outer).is_ok());
+        assert!(!fs_covers(&outer, &inner));
+        assert!(fs_covers(&outer, &format!("{inner}/x")));
+        assert!(started.elapsed() < std::time::Duration::from_secs(1));
+
+        assert!(match_segment(b"*.t?ml", b"tool.toml"));
+        assert!(match_segment(b"a*b*c", b"aXbYbc"));
+        assert!(!match_segment(b"a*b?c", b"abc"));
+    }
+
+    #[test]
+    fn test_env_and_subprocess() {
+        let manifest = PluginManifest::from_toml(
+            r#"
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.0.0"
+type = "extension"
+
+[permissions.env]
+allow = ["ACME_*", "HOME"]
+
+[permissions.subprocess]
+allow = ["git", "bad pattern*x"]
+"#,
+        )
+        .unwrap();
+        assert!(manifest.is_env_allowed("ACME_TOKEN"));
+        assert!(!manifest.is_env_allowed("PATH"));
+        assert!(manifest.is_command_allowed("/usr/bin/git"));
+        assert!(manifest.is_command_allowed("git.exe"));
+        assert!(!manifest.is_command_allowed("docker"));
+        let issues = manifest.validate();
+        assert!(issues
+            .iter()
+            .any(|i| i.path == "permissions.subprocess.allow[1]"));
+    }
+
+    #[test]
+    fn test_is_subset_of() {
+        let broad = Permissions {
+            network: Some(NetworkPermissions {
+                allow: vec!["*.acme.dev".into(), "localhost:8000-8100".into()],
+            }),
+            fs: Some(FsPermissions {
+                read: vec![],
+                write: vec!["$WORKSPACE/**".into()],
+            }),
+            env: Some(EnvPermissions {
+                allow: vec!["ACME_*".into()],
+            }),
+            subprocess: None,
+        };
+        let narrow = Permissions {
+            network: Some(NetworkPermissions {
+                allow: vec!["api.acme.dev:443".into(), "localhost:8050".into()],
+            }),
+            fs: Some(FsPermissions {
+                read: vec!["$WORKSPACE/src/*.rs".into()],
+                write: vec!["$WORKSPACE/build/**".into()],
+            }),
+            env: Some(EnvPermissions {
+                allow: vec!["ACME_TOKEN".into()],
+            }),
+            subprocess: None,
+        };
+
+        assert!(narrow.is_subset_of(&broad));
+        assert!(!broad.is_subset_of(&narrow));
+        assert!(Permissions::default().is_subset_of(&narrow));
+
+        let mut spawns = narrow.clone();
+        spawns.subprocess = Some(SubprocessPermissions {
+            allow: vec!["git".into()],
+        });
+        assert!(!spawns.is_subset_of(&broad));
+    }
+}
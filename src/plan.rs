@@ -0,0 +1,246 @@
+//! Owned install plans and subsets of packages.
+//!
+//! Unlike `PackageManifest::install_order()`, a plan owns its entries and
+//! records why each plugin is being installed.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::error::ManifestError;
+use crate::package::{PackageManifest, PluginDef};
+
+/// A single plugin in an install plan.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct InstallStep {
+    /// Plugin definition
+    pub plugin: PluginDef,
+
+    /// Whether the plugin was explicitly requested
+    pub requested: bool,
+
+    /// Plugins in the plan that depend on this one
+    pub required_by: Vec<String>,
+}
+
+impl fmt::Display for InstallStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.plugin.id)?;
+        match (self.requested, self.required_by.is_empty()) {
+            (true, true) => write!(f, " (requested)"),
+            (true, false) => write!(
+                f,
+                " (requested, required by {})",
+                self.required_by.join(", ")
+            ),
+            (false, _) => write!(f, " (required by {})", self.required_by.join(", ")),
+        }
+    }
+}
+
+impl PackageManifest {
+    /// Build an install plan for the requested plugins and their dependencies.
+    ///
+    /// Steps follow `install_order()`; only the plugins in the plan are
+    /// ordered, so a dependency cycle elsewhere in the package doesn't
+    /// matter. An empty `requested` list gives an empty plan.
+    pub fn install_plan(&self, requested: &[&str]) -> Result<Vec<InstallStep>, ManifestError> {
+        self.plan(requested.iter().copied().collect())
+    }
+
+    /// Build an install plan for every plugin in the package.
+    ///
+    /// Every plugin counts as requested, so every step has `requested` set;
+    /// `required_by` still lists the plugins in the package that depend on it.
+    pub fn install_plan_all(&self) -> Result<Vec<InstallStep>, ManifestError> {
+        self.plan(self.plugins.iter().map(|p| p.id.as_str()).collect())
+    }
+
+    fn plan(&self, requested: HashSet<&str>) -> Result<Vec<InstallStep>, ManifestError> {
+        let included = self.dependency_closure(&requested)?;
+
+        Ok(self
+            .install_order_of(|p| included.contains(p.id.as_str()))?
+            .into_iter()
+            .map(|plugin| InstallStep {
+                plugin: plugin.clone(),
+                requested: requested.contains(plugin.id.as_str()),
+                required_by: self
+                    .plugins
+                    .iter()
+                    .filter(|p| {
+                        included.contains(p.id.as_str()) && p.depends_on.contains(&plugin.id)
+                    })
+                    .map(|p| p.id.clone())
+                    .collect(),
+            })
+            .collect())
+    }
+
+    /// Build a new package containing only the selected plugins and their
+    /// transitive in-package dependencies, in declaration order.
+    ///
+    /// Fails if a selected plugin depends on one that isn't in the package.
+    /// Archive checksums, sizes and the signature describe the full package,
+    /// so they are dropped from the subset.
+    pub fn subset(&self, ids: &[&str]) -> Result<PackageManifest, ManifestError> {
+        let requested: HashSet<&str> = ids.iter().copied().collect();
+        let included = self.dependency_closure(&requested)?;
+        for plugin in self
+            .plugins
+            .iter()
+            .filter(|p| included.contains(p.id.as_str()))
+        {
+            if let Some(dep) = plugin
+                .depends_on
+                .iter()
+                .find(|dep| !self.plugins.iter().any(|p| p.id == **dep))
+            {
+                return Err(ManifestError::InvalidFormat(format!(
+                    "Plugin '{}' depends on '{dep}', which is not in the package",
+                    plugin.id
+                )));
+            }
+        }
+
+        let mut subset = self.clone();
+        subset.plugins.retain(|p| included.contains(p.id.as_str()));
+        subset.binary = Default::default();
+        subset.signature = None;
+        Ok(subset)
+    }
+
+    /// Close a set of plugin IDs over in-package dependencies.
+    fn dependency_closure<'a>(
+        &'a self,
+        requested: &HashSet<&'a str>,
+    ) -> Result<HashSet<&'a str>, ManifestError> {
+        if let Some(unknown) = requested
+            .iter()
+            .find(|id| !self.plugins.iter().any(|p| p.id == **id))
+        {
+            return Err(ManifestError::UnknownPlugin(unknown.to_string()));
+        }
+
+        let mut included = HashSet::new();
+        let mut pending: Vec<&str> = requested.iter().copied().collect();
+        while let Some(id) = pending.pop() {
+            if !included.insert(id) {
+                continue;
+            }
+            if let Some(plugin) = self.plugins.iter().find(|p| p.id == id) {
+                pending.extend(plugin.depends_on.iter().map(String::as_str));
+            }
+        }
+        Ok(included)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PACKAGE: &str = r#"
+[package]
+id = "vendor.pack"
+name = "Test Pack"
+version = "1.0.0"
+
+[[plugins]]
+id = "vendor.ui"
+name = "UI"
+type = "extension"
+binary = "ui"
+depends_on = ["vendor.core"]
+
+[[plugins]]
+id = "vendor.core"
+name = "Core"
+type = "extension"
+binary = "core"
+
+[[plugins]]
+id = "vendor.sync"
+name = "Sync"
+type = "extension"
+binary = "sync"
+depends_on = ["vendor.core"]
+"#;
+
+    #[test]
+    fn test_install_plan() {
+        let manifest = PackageManifest::from_toml(PACKAGE).unwrap();
+        let plan = manifest.install_plan(&["vendor.ui"]).unwrap();
+
+        assert_eq!(plan.len(), 2);
+        assert_eq!(plan[0].plugin.id, "vendor.core");
+        assert!(!plan[0].requested);
+        assert_eq!(plan[0].required_by, ["vendor.ui"]);
+        assert_eq!(plan[0].to_string(), "vendor.core (required by vendor.ui)");
+        assert_eq!(plan[1].to_string(), "vendor.ui (requested)");
+
+        assert!(manifest.install_plan(&[]).unwrap().is_empty());
+        let full = manifest.install_plan_all().unwrap();
+        assert_eq!(full.len(), 3);
+        assert!(full.iter().all(|s| s.requested));
+        assert_eq!(full[0].required_by, ["vendor.ui", "vendor.sync"]);
+    }
+
+    #[test]
+    fn test_install_plan_ignores_unrelated_cycles() {
+        let cyclic = format!(
+            "{PACKAGE}
+[[plugins]]
+id = \"vendor.a\"
+name = \"A\"
+type = \"extension\"
+binary = \"a\"
+depends_on = [\"vendor.b\"]
+
+[[plugins]]
+id = \"vendor.b\"
+name = \"B\"
+type = \"extension\"
+binary = \"b\"
+depends_on = [\"vendor.a\"]
+"
+        );
+        let manifest = PackageManifest::from_toml(&cyclic).unwrap();
+        assert!(manifest.install_order().is_err());
+
+        let plan = manifest.install_plan(&["vendor.ui"]).unwrap();
+        let ids: Vec<&str> = plan.iter().map(|s| s.plugin.id.as_str()).collect();
+        assert_eq!(ids, ["vendor.core", "vendor.ui"]);
+        assert!(matches!(
+            manifest.install_plan(&["vendor.a"]),
+            Err(ManifestError::CircularDependency(_))
+        ));
+    }
+
+    #[test]
+    fn test_subset() {
+        let manifest = PackageManifest::from_toml(PACKAGE).unwrap();
+        let subset = manifest.subset(&["vendor.sync"]).unwrap();
+
+        let ids: Vec<&str> = subset.plugins.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, ["vendor.core", "vendor.sync"]);
+        assert!(subset.validate().is_empty());
+        assert!(PackageManifest::from_toml(&subset.to_toml().unwrap()).is_ok());
+
+        let broken = PACKAGE.replace(
+            "binary = \"sync\"\ndepends_on = [\"vendor.core\"]",
+            "binary = \"sync\"\ndepends_on = [\"vendor.gone\"]",
+        );
+        let manifest = PackageManifest::from_toml(&broken).unwrap();
+        assert!(manifest.subset(&["vendor.sync"]).is_err());
+        assert!(manifest.subset(&["vendor.ui"]).is_ok());
+    }
+
+    #[test]
+    fn test_unknown_plugin() {
+        let manifest = PackageManifest::from_toml(PACKAGE).unwrap();
+        let result = manifest.install_plan(&["vendor.missing"]);
+        assert!(matches!(result, Err(ManifestError::UnknownPlugin(_))));
+    }
+}
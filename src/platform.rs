@@ -1,9 +1,36 @@
 //! Platform detection and binary filename utilities.
+//!
+//! Manifests name platforms as `<os>-<arch>` ("darwin-aarch64"). `Platform`
+//! also understands Rust target triples ("aarch64-apple-darwin"), common
+//! aliases ("macos", "arm64", "amd64") and glob patterns ("linux-*"), and
+//! always serializes to the `<os>-<arch>` form.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::ManifestError;
+
+/// Platform identifier of WASI WebAssembly hosts.
+pub const WASM_WASI: &str = "wasm32-wasi";
+
+/// Platform identifier of WebAssembly hosts without WASI.
+pub const WASM_UNKNOWN: &str = "wasm32-unknown";
 
 /// Get the current platform identifier.
 ///
-/// Returns a string like "darwin-aarch64", "linux-x86_64", etc.
+/// Returns a string like "darwin-aarch64", "linux-x86_64", etc., or
+/// "wasm32-wasi" / "wasm32-unknown" when compiled to WebAssembly.
 pub fn current_platform() -> String {
+    if cfg!(target_arch = "wasm32") {
+        let platform = if cfg!(target_os = "wasi") {
+            WASM_WASI
+        } else {
+            WASM_UNKNOWN
+        };
+        return platform.to_string();
+    }
+
     let os = if cfg!(target_os = "macos") {
         "darwin"
     } else if cfg!(target_os = "linux") {
@@ -29,8 +56,13 @@ pub fn current_platform() -> String {
 
 /// Get the library filename for a given binary name on the current platform.
 ///
-/// Adds the appropriate prefix (lib on Unix) and extension (.dylib, .so, .dll).
+/// Adds the appropriate prefix (lib on Unix) and extension (.dylib, .so, .dll),
+/// or just `.wasm` on WebAssembly.
 pub fn library_filename(name: &str) -> String {
+    if cfg!(target_arch = "wasm32") {
+        return wasm_filename(name);
+    }
+
     let prefix = if cfg!(target_os = "windows") {
         ""
     } else {
@@ -48,10 +80,239 @@ pub fn library_filename(name: &str) -> String {
     format!("{}{}.{}", prefix, name, ext)
 }
 
-/// Check if the current platform matches a platform identifier.
+/// Get the library filename for a given binary name on a specific platform.
+///
+/// Like `library_filename()`, but for a platform identifier such as
+/// "darwin-aarch64" rather than the platform this code runs on.
+pub fn library_filename_for(name: &str, platform: &str) -> String {
+    if is_wasm_platform(platform) {
+        return wasm_filename(name);
+    }
+    let os = platform.split('-').next().unwrap_or(platform);
+    match os {
+        "darwin" => format!("lib{}.dylib", name),
+        "windows" => format!("{}.dll", name),
+        _ => format!("lib{}.so", name),
+    }
+}
+
+/// Get the filename of an executable on a specific platform (`name.exe` on
+/// Windows, `name` elsewhere).
+pub fn executable_filename_for(name: &str, platform: &str) -> String {
+    if platform.starts_with("windows-") {
+        format!("{name}.exe")
+    } else {
+        name.to_string()
+    }
+}
+
+/// Get the filename of a WebAssembly module (`name.wasm`).
+pub fn wasm_filename(name: &str) -> String {
+    format!("{name}.wasm")
+}
+
+/// Check if a platform identifier names a WebAssembly host.
+pub fn is_wasm_platform(platform: &str) -> bool {
+    platform == WASM_WASI || platform == WASM_UNKNOWN
+}
+
+/// Operating systems used in platform identifiers.
+pub const KNOWN_OS: &[&str] = &["darwin", "linux", "windows"];
+
+/// Architectures used in platform identifiers.
+pub const KNOWN_ARCH: &[&str] = &["aarch64", "x86_64", "x86"];
+
+/// Check if a platform identifier is "all", a WebAssembly host, a known
+/// `<os>-<arch>` pair, a target triple for one, or a glob pattern.
+///
+/// Aliases ("macos-arm64") parse but aren't considered known, so validation
+/// nudges manifests towards the canonical spelling.
+pub fn is_known_platform(platform: &str) -> bool {
+    platform == "all"
+        || platform.contains('*')
+        || Platform::parse(platform)
+            .is_ok_and(|p| p.to_string() == platform || p.to_triple() == platform)
+}
+
+/// Check if the current platform matches a platform identifier or pattern.
 pub fn matches_platform(platform: &str) -> bool {
-    let current = current_platform();
-    platform == current || platform == "all"
+    Platform::current().matches(platform)
+}
+
+/// A platform: operating system and architecture.
+///
+/// WebAssembly hosts use "wasm32" as the architecture and "wasi" or
+/// "unknown" as the OS, and display as "wasm32-wasi" / "wasm32-unknown".
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Platform {
+    /// Operating system (e.g., "darwin", "linux", "windows", "wasi")
+    pub os: String,
+
+    /// Architecture (e.g., "aarch64", "x86_64", "x86", "wasm32")
+    pub arch: String,
+}
+
+impl Platform {
+    /// Parse a platform identifier or target triple.
+    ///
+    /// Accepts "darwin-aarch64", "aarch64-apple-darwin",
+    /// "x86_64-unknown-linux-gnu", "x86_64-pc-windows-msvc", "wasm32-wasi",
+    /// "wasm32-unknown-unknown" and aliases such as "macos-arm64".
+    pub fn parse(s: &str) -> Result<Self, ManifestError> {
+        let lower = s.trim().to_ascii_lowercase();
+        let parts: Vec<&str> = lower.split('-').collect();
+        let unknown = || ManifestError::InvalidFormat(format!("Unknown platform '{s}'"));
+
+        if parts[0] == "wasm32" {
+            let os = match parts.get(1).copied() {
+                Some(os) if os.starts_with("wasi") => "wasi",
+                None | Some("unknown") => "unknown",
+                Some(_) => return Err(unknown()),
+            };
+            return Ok(Self::new(os, "wasm32"));
+        }
+
+        if let [os, arch] = parts.as_slice() {
+            if let (Some(os), Some(arch)) = (canonical_os(os), canonical_arch(arch)) {
+                return Ok(Self::new(os, arch));
+            }
+        }
+
+        // Target triple: <arch>-<vendor>-<os>[-<env>]
+        let arch = canonical_arch(parts[0]).ok_or_else(unknown)?;
+        let os = parts[1..]
+            .iter()
+            .find_map(|p| canonical_os(p))
+            .ok_or_else(unknown)?;
+        Ok(Self::new(os, arch))
+    }
+
+    fn new(os: &str, arch: &str) -> Self {
+        Self {
+            os: os.to_string(),
+            arch: arch.to_string(),
+        }
+    }
+
+    /// The platform this code runs on.
+    pub fn current() -> Self {
+        Self::parse(&current_platform()).unwrap_or_else(|_| Self::new("unknown", "unknown"))
+    }
+
+    /// Whether this is a WebAssembly platform.
+    pub fn is_wasm(&self) -> bool {
+        self.arch == "wasm32"
+    }
+
+    /// The Rust target triple (e.g., "aarch64-apple-darwin").
+    pub fn to_triple(&self) -> String {
+        match (self.os.as_str(), self.arch.as_str()) {
+            ("wasi", "wasm32") => WASM_WASI.to_string(),
+            ("unknown", "wasm32") => "wasm32-unknown-unknown".to_string(),
+            ("darwin", arch) => format!("{arch}-apple-darwin"),
+            ("windows", "x86") => "i686-pc-windows-msvc".to_string(),
+            ("windows", arch) => format!("{arch}-pc-windows-msvc"),
+            ("linux", "x86") => "i686-unknown-linux-gnu".to_string(),
+            (os, arch) => format!("{arch}-unknown-{os}-gnu"),
+        }
+    }
+
+    /// Check whether the platform matches an identifier, triple or pattern.
+    ///
+    /// "all" and "*" match everything. Patterns containing `*` are matched
+    /// against the `<os>-<arch>` form ("linux-*", "*-aarch64", "wasm32-*").
+    pub fn matches(&self, pattern: &str) -> bool {
+        if pattern == "all" {
+            return true;
+        }
+        if pattern.contains('*') {
+            return glob_match(pattern, &self.to_string());
+        }
+        Platform::parse(pattern).is_ok_and(|p| p == *self)
+    }
+}
+
+impl fmt::Display for Platform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_wasm() {
+            write!(f, "{}-{}", self.arch, self.os)
+        } else {
+            write!(f, "{}-{}", self.os, self.arch)
+        }
+    }
+}
+
+impl FromStr for Platform {
+    type Err = ManifestError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl Serialize for Platform {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Platform {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Platform::parse(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+fn canonical_os(os: &str) -> Option<&'static str> {
+    match os {
+        "darwin" | "macos" | "osx" => Some("darwin"),
+        "linux" => Some("linux"),
+        "windows" | "win32" => Some("windows"),
+        _ => None,
+    }
+}
+
+fn canonical_arch(arch: &str) -> Option<&'static str> {
+    match arch {
+        "aarch64" | "arm64" => Some("aarch64"),
+        "x86_64" | "amd64" | "x64" => Some("x86_64"),
+        "x86" | "i686" | "i386" => Some("x86"),
+        _ => None,
+    }
+}
+
+/// Match `text` against a pattern where `*` matches any sequence.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == text,
+        Some((prefix, rest)) => {
+            let Some(text) = text.strip_prefix(prefix) else {
+                return false;
+            };
+            (0..=text.len())
+                .filter(|&i| text.is_char_boundary(i))
+                .any(|i| glob_match(rest, &text[i..]))
+        }
+    }
+}
+
+/// Find the key naming `platform` in a platform-keyed map: an exact match,
+/// else a key that is an alias or triple for the same platform.
+pub(crate) fn find_platform_key<'a>(
+    keys: impl IntoIterator<Item = &'a String>,
+    platform: &str,
+) -> Option<&'a String> {
+    let target = Platform::parse(platform).ok();
+    let mut alias = None;
+    for key in keys {
+        if key == platform {
+            return Some(key);
+        }
+        if alias.is_none() && target.is_some() && Platform::parse(key).ok() == target {
+            alias = Some(key);
+        }
+    }
+    alias
 }
 
 #[cfg(test)]
@@ -65,16 +326,75 @@ mod tests {
         assert!(platform.contains('-'));
     }
 
+    #[test]
+    fn test_known_platform() {
+        assert!(is_known_platform("darwin-aarch64"));
+        assert!(is_known_platform("all"));
+        assert!(is_known_platform("wasm32-wasi"));
+        assert!(!is_known_platform("macos-arm64"));
+        assert!(!is_known_platform("linux"));
+    }
+
     #[test]
     fn test_library_filename() {
         let name = library_filename("my_plugin");
         assert!(name.contains("my_plugin"));
     }
 
+    #[test]
+    fn test_library_filename_for() {
+        assert_eq!(
+            library_filename_for("tool", "darwin-aarch64"),
+            "libtool.dylib"
+        );
+        assert_eq!(library_filename_for("tool", "linux-x86_64"), "libtool.so");
+        assert_eq!(library_filename_for("tool", "windows-x86_64"), "tool.dll");
+        assert_eq!(library_filename_for("tool", "wasm32-wasi"), "tool.wasm");
+        assert_eq!(
+            library_filename_for("tool", &current_platform()),
+            library_filename("tool")
+        );
+    }
+
     #[test]
     fn test_matches_platform() {
         assert!(matches_platform(&current_platform()));
         assert!(matches_platform("all"));
         assert!(!matches_platform("nonexistent-platform"));
     }
+
+    #[test]
+    fn test_platform_parse() {
+        let darwin = Platform::parse("darwin-aarch64").unwrap();
+        assert_eq!(Platform::parse("aarch64-apple-darwin").unwrap(), darwin);
+        assert_eq!(Platform::parse("macos-arm64").unwrap(), darwin);
+        assert_eq!(darwin.to_triple(), "aarch64-apple-darwin");
+        assert_eq!(
+            Platform::parse("x86_64-unknown-linux-gnu")
+                .unwrap()
+                .to_string(),
+            "linux-x86_64"
+        );
+        assert_eq!(
+            Platform::parse("i686-pc-windows-msvc").unwrap().to_string(),
+            "windows-x86"
+        );
+        assert_eq!(
+            Platform::parse("wasm32-unknown-unknown")
+                .unwrap()
+                .to_string(),
+            "wasm32-unknown"
+        );
+        assert!(Platform::parse("plan9-mips").is_err());
+
+        assert!(darwin.matches("darwin-*"));
+        assert!(darwin.matches("*-aarch64"));
+        assert!(darwin.matches("aarch64-apple-darwin"));
+        assert!(!darwin.matches("linux-*"));
+        assert!(is_known_platform("linux-*"));
+        assert!(is_known_platform("x86_64-unknown-linux-gnu"));
+
+        let json = toml::Value::try_from(&darwin).unwrap();
+        assert_eq!(json.as_str(), Some("darwin-aarch64"));
+    }
 }
@@ -1,15 +1,44 @@
 //! Single plugin manifest (plugin.toml).
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
 
+use crate::abi::AbiRequirements;
+use crate::assets::Asset;
+use crate::cli::{CliArg, CliSubcommand};
+use crate::effective::LocalizedMeta;
 use crate::error::ManifestError;
-use crate::platform::{current_platform, library_filename};
+use crate::health::HealthInfo;
+use crate::hooks::HooksInfo;
+use crate::hosts::HostVersionRange;
+use crate::logging::LoggingInfo;
+use crate::messaging::MessagingTopic;
+use crate::migrate::{current_manifest_version, CURRENT_MANIFEST_VERSION};
+use crate::migrations::Migration;
+use crate::observability::ObservabilityInfo;
+use crate::options::{parse_document, ManifestOrigin, ParseOptions, DEFAULT_API_VERSION};
+use crate::permissions::Permissions;
+use crate::platform::{
+    current_platform, executable_filename_for, find_platform_key, library_filename_for,
+    wasm_filename, Platform,
+};
+use crate::plugin_type::PluginType;
+use crate::post_install::PostInstallMessage;
+use crate::resources::ResourcesInfo;
+use crate::shims::CompatInfo;
+use crate::storage::StorageInfo;
+use crate::uninstall::UninstallInfo;
 
 /// A single plugin manifest parsed from plugin.toml.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PluginManifest {
+    /// Manifest format version
+    #[serde(default = "current_manifest_version")]
+    pub manifest_version: u32,
+
     /// Plugin metadata
     pub plugin: PluginMeta,
 
@@ -63,16 +92,146 @@ pub struct PluginManifest {
     #[serde(default)]
     pub language: Option<LanguageInfo>,
 
+    /// Theme metadata (for theme type)
+    #[serde(default)]
+    pub theme: Option<ThemeInfo>,
+
     /// Platform requirements
     #[serde(default)]
     pub requirements: Option<RequirementsInfo>,
+
+    /// Lifecycle hooks (optional)
+    #[serde(default)]
+    pub hooks: Option<HooksInfo>,
+
+    /// Uninstall cleanup metadata (optional)
+    #[serde(default)]
+    pub uninstall: Option<UninstallInfo>,
+
+    /// Message shown after a successful install (optional)
+    #[serde(default)]
+    pub post_install_message: Option<PostInstallMessage>,
+
+    /// Data migrations between versions
+    #[serde(default)]
+    pub migrations: Vec<Migration>,
+
+    /// Storage directories (optional)
+    #[serde(default)]
+    pub storage: Option<StorageInfo>,
+
+    /// Sandbox permissions (optional)
+    #[serde(default)]
+    pub permissions: Option<Permissions>,
+
+    /// Pub/sub topics
+    #[serde(default)]
+    pub messaging: Vec<MessagingTopic>,
+
+    /// Non-binary files (themes, fonts, icons)
+    #[serde(default)]
+    pub assets: Vec<Asset>,
+
+    /// Shared resource claims (optional)
+    #[serde(default)]
+    pub resources: Option<ResourcesInfo>,
+
+    /// Health check (optional)
+    #[serde(default)]
+    pub health: Option<HealthInfo>,
+
+    /// Metrics and tracing metadata (optional)
+    #[serde(default)]
+    pub observability: Option<ObservabilityInfo>,
+
+    /// Logging configuration (optional)
+    #[serde(default)]
+    pub logging: Option<LoggingInfo>,
+
+    /// Per-platform override tables (`[target.<platform>]`)
+    #[serde(default)]
+    #[cfg_attr(
+        feature = "typescript",
+        ts(type = "Record<string, Record<string, unknown>>")
+    )]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(with = "BTreeMap<String, serde_json::Map<String, serde_json::Value>>")
+    )]
+    pub target: BTreeMap<String, toml::Table>,
+
+    /// Optional feature override tables (`[features.<name>]`)
+    #[serde(default)]
+    #[cfg_attr(
+        feature = "typescript",
+        ts(type = "Record<string, Record<string, unknown>>")
+    )]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(with = "BTreeMap<String, serde_json::Map<String, serde_json::Value>>")
+    )]
+    pub features: BTreeMap<String, toml::Table>,
+
+    /// Localized metadata (`[locales.<locale>]`)
+    #[serde(default)]
+    pub locales: BTreeMap<String, LocalizedMeta>,
+
+    /// Host API shims (optional)
+    #[serde(default)]
+    pub compat: Option<CompatInfo>,
+
+    /// Where the manifest was parsed from (not part of the document)
+    #[serde(skip)]
+    pub origin: ManifestOrigin,
+}
+
+impl Default for PluginManifest {
+    fn default() -> Self {
+        Self {
+            manifest_version: CURRENT_MANIFEST_VERSION,
+            plugin: PluginMeta::default(),
+            compatibility: CompatibilityInfo::default(),
+            binary: BinaryInfo::default(),
+            signature: None,
+            config: ConfigInfo::default(),
+            provides: Vec::new(),
+            requires: Vec::new(),
+            cli: None,
+            capabilities: Vec::new(),
+            tags: None,
+            hive: None,
+            translation: None,
+            language: None,
+            theme: None,
+            requirements: None,
+            hooks: None,
+            uninstall: None,
+            post_install_message: None,
+            migrations: Vec::new(),
+            storage: None,
+            permissions: None,
+            messaging: Vec::new(),
+            assets: Vec::new(),
+            resources: None,
+            health: None,
+            observability: None,
+            logging: None,
+            target: BTreeMap::new(),
+            features: BTreeMap::new(),
+            locales: BTreeMap::new(),
+            compat: None,
+            origin: ManifestOrigin::default(),
+        }
+    }
 }
 
 /// CLI command configuration for plugins that provide top-level commands.
 ///
 /// When a plugin has a `[cli]` section, it will be registered as a
 /// direct subcommand of the `adi` CLI (e.g., `adi tasks`, `adi lint`).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct CliConfig {
     /// The command name (e.g., "tasks", "lint")
     /// Must be lowercase alphanumeric with hyphens
@@ -91,31 +250,70 @@ pub struct CliConfig {
     /// completion\tdescription pairs, one per line.
     #[serde(default)]
     pub dynamic_completions: bool,
+
+    /// Arguments of the command itself
+    #[serde(default)]
+    pub args: Vec<CliArg>,
+
+    /// Subcommands (`[[cli.subcommands]]`)
+    #[serde(default)]
+    pub subcommands: Vec<CliSubcommand>,
 }
 
 impl PluginManifest {
     /// Parse from TOML string.
     pub fn from_toml(content: &str) -> Result<Self, ManifestError> {
-        toml::from_str(content).map_err(ManifestError::TomlParse)
+        Self::from_toml_with(content, &ParseOptions::default())
+    }
+
+    /// Parse from TOML string with custom options.
+    pub fn from_toml_with(content: &str, options: &ParseOptions) -> Result<Self, ManifestError> {
+        let (mut manifest, origin): (Self, _) = parse_document(content, options)?;
+        options.enforce(|policy| manifest.validate_with(policy))?;
+        if options.resolves_sections() {
+            let platform = options.platform.clone().unwrap_or_else(current_platform);
+            manifest = manifest.effective(&platform, &[], None, options.locale.as_deref())?;
+        }
+        manifest.origin = origin;
+        Ok(manifest)
     }
 
     /// Parse from file.
     pub fn from_file(path: &Path) -> Result<Self, ManifestError> {
+        Self::from_file_with(path, &ParseOptions::default())
+    }
+
+    /// Parse from file with custom options.
+    pub fn from_file_with(path: &Path, options: &ParseOptions) -> Result<Self, ManifestError> {
         let content = std::fs::read_to_string(path)?;
-        Self::from_toml(&content)
+        Self::from_toml_with(&content, options)
+    }
+
+    /// Check whether a field (e.g., "compatibility.api_version") was filled
+    /// in from a default rather than declared in the manifest.
+    pub fn is_defaulted(&self, field: &str) -> bool {
+        self.origin.defaulted_fields.iter().any(|f| f == field)
     }
 
     /// Get the binary filename for the current platform.
     pub fn binary_filename(&self) -> String {
-        library_filename(&self.binary.name)
+        self.binary.filename_for(&current_platform())
     }
 
     /// Get the checksum for the current platform (if available).
     pub fn checksum_for_current_platform(&self) -> Option<&str> {
-        self.binary
-            .checksums
-            .get(&current_platform())
-            .map(|s| s.as_str())
+        self.binary.checksum_for(&current_platform())
+    }
+
+    /// Get the expected binary size for the current platform (if declared).
+    pub fn size_for_current_platform(&self) -> Option<u64> {
+        self.binary.size_for(&current_platform())
+    }
+
+    /// Check the size of a downloaded binary for the current platform.
+    pub fn verify_binary_size(&self, path: &Path) -> Result<(), ManifestError> {
+        let actual = std::fs::metadata(path)?.len();
+        self.binary.verify_size(&current_platform(), actual)
     }
 
     /// Check if the current platform is supported.
@@ -123,16 +321,29 @@ impl PluginManifest {
         if self.compatibility.platforms.is_empty() {
             return true; // No platform restriction
         }
-        let current = current_platform();
+        let current = Platform::current();
         self.compatibility
             .platforms
             .iter()
-            .any(|p| p == &current || p == "all")
+            .any(|p| current.matches(p))
+    }
+
+    /// Get the provided service implementing a capability protocol.
+    pub fn service_for_capability(&self, protocol: &str) -> Option<&ServiceDeclaration> {
+        let service = self
+            .capabilities
+            .iter()
+            .find(|c| c.protocol == protocol)?
+            .implements
+            .as_ref()?;
+        self.provides.iter().find(|s| s.id == *service)
     }
 }
 
 /// Plugin metadata.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PluginMeta {
     /// Unique identifier (e.g., "vendor.plugin-name")
     pub id: String,
@@ -145,7 +356,9 @@ pub struct PluginMeta {
 
     /// Plugin type (e.g., "theme", "extension", "font")
     #[serde(rename = "type")]
-    pub plugin_type: String,
+    #[cfg_attr(feature = "typescript", ts(type = "string"))]
+    #[cfg_attr(feature = "schema", schemars(with = "String"))]
+    pub plugin_type: PluginType,
 
     /// Author
     #[serde(default)]
@@ -162,10 +375,20 @@ pub struct PluginMeta {
     /// Homepage URL
     #[serde(default)]
     pub homepage: Option<String>,
+
+    /// Source repository URL
+    #[serde(default)]
+    pub repository: Option<String>,
+
+    /// License text file (relative to the manifest)
+    #[serde(default)]
+    pub license_file: Option<String>,
 }
 
 /// Compatibility information.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct CompatibilityInfo {
     /// Plugin API version
     #[serde(default = "default_api_version")]
@@ -186,6 +409,14 @@ pub struct CompatibilityInfo {
     /// Plugin dependencies (other plugin IDs that must be loaded first)
     #[serde(default)]
     pub depends_on: Vec<String>,
+
+    /// Host applications the plugin targets (empty = all hosts)
+    #[serde(default)]
+    pub hosts: Vec<String>,
+
+    /// Per-host version ranges, replacing the default range for that host
+    #[serde(default)]
+    pub host_versions: BTreeMap<String, HostVersionRange>,
 }
 
 impl Default for CompatibilityInfo {
@@ -196,16 +427,24 @@ impl Default for CompatibilityInfo {
             max_host_version: None,
             platforms: Vec::new(),
             depends_on: Vec::new(),
+            hosts: Vec::new(),
+            host_versions: BTreeMap::new(),
         }
     }
 }
 
 fn default_api_version() -> u32 {
-    2 // Match PLUGIN_API_VERSION in lib-plugin-abi
+    DEFAULT_API_VERSION
+}
+
+fn default_capability_version() -> String {
+    "1.0.0".to_string()
 }
 
 /// Binary information.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct BinaryInfo {
     /// Binary name (without lib prefix and extension)
     #[serde(default = "default_binary_name")]
@@ -214,6 +453,67 @@ pub struct BinaryInfo {
     /// SHA256 checksums per platform
     #[serde(default)]
     pub checksums: HashMap<String, String>,
+
+    /// Expected binary size in bytes per platform
+    #[serde(default)]
+    pub sizes: HashMap<String, u64>,
+
+    /// Directory containing the binary, relative to the plugin install directory
+    #[serde(default)]
+    pub path: Option<String>,
+
+    /// Per-platform overrides (`[binary.platform.<platform>]`)
+    #[serde(default)]
+    pub platform: BTreeMap<String, BinaryOverride>,
+
+    /// Native library, executable, WebAssembly module or script
+    #[serde(default)]
+    pub kind: BinaryKind,
+
+    /// Minimum system library versions (`[binary.abi_requirements]`)
+    #[serde(default)]
+    pub abi_requirements: Option<AbiRequirements>,
+}
+
+/// Kind of plugin binary.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum BinaryKind {
+    /// Native dynamic library (`libname.so`, `libname.dylib`, `name.dll`)
+    #[default]
+    Native,
+    /// WebAssembly module (`name.wasm`)
+    Wasm,
+    /// Native executable (`name`, `name.exe` on Windows)
+    Executable,
+    /// Interpreted script; `name` is the file name including its extension
+    Script,
+}
+
+/// Binary settings for one platform, overriding `[binary]`.
+///
+/// ```toml
+/// [binary.platform.linux-x86_64]
+/// name = "tool_musl"
+/// checksum = "sha256:..."
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct BinaryOverride {
+    /// Binary name on this platform
+    #[serde(default)]
+    pub name: Option<String>,
+
+    /// Binary directory on this platform
+    #[serde(default)]
+    pub path: Option<String>,
+
+    /// Checksum of the binary on this platform
+    #[serde(default)]
+    pub checksum: Option<String>,
 }
 
 fn default_binary_name() -> String {
@@ -225,12 +525,89 @@ impl Default for BinaryInfo {
         Self {
             name: default_binary_name(),
             checksums: HashMap::new(),
+            sizes: HashMap::new(),
+            path: None,
+            platform: BTreeMap::new(),
+            kind: BinaryKind::Native,
+            abi_requirements: None,
         }
     }
 }
 
+impl BinaryInfo {
+    /// Binary name for a platform, honoring its override.
+    pub fn name_for(&self, platform: &str) -> &str {
+        self.platform
+            .get(platform)
+            .and_then(|o| o.name.as_deref())
+            .unwrap_or(&self.name)
+    }
+
+    /// Binary directory for a platform, honoring its override.
+    pub fn path_for(&self, platform: &str) -> Option<&str> {
+        self.platform
+            .get(platform)
+            .and_then(|o| o.path.as_deref())
+            .or(self.path.as_deref())
+    }
+
+    /// Binary filename for a platform, e.g. "libtool.so" or "tool.wasm".
+    pub fn filename_for(&self, platform: &str) -> String {
+        let name = self.name_for(platform);
+        match self.kind {
+            BinaryKind::Wasm => wasm_filename(name),
+            BinaryKind::Native => library_filename_for(name, platform),
+            BinaryKind::Executable => executable_filename_for(name, platform),
+            BinaryKind::Script => name.to_string(),
+        }
+    }
+
+    /// Checksum for a platform: the override's, then `[binary.checksums]`.
+    ///
+    /// Keys written as aliases or target triples match too.
+    pub fn checksum_for(&self, platform: &str) -> Option<&str> {
+        find_platform_key(self.platform.keys(), platform)
+            .and_then(|key| self.platform[key].checksum.as_deref())
+            .or_else(|| {
+                find_platform_key(self.checksums.keys(), platform)
+                    .map(|key| self.checksums[key].as_str())
+            })
+    }
+
+    /// Get the declared size for a platform (if any).
+    pub fn size_for(&self, platform: &str) -> Option<u64> {
+        find_platform_key(self.sizes.keys(), platform).map(|key| self.sizes[key])
+    }
+
+    /// Check a downloaded artifact's size against the declared size.
+    ///
+    /// Passes if no size is declared for the platform. Cheap enough to run
+    /// before hashing, so truncated downloads are caught early.
+    pub fn verify_size(&self, platform: &str, actual: u64) -> Result<(), ManifestError> {
+        verify_size(&self.sizes, platform, actual)
+    }
+}
+
+/// Check an actual size against a platform -> size map.
+pub(crate) fn verify_size(
+    sizes: &HashMap<String, u64>,
+    platform: &str,
+    actual: u64,
+) -> Result<(), ManifestError> {
+    match find_platform_key(sizes.keys(), platform).map(|key| sizes[key]) {
+        Some(expected) if expected != actual => Err(ManifestError::SizeMismatch {
+            platform: platform.to_string(),
+            expected,
+            actual,
+        }),
+        _ => Ok(()),
+    }
+}
+
 /// Signature information.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct SignatureInfo {
     /// Ed25519 public key (base64 encoded)
     pub public_key: String,
@@ -241,14 +618,35 @@ pub struct SignatureInfo {
 
 /// Default configuration values.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ConfigInfo {
     /// Default configuration values
     #[serde(default)]
+    #[cfg_attr(feature = "typescript", ts(type = "Record<string, unknown>"))]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(with = "HashMap<String, serde_json::Value>")
+    )]
     pub defaults: HashMap<String, toml::Value>,
+
+    /// Named profiles overriding the defaults
+    #[serde(default)]
+    #[cfg_attr(
+        feature = "typescript",
+        ts(type = "Record<string, Record<string, unknown>>")
+    )]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(with = "BTreeMap<String, serde_json::Map<String, serde_json::Value>>")
+    )]
+    pub profiles: BTreeMap<String, toml::Table>,
 }
 
 /// Service provided by this plugin.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ServiceDeclaration {
     /// Service ID (e.g., "adi.indexer.search")
     pub id: String,
@@ -259,10 +657,20 @@ pub struct ServiceDeclaration {
     /// Human-readable description
     #[serde(default)]
     pub description: String,
+
+    /// Whether the service is deprecated
+    #[serde(default)]
+    pub deprecated: bool,
+
+    /// ID of the service replacing this one (optional)
+    #[serde(default)]
+    pub replaced_by: Option<String>,
 }
 
 /// Service required by this plugin.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ServiceRequirement {
     /// Required service ID
     pub id: String,
@@ -274,6 +682,25 @@ pub struct ServiceRequirement {
     /// Whether this requirement is optional (defaults to false = required)
     #[serde(default)]
     pub optional: bool,
+
+    /// Who may provide the service (defaults to another plugin)
+    #[serde(default)]
+    pub provider: ServiceSource,
+}
+
+/// Who may provide a required service.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ServiceSource {
+    /// Another plugin
+    #[default]
+    Plugin,
+    /// The host itself
+    Host,
+    /// A plugin, or the host if no plugin provides it
+    Any,
 }
 
 /// Capability declaration for hybrid cloud routing.
@@ -281,20 +708,29 @@ pub struct ServiceRequirement {
 /// Capabilities are advertised to the signaling server, allowing cocoons
 /// to discover and request services from each other (e.g., embeddings, LLM chat).
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct CapabilityDeclaration {
     /// Protocol/capability name (e.g., "tasks", "embeddings", "llm.chat")
     pub protocol: String,
 
-    /// Semantic version (e.g., "1.0.0", "2.3.1")
+    /// Semantic version (e.g., "1.0.0", "2.3.1"; defaults to "1.0.0")
+    #[serde(default = "default_capability_version")]
     pub version: String,
 
     /// Human-readable description (optional)
     #[serde(default)]
     pub description: String,
+
+    /// ID of the provided service implementing this capability (optional)
+    #[serde(default)]
+    pub implements: Option<String>,
 }
 
 /// Tags for plugin categorization and discovery.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct TagsInfo {
     /// Category tags (e.g., ["tasks", "workflow"])
     #[serde(default)]
@@ -306,7 +742,9 @@ pub struct TagsInfo {
 }
 
 /// Hive plugin metadata.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct HiveInfo {
     /// Plugin category within hive (e.g., "runner", "proxy", "health")
     pub category: String,
@@ -316,7 +754,9 @@ pub struct HiveInfo {
 }
 
 /// Translation plugin metadata.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct TranslationInfo {
     /// Plugin ID this translates (e.g., "adi.workflow")
     pub translates: String,
@@ -325,24 +765,44 @@ pub struct TranslationInfo {
     pub language: String,
 
     /// Human-readable language name (e.g., "English (United States)")
+    #[serde(default)]
     pub language_name: String,
 
     /// Translation namespace (e.g., "workflow")
+    #[serde(default)]
     pub namespace: String,
 }
 
 /// Language analyzer plugin metadata.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct LanguageInfo {
     /// Language identifier (e.g., "rust", "python")
     pub id: String,
 
     /// File extensions (e.g., ["rs"], ["py", "pyi"])
+    #[serde(default)]
     pub extensions: Vec<String>,
 }
 
+/// Theme plugin metadata.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ThemeInfo {
+    /// Color scheme the theme is made for ("dark" or "light")
+    pub appearance: String,
+
+    /// Theme this one builds on (e.g., "adi.theme-dark")
+    #[serde(default)]
+    pub extends: Option<String>,
+}
+
 /// Platform requirements for the plugin.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct RequirementsInfo {
     /// Required OS (e.g., "darwin", "linux")
     #[serde(default)]
@@ -360,9 +820,8 @@ pub struct RequirementsInfo {
 impl PluginManifest {
     /// Serialize to TOML string.
     pub fn to_toml(&self) -> Result<String, ManifestError> {
-        toml::to_string_pretty(self).map_err(|e| {
-            ManifestError::InvalidFormat(format!("Failed to serialize manifest: {e}"))
-        })
+        toml::to_string_pretty(self)
+            .map_err(|e| ManifestError::InvalidFormat(format!("Failed to serialize manifest: {e}")))
     }
 }
 
@@ -399,7 +858,7 @@ categories = ["remote", "execution", "terminal", "pty"]
 "#;
         let manifest = PluginManifest::from_toml(toml).unwrap();
         assert_eq!(manifest.plugin.id, "adi.cocoon");
-        assert_eq!(manifest.plugin.plugin_type, "core");
+        assert_eq!(manifest.plugin.plugin_type, PluginType::Core);
     }
 
     #[test]
@@ -430,7 +889,7 @@ enabled = true
         assert_eq!(manifest.plugin.id, "vendor.test-plugin");
         assert_eq!(manifest.plugin.name, "Test Plugin");
         assert_eq!(manifest.plugin.version, "1.0.0");
-        assert_eq!(manifest.plugin.plugin_type, "extension");
+        assert_eq!(manifest.plugin.plugin_type, PluginType::Extension);
         assert_eq!(manifest.compatibility.api_version, 1);
         assert_eq!(manifest.binary.name, "test_plugin");
     }
@@ -639,5 +1098,202 @@ name = "tasks_plugin"
         assert_eq!(manifest.capabilities[0].description, "Task management API");
         assert_eq!(manifest.capabilities[1].protocol, "tasks.execute");
         assert_eq!(manifest.capabilities[1].version, "1.0.0");
+        assert!(manifest.capabilities[0].implements.is_none());
+    }
+
+    #[test]
+    fn test_capability_implements() {
+        let toml = r#"
+[plugin]
+id = "adi.tasks"
+name = "ADI Tasks"
+version = "1.0.0"
+type = "core"
+
+[[provides]]
+id = "adi.tasks.api"
+version = "1.0.0"
+
+[[capabilities]]
+protocol = "tasks"
+version = "1.0.0"
+implements = "adi.tasks.api"
+"#;
+        let manifest = PluginManifest::from_toml(toml).unwrap();
+        let service = manifest.service_for_capability("tasks").unwrap();
+        assert_eq!(service.id, "adi.tasks.api");
+        assert!(manifest.validate().is_empty());
+
+        let dangling = toml.replace(
+            "implements = \"adi.tasks.api\"",
+            "implements = \"adi.other\"",
+        );
+        let manifest = PluginManifest::from_toml(&dangling).unwrap();
+        assert!(manifest.service_for_capability("tasks").is_none());
+        let issues = manifest.validate();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].path, "capabilities[0].implements");
+    }
+
+    #[test]
+    fn test_extended_sections_roundtrip() {
+        let toml_input = r#"
+[plugin]
+id = "adi.lang.rust"
+name = "Rust Language Support"
+version = "3.0.0"
+type = "lang"
+
+[cli]
+command = "rust"
+description = "Rust tooling"
+aliases = ["rs"]
+dynamic_completions = true
+
+[[capabilities]]
+protocol = "lang.analyze"
+version = "1.0.0"
+description = "Source analysis"
+
+[tags]
+categories = ["lang"]
+platforms = ["darwin-aarch64"]
+
+[hive]
+category = "runner"
+name = "rust"
+
+[translation]
+translates = "adi.lang"
+language = "en-US"
+language_name = "English (United States)"
+namespace = "lang"
+
+[language]
+id = "rust"
+extensions = ["rs"]
+
+[requirements]
+os = "darwin"
+arch = "aarch64"
+notes = "Requires Xcode command line tools"
+"#;
+        let manifest = PluginManifest::from_toml(toml_input).unwrap();
+        let reparsed = PluginManifest::from_toml(&manifest.to_toml().unwrap()).unwrap();
+
+        assert_eq!(reparsed.cli, manifest.cli);
+        assert!(reparsed.cli.as_ref().unwrap().dynamic_completions);
+        assert_eq!(reparsed.capabilities, manifest.capabilities);
+        assert_eq!(reparsed.tags, manifest.tags);
+        assert_eq!(reparsed.hive, manifest.hive);
+        assert_eq!(reparsed.translation, manifest.translation);
+        assert_eq!(reparsed.language, manifest.language);
+        assert_eq!(reparsed.requirements, manifest.requirements);
+        assert_eq!(
+            reparsed.requirements.unwrap().notes.as_deref(),
+            Some("Requires Xcode command line tools")
+        );
+
+        // Optional section fields default the same way cargo extraction does
+        let minimal = r#"
+[plugin]
+id = "adi.workflow.en-US"
+name = "ADI Workflow - English"
+version = "1.0.0"
+type = "translation"
+
+[[capabilities]]
+protocol = "tasks"
+
+[translation]
+translates = "adi.workflow"
+language = "en-US"
+
+[language]
+id = "rust"
+"#;
+        let manifest = PluginManifest::from_toml(minimal).unwrap();
+        assert_eq!(manifest.capabilities[0].version, "1.0.0");
+        assert_eq!(manifest.translation.as_ref().unwrap().namespace, "");
+        assert!(manifest.language.as_ref().unwrap().extensions.is_empty());
+
+        let reparsed = PluginManifest::from_toml(&manifest.to_toml().unwrap()).unwrap();
+        assert_eq!(reparsed.translation, manifest.translation);
+        assert_eq!(reparsed.language, manifest.language);
+    }
+
+    #[test]
+    fn test_binary_sizes() {
+        let toml = r#"
+[plugin]
+id = "test.plugin"
+name = "Test"
+version = "1.0.0"
+type = "test"
+
+[binary]
+name = "my_plugin"
+
+[binary.sizes]
+linux-x86_64 = 1024
+"#;
+        let manifest = PluginManifest::from_toml(toml).unwrap();
+        assert_eq!(manifest.binary.sizes.get("linux-x86_64"), Some(&1024));
+        assert!(manifest.binary.verify_size("linux-x86_64", 1024).is_ok());
+        assert!(matches!(
+            manifest.binary.verify_size("linux-x86_64", 512),
+            Err(ManifestError::SizeMismatch {
+                expected: 1024,
+                actual: 512,
+                ..
+            })
+        ));
+        // Target triples find the declared size
+        assert_eq!(manifest.binary.size_for("x86_64-unknown-linux-gnu"), Some(1024));
+        assert!(manifest
+            .binary
+            .verify_size("x86_64-unknown-linux-gnu", 512)
+            .is_err());
+        // No declared size for the platform
+        assert!(manifest.binary.verify_size("darwin-aarch64", 1).is_ok());
+    }
+
+    #[test]
+    fn test_binary_platform_overrides() {
+        let toml = r#"
+[plugin]
+id = "test.plugin"
+name = "Test"
+version = "1.0.0"
+type = "test"
+
+[binary]
+name = "my_plugin"
+path = "bin"
+
+[binary.checksums]
+linux-x86_64 = "abc"
+darwin-aarch64 = "def"
+
+[binary.platform.linux-x86_64]
+name = "my_plugin_musl"
+checksum = "123"
+"#;
+        let manifest = PluginManifest::from_toml(toml).unwrap();
+        let binary = &manifest.binary;
+        assert_eq!(binary.name_for("linux-x86_64"), "my_plugin_musl");
+        assert_eq!(binary.name_for("darwin-aarch64"), "my_plugin");
+        assert_eq!(binary.path_for("linux-x86_64"), Some("bin"));
+        assert_eq!(binary.checksum_for("linux-x86_64"), Some("123"));
+        assert_eq!(binary.checksum_for("darwin-aarch64"), Some("def"));
+
+        assert_eq!(binary.filename_for("linux-x86_64"), "libmy_plugin_musl.so");
+
+        let wasm = PluginManifest::from_toml(
+            "[plugin]\nid = \"test.wasm\"\nname = \"Wasm\"\nversion = \"1.0.0\"\ntype = \"test\"\n\n[binary]\nname = \"tool\"\nkind = \"wasm\"\n",
+        )
+        .unwrap();
+        assert_eq!(wasm.binary.kind, BinaryKind::Wasm);
+        assert_eq!(wasm.binary_filename(), "tool.wasm");
     }
 }
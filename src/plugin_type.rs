@@ -0,0 +1,330 @@
+//! Plugin type registry.
+//!
+//! The `type` field of a plugin is a [`PluginType`]. It (de)serializes as
+//! the plain string, and types without a variant are kept as `Custom`, so
+//! host-specific types parse and round-trip. The registry knows the
+//! built-in types and lets hosts register their own; validation warns about
+//! types nobody understands, and a policy with `reject_unknown_types` (or
+//! strict parsing) rejects them, typos like "extenson" included.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeSet;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::package::PluginDef;
+use crate::plugin::PluginMeta;
+
+/// Plugin types understood out of the box.
+pub const BUILTIN_PLUGIN_TYPES: &[&str] = &[
+    "extension",
+    "theme",
+    "font",
+    "core",
+    "hive-plugin",
+    "translation",
+    "lang",
+];
+
+/// Set of known plugin types.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginTypeRegistry {
+    types: BTreeSet<String>,
+}
+
+impl Default for PluginTypeRegistry {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl PluginTypeRegistry {
+    /// Create a registry with the built-in types.
+    pub fn builtin() -> Self {
+        Self {
+            types: BUILTIN_PLUGIN_TYPES.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    /// Create an empty registry.
+    pub fn empty() -> Self {
+        Self {
+            types: BTreeSet::new(),
+        }
+    }
+
+    /// Register a custom plugin type.
+    ///
+    /// Returns false if the type was already known.
+    pub fn register(&mut self, plugin_type: impl Into<String>) -> bool {
+        self.types.insert(plugin_type.into())
+    }
+
+    /// Builder-style variant of `register()`.
+    pub fn with_type(mut self, plugin_type: impl Into<String>) -> Self {
+        self.register(plugin_type);
+        self
+    }
+
+    /// Check if a plugin type is known.
+    pub fn contains(&self, plugin_type: &str) -> bool {
+        self.types.contains(plugin_type)
+    }
+
+    /// Iterate over all known types in sorted order.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.types.iter().map(|t| t.as_str())
+    }
+}
+
+/// Typed view of a plugin's `type` field.
+///
+/// Unknown types are kept as `Custom` so parsing never fails.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub enum PluginType {
+    /// General-purpose extension
+    #[default]
+    Extension,
+    /// Visual theme
+    Theme,
+    /// Font package
+    Font,
+    /// First-party core plugin
+    Core,
+    /// Hive plugin (runner, proxy, health, ...)
+    HivePlugin,
+    /// Translation of another plugin
+    Translation,
+    /// Language analyzer
+    Lang,
+    /// Any other type
+    Custom(String),
+}
+
+/// Former name of [`PluginType`].
+#[deprecated(note = "renamed to `PluginType`; `Other` is now `Custom`")]
+pub type PluginKind = PluginType;
+
+impl PluginType {
+    /// Parse a plugin type string.
+    pub fn parse(plugin_type: &str) -> Self {
+        match plugin_type {
+            "extension" => PluginType::Extension,
+            "theme" => PluginType::Theme,
+            "font" => PluginType::Font,
+            "core" => PluginType::Core,
+            "hive-plugin" => PluginType::HivePlugin,
+            "translation" => PluginType::Translation,
+            "lang" => PluginType::Lang,
+            other => PluginType::Custom(other.to_string()),
+        }
+    }
+
+    /// Get the type string as it appears in manifests.
+    pub fn as_str(&self) -> &str {
+        match self {
+            PluginType::Extension => "extension",
+            PluginType::Theme => "theme",
+            PluginType::Font => "font",
+            PluginType::Core => "core",
+            PluginType::HivePlugin => "hive-plugin",
+            PluginType::Translation => "translation",
+            PluginType::Lang => "lang",
+            PluginType::Custom(s) => s,
+        }
+    }
+
+    /// Check if this is a built-in type.
+    pub fn is_builtin(&self) -> bool {
+        !matches!(self, PluginType::Custom(_))
+    }
+
+    /// Check if this is an extension.
+    pub fn is_extension(&self) -> bool {
+        matches!(self, PluginType::Extension)
+    }
+
+    /// Check if this is a theme.
+    pub fn is_theme(&self) -> bool {
+        matches!(self, PluginType::Theme)
+    }
+
+    /// Check if this is a font.
+    pub fn is_font(&self) -> bool {
+        matches!(self, PluginType::Font)
+    }
+
+    /// Check if this is a core plugin.
+    pub fn is_core(&self) -> bool {
+        matches!(self, PluginType::Core)
+    }
+
+    /// Check if this is a hive plugin.
+    pub fn is_hive_plugin(&self) -> bool {
+        matches!(self, PluginType::HivePlugin)
+    }
+
+    /// Check if this is a translation.
+    pub fn is_translation(&self) -> bool {
+        matches!(self, PluginType::Translation)
+    }
+
+    /// Check if this is a language analyzer.
+    pub fn is_lang(&self) -> bool {
+        matches!(self, PluginType::Lang)
+    }
+}
+
+impl fmt::Display for PluginType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for PluginType {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(PluginType::parse(s))
+    }
+}
+
+impl From<&str> for PluginType {
+    fn from(s: &str) -> Self {
+        PluginType::parse(s)
+    }
+}
+
+impl From<String> for PluginType {
+    fn from(s: String) -> Self {
+        PluginType::parse(&s)
+    }
+}
+
+impl Serialize for PluginType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for PluginType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Ok(PluginType::parse(&s))
+    }
+}
+
+impl PluginMeta {
+    /// Get the typed plugin kind.
+    pub fn kind(&self) -> PluginType {
+        self.plugin_type.clone()
+    }
+
+    /// Set the plugin type from a typed kind.
+    pub fn set_kind(&mut self, kind: PluginType) {
+        self.plugin_type = kind;
+    }
+}
+
+impl PluginDef {
+    /// Get the typed plugin kind.
+    pub fn kind(&self) -> PluginType {
+        self.plugin_type.clone()
+    }
+
+    /// Set the plugin type from a typed kind.
+    pub fn set_kind(&mut self, kind: PluginType) {
+        self.plugin_type = kind;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_types() {
+        let registry = PluginTypeRegistry::builtin();
+        assert!(registry.contains("extension"));
+        assert!(registry.contains("hive-plugin"));
+        assert!(!registry.contains("extenson"));
+    }
+
+    #[test]
+    fn test_register_custom_type() {
+        let mut registry = PluginTypeRegistry::builtin();
+        assert!(registry.register("dashboard-widget"));
+        assert!(!registry.register("dashboard-widget"));
+        assert!(registry.contains("dashboard-widget"));
+
+        let registry = PluginTypeRegistry::empty().with_type("widget");
+        assert_eq!(registry.iter().collect::<Vec<_>>(), vec!["widget"]);
+    }
+
+    #[test]
+    fn test_plugin_kind_roundtrip() {
+        for t in BUILTIN_PLUGIN_TYPES {
+            let kind = PluginType::parse(t);
+            assert!(kind.is_builtin());
+            assert_eq!(kind.as_str(), *t);
+        }
+        let kind: PluginType = "widget".parse().unwrap();
+        assert_eq!(kind, PluginType::Custom("widget".into()));
+        assert_eq!(kind.to_string(), "widget");
+    }
+
+    #[test]
+    fn test_plugin_kind_serde() {
+        #[derive(Serialize, Deserialize)]
+        struct Entry {
+            kind: PluginType,
+        }
+        let entry: Entry = toml::from_str(r#"kind = "hive-plugin""#).unwrap();
+        assert!(entry.kind.is_hive_plugin());
+        let entry: Entry = toml::from_str(r#"kind = "extenson""#).unwrap();
+        assert_eq!(entry.kind, PluginType::Custom("extenson".into()));
+        assert_eq!(
+            toml::to_string(&entry).unwrap().trim(),
+            r#"kind = "extenson""#
+        );
+    }
+
+    #[test]
+    fn test_plugin_kind_accessors() {
+        let toml = r#"
+[plugin]
+id = "adi.workflow.en-US"
+name = "ADI Workflow - English"
+version = "1.0.0"
+type = "translation"
+"#;
+        let manifest = crate::PluginManifest::from_toml(toml).unwrap();
+        let kind = manifest.plugin.kind();
+        assert!(kind.is_translation());
+        assert!(!kind.is_theme());
+    }
+
+    #[test]
+    fn test_typed_manifest_field() {
+        let toml = r#"
+[plugin]
+id = "acme.widget"
+name = "Widget"
+version = "1.0.0"
+type = "dashboard-widget"
+"#;
+        let mut manifest = crate::PluginManifest::from_toml(toml).unwrap();
+        assert_eq!(
+            manifest.plugin.plugin_type,
+            PluginType::Custom("dashboard-widget".into())
+        );
+        assert!(manifest
+            .to_toml()
+            .unwrap()
+            .contains(r#"type = "dashboard-widget""#));
+
+        manifest.plugin.set_kind(PluginType::Theme);
+        let reparsed = crate::PluginManifest::from_toml(&manifest.to_toml().unwrap()).unwrap();
+        assert!(reparsed.plugin.plugin_type.is_theme());
+    }
+}
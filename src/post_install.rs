@@ -0,0 +1,109 @@
+//! Message shown by installers after a successful install.
+//!
+//! ```toml
+//! [post_install_message]
+//! text = "Restart the host to activate **Tool**."
+//! format = "markdown"
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::plugin::PluginManifest;
+use crate::validate::ValidationIssue;
+
+/// Maximum length of a post-install message, in characters.
+pub const MAX_POST_INSTALL_MESSAGE_LEN: usize = 1000;
+
+/// Text format of a message.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum MessageFormat {
+    /// Plain text
+    #[default]
+    Plain,
+    /// Markdown
+    Markdown,
+}
+
+/// Post-install message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PostInstallMessage {
+    /// Message text
+    pub text: String,
+
+    /// Text format (defaults to plain text)
+    #[serde(default)]
+    pub format: MessageFormat,
+}
+
+impl PluginManifest {
+    /// Check the post-install message length.
+    pub(crate) fn check_post_install_message(&self) -> Vec<ValidationIssue> {
+        let Some(message) = &self.post_install_message else {
+            return Vec::new();
+        };
+
+        let len = message.text.chars().count();
+        if message.text.trim().is_empty() {
+            vec![ValidationIssue::warning(
+                "post_install_message.text",
+                "Post-install message is empty",
+            )]
+        } else if len > MAX_POST_INSTALL_MESSAGE_LEN {
+            vec![ValidationIssue::error(
+                "post_install_message.text",
+                format!(
+                    "Post-install message is {len} characters long (max {MAX_POST_INSTALL_MESSAGE_LEN})"
+                ),
+            )]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate::has_errors;
+
+    const BASE: &str = r#"
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.0.0"
+type = "extension"
+"#;
+
+    #[test]
+    fn test_post_install_message() {
+        let toml = format!(
+            "{BASE}\n[post_install_message]\ntext = \"Restart **now**\"\nformat = \"markdown\"\n"
+        );
+        let manifest = PluginManifest::from_toml(&toml).unwrap();
+        let message = manifest.post_install_message.as_ref().unwrap();
+        assert_eq!(message.format, MessageFormat::Markdown);
+        assert!(manifest.validate().is_empty());
+
+        let toml = format!("{BASE}\n[post_install_message]\ntext = \"Restart\"\n");
+        let manifest = PluginManifest::from_toml(&toml).unwrap();
+        assert_eq!(
+            manifest.post_install_message.unwrap().format,
+            MessageFormat::Plain
+        );
+    }
+
+    #[test]
+    fn test_post_install_message_too_long() {
+        let mut manifest = PluginManifest::from_toml(BASE).unwrap();
+        manifest.post_install_message = Some(PostInstallMessage {
+            text: "x".repeat(MAX_POST_INSTALL_MESSAGE_LEN + 1),
+            format: MessageFormat::Plain,
+        });
+        assert!(has_errors(&manifest.validate()));
+    }
+}
@@ -0,0 +1,448 @@
+//! Host-side preflight checks run before loading a plugin.
+//!
+//! `preflight()` combines platform support, host/API compatibility,
+//! requirements, permission policy and checksum presence into a single
+//! report the host can render.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::platform::{current_platform, Platform};
+use crate::plugin::PluginManifest;
+use crate::shims::ApiSupport;
+use crate::version::Version;
+
+/// The host loading the plugin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostInfo {
+    /// Host version
+    pub version: Version,
+
+    /// Plugin API version implemented by the host
+    pub api_version: u32,
+
+    /// Compatibility shims available on the host, by name
+    pub shims: Vec<String>,
+}
+
+/// Permissions the host is willing to grant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PermissionPolicy {
+    /// Allow network access
+    pub allow_network: bool,
+
+    /// Allow filesystem writes
+    pub allow_fs_write: bool,
+
+    /// Allow catch-all grants (any host, all of `$HOME`)
+    pub allow_wildcards: bool,
+}
+
+impl Default for PermissionPolicy {
+    fn default() -> Self {
+        Self {
+            allow_network: true,
+            allow_fs_write: true,
+            allow_wildcards: true,
+        }
+    }
+}
+
+/// The environment the plugin would be loaded into.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Environment {
+    /// Platform identifier (e.g., "darwin-aarch64")
+    pub platform: String,
+
+    /// Permission policy
+    pub permissions: PermissionPolicy,
+
+    /// Fail instead of warn when no checksum is declared for the platform
+    pub require_checksums: bool,
+}
+
+impl Environment {
+    /// The current platform with a permissive policy.
+    pub fn current() -> Self {
+        Self {
+            platform: current_platform(),
+            permissions: PermissionPolicy::default(),
+            require_checksums: false,
+        }
+    }
+}
+
+/// Outcome of a preflight check, ordered by severity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "snake_case")]
+pub enum PreflightStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+/// A single preflight check result.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct PreflightCheck {
+    /// Check name ("platform", "compatibility", "requirements", "permissions", "checksum")
+    pub name: String,
+
+    /// Outcome
+    pub status: PreflightStatus,
+
+    /// Human-readable explanation
+    pub message: String,
+}
+
+impl PreflightCheck {
+    fn new(name: &str, status: PreflightStatus, message: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for PreflightCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = match self.status {
+            PreflightStatus::Pass => "pass",
+            PreflightStatus::Warn => "warn",
+            PreflightStatus::Fail => "FAIL",
+        };
+        write!(f, "[{status}] {}: {}", self.name, self.message)
+    }
+}
+
+/// Result of `preflight()`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct PreflightReport {
+    /// Individual check results
+    pub checks: Vec<PreflightCheck>,
+}
+
+impl PreflightReport {
+    /// Worst status across all checks.
+    pub fn status(&self) -> PreflightStatus {
+        self.checks
+            .iter()
+            .map(|c| c.status)
+            .max()
+            .unwrap_or(PreflightStatus::Pass)
+    }
+
+    /// Whether the plugin may be loaded (no failures).
+    pub fn can_load(&self) -> bool {
+        self.status() != PreflightStatus::Fail
+    }
+
+    /// Checks that failed.
+    pub fn failures(&self) -> impl Iterator<Item = &PreflightCheck> {
+        self.checks
+            .iter()
+            .filter(|c| c.status == PreflightStatus::Fail)
+    }
+}
+
+/// Run all preflight checks for a plugin.
+pub fn preflight(manifest: &PluginManifest, host: &HostInfo, env: &Environment) -> PreflightReport {
+    PreflightReport {
+        checks: vec![
+            check_platform(manifest, env),
+            check_compatibility(manifest, host),
+            check_requirements(manifest, env),
+            check_permissions(manifest, env),
+            check_checksum(manifest, env),
+        ],
+    }
+}
+
+fn check_platform(manifest: &PluginManifest, env: &Environment) -> PreflightCheck {
+    let platforms = &manifest.compatibility.platforms;
+    let supported = match Platform::parse(&env.platform) {
+        Ok(platform) => platforms.iter().any(|p| platform.matches(p)),
+        Err(_) => platforms.iter().any(|p| *p == env.platform || p == "all"),
+    };
+    if platforms.is_empty() || supported {
+        PreflightCheck::new(
+            "platform",
+            PreflightStatus::Pass,
+            format!("{} is supported", env.platform),
+        )
+    } else {
+        PreflightCheck::new(
+            "platform",
+            PreflightStatus::Fail,
+            format!("{} is not in [{}]", env.platform, platforms.join(", ")),
+        )
+    }
+}
+
+fn check_compatibility(manifest: &PluginManifest, host: &HostInfo) -> PreflightCheck {
+    let compat = &manifest.compatibility;
+    let fail =
+        |message: String| PreflightCheck::new("compatibility", PreflightStatus::Fail, message);
+
+    let shim = match manifest.api_support(host.api_version, &host.shims) {
+        ApiSupport::Native => None,
+        ApiSupport::Shim(name) => Some(name),
+        ApiSupport::Unsupported => {
+            let missing = manifest
+                .shim_for(host.api_version)
+                .map(|name| format!(" (shim '{name}' is not available)"))
+                .unwrap_or_default();
+            return fail(format!(
+                "plugin API version {} does not match host API version {}{missing}",
+                compat.api_version, host.api_version
+            ));
+        }
+    };
+    match compat.satisfies_host(&host.version.to_string()) {
+        Ok(true) => {}
+        Ok(false) => {
+            let range = [
+                compat.min_host_version.as_ref().map(|v| format!(">={v}")),
+                compat.max_host_version.as_ref().map(|v| format!("<={v}")),
+            ];
+            let range: Vec<String> = range.into_iter().flatten().collect();
+            return fail(format!(
+                "host {} is outside {}",
+                host.version,
+                range.join(", ")
+            ));
+        }
+        Err(e) => return fail(e.to_string()),
+    }
+    match shim {
+        None => PreflightCheck::new(
+            "compatibility",
+            PreflightStatus::Pass,
+            format!(
+                "compatible with host {} (API {})",
+                host.version, host.api_version
+            ),
+        ),
+        Some(name) => PreflightCheck::new(
+            "compatibility",
+            PreflightStatus::Warn,
+            format!(
+                "compatible with host {} (API {}) through shim '{name}'",
+                host.version, host.api_version
+            ),
+        ),
+    }
+}
+
+fn check_requirements(manifest: &PluginManifest, env: &Environment) -> PreflightCheck {
+    let Some(requirements) = &manifest.requirements else {
+        return PreflightCheck::new(
+            "requirements",
+            PreflightStatus::Pass,
+            "no requirements declared",
+        );
+    };
+    let (os, arch) = env.platform.split_once('-').unwrap_or((&env.platform, ""));
+    let mut unmet = Vec::new();
+    if requirements
+        .os
+        .as_deref()
+        .is_some_and(|required| required != os)
+    {
+        unmet.push(format!(
+            "os {}",
+            requirements.os.as_deref().unwrap_or_default()
+        ));
+    }
+    if requirements
+        .arch
+        .as_deref()
+        .is_some_and(|required| required != arch)
+    {
+        unmet.push(format!(
+            "arch {}",
+            requirements.arch.as_deref().unwrap_or_default()
+        ));
+    }
+    if unmet.is_empty() {
+        PreflightCheck::new("requirements", PreflightStatus::Pass, "requirements met")
+    } else {
+        PreflightCheck::new(
+            "requirements",
+            PreflightStatus::Fail,
+            format!("requires {}", unmet.join(", ")),
+        )
+    }
+}
+
+fn check_permissions(manifest: &PluginManifest, env: &Environment) -> PreflightCheck {
+    let Some(permissions) = &manifest.permissions else {
+        return PreflightCheck::new(
+            "permissions",
+            PreflightStatus::Pass,
+            "no permissions requested",
+        );
+    };
+    let policy = &env.permissions;
+    let mut denied = Vec::new();
+    if !policy.allow_network
+        && permissions
+            .network
+            .as_ref()
+            .is_some_and(|n| !n.allow.is_empty())
+    {
+        denied.push("network access".to_string());
+    }
+    if !policy.allow_fs_write
+        && permissions
+            .fs
+            .as_ref()
+            .is_some_and(|fs| !fs.write.is_empty())
+    {
+        denied.push("filesystem writes".to_string());
+    }
+    let wildcards = permissions.wildcards();
+    if !policy.allow_wildcards {
+        denied.extend(wildcards.iter().map(|w| format!("wildcard grant ({w})")));
+    }
+
+    if !denied.is_empty() {
+        PreflightCheck::new(
+            "permissions",
+            PreflightStatus::Fail,
+            format!("policy denies {}", denied.join(", ")),
+        )
+    } else if !wildcards.is_empty() {
+        PreflightCheck::new(
+            "permissions",
+            PreflightStatus::Warn,
+            format!("broad grants: {}", wildcards.join(", ")),
+        )
+    } else {
+        PreflightCheck::new(
+            "permissions",
+            PreflightStatus::Pass,
+            "permissions allowed by policy",
+        )
+    }
+}
+
+fn check_checksum(manifest: &PluginManifest, env: &Environment) -> PreflightCheck {
+    if manifest.binary.checksum_for(&env.platform).is_some() {
+        return PreflightCheck::new("checksum", PreflightStatus::Pass, "checksum declared");
+    }
+    let status = if env.require_checksums {
+        PreflightStatus::Fail
+    } else {
+        PreflightStatus::Warn
+    };
+    PreflightCheck::new(
+        "checksum",
+        status,
+        format!("no checksum declared for {}", env.platform),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: &str = r#"
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.0.0"
+type = "extension"
+
+[compatibility]
+api_version = 2
+min_host_version = "0.8.0"
+platforms = ["linux-x86_64"]
+
+[binary.checksums]
+linux-x86_64 = "abc123"
+"#;
+
+    fn host(version: &str) -> HostInfo {
+        HostInfo {
+            version: Version::parse(version).unwrap(),
+            api_version: 2,
+            shims: Vec::new(),
+        }
+    }
+
+    fn linux() -> Environment {
+        Environment {
+            platform: "linux-x86_64".to_string(),
+            ..Environment::current()
+        }
+    }
+
+    #[test]
+    fn test_preflight_pass() {
+        let manifest = PluginManifest::from_toml(BASE).unwrap();
+        let report = preflight(&manifest, &host("0.9.0"), &linux());
+        assert_eq!(report.status(), PreflightStatus::Pass);
+        assert_eq!(report.checks.len(), 5);
+
+        let mut env = linux();
+        env.platform = "darwin-aarch64".to_string();
+        let report = preflight(&manifest, &host("0.7.0"), &env);
+        assert!(!report.can_load());
+        let failed: Vec<&str> = report.failures().map(|c| c.name.as_str()).collect();
+        assert_eq!(failed, ["platform", "compatibility"]);
+    }
+
+    #[test]
+    fn test_preflight_platform_aliases() {
+        let mut env = linux();
+        env.platform = "x86_64-unknown-linux-gnu".to_string();
+        env.require_checksums = true;
+        let manifest = PluginManifest::from_toml(BASE).unwrap();
+        let report = preflight(&manifest, &host("0.9.0"), &env);
+        assert_eq!(report.status(), PreflightStatus::Pass);
+
+        let toml = BASE.replace(r#"platforms = ["linux-x86_64"]"#, r#"platforms = ["linux-*"]"#);
+        let manifest = PluginManifest::from_toml(&toml).unwrap();
+        assert!(preflight(&manifest, &host("0.9.0"), &linux()).can_load());
+    }
+
+    #[test]
+    fn test_preflight_permissions() {
+        let toml = format!("{BASE}\n[permissions.network]\nallow = [\"*\"]\n");
+        let manifest = PluginManifest::from_toml(&toml).unwrap();
+
+        let report = preflight(&manifest, &host("1.0.0"), &linux());
+        assert_eq!(report.status(), PreflightStatus::Warn);
+
+        let mut env = linux();
+        env.permissions.allow_network = false;
+        let report = preflight(&manifest, &host("1.0.0"), &env);
+        assert_eq!(report.status(), PreflightStatus::Fail);
+        assert!(report
+            .failures()
+            .next()
+            .unwrap()
+            .to_string()
+            .contains("network access"));
+    }
+
+    #[test]
+    fn test_preflight_shims() {
+        let toml = BASE.replace("api_version = 2", "api_version = 3")
+            + "\n[compat.shims.api-v2]\napi_version = 2\n";
+        let manifest = PluginManifest::from_toml(&toml).unwrap();
+
+        let report = preflight(&manifest, &host("1.0.0"), &linux());
+        assert!(report.failures().next().unwrap().message.contains("api-v2"));
+
+        let mut host = host("1.0.0");
+        host.shims.push("api-v2".to_string());
+        let report = preflight(&manifest, &host, &linux());
+        assert_eq!(report.status(), PreflightStatus::Warn);
+        assert!(report.can_load());
+    }
+}
@@ -0,0 +1,2176 @@
+//! Protobuf representation of the manifest model.
+//!
+//! The messages match `proto/plugin_manifest.proto` and are encoded with
+//! prost (`prost::Message::encode_to_vec` / `decode`). Conversions from the
+//! core types are infallible; conversions back fail if a required message
+//! is missing or a config value isn't a valid TOML literal.
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::assets;
+use crate::cli;
+use crate::effective;
+use crate::error::ManifestError;
+use crate::health;
+use crate::hooks;
+use crate::hosts;
+use crate::logging;
+use crate::messaging;
+use crate::migrate::CURRENT_MANIFEST_VERSION;
+use crate::migrations;
+use crate::observability;
+use crate::options;
+use crate::package;
+use crate::permissions;
+use crate::plugin;
+use crate::post_install;
+use crate::resources;
+use crate::shims;
+use crate::storage;
+use crate::uninstall;
+
+/// Protobuf `PluginManifest`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct PluginManifest {
+    #[prost(message, optional, tag = "1")]
+    pub plugin: Option<PluginMeta>,
+    #[prost(message, optional, tag = "2")]
+    pub compatibility: Option<CompatibilityInfo>,
+    #[prost(message, optional, tag = "3")]
+    pub binary: Option<BinaryInfo>,
+    #[prost(message, optional, tag = "4")]
+    pub signature: Option<SignatureInfo>,
+    #[prost(message, optional, tag = "5")]
+    pub config: Option<ConfigInfo>,
+    #[prost(message, repeated, tag = "6")]
+    pub provides: Vec<ServiceDeclaration>,
+    #[prost(message, repeated, tag = "7")]
+    pub requires: Vec<ServiceRequirement>,
+    #[prost(message, optional, tag = "8")]
+    pub cli: Option<CliConfig>,
+    #[prost(message, repeated, tag = "9")]
+    pub capabilities: Vec<CapabilityDeclaration>,
+    #[prost(message, optional, tag = "10")]
+    pub tags: Option<TagsInfo>,
+    #[prost(message, optional, tag = "11")]
+    pub hive: Option<HiveInfo>,
+    #[prost(message, optional, tag = "12")]
+    pub translation: Option<TranslationInfo>,
+    #[prost(message, optional, tag = "13")]
+    pub language: Option<LanguageInfo>,
+    #[prost(message, optional, tag = "14")]
+    pub requirements: Option<RequirementsInfo>,
+    #[prost(message, optional, tag = "15")]
+    pub hooks: Option<HooksInfo>,
+    #[prost(message, optional, tag = "16")]
+    pub uninstall: Option<UninstallInfo>,
+    #[prost(message, optional, tag = "17")]
+    pub post_install_message: Option<PostInstallMessage>,
+    #[prost(message, repeated, tag = "18")]
+    pub migrations: Vec<Migration>,
+    #[prost(message, optional, tag = "19")]
+    pub storage: Option<StorageInfo>,
+    #[prost(message, optional, tag = "20")]
+    pub permissions: Option<Permissions>,
+    #[prost(message, repeated, tag = "21")]
+    pub messaging: Vec<MessagingTopic>,
+    #[prost(message, optional, tag = "22")]
+    pub resources: Option<ResourcesInfo>,
+    #[prost(message, optional, tag = "23")]
+    pub health: Option<HealthInfo>,
+    #[prost(message, optional, tag = "24")]
+    pub observability: Option<ObservabilityInfo>,
+    #[prost(message, optional, tag = "25")]
+    pub logging: Option<LoggingInfo>,
+    #[prost(map = "string, string", tag = "26")]
+    pub target: HashMap<String, String>,
+    #[prost(map = "string, string", tag = "27")]
+    pub features: HashMap<String, String>,
+    #[prost(map = "string, message", tag = "28")]
+    pub locales: HashMap<String, LocalizedMeta>,
+    #[prost(message, optional, tag = "29")]
+    pub compat: Option<CompatInfo>,
+    #[prost(uint32, tag = "30")]
+    pub manifest_version: u32,
+    #[prost(message, repeated, tag = "31")]
+    pub assets: Vec<Asset>,
+    #[prost(message, optional, tag = "32")]
+    pub theme: Option<ThemeInfo>,
+}
+
+/// Protobuf `PluginMeta`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct PluginMeta {
+    #[prost(string, tag = "1")]
+    pub id: String,
+    #[prost(string, tag = "2")]
+    pub name: String,
+    #[prost(string, tag = "3")]
+    pub version: String,
+    #[prost(string, tag = "4")]
+    pub plugin_type: String,
+    #[prost(string, tag = "5")]
+    pub author: String,
+    #[prost(string, tag = "6")]
+    pub description: String,
+    #[prost(string, optional, tag = "7")]
+    pub license: Option<String>,
+    #[prost(string, optional, tag = "8")]
+    pub homepage: Option<String>,
+    #[prost(string, optional, tag = "9")]
+    pub repository: Option<String>,
+    #[prost(string, optional, tag = "10")]
+    pub license_file: Option<String>,
+}
+
+/// Protobuf `CompatibilityInfo`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct CompatibilityInfo {
+    #[prost(uint32, tag = "1")]
+    pub api_version: u32,
+    #[prost(string, optional, tag = "2")]
+    pub min_host_version: Option<String>,
+    #[prost(string, optional, tag = "3")]
+    pub max_host_version: Option<String>,
+    #[prost(string, repeated, tag = "4")]
+    pub platforms: Vec<String>,
+    #[prost(string, repeated, tag = "5")]
+    pub depends_on: Vec<String>,
+    #[prost(string, repeated, tag = "6")]
+    pub hosts: Vec<String>,
+    #[prost(map = "string, message", tag = "7")]
+    pub host_versions: HashMap<String, HostVersionRange>,
+}
+
+/// Protobuf `HostVersionRange`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct HostVersionRange {
+    #[prost(string, optional, tag = "1")]
+    pub min_host_version: Option<String>,
+    #[prost(string, optional, tag = "2")]
+    pub max_host_version: Option<String>,
+}
+
+/// Protobuf `BinaryInfo`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct BinaryInfo {
+    #[prost(string, tag = "1")]
+    pub name: String,
+    #[prost(map = "string, string", tag = "2")]
+    pub checksums: HashMap<String, String>,
+    #[prost(map = "string, uint64", tag = "3")]
+    pub sizes: HashMap<String, u64>,
+    #[prost(string, optional, tag = "4")]
+    pub path: Option<String>,
+    #[prost(map = "string, message", tag = "5")]
+    pub platform: HashMap<String, BinaryOverride>,
+    #[prost(enumeration = "BinaryKind", tag = "6")]
+    pub kind: i32,
+    #[prost(message, optional, tag = "7")]
+    pub abi_requirements: Option<AbiRequirements>,
+}
+
+/// Protobuf `AbiRequirements`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct AbiRequirements {
+    #[prost(string, optional, tag = "1")]
+    pub glibc: Option<String>,
+    #[prost(string, optional, tag = "2")]
+    pub macos: Option<String>,
+    #[prost(string, optional, tag = "3")]
+    pub msvc_runtime: Option<String>,
+}
+
+/// Protobuf `BinaryKind`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum BinaryKind {
+    Native = 0,
+    Wasm = 1,
+    Executable = 2,
+    Script = 3,
+}
+
+/// Protobuf `BinaryOverride`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct BinaryOverride {
+    #[prost(string, optional, tag = "1")]
+    pub name: Option<String>,
+    #[prost(string, optional, tag = "2")]
+    pub path: Option<String>,
+    #[prost(string, optional, tag = "3")]
+    pub checksum: Option<String>,
+}
+
+/// Protobuf `SignatureInfo`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct SignatureInfo {
+    #[prost(string, tag = "1")]
+    pub public_key: String,
+    #[prost(string, tag = "2")]
+    pub signature_file: String,
+}
+
+/// Protobuf `ConfigInfo`. Values are TOML value literals.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ConfigInfo {
+    #[prost(map = "string, string", tag = "1")]
+    pub defaults: HashMap<String, String>,
+    #[prost(map = "string, string", tag = "2")]
+    pub profiles: HashMap<String, String>,
+}
+
+/// Protobuf `ServiceDeclaration`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ServiceDeclaration {
+    #[prost(string, tag = "1")]
+    pub id: String,
+    #[prost(string, tag = "2")]
+    pub version: String,
+    #[prost(string, tag = "3")]
+    pub description: String,
+    #[prost(bool, tag = "4")]
+    pub deprecated: bool,
+    #[prost(string, optional, tag = "5")]
+    pub replaced_by: Option<String>,
+}
+
+/// Protobuf `ServiceRequirement`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ServiceRequirement {
+    #[prost(string, tag = "1")]
+    pub id: String,
+    #[prost(string, optional, tag = "2")]
+    pub min_version: Option<String>,
+    #[prost(bool, tag = "3")]
+    pub optional: bool,
+    #[prost(enumeration = "ServiceSource", tag = "4")]
+    pub provider: i32,
+}
+
+/// Protobuf `ServiceSource`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum ServiceSource {
+    Plugin = 0,
+    Host = 1,
+    Any = 2,
+}
+
+/// Protobuf `CliConfig`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct CliConfig {
+    #[prost(string, tag = "1")]
+    pub command: String,
+    #[prost(string, tag = "2")]
+    pub description: String,
+    #[prost(string, repeated, tag = "3")]
+    pub aliases: Vec<String>,
+    #[prost(bool, tag = "4")]
+    pub dynamic_completions: bool,
+    #[prost(message, repeated, tag = "5")]
+    pub args: Vec<CliArg>,
+    #[prost(message, repeated, tag = "6")]
+    pub subcommands: Vec<CliSubcommand>,
+}
+
+/// Protobuf `CliSubcommand`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct CliSubcommand {
+    #[prost(string, tag = "1")]
+    pub name: String,
+    #[prost(string, tag = "2")]
+    pub description: String,
+    #[prost(string, repeated, tag = "3")]
+    pub aliases: Vec<String>,
+    #[prost(message, repeated, tag = "4")]
+    pub args: Vec<CliArg>,
+    #[prost(message, repeated, tag = "5")]
+    pub subcommands: Vec<CliSubcommand>,
+}
+
+/// Protobuf `CliArg`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct CliArg {
+    #[prost(string, tag = "1")]
+    pub name: String,
+    #[prost(string, tag = "2")]
+    pub description: String,
+    #[prost(enumeration = "CliArgType", tag = "3")]
+    pub arg_type: i32,
+    #[prost(bool, tag = "4")]
+    pub required: bool,
+    #[prost(string, optional, tag = "5")]
+    pub long: Option<String>,
+    #[prost(string, optional, tag = "6")]
+    pub short: Option<String>,
+    #[prost(string, repeated, tag = "7")]
+    pub choices: Vec<String>,
+}
+
+/// Protobuf `CliArgType`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum CliArgType {
+    String = 0,
+    Path = 1,
+    Number = 2,
+    Bool = 3,
+}
+
+/// Protobuf `CapabilityDeclaration`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct CapabilityDeclaration {
+    #[prost(string, tag = "1")]
+    pub protocol: String,
+    #[prost(string, tag = "2")]
+    pub version: String,
+    #[prost(string, tag = "3")]
+    pub description: String,
+    #[prost(string, optional, tag = "4")]
+    pub implements: Option<String>,
+}
+
+/// Protobuf `TagsInfo`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct TagsInfo {
+    #[prost(string, repeated, tag = "1")]
+    pub categories: Vec<String>,
+    #[prost(string, repeated, tag = "2")]
+    pub platforms: Vec<String>,
+}
+
+/// Protobuf `HiveInfo`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct HiveInfo {
+    #[prost(string, tag = "1")]
+    pub category: String,
+    #[prost(string, tag = "2")]
+    pub name: String,
+}
+
+/// Protobuf `TranslationInfo`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct TranslationInfo {
+    #[prost(string, tag = "1")]
+    pub translates: String,
+    #[prost(string, tag = "2")]
+    pub language: String,
+    #[prost(string, tag = "3")]
+    pub language_name: String,
+    #[prost(string, tag = "4")]
+    pub namespace: String,
+}
+
+/// Protobuf `LanguageInfo`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct LanguageInfo {
+    #[prost(string, tag = "1")]
+    pub id: String,
+    #[prost(string, repeated, tag = "2")]
+    pub extensions: Vec<String>,
+}
+
+/// Protobuf `ThemeInfo`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ThemeInfo {
+    #[prost(string, tag = "1")]
+    pub appearance: String,
+    #[prost(string, optional, tag = "2")]
+    pub extends: Option<String>,
+}
+
+/// Protobuf `RequirementsInfo`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RequirementsInfo {
+    #[prost(string, optional, tag = "1")]
+    pub os: Option<String>,
+    #[prost(string, optional, tag = "2")]
+    pub arch: Option<String>,
+    #[prost(string, optional, tag = "3")]
+    pub notes: Option<String>,
+}
+
+/// Protobuf `HookAction`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct HookAction {
+    #[prost(string, optional, tag = "1")]
+    pub script: Option<String>,
+    #[prost(string, optional, tag = "2")]
+    pub service: Option<String>,
+}
+
+/// Protobuf `HooksInfo`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct HooksInfo {
+    #[prost(message, optional, tag = "1")]
+    pub pre_install: Option<HookAction>,
+    #[prost(message, optional, tag = "2")]
+    pub post_install: Option<HookAction>,
+    #[prost(message, optional, tag = "3")]
+    pub pre_update: Option<HookAction>,
+    #[prost(message, optional, tag = "4")]
+    pub pre_uninstall: Option<HookAction>,
+}
+
+/// Protobuf `CleanupPolicy`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum CleanupPolicy {
+    Remove = 0,
+    KeepByDefault = 1,
+}
+
+/// Protobuf `CleanupPath`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct CleanupPath {
+    #[prost(string, tag = "1")]
+    pub path: String,
+    #[prost(enumeration = "CleanupPolicy", tag = "2")]
+    pub policy: i32,
+    #[prost(string, optional, tag = "3")]
+    pub description: Option<String>,
+}
+
+/// Protobuf `CleanupResource`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct CleanupResource {
+    #[prost(string, tag = "1")]
+    pub id: String,
+    #[prost(enumeration = "CleanupPolicy", tag = "2")]
+    pub policy: i32,
+    #[prost(string, optional, tag = "3")]
+    pub description: Option<String>,
+}
+
+/// Protobuf `UninstallInfo`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct UninstallInfo {
+    #[prost(message, repeated, tag = "1")]
+    pub data: Vec<CleanupPath>,
+    #[prost(message, repeated, tag = "2")]
+    pub caches: Vec<CleanupPath>,
+    #[prost(message, repeated, tag = "3")]
+    pub resources: Vec<CleanupResource>,
+}
+
+/// Protobuf `MessageFormat`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum MessageFormat {
+    Plain = 0,
+    Markdown = 1,
+}
+
+/// Protobuf `PostInstallMessage`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct PostInstallMessage {
+    #[prost(string, tag = "1")]
+    pub text: String,
+    #[prost(enumeration = "MessageFormat", tag = "2")]
+    pub format: i32,
+}
+
+/// Protobuf `Migration`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Migration {
+    #[prost(string, tag = "1")]
+    pub from_version: String,
+    #[prost(string, optional, tag = "2")]
+    pub script: Option<String>,
+    #[prost(string, optional, tag = "3")]
+    pub service: Option<String>,
+    #[prost(string, tag = "4")]
+    pub description: String,
+}
+
+/// Protobuf `StorageDir`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct StorageDir {
+    #[prost(uint64, optional, tag = "1")]
+    pub quota_mb: Option<u64>,
+}
+
+/// Protobuf `StorageInfo`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct StorageInfo {
+    #[prost(message, optional, tag = "1")]
+    pub data: Option<StorageDir>,
+    #[prost(message, optional, tag = "2")]
+    pub cache: Option<StorageDir>,
+    #[prost(message, optional, tag = "3")]
+    pub state: Option<StorageDir>,
+}
+
+/// Protobuf `NetworkPermissions`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct NetworkPermissions {
+    #[prost(string, repeated, tag = "1")]
+    pub allow: Vec<String>,
+}
+
+/// Protobuf `FsPermissions`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct FsPermissions {
+    #[prost(string, repeated, tag = "1")]
+    pub read: Vec<String>,
+    #[prost(string, repeated, tag = "2")]
+    pub write: Vec<String>,
+}
+
+/// Protobuf `EnvPermissions`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct EnvPermissions {
+    #[prost(string, repeated, tag = "1")]
+    pub allow: Vec<String>,
+}
+
+/// Protobuf `SubprocessPermissions`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct SubprocessPermissions {
+    #[prost(string, repeated, tag = "1")]
+    pub allow: Vec<String>,
+}
+
+/// Protobuf `Permissions`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Permissions {
+    #[prost(message, optional, tag = "1")]
+    pub network: Option<NetworkPermissions>,
+    #[prost(message, optional, tag = "2")]
+    pub fs: Option<FsPermissions>,
+    #[prost(message, optional, tag = "3")]
+    pub env: Option<EnvPermissions>,
+    #[prost(message, optional, tag = "4")]
+    pub subprocess: Option<SubprocessPermissions>,
+}
+
+/// Protobuf `AssetKind`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum AssetKind {
+    Theme = 0,
+    Font = 1,
+    Icon = 2,
+    Image = 3,
+    Data = 4,
+}
+
+/// Protobuf `Asset`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Asset {
+    #[prost(string, tag = "1")]
+    pub path: String,
+    #[prost(enumeration = "AssetKind", tag = "2")]
+    pub kind: i32,
+    #[prost(string, optional, tag = "3")]
+    pub target: Option<String>,
+    #[prost(string, repeated, tag = "4")]
+    pub platforms: Vec<String>,
+    #[prost(string, optional, tag = "5")]
+    pub checksum: Option<String>,
+}
+
+/// Protobuf `TopicDirection`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum TopicDirection {
+    Publish = 0,
+    Subscribe = 1,
+    Both = 2,
+}
+
+/// Protobuf `MessagingTopic`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct MessagingTopic {
+    #[prost(string, tag = "1")]
+    pub topic: String,
+    #[prost(uint32, tag = "2")]
+    pub schema_version: u32,
+    #[prost(enumeration = "TopicDirection", tag = "3")]
+    pub direction: i32,
+}
+
+/// Protobuf `ResourceKind`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum ResourceKind {
+    Port = 0,
+    Cache = 1,
+    Hardware = 2,
+    Lock = 3,
+}
+
+/// Protobuf `ResourceClaim`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ResourceClaim {
+    #[prost(enumeration = "ResourceKind", tag = "1")]
+    pub kind: i32,
+    #[prost(string, tag = "2")]
+    pub name: String,
+    #[prost(bool, optional, tag = "3")]
+    pub exclusive: Option<bool>,
+}
+
+/// Protobuf `ResourcesInfo`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ResourcesInfo {
+    #[prost(message, repeated, tag = "1")]
+    pub claims: Vec<ResourceClaim>,
+}
+
+/// Protobuf `ProbeKind`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum ProbeKind {
+    ServiceCall = 0,
+    Command = 1,
+}
+
+/// Protobuf `RestartPolicy`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum RestartPolicy {
+    Never = 0,
+    OnFailure = 1,
+}
+
+/// Protobuf `HealthInfo`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct HealthInfo {
+    #[prost(enumeration = "ProbeKind", tag = "1")]
+    pub probe: i32,
+    #[prost(string, tag = "2")]
+    pub target: String,
+    #[prost(uint64, tag = "3")]
+    pub interval_secs: u64,
+    #[prost(uint32, tag = "4")]
+    pub failure_threshold: u32,
+    #[prost(enumeration = "RestartPolicy", tag = "5")]
+    pub restart: i32,
+}
+
+/// Protobuf `ObservabilityInfo`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ObservabilityInfo {
+    #[prost(string, repeated, tag = "1")]
+    pub metrics: Vec<String>,
+    #[prost(bool, tag = "2")]
+    pub tracing: bool,
+}
+
+/// Protobuf `LogLevel`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum LogLevel {
+    Info = 0,
+    Error = 1,
+    Warn = 2,
+    Debug = 3,
+    Trace = 4,
+}
+
+/// Protobuf `LoggingInfo`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct LoggingInfo {
+    #[prost(enumeration = "LogLevel", tag = "1")]
+    pub level: i32,
+    #[prost(map = "string, enumeration(LogLevel)", tag = "2")]
+    pub targets: HashMap<String, i32>,
+    #[prost(bool, tag = "3")]
+    pub user_content: bool,
+}
+
+/// Protobuf `LocalizedMeta`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct LocalizedMeta {
+    #[prost(string, optional, tag = "1")]
+    pub name: Option<String>,
+    #[prost(string, optional, tag = "2")]
+    pub description: Option<String>,
+}
+
+/// Protobuf `CompatInfo`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct CompatInfo {
+    #[prost(map = "string, message", tag = "1")]
+    pub shims: HashMap<String, ShimInfo>,
+}
+
+/// Protobuf `ShimInfo`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ShimInfo {
+    #[prost(uint32, tag = "1")]
+    pub api_version: u32,
+}
+
+/// Protobuf `PackageManifest`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct PackageManifest {
+    #[prost(message, optional, tag = "1")]
+    pub package: Option<PackageMeta>,
+    #[prost(message, optional, tag = "2")]
+    pub compatibility: Option<CompatibilityInfo>,
+    #[prost(message, repeated, tag = "3")]
+    pub plugins: Vec<PluginDef>,
+    #[prost(message, optional, tag = "4")]
+    pub binary: Option<PackageBinaryInfo>,
+    #[prost(message, optional, tag = "5")]
+    pub signature: Option<SignatureInfo>,
+    #[prost(uint32, tag = "6")]
+    pub manifest_version: u32,
+}
+
+/// Protobuf `PackageMeta`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct PackageMeta {
+    #[prost(string, tag = "1")]
+    pub id: String,
+    #[prost(string, tag = "2")]
+    pub name: String,
+    #[prost(string, tag = "3")]
+    pub version: String,
+    #[prost(string, tag = "4")]
+    pub author: String,
+    #[prost(string, tag = "5")]
+    pub description: String,
+    #[prost(string, optional, tag = "6")]
+    pub license: Option<String>,
+    #[prost(string, optional, tag = "7")]
+    pub homepage: Option<String>,
+    #[prost(string, optional, tag = "8")]
+    pub repository: Option<String>,
+    #[prost(string, optional, tag = "9")]
+    pub license_file: Option<String>,
+}
+
+/// Protobuf `PluginDef`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct PluginDef {
+    #[prost(string, tag = "1")]
+    pub id: String,
+    #[prost(string, tag = "2")]
+    pub name: String,
+    #[prost(string, tag = "3")]
+    pub plugin_type: String,
+    #[prost(string, tag = "4")]
+    pub binary: String,
+    #[prost(string, optional, tag = "5")]
+    pub description: Option<String>,
+    #[prost(string, repeated, tag = "6")]
+    pub depends_on: Vec<String>,
+    #[prost(message, optional, tag = "7")]
+    pub config: Option<ConfigInfo>,
+    #[prost(message, repeated, tag = "8")]
+    pub provides: Vec<ServiceDeclaration>,
+    #[prost(message, repeated, tag = "9")]
+    pub requires: Vec<ServiceRequirement>,
+    #[prost(message, repeated, tag = "10")]
+    pub assets: Vec<Asset>,
+    #[prost(message, optional, tag = "11")]
+    pub compatibility: Option<CompatibilityOverride>,
+}
+
+/// Protobuf `CompatibilityOverride`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct CompatibilityOverride {
+    #[prost(string, optional, tag = "1")]
+    pub min_host_version: Option<String>,
+    #[prost(string, optional, tag = "2")]
+    pub max_host_version: Option<String>,
+    #[prost(string, repeated, tag = "3")]
+    pub platforms: Vec<String>,
+}
+
+/// Protobuf `PackageBinaryInfo`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct PackageBinaryInfo {
+    #[prost(map = "string, string", tag = "1")]
+    pub checksums: HashMap<String, String>,
+    #[prost(map = "string, uint64", tag = "2")]
+    pub sizes: HashMap<String, u64>,
+}
+
+// Core -> protobuf
+
+/// Messages encoded before `manifest_version` existed decode as 0, which
+/// proto3 also uses for the field's default; both mean the current format.
+fn decode_manifest_version(version: u32) -> u32 {
+    if version == 0 {
+        CURRENT_MANIFEST_VERSION
+    } else {
+        version
+    }
+}
+
+impl From<&plugin::PluginManifest> for PluginManifest {
+    fn from(m: &plugin::PluginManifest) -> Self {
+        Self {
+            manifest_version: m.manifest_version,
+            plugin: Some((&m.plugin).into()),
+            compatibility: Some((&m.compatibility).into()),
+            binary: Some((&m.binary).into()),
+            signature: m.signature.as_ref().map(Into::into),
+            config: Some((&m.config).into()),
+            provides: m.provides.iter().map(Into::into).collect(),
+            requires: m.requires.iter().map(Into::into).collect(),
+            cli: m.cli.as_ref().map(Into::into),
+            capabilities: m.capabilities.iter().map(Into::into).collect(),
+            tags: m.tags.as_ref().map(Into::into),
+            hive: m.hive.as_ref().map(Into::into),
+            translation: m.translation.as_ref().map(Into::into),
+            language: m.language.as_ref().map(Into::into),
+            theme: m.theme.as_ref().map(Into::into),
+            requirements: m.requirements.as_ref().map(Into::into),
+            hooks: m.hooks.as_ref().map(Into::into),
+            uninstall: m.uninstall.as_ref().map(Into::into),
+            post_install_message: m.post_install_message.as_ref().map(Into::into),
+            migrations: m.migrations.iter().map(Into::into).collect(),
+            storage: m.storage.as_ref().map(Into::into),
+            permissions: m.permissions.as_ref().map(Into::into),
+            messaging: m.messaging.iter().map(Into::into).collect(),
+            assets: m.assets.iter().map(Into::into).collect(),
+            resources: m.resources.as_ref().map(Into::into),
+            health: m.health.as_ref().map(Into::into),
+            observability: m.observability.as_ref().map(|o| ObservabilityInfo {
+                metrics: o.metrics.clone(),
+                tracing: o.tracing,
+            }),
+            logging: m.logging.as_ref().map(Into::into),
+            target: table_literals(&m.target),
+            features: table_literals(&m.features),
+            locales: m
+                .locales
+                .iter()
+                .map(|(k, l)| {
+                    let localized = LocalizedMeta {
+                        name: l.name.clone(),
+                        description: l.description.clone(),
+                    };
+                    (k.clone(), localized)
+                })
+                .collect(),
+            compat: m.compat.as_ref().map(|c| CompatInfo {
+                shims: c
+                    .shims
+                    .iter()
+                    .map(|(name, s)| {
+                        let shim = ShimInfo {
+                            api_version: s.api_version,
+                        };
+                        (name.clone(), shim)
+                    })
+                    .collect(),
+            }),
+        }
+    }
+}
+
+impl From<&plugin::PluginMeta> for PluginMeta {
+    fn from(m: &plugin::PluginMeta) -> Self {
+        Self {
+            id: m.id.clone(),
+            name: m.name.clone(),
+            version: m.version.clone(),
+            plugin_type: m.plugin_type.to_string(),
+            author: m.author.clone(),
+            description: m.description.clone(),
+            license: m.license.clone(),
+            homepage: m.homepage.clone(),
+            repository: m.repository.clone(),
+            license_file: m.license_file.clone(),
+        }
+    }
+}
+
+impl From<&plugin::CompatibilityInfo> for CompatibilityInfo {
+    fn from(c: &plugin::CompatibilityInfo) -> Self {
+        Self {
+            api_version: c.api_version,
+            min_host_version: c.min_host_version.clone(),
+            max_host_version: c.max_host_version.clone(),
+            platforms: c.platforms.clone(),
+            depends_on: c.depends_on.clone(),
+            hosts: c.hosts.clone(),
+            host_versions: c
+                .host_versions
+                .iter()
+                .map(|(host, r)| {
+                    let range = HostVersionRange {
+                        min_host_version: r.min_host_version.clone(),
+                        max_host_version: r.max_host_version.clone(),
+                    };
+                    (host.clone(), range)
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<&plugin::BinaryInfo> for BinaryInfo {
+    fn from(b: &plugin::BinaryInfo) -> Self {
+        Self {
+            name: b.name.clone(),
+            checksums: b.checksums.clone(),
+            sizes: b.sizes.clone(),
+            path: b.path.clone(),
+            platform: b
+                .platform
+                .iter()
+                .map(|(k, o)| {
+                    let o = BinaryOverride {
+                        name: o.name.clone(),
+                        path: o.path.clone(),
+                        checksum: o.checksum.clone(),
+                    };
+                    (k.clone(), o)
+                })
+                .collect(),
+            kind: match b.kind {
+                plugin::BinaryKind::Native => BinaryKind::Native,
+                plugin::BinaryKind::Wasm => BinaryKind::Wasm,
+                plugin::BinaryKind::Executable => BinaryKind::Executable,
+                plugin::BinaryKind::Script => BinaryKind::Script,
+            } as i32,
+            abi_requirements: b.abi_requirements.as_ref().map(|a| AbiRequirements {
+                glibc: a.glibc.clone(),
+                macos: a.macos.clone(),
+                msvc_runtime: a.msvc_runtime.clone(),
+            }),
+        }
+    }
+}
+
+impl From<&plugin::SignatureInfo> for SignatureInfo {
+    fn from(s: &plugin::SignatureInfo) -> Self {
+        Self {
+            public_key: s.public_key.clone(),
+            signature_file: s.signature_file.clone(),
+        }
+    }
+}
+
+impl From<&plugin::ConfigInfo> for ConfigInfo {
+    fn from(c: &plugin::ConfigInfo) -> Self {
+        Self {
+            defaults: c
+                .defaults
+                .iter()
+                .map(|(k, v)| (k.clone(), v.to_string()))
+                .collect(),
+            profiles: table_literals(&c.profiles),
+        }
+    }
+}
+
+impl From<&plugin::ServiceDeclaration> for ServiceDeclaration {
+    fn from(s: &plugin::ServiceDeclaration) -> Self {
+        Self {
+            id: s.id.clone(),
+            version: s.version.clone(),
+            description: s.description.clone(),
+            deprecated: s.deprecated,
+            replaced_by: s.replaced_by.clone(),
+        }
+    }
+}
+
+impl From<&plugin::ServiceRequirement> for ServiceRequirement {
+    fn from(s: &plugin::ServiceRequirement) -> Self {
+        Self {
+            id: s.id.clone(),
+            min_version: s.min_version.clone(),
+            optional: s.optional,
+            provider: match s.provider {
+                plugin::ServiceSource::Plugin => ServiceSource::Plugin,
+                plugin::ServiceSource::Host => ServiceSource::Host,
+                plugin::ServiceSource::Any => ServiceSource::Any,
+            } as i32,
+        }
+    }
+}
+
+impl From<&plugin::CliConfig> for CliConfig {
+    fn from(c: &plugin::CliConfig) -> Self {
+        Self {
+            command: c.command.clone(),
+            description: c.description.clone(),
+            aliases: c.aliases.clone(),
+            dynamic_completions: c.dynamic_completions,
+            args: c.args.iter().map(Into::into).collect(),
+            subcommands: c.subcommands.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<&cli::CliSubcommand> for CliSubcommand {
+    fn from(s: &cli::CliSubcommand) -> Self {
+        Self {
+            name: s.name.clone(),
+            description: s.description.clone(),
+            aliases: s.aliases.clone(),
+            args: s.args.iter().map(Into::into).collect(),
+            subcommands: s.subcommands.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<&cli::CliArg> for CliArg {
+    fn from(a: &cli::CliArg) -> Self {
+        Self {
+            name: a.name.clone(),
+            description: a.description.clone(),
+            arg_type: match a.arg_type {
+                cli::CliArgType::String => CliArgType::String,
+                cli::CliArgType::Path => CliArgType::Path,
+                cli::CliArgType::Number => CliArgType::Number,
+                cli::CliArgType::Bool => CliArgType::Bool,
+            } as i32,
+            required: a.required,
+            long: a.long.clone(),
+            short: a.short.clone(),
+            choices: a.choices.clone(),
+        }
+    }
+}
+
+impl From<&plugin::CapabilityDeclaration> for CapabilityDeclaration {
+    fn from(c: &plugin::CapabilityDeclaration) -> Self {
+        Self {
+            protocol: c.protocol.clone(),
+            version: c.version.clone(),
+            description: c.description.clone(),
+            implements: c.implements.clone(),
+        }
+    }
+}
+
+impl From<&plugin::TagsInfo> for TagsInfo {
+    fn from(t: &plugin::TagsInfo) -> Self {
+        Self {
+            categories: t.categories.clone(),
+            platforms: t.platforms.clone(),
+        }
+    }
+}
+
+impl From<&plugin::HiveInfo> for HiveInfo {
+    fn from(h: &plugin::HiveInfo) -> Self {
+        Self {
+            category: h.category.clone(),
+            name: h.name.clone(),
+        }
+    }
+}
+
+impl From<&plugin::TranslationInfo> for TranslationInfo {
+    fn from(t: &plugin::TranslationInfo) -> Self {
+        Self {
+            translates: t.translates.clone(),
+            language: t.language.clone(),
+            language_name: t.language_name.clone(),
+            namespace: t.namespace.clone(),
+        }
+    }
+}
+
+impl From<&plugin::LanguageInfo> for LanguageInfo {
+    fn from(l: &plugin::LanguageInfo) -> Self {
+        Self {
+            id: l.id.clone(),
+            extensions: l.extensions.clone(),
+        }
+    }
+}
+
+impl From<&plugin::ThemeInfo> for ThemeInfo {
+    fn from(t: &plugin::ThemeInfo) -> Self {
+        Self {
+            appearance: t.appearance.clone(),
+            extends: t.extends.clone(),
+        }
+    }
+}
+
+impl From<&plugin::RequirementsInfo> for RequirementsInfo {
+    fn from(r: &plugin::RequirementsInfo) -> Self {
+        Self {
+            os: r.os.clone(),
+            arch: r.arch.clone(),
+            notes: r.notes.clone(),
+        }
+    }
+}
+
+impl From<&hooks::HookAction> for HookAction {
+    fn from(a: &hooks::HookAction) -> Self {
+        Self {
+            script: a.script.clone(),
+            service: a.service.clone(),
+        }
+    }
+}
+
+impl From<&hooks::HooksInfo> for HooksInfo {
+    fn from(h: &hooks::HooksInfo) -> Self {
+        Self {
+            pre_install: h.pre_install.as_ref().map(Into::into),
+            post_install: h.post_install.as_ref().map(Into::into),
+            pre_update: h.pre_update.as_ref().map(Into::into),
+            pre_uninstall: h.pre_uninstall.as_ref().map(Into::into),
+        }
+    }
+}
+
+impl From<uninstall::CleanupPolicy> for CleanupPolicy {
+    fn from(p: uninstall::CleanupPolicy) -> Self {
+        match p {
+            uninstall::CleanupPolicy::Remove => CleanupPolicy::Remove,
+            uninstall::CleanupPolicy::KeepByDefault => CleanupPolicy::KeepByDefault,
+        }
+    }
+}
+
+impl From<&uninstall::CleanupPath> for CleanupPath {
+    fn from(e: &uninstall::CleanupPath) -> Self {
+        Self {
+            path: e.path.clone(),
+            policy: CleanupPolicy::from(e.policy) as i32,
+            description: e.description.clone(),
+        }
+    }
+}
+
+impl From<&uninstall::CleanupResource> for CleanupResource {
+    fn from(r: &uninstall::CleanupResource) -> Self {
+        Self {
+            id: r.id.clone(),
+            policy: CleanupPolicy::from(r.policy) as i32,
+            description: r.description.clone(),
+        }
+    }
+}
+
+impl From<&uninstall::UninstallInfo> for UninstallInfo {
+    fn from(u: &uninstall::UninstallInfo) -> Self {
+        Self {
+            data: u.data.iter().map(Into::into).collect(),
+            caches: u.caches.iter().map(Into::into).collect(),
+            resources: u.resources.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<&post_install::PostInstallMessage> for PostInstallMessage {
+    fn from(m: &post_install::PostInstallMessage) -> Self {
+        let format = match m.format {
+            post_install::MessageFormat::Plain => MessageFormat::Plain,
+            post_install::MessageFormat::Markdown => MessageFormat::Markdown,
+        };
+        Self {
+            text: m.text.clone(),
+            format: format as i32,
+        }
+    }
+}
+
+impl From<&migrations::Migration> for Migration {
+    fn from(m: &migrations::Migration) -> Self {
+        Self {
+            from_version: m.from_version.clone(),
+            script: m.script.clone(),
+            service: m.service.clone(),
+            description: m.description.clone(),
+        }
+    }
+}
+
+impl From<&storage::StorageDir> for StorageDir {
+    fn from(d: &storage::StorageDir) -> Self {
+        Self {
+            quota_mb: d.quota_mb,
+        }
+    }
+}
+
+impl From<&storage::StorageInfo> for StorageInfo {
+    fn from(s: &storage::StorageInfo) -> Self {
+        Self {
+            data: s.data.as_ref().map(Into::into),
+            cache: s.cache.as_ref().map(Into::into),
+            state: s.state.as_ref().map(Into::into),
+        }
+    }
+}
+
+impl From<&permissions::Permissions> for Permissions {
+    fn from(p: &permissions::Permissions) -> Self {
+        Self {
+            network: p.network.as_ref().map(|n| NetworkPermissions {
+                allow: n.allow.clone(),
+            }),
+            fs: p.fs.as_ref().map(|f| FsPermissions {
+                read: f.read.clone(),
+                write: f.write.clone(),
+            }),
+            env: p.env.as_ref().map(|e| EnvPermissions {
+                allow: e.allow.clone(),
+            }),
+            subprocess: p.subprocess.as_ref().map(|s| SubprocessPermissions {
+                allow: s.allow.clone(),
+            }),
+        }
+    }
+}
+
+impl From<&assets::Asset> for Asset {
+    fn from(a: &assets::Asset) -> Self {
+        let kind = match a.kind {
+            assets::AssetKind::Theme => AssetKind::Theme,
+            assets::AssetKind::Font => AssetKind::Font,
+            assets::AssetKind::Icon => AssetKind::Icon,
+            assets::AssetKind::Image => AssetKind::Image,
+            assets::AssetKind::Data => AssetKind::Data,
+        };
+        Self {
+            path: a.path.clone(),
+            kind: kind as i32,
+            target: a.target.clone(),
+            platforms: a.platforms.clone(),
+            checksum: a.checksum.clone(),
+        }
+    }
+}
+
+impl From<&messaging::MessagingTopic> for MessagingTopic {
+    fn from(t: &messaging::MessagingTopic) -> Self {
+        let direction = match t.direction {
+            messaging::TopicDirection::Publish => TopicDirection::Publish,
+            messaging::TopicDirection::Subscribe => TopicDirection::Subscribe,
+            messaging::TopicDirection::Both => TopicDirection::Both,
+        };
+        Self {
+            topic: t.topic.clone(),
+            schema_version: t.schema_version,
+            direction: direction as i32,
+        }
+    }
+}
+
+impl From<&resources::ResourcesInfo> for ResourcesInfo {
+    fn from(r: &resources::ResourcesInfo) -> Self {
+        Self {
+            claims: r
+                .claims
+                .iter()
+                .map(|c| {
+                    let kind = match c.kind {
+                        resources::ResourceKind::Port => ResourceKind::Port,
+                        resources::ResourceKind::Cache => ResourceKind::Cache,
+                        resources::ResourceKind::Hardware => ResourceKind::Hardware,
+                        resources::ResourceKind::Lock => ResourceKind::Lock,
+                    };
+                    ResourceClaim {
+                        kind: kind as i32,
+                        name: c.name.clone(),
+                        exclusive: c.exclusive,
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<&health::HealthInfo> for HealthInfo {
+    fn from(h: &health::HealthInfo) -> Self {
+        let probe = match h.probe {
+            health::ProbeKind::ServiceCall => ProbeKind::ServiceCall,
+            health::ProbeKind::Command => ProbeKind::Command,
+        };
+        let restart = match h.restart {
+            health::RestartPolicy::Never => RestartPolicy::Never,
+            health::RestartPolicy::OnFailure => RestartPolicy::OnFailure,
+        };
+        Self {
+            probe: probe as i32,
+            target: h.target.clone(),
+            interval_secs: h.interval_secs,
+            failure_threshold: h.failure_threshold,
+            restart: restart as i32,
+        }
+    }
+}
+
+impl From<logging::LogLevel> for LogLevel {
+    fn from(l: logging::LogLevel) -> Self {
+        match l {
+            logging::LogLevel::Error => LogLevel::Error,
+            logging::LogLevel::Warn => LogLevel::Warn,
+            logging::LogLevel::Info => LogLevel::Info,
+            logging::LogLevel::Debug => LogLevel::Debug,
+            logging::LogLevel::Trace => LogLevel::Trace,
+        }
+    }
+}
+
+impl From<&logging::LoggingInfo> for LoggingInfo {
+    fn from(l: &logging::LoggingInfo) -> Self {
+        Self {
+            level: LogLevel::from(l.level) as i32,
+            targets: l
+                .targets
+                .iter()
+                .map(|(name, level)| (name.clone(), LogLevel::from(*level) as i32))
+                .collect(),
+            user_content: l.user_content,
+        }
+    }
+}
+
+impl From<&package::PackageManifest> for PackageManifest {
+    fn from(m: &package::PackageManifest) -> Self {
+        Self {
+            manifest_version: m.manifest_version,
+            package: Some((&m.package).into()),
+            compatibility: Some((&m.compatibility).into()),
+            plugins: m.plugins.iter().map(Into::into).collect(),
+            binary: Some((&m.binary).into()),
+            signature: m.signature.as_ref().map(Into::into),
+        }
+    }
+}
+
+impl From<&package::PackageMeta> for PackageMeta {
+    fn from(m: &package::PackageMeta) -> Self {
+        Self {
+            id: m.id.clone(),
+            name: m.name.clone(),
+            version: m.version.clone(),
+            author: m.author.clone(),
+            description: m.description.clone(),
+            license: m.license.clone(),
+            homepage: m.homepage.clone(),
+            repository: m.repository.clone(),
+            license_file: m.license_file.clone(),
+        }
+    }
+}
+
+impl From<&package::PluginDef> for PluginDef {
+    fn from(p: &package::PluginDef) -> Self {
+        Self {
+            id: p.id.clone(),
+            name: p.name.clone(),
+            plugin_type: p.plugin_type.to_string(),
+            binary: p.binary.clone(),
+            description: p.description.clone(),
+            depends_on: p.depends_on.clone(),
+            config: p.config.as_ref().map(Into::into),
+            provides: p.provides.iter().map(Into::into).collect(),
+            requires: p.requires.iter().map(Into::into).collect(),
+            assets: p.assets.iter().map(Into::into).collect(),
+            compatibility: p.compatibility.as_ref().map(|c| CompatibilityOverride {
+                min_host_version: c.min_host_version.clone(),
+                max_host_version: c.max_host_version.clone(),
+                platforms: c.platforms.clone(),
+            }),
+        }
+    }
+}
+
+impl From<&package::PackageBinaryInfo> for PackageBinaryInfo {
+    fn from(b: &package::PackageBinaryInfo) -> Self {
+        Self {
+            checksums: b.checksums.clone(),
+            sizes: b.sizes.clone(),
+        }
+    }
+}
+
+// Protobuf -> core
+
+impl TryFrom<PluginManifest> for plugin::PluginManifest {
+    type Error = ManifestError;
+
+    fn try_from(m: PluginManifest) -> Result<Self, Self::Error> {
+        let plugin = m
+            .plugin
+            .ok_or_else(|| ManifestError::MissingField("plugin".to_string()))?;
+
+        Ok(Self {
+            manifest_version: decode_manifest_version(m.manifest_version),
+            plugin: plugin.into(),
+            compatibility: m.compatibility.map(Into::into).unwrap_or_default(),
+            binary: m.binary.map(Into::into).unwrap_or_default(),
+            signature: m.signature.map(Into::into),
+            config: m
+                .config
+                .map(TryInto::try_into)
+                .transpose()?
+                .unwrap_or_default(),
+            provides: m.provides.into_iter().map(Into::into).collect(),
+            requires: m.requires.into_iter().map(Into::into).collect(),
+            cli: m.cli.map(Into::into),
+            capabilities: m.capabilities.into_iter().map(Into::into).collect(),
+            tags: m.tags.map(Into::into),
+            hive: m.hive.map(Into::into),
+            translation: m.translation.map(Into::into),
+            language: m.language.map(Into::into),
+            theme: m.theme.map(Into::into),
+            requirements: m.requirements.map(Into::into),
+            hooks: m.hooks.map(Into::into),
+            uninstall: m.uninstall.map(Into::into),
+            post_install_message: m.post_install_message.map(Into::into),
+            migrations: m.migrations.into_iter().map(Into::into).collect(),
+            storage: m.storage.map(Into::into),
+            permissions: m.permissions.map(Into::into),
+            messaging: m
+                .messaging
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+            assets: m
+                .assets
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+            resources: m.resources.map(TryInto::try_into).transpose()?,
+            health: m.health.map(TryInto::try_into).transpose()?,
+            observability: m.observability.map(|o| observability::ObservabilityInfo {
+                metrics: o.metrics,
+                tracing: o.tracing,
+            }),
+            logging: m.logging.map(TryInto::try_into).transpose()?,
+            target: parse_table_literals("target", m.target)?,
+            features: parse_table_literals("features", m.features)?,
+            locales: m
+                .locales
+                .into_iter()
+                .map(|(k, l)| {
+                    let localized = effective::LocalizedMeta {
+                        name: l.name,
+                        description: l.description,
+                    };
+                    (k, localized)
+                })
+                .collect(),
+            compat: m.compat.map(|c| shims::CompatInfo {
+                shims: c
+                    .shims
+                    .into_iter()
+                    .map(|(name, s)| {
+                        let shim = shims::ShimInfo {
+                            api_version: s.api_version,
+                        };
+                        (name, shim)
+                    })
+                    .collect(),
+            }),
+            origin: options::ManifestOrigin::default(),
+        })
+    }
+}
+
+impl From<PluginMeta> for plugin::PluginMeta {
+    fn from(m: PluginMeta) -> Self {
+        Self {
+            id: m.id,
+            name: m.name,
+            version: m.version,
+            plugin_type: m.plugin_type.into(),
+            author: m.author,
+            description: m.description,
+            license: m.license,
+            homepage: m.homepage,
+            repository: m.repository,
+            license_file: m.license_file,
+        }
+    }
+}
+
+impl From<CompatibilityInfo> for plugin::CompatibilityInfo {
+    fn from(c: CompatibilityInfo) -> Self {
+        Self {
+            api_version: c.api_version,
+            min_host_version: c.min_host_version,
+            max_host_version: c.max_host_version,
+            platforms: c.platforms,
+            depends_on: c.depends_on,
+            hosts: c.hosts,
+            host_versions: c
+                .host_versions
+                .into_iter()
+                .map(|(host, r)| {
+                    let range = hosts::HostVersionRange {
+                        min_host_version: r.min_host_version,
+                        max_host_version: r.max_host_version,
+                    };
+                    (host, range)
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<BinaryInfo> for plugin::BinaryInfo {
+    fn from(b: BinaryInfo) -> Self {
+        Self {
+            name: b.name,
+            checksums: b.checksums,
+            sizes: b.sizes,
+            path: b.path,
+            platform: b
+                .platform
+                .into_iter()
+                .map(|(k, o)| {
+                    let o = plugin::BinaryOverride {
+                        name: o.name,
+                        path: o.path,
+                        checksum: o.checksum,
+                    };
+                    (k, o)
+                })
+                .collect(),
+            // Unknown kinds fall back to the default
+            kind: match BinaryKind::try_from(b.kind) {
+                Ok(BinaryKind::Wasm) => plugin::BinaryKind::Wasm,
+                Ok(BinaryKind::Executable) => plugin::BinaryKind::Executable,
+                Ok(BinaryKind::Script) => plugin::BinaryKind::Script,
+                _ => plugin::BinaryKind::Native,
+            },
+            abi_requirements: b.abi_requirements.map(|a| crate::abi::AbiRequirements {
+                glibc: a.glibc,
+                macos: a.macos,
+                msvc_runtime: a.msvc_runtime,
+            }),
+        }
+    }
+}
+
+impl From<SignatureInfo> for plugin::SignatureInfo {
+    fn from(s: SignatureInfo) -> Self {
+        Self {
+            public_key: s.public_key,
+            signature_file: s.signature_file,
+        }
+    }
+}
+
+impl TryFrom<ConfigInfo> for plugin::ConfigInfo {
+    type Error = ManifestError;
+
+    fn try_from(c: ConfigInfo) -> Result<Self, Self::Error> {
+        let mut defaults = HashMap::new();
+        for (key, literal) in c.defaults {
+            // Parse the literal as the value of a one-key document
+            let mut table: toml::Table =
+                toml::from_str(&format!("v = {literal}")).map_err(|e| {
+                    ManifestError::InvalidFormat(format!("Invalid config value for '{key}': {e}"))
+                })?;
+            if let Some(value) = table.remove("v") {
+                defaults.insert(key, value);
+            }
+        }
+        Ok(Self {
+            defaults,
+            profiles: parse_table_literals("config.profiles", c.profiles)?,
+        })
+    }
+}
+
+/// Encode override tables as TOML inline-table literals.
+fn table_literals(tables: &BTreeMap<String, toml::Table>) -> HashMap<String, String> {
+    tables
+        .iter()
+        .map(|(k, t)| (k.clone(), toml::Value::Table(t.clone()).to_string()))
+        .collect()
+}
+
+/// Decode override tables from TOML inline-table literals.
+fn parse_table_literals(
+    what: &str,
+    literals: HashMap<String, String>,
+) -> Result<BTreeMap<String, toml::Table>, ManifestError> {
+    literals
+        .into_iter()
+        .map(|(key, literal)| {
+            let mut table: toml::Table =
+                toml::from_str(&format!("v = {literal}")).map_err(|e| {
+                    ManifestError::InvalidFormat(format!("Invalid {what} table '{key}': {e}"))
+                })?;
+            match table.remove("v") {
+                Some(toml::Value::Table(t)) => Ok((key, t)),
+                _ => Err(ManifestError::InvalidFormat(format!(
+                    "Invalid {what} table '{key}'"
+                ))),
+            }
+        })
+        .collect()
+}
+
+impl From<ServiceDeclaration> for plugin::ServiceDeclaration {
+    fn from(s: ServiceDeclaration) -> Self {
+        Self {
+            id: s.id,
+            version: s.version,
+            description: s.description,
+            deprecated: s.deprecated,
+            replaced_by: s.replaced_by,
+        }
+    }
+}
+
+impl From<ServiceRequirement> for plugin::ServiceRequirement {
+    fn from(s: ServiceRequirement) -> Self {
+        Self {
+            id: s.id,
+            min_version: s.min_version,
+            optional: s.optional,
+            // Unknown sources fall back to the default
+            provider: match ServiceSource::try_from(s.provider) {
+                Ok(ServiceSource::Host) => plugin::ServiceSource::Host,
+                Ok(ServiceSource::Any) => plugin::ServiceSource::Any,
+                _ => plugin::ServiceSource::Plugin,
+            },
+        }
+    }
+}
+
+impl From<CliConfig> for plugin::CliConfig {
+    fn from(c: CliConfig) -> Self {
+        Self {
+            command: c.command,
+            description: c.description,
+            aliases: c.aliases,
+            dynamic_completions: c.dynamic_completions,
+            args: c.args.into_iter().map(Into::into).collect(),
+            subcommands: c.subcommands.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<CliSubcommand> for cli::CliSubcommand {
+    fn from(s: CliSubcommand) -> Self {
+        Self {
+            name: s.name,
+            description: s.description,
+            aliases: s.aliases,
+            args: s.args.into_iter().map(Into::into).collect(),
+            subcommands: s.subcommands.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<CliArg> for cli::CliArg {
+    fn from(a: CliArg) -> Self {
+        Self {
+            name: a.name,
+            description: a.description,
+            arg_type: match CliArgType::try_from(a.arg_type) {
+                Ok(CliArgType::Path) => cli::CliArgType::Path,
+                Ok(CliArgType::Number) => cli::CliArgType::Number,
+                Ok(CliArgType::Bool) => cli::CliArgType::Bool,
+                _ => cli::CliArgType::String,
+            },
+            required: a.required,
+            long: a.long,
+            short: a.short,
+            choices: a.choices,
+        }
+    }
+}
+
+impl From<CapabilityDeclaration> for plugin::CapabilityDeclaration {
+    fn from(c: CapabilityDeclaration) -> Self {
+        Self {
+            protocol: c.protocol,
+            version: c.version,
+            description: c.description,
+            implements: c.implements,
+        }
+    }
+}
+
+impl From<TagsInfo> for plugin::TagsInfo {
+    fn from(t: TagsInfo) -> Self {
+        Self {
+            categories: t.categories,
+            platforms: t.platforms,
+        }
+    }
+}
+
+impl From<HiveInfo> for plugin::HiveInfo {
+    fn from(h: HiveInfo) -> Self {
+        Self {
+            category: h.category,
+            name: h.name,
+        }
+    }
+}
+
+impl From<TranslationInfo> for plugin::TranslationInfo {
+    fn from(t: TranslationInfo) -> Self {
+        Self {
+            translates: t.translates,
+            language: t.language,
+            language_name: t.language_name,
+            namespace: t.namespace,
+        }
+    }
+}
+
+impl From<LanguageInfo> for plugin::LanguageInfo {
+    fn from(l: LanguageInfo) -> Self {
+        Self {
+            id: l.id,
+            extensions: l.extensions,
+        }
+    }
+}
+
+impl From<ThemeInfo> for plugin::ThemeInfo {
+    fn from(t: ThemeInfo) -> Self {
+        Self {
+            appearance: t.appearance,
+            extends: t.extends,
+        }
+    }
+}
+
+impl From<RequirementsInfo> for plugin::RequirementsInfo {
+    fn from(r: RequirementsInfo) -> Self {
+        Self {
+            os: r.os,
+            arch: r.arch,
+            notes: r.notes,
+        }
+    }
+}
+
+impl From<HookAction> for hooks::HookAction {
+    fn from(a: HookAction) -> Self {
+        Self {
+            script: a.script,
+            service: a.service,
+        }
+    }
+}
+
+impl From<HooksInfo> for hooks::HooksInfo {
+    fn from(h: HooksInfo) -> Self {
+        Self {
+            pre_install: h.pre_install.map(Into::into),
+            post_install: h.post_install.map(Into::into),
+            pre_update: h.pre_update.map(Into::into),
+            pre_uninstall: h.pre_uninstall.map(Into::into),
+        }
+    }
+}
+
+/// Decode a wire policy value. Unknown values keep the data, so a newer
+/// sender can never cause data to be removed by accident.
+fn cleanup_policy(value: i32) -> uninstall::CleanupPolicy {
+    match CleanupPolicy::try_from(value) {
+        Ok(CleanupPolicy::Remove) => uninstall::CleanupPolicy::Remove,
+        _ => uninstall::CleanupPolicy::KeepByDefault,
+    }
+}
+
+impl From<CleanupPath> for uninstall::CleanupPath {
+    fn from(e: CleanupPath) -> Self {
+        Self {
+            path: e.path,
+            policy: cleanup_policy(e.policy),
+            description: e.description,
+        }
+    }
+}
+
+impl From<CleanupResource> for uninstall::CleanupResource {
+    fn from(r: CleanupResource) -> Self {
+        Self {
+            id: r.id,
+            policy: cleanup_policy(r.policy),
+            description: r.description,
+        }
+    }
+}
+
+impl From<UninstallInfo> for uninstall::UninstallInfo {
+    fn from(u: UninstallInfo) -> Self {
+        Self {
+            data: u.data.into_iter().map(Into::into).collect(),
+            caches: u.caches.into_iter().map(Into::into).collect(),
+            resources: u.resources.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<PostInstallMessage> for post_install::PostInstallMessage {
+    fn from(m: PostInstallMessage) -> Self {
+        // Unknown formats are shown as plain text
+        let format = match MessageFormat::try_from(m.format) {
+            Ok(MessageFormat::Markdown) => post_install::MessageFormat::Markdown,
+            _ => post_install::MessageFormat::Plain,
+        };
+        Self {
+            text: m.text,
+            format,
+        }
+    }
+}
+
+impl From<Migration> for migrations::Migration {
+    fn from(m: Migration) -> Self {
+        Self {
+            from_version: m.from_version,
+            script: m.script,
+            service: m.service,
+            description: m.description,
+        }
+    }
+}
+
+impl From<StorageDir> for storage::StorageDir {
+    fn from(d: StorageDir) -> Self {
+        Self {
+            quota_mb: d.quota_mb,
+        }
+    }
+}
+
+impl From<StorageInfo> for storage::StorageInfo {
+    fn from(s: StorageInfo) -> Self {
+        Self {
+            data: s.data.map(Into::into),
+            cache: s.cache.map(Into::into),
+            state: s.state.map(Into::into),
+        }
+    }
+}
+
+impl From<Permissions> for permissions::Permissions {
+    fn from(p: Permissions) -> Self {
+        Self {
+            network: p
+                .network
+                .map(|n| permissions::NetworkPermissions { allow: n.allow }),
+            fs: p.fs.map(|f| permissions::FsPermissions {
+                read: f.read,
+                write: f.write,
+            }),
+            env: p
+                .env
+                .map(|e| permissions::EnvPermissions { allow: e.allow }),
+            subprocess: p
+                .subprocess
+                .map(|s| permissions::SubprocessPermissions { allow: s.allow }),
+        }
+    }
+}
+
+impl TryFrom<Asset> for assets::Asset {
+    type Error = ManifestError;
+
+    fn try_from(a: Asset) -> Result<Self, Self::Error> {
+        let kind = match AssetKind::try_from(a.kind) {
+            Ok(AssetKind::Theme) => assets::AssetKind::Theme,
+            Ok(AssetKind::Font) => assets::AssetKind::Font,
+            Ok(AssetKind::Icon) => assets::AssetKind::Icon,
+            Ok(AssetKind::Image) => assets::AssetKind::Image,
+            Ok(AssetKind::Data) => assets::AssetKind::Data,
+            Err(_) => {
+                return Err(ManifestError::InvalidFormat(format!(
+                    "Unknown kind {} for asset '{}'",
+                    a.kind, a.path
+                )))
+            }
+        };
+        Ok(Self {
+            path: a.path,
+            kind,
+            target: a.target,
+            platforms: a.platforms,
+            checksum: a.checksum,
+        })
+    }
+}
+
+impl TryFrom<MessagingTopic> for messaging::MessagingTopic {
+    type Error = ManifestError;
+
+    fn try_from(t: MessagingTopic) -> Result<Self, Self::Error> {
+        let direction = match TopicDirection::try_from(t.direction) {
+            Ok(TopicDirection::Publish) => messaging::TopicDirection::Publish,
+            Ok(TopicDirection::Subscribe) => messaging::TopicDirection::Subscribe,
+            Ok(TopicDirection::Both) => messaging::TopicDirection::Both,
+            Err(_) => {
+                return Err(ManifestError::InvalidFormat(format!(
+                    "Unknown direction {} for topic '{}'",
+                    t.direction, t.topic
+                )))
+            }
+        };
+        Ok(Self {
+            topic: t.topic,
+            schema_version: t.schema_version,
+            direction,
+        })
+    }
+}
+
+impl TryFrom<ResourcesInfo> for resources::ResourcesInfo {
+    type Error = ManifestError;
+
+    fn try_from(r: ResourcesInfo) -> Result<Self, Self::Error> {
+        let mut claims = Vec::new();
+        for c in r.claims {
+            let kind = match ResourceKind::try_from(c.kind) {
+                Ok(ResourceKind::Port) => resources::ResourceKind::Port,
+                Ok(ResourceKind::Cache) => resources::ResourceKind::Cache,
+                Ok(ResourceKind::Hardware) => resources::ResourceKind::Hardware,
+                Ok(ResourceKind::Lock) => resources::ResourceKind::Lock,
+                Err(_) => {
+                    return Err(ManifestError::InvalidFormat(format!(
+                        "Unknown kind {} for resource '{}'",
+                        c.kind, c.name
+                    )))
+                }
+            };
+            claims.push(resources::ResourceClaim {
+                kind,
+                name: c.name,
+                exclusive: c.exclusive,
+            });
+        }
+        Ok(Self { claims })
+    }
+}
+
+impl TryFrom<HealthInfo> for health::HealthInfo {
+    type Error = ManifestError;
+
+    fn try_from(h: HealthInfo) -> Result<Self, Self::Error> {
+        let probe = match ProbeKind::try_from(h.probe) {
+            Ok(ProbeKind::ServiceCall) => health::ProbeKind::ServiceCall,
+            Ok(ProbeKind::Command) => health::ProbeKind::Command,
+            Err(_) => {
+                return Err(ManifestError::InvalidFormat(format!(
+                    "Unknown health probe kind {}",
+                    h.probe
+                )))
+            }
+        };
+        // Unknown policies fall back to the default
+        let restart = match RestartPolicy::try_from(h.restart) {
+            Ok(RestartPolicy::Never) => health::RestartPolicy::Never,
+            _ => health::RestartPolicy::OnFailure,
+        };
+        Ok(Self {
+            probe,
+            target: h.target,
+            interval_secs: h.interval_secs,
+            failure_threshold: h.failure_threshold,
+            restart,
+        })
+    }
+}
+
+impl From<LogLevel> for logging::LogLevel {
+    fn from(l: LogLevel) -> Self {
+        match l {
+            LogLevel::Error => logging::LogLevel::Error,
+            LogLevel::Warn => logging::LogLevel::Warn,
+            LogLevel::Info => logging::LogLevel::Info,
+            LogLevel::Debug => logging::LogLevel::Debug,
+            LogLevel::Trace => logging::LogLevel::Trace,
+        }
+    }
+}
+
+impl TryFrom<LoggingInfo> for logging::LoggingInfo {
+    type Error = ManifestError;
+
+    fn try_from(l: LoggingInfo) -> Result<Self, Self::Error> {
+        let level = |value: i32| {
+            LogLevel::try_from(value)
+                .map(Into::into)
+                .map_err(|_| ManifestError::InvalidFormat(format!("Unknown log level {value}")))
+        };
+        Ok(Self {
+            level: level(l.level)?,
+            targets: l
+                .targets
+                .into_iter()
+                .map(|(name, value)| Ok((name, level(value)?)))
+                .collect::<Result<_, ManifestError>>()?,
+            user_content: l.user_content,
+        })
+    }
+}
+
+impl TryFrom<PackageManifest> for package::PackageManifest {
+    type Error = ManifestError;
+
+    fn try_from(m: PackageManifest) -> Result<Self, Self::Error> {
+        let package = m
+            .package
+            .ok_or_else(|| ManifestError::MissingField("package".to_string()))?;
+
+        Ok(Self {
+            manifest_version: decode_manifest_version(m.manifest_version),
+            package: package.into(),
+            compatibility: m.compatibility.map(Into::into).unwrap_or_default(),
+            plugins: m
+                .plugins
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+            binary: m.binary.map(Into::into).unwrap_or_default(),
+            signature: m.signature.map(Into::into),
+            origin: options::ManifestOrigin::default(),
+        })
+    }
+}
+
+impl From<PackageMeta> for package::PackageMeta {
+    fn from(m: PackageMeta) -> Self {
+        Self {
+            id: m.id,
+            name: m.name,
+            version: m.version,
+            author: m.author,
+            description: m.description,
+            license: m.license,
+            homepage: m.homepage,
+            repository: m.repository,
+            license_file: m.license_file,
+        }
+    }
+}
+
+impl TryFrom<PluginDef> for package::PluginDef {
+    type Error = ManifestError;
+
+    fn try_from(p: PluginDef) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: p.id,
+            name: p.name,
+            plugin_type: p.plugin_type.into(),
+            binary: p.binary,
+            description: p.description,
+            depends_on: p.depends_on,
+            config: p.config.map(TryInto::try_into).transpose()?,
+            provides: p.provides.into_iter().map(Into::into).collect(),
+            requires: p.requires.into_iter().map(Into::into).collect(),
+            assets: p
+                .assets
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+            compatibility: p
+                .compatibility
+                .map(|c| crate::narrow::CompatibilityOverride {
+                    min_host_version: c.min_host_version,
+                    max_host_version: c.max_host_version,
+                    platforms: c.platforms,
+                }),
+        })
+    }
+}
+
+impl From<PackageBinaryInfo> for package::PackageBinaryInfo {
+    fn from(b: PackageBinaryInfo) -> Self {
+        Self {
+            checksums: b.checksums,
+            sizes: b.sizes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost::Message;
+
+    #[test]
+    fn test_plugin_round_trip() {
+        let toml = r#"
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.0.0"
+type = "extension"
+license = "MIT"
+
+[binary]
+name = "tool"
+checksums = { linux-x86_64 = "abc" }
+sizes = { linux-x86_64 = 1024 }
+
+[config.defaults]
+theme = "dark"
+limit = 42
+paths = ["a", "b"]
+
+[config.profiles.dev]
+theme = "light"
+
+[target.darwin-aarch64]
+binary = { name = "tool_macos", checksums = { darwin-aarch64 = "def" } }
+
+[[requires]]
+id = "acme.search"
+optional = true
+
+[cli]
+command = "tool"
+description = "Run the tool"
+
+[compat.shims.api-v1]
+api_version = 1
+"#;
+        let manifest = plugin::PluginManifest::from_toml(toml).unwrap();
+        let bytes = PluginManifest::from(&manifest).encode_to_vec();
+        let decoded = PluginManifest::decode(bytes.as_slice()).unwrap();
+        let back = plugin::PluginManifest::try_from(decoded).unwrap();
+
+        assert_eq!(back.plugin.license.as_deref(), Some("MIT"));
+        assert_eq!(back.binary.checksums, manifest.binary.checksums);
+        assert_eq!(back.binary.sizes, manifest.binary.sizes);
+        assert_eq!(back.config.defaults, manifest.config.defaults);
+        assert_eq!(back.config.profiles, manifest.config.profiles);
+        assert_eq!(back.target, manifest.target);
+        assert!(back.requires[0].optional);
+        assert_eq!(back.cli, manifest.cli);
+        assert_eq!(back.compat, manifest.compat);
+    }
+
+    #[test]
+    fn test_package_round_trip() {
+        let toml = r#"
+[package]
+id = "acme.themes"
+name = "Themes"
+version = "2.0.0"
+
+[[plugins]]
+id = "acme.theme-dark"
+name = "Dark"
+type = "theme"
+binary = "dark"
+"#;
+        let manifest = package::PackageManifest::from_toml(toml).unwrap();
+        let proto = PackageManifest::from(&manifest);
+        let back = package::PackageManifest::try_from(proto).unwrap();
+
+        assert_eq!(back.to_toml().unwrap(), manifest.to_toml().unwrap());
+    }
+
+    #[test]
+    fn test_missing_meta_is_an_error() {
+        let result = plugin::PluginManifest::try_from(PluginManifest::default());
+        assert!(matches!(result, Err(ManifestError::MissingField(_))));
+    }
+}
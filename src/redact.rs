@@ -0,0 +1,229 @@
+//! Redaction of sensitive manifest fields.
+//!
+//! `redact()` produces a copy of a manifest that is safe to attach to bug
+//! reports and telemetry. A `RedactionPolicy` decides what counts as
+//! sensitive:
+//!
+//! - the `[signature]` section (removed)
+//! - URLs pointing at internal hosts, in metadata and config values (masked)
+//! - config defaults and profile values whose key looks like a secret, such
+//!   as `api_token` or `db_password` (masked)
+
+use std::sync::Arc;
+
+use crate::package::PackageManifest;
+use crate::plugin::{ConfigInfo, PluginManifest};
+use crate::Manifest;
+
+/// Replacement text for masked values.
+pub const REDACTED: &str = "[redacted]";
+
+/// What `redact()` strips or masks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RedactionPolicy {
+    /// Remove the `[signature]` section
+    pub strip_signature: bool,
+
+    /// Hosts whose URLs are masked; subdomains match too
+    /// (e.g., "corp.example" masks "https://git.corp.example/tool")
+    pub internal_hosts: Vec<String>,
+
+    /// Config keys containing any of these fragments (case-insensitive)
+    /// have their values masked
+    pub secret_key_fragments: Vec<String>,
+}
+
+impl Default for RedactionPolicy {
+    fn default() -> Self {
+        Self {
+            strip_signature: true,
+            internal_hosts: Vec::new(),
+            secret_key_fragments: ["secret", "token", "password", "api_key", "credential"]
+                .map(String::from)
+                .to_vec(),
+        }
+    }
+}
+
+impl RedactionPolicy {
+    /// Builder-style addition of an internal host.
+    pub fn internal_host(mut self, host: impl Into<String>) -> Self {
+        self.internal_hosts.push(host.into());
+        self
+    }
+
+    /// Check whether a config key names a secret.
+    pub fn is_secret_key(&self, key: &str) -> bool {
+        let key = key.to_ascii_lowercase();
+        self.secret_key_fragments
+            .iter()
+            .any(|f| key.contains(&f.to_ascii_lowercase()))
+    }
+
+    /// Check whether a string is a URL on an internal host.
+    pub fn is_internal_url(&self, value: &str) -> bool {
+        let Some((_, rest)) = value.split_once("://") else {
+            return false;
+        };
+        let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+        let host = authority.rsplit('@').next().unwrap_or_default();
+        let host = host.split(':').next().unwrap_or_default();
+        self.internal_hosts.iter().any(|internal| {
+            host == internal
+                || host
+                    .strip_suffix(internal.as_str())
+                    .is_some_and(|prefix| prefix.ends_with('.'))
+        })
+    }
+
+    fn redact_url(&self, url: &mut Option<String>) {
+        if url.as_deref().is_some_and(|u| self.is_internal_url(u)) {
+            *url = Some(REDACTED.to_string());
+        }
+    }
+
+    fn redact_value(&self, key: &str, value: &mut toml::Value) {
+        match value {
+            _ if self.is_secret_key(key) => *value = toml::Value::String(REDACTED.to_string()),
+            toml::Value::String(s) if self.is_internal_url(s) => *s = REDACTED.to_string(),
+            toml::Value::Table(table) => {
+                for (key, value) in table.iter_mut() {
+                    self.redact_value(key, value);
+                }
+            }
+            toml::Value::Array(items) => {
+                for item in items {
+                    self.redact_value("", item);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn redact_config(&self, config: &mut ConfigInfo) {
+        for (key, value) in config.defaults.iter_mut() {
+            self.redact_value(key, value);
+        }
+        for profile in config.profiles.values_mut() {
+            for (key, value) in profile.iter_mut() {
+                self.redact_value(key, value);
+            }
+        }
+    }
+}
+
+impl PluginManifest {
+    /// Copy of the manifest with sensitive fields stripped or masked.
+    pub fn redact(&self, policy: &RedactionPolicy) -> PluginManifest {
+        let mut redacted = self.clone();
+        // The source document still holds the unmasked values
+        redacted.origin.declared = None;
+        redacted.origin.source_package = self
+            .origin
+            .source_package
+            .as_ref()
+            .map(|package| Arc::new(package.redact(policy)));
+        if policy.strip_signature {
+            redacted.signature = None;
+            redacted.origin.source_package = None;
+        }
+        policy.redact_url(&mut redacted.plugin.homepage);
+        policy.redact_url(&mut redacted.plugin.repository);
+        policy.redact_config(&mut redacted.config);
+        redacted
+    }
+}
+
+impl PackageManifest {
+    /// Copy of the manifest with sensitive fields stripped or masked.
+    pub fn redact(&self, policy: &RedactionPolicy) -> PackageManifest {
+        let mut redacted = self.clone();
+        redacted.origin.declared = None;
+        if policy.strip_signature {
+            redacted.signature = None;
+        }
+        policy.redact_url(&mut redacted.package.homepage);
+        policy.redact_url(&mut redacted.package.repository);
+        for config in redacted
+            .plugins
+            .iter_mut()
+            .filter_map(|p| p.config.as_mut())
+        {
+            policy.redact_config(config);
+        }
+        redacted
+    }
+}
+
+impl Manifest {
+    /// Copy of the manifest with sensitive fields stripped or masked.
+    pub fn redact(&self, policy: &RedactionPolicy) -> Manifest {
+        match self {
+            Manifest::Single(m) => Manifest::Single(m.redact(policy)),
+            Manifest::Package(m) => Manifest::Package(m.redact(policy)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact() {
+        let manifest = PluginManifest::from_toml(
+            r#"
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.0.0"
+type = "extension"
+homepage = "https://tool.acme.example"
+repository = "https://git.corp.example/acme/tool"
+
+[signature]
+public_key = "abc"
+signature_file = "plugin.sig"
+
+[config.defaults]
+endpoint = "http://api.corp.example:8080/v1"
+api_token = "hunter2"
+log_level = "info"
+
+[config.profiles.dev]
+DB_PASSWORD = "dev"
+"#,
+        )
+        .unwrap();
+        let policy = RedactionPolicy::default().internal_host("corp.example");
+
+        let redacted = manifest.redact(&policy);
+        assert!(redacted.signature.is_none());
+        assert_eq!(
+            redacted.plugin.homepage.as_deref(),
+            Some("https://tool.acme.example")
+        );
+        assert_eq!(redacted.plugin.repository.as_deref(), Some(REDACTED));
+        let defaults = &redacted.config.defaults;
+        assert_eq!(defaults["endpoint"].as_str(), Some(REDACTED));
+        assert_eq!(defaults["api_token"].as_str(), Some(REDACTED));
+        assert_eq!(defaults["log_level"].as_str(), Some("info"));
+        assert_eq!(
+            redacted.config.profiles["dev"]["DB_PASSWORD"].as_str(),
+            Some(REDACTED)
+        );
+
+        assert!(!policy.is_internal_url("https://notcorp.example"));
+
+        // Nothing kept from the source document leaks the masked values
+        let kept = RedactionPolicy {
+            strip_signature: false,
+            ..RedactionPolicy::default()
+        };
+        let redacted = manifest.redact(&kept);
+        assert!(!format!("{redacted:?}").contains("hunter2"));
+        assert!(!String::from_utf8(redacted.signing_payload().unwrap())
+            .unwrap()
+            .contains("hunter2"));
+    }
+}
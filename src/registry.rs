@@ -0,0 +1,305 @@
+//! Registry index (`index.toml`).
+//!
+//! A marketplace lists every published plugin and package in one index,
+//! generated from a directory of manifests with `RegistryIndex::from_dir()`:
+//!
+//! ```toml
+//! index_version = 1
+//!
+//! [[plugins]]
+//! id = "acme.tool"
+//! name = "Tool"
+//!
+//! [[plugins.versions]]
+//! version = "1.0.0"
+//! platforms = ["darwin-aarch64", "linux-x86_64"]
+//! yanked = true
+//!
+//! [plugins.versions.checksums]
+//! linux-x86_64 = "60303ae22b998861bce3b28f33eec1be758a213c86c93c076dbe9f558c11c752"
+//!
+//! [[plugins.versions]]
+//! version = "1.1.0"
+//! ```
+//!
+//! Entries are sorted by ID and versions by semver, so regenerating an
+//! unchanged tree produces an identical file.
+
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::discover::collect_manifests;
+use crate::error::ManifestError;
+use crate::Manifest;
+
+/// Conventional index file name.
+pub const INDEX_FILE_NAME: &str = "index.toml";
+
+/// Index format version written by this crate.
+pub const INDEX_VERSION: u32 = 1;
+
+/// Every published plugin and package with its versions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegistryIndex {
+    /// Index format version
+    pub index_version: u32,
+
+    /// Entries, sorted by ID
+    #[serde(default)]
+    pub plugins: Vec<IndexEntry>,
+}
+
+/// A plugin or package in the index.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexEntry {
+    /// Plugin or package ID
+    pub id: String,
+
+    /// Name from the latest version
+    pub name: String,
+
+    /// Whether the entry is a package
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub package: bool,
+
+    /// Published versions, oldest first
+    #[serde(default)]
+    pub versions: Vec<IndexVersion>,
+}
+
+/// One published version of an index entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexVersion {
+    /// Exact version
+    pub version: String,
+
+    /// Supported platforms (empty means all)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub platforms: Vec<String>,
+
+    /// Binary checksums per platform
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub checksums: BTreeMap<String, String>,
+
+    /// Withdrawn from new installs
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub yanked: bool,
+}
+
+impl IndexEntry {
+    /// Newest version that isn't yanked.
+    pub fn latest(&self) -> Option<&IndexVersion> {
+        self.versions.iter().rev().find(|v| !v.yanked)
+    }
+
+    /// A specific version.
+    pub fn version(&self, version: &str) -> Option<&IndexVersion> {
+        self.versions.iter().find(|v| v.version == version)
+    }
+}
+
+impl RegistryIndex {
+    /// Aggregate manifests into an index.
+    ///
+    /// Fails on unparsable versions and on an ID and version published twice.
+    pub fn from_manifests(
+        manifests: impl IntoIterator<Item = Manifest>,
+    ) -> Result<Self, ManifestError> {
+        let mut entries: BTreeMap<String, (IndexEntry, Vec<Version>)> = BTreeMap::new();
+        for manifest in manifests {
+            let (name, compatibility, checksums) = match &manifest {
+                Manifest::Single(m) => (&m.plugin.name, &m.compatibility, &m.binary.checksums),
+                Manifest::Package(m) => (&m.package.name, &m.compatibility, &m.binary.checksums),
+            };
+            let id = manifest.id();
+            let parsed = Version::parse(manifest.version())
+                .map_err(|e| ManifestError::InvalidVersion(format!("{id}: {e}")))?;
+            let (entry, versions) = entries.entry(id.to_string()).or_insert_with(|| {
+                let entry = IndexEntry {
+                    id: id.to_string(),
+                    name: name.clone(),
+                    package: manifest.is_package(),
+                    versions: Vec::new(),
+                };
+                (entry, Vec::new())
+            });
+            let pos = match versions.binary_search(&parsed) {
+                Ok(_) => {
+                    return Err(ManifestError::InvalidFormat(format!(
+                        "{id} {parsed} appears more than once"
+                    )))
+                }
+                Err(pos) => pos,
+            };
+            if pos == versions.len() {
+                entry.name = name.clone();
+            }
+            versions.insert(pos, parsed);
+            entry.versions.insert(
+                pos,
+                IndexVersion {
+                    version: manifest.version().to_string(),
+                    platforms: compatibility.platforms.clone(),
+                    checksums: checksums.clone().into_iter().collect(),
+                    yanked: false,
+                },
+            );
+        }
+        Ok(Self {
+            index_version: INDEX_VERSION,
+            plugins: entries.into_values().map(|(entry, _)| entry).collect(),
+        })
+    }
+
+    /// Aggregate every plugin.toml and package.toml under `root`.
+    pub fn from_dir(root: &Path) -> Result<Self, ManifestError> {
+        let manifests = collect_manifests(root)?
+            .iter()
+            .map(|path| Manifest::from_file(path))
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::from_manifests(manifests)
+    }
+
+    /// Look up an entry by ID.
+    pub fn get(&self, id: &str) -> Option<&IndexEntry> {
+        self.plugins.iter().find(|e| e.id == id)
+    }
+
+    /// Mark a version as yanked.
+    pub fn yank(&mut self, id: &str, version: &str) -> Result<(), ManifestError> {
+        let entry = self
+            .plugins
+            .iter_mut()
+            .find(|e| e.id == id)
+            .ok_or_else(|| ManifestError::UnknownPlugin(id.to_string()))?;
+        let indexed = entry
+            .versions
+            .iter_mut()
+            .find(|v| v.version == version)
+            .ok_or_else(|| {
+                ManifestError::InvalidVersion(format!("{id} {version} is not indexed"))
+            })?;
+        indexed.yanked = true;
+        Ok(())
+    }
+
+    /// Copy yanked flags from a previous index, e.g. after regenerating it
+    /// with `from_dir()`.
+    pub fn keep_yanked(&mut self, previous: &RegistryIndex) {
+        for entry in &mut self.plugins {
+            let Some(before) = previous.get(&entry.id) else {
+                continue;
+            };
+            for version in &mut entry.versions {
+                if before.version(&version.version).is_some_and(|v| v.yanked) {
+                    version.yanked = true;
+                }
+            }
+        }
+    }
+
+    /// Parse from TOML string.
+    pub fn from_toml(content: &str) -> Result<Self, ManifestError> {
+        let index: Self = toml::from_str(content)?;
+        if index.index_version > INDEX_VERSION {
+            return Err(ManifestError::InvalidFormat(format!(
+                "Index version {} is newer than supported version {INDEX_VERSION}",
+                index.index_version
+            )));
+        }
+        Ok(index)
+    }
+
+    /// Parse from file.
+    pub fn from_file(path: &Path) -> Result<Self, ManifestError> {
+        let content = std::fs::read_to_string(path)?;
+        Self::from_toml(&content)
+    }
+
+    /// Serialize to TOML string.
+    pub fn to_toml(&self) -> Result<String, ManifestError> {
+        toml::to_string_pretty(self)
+            .map_err(|e| ManifestError::InvalidFormat(format!("Failed to serialize index: {e}")))
+    }
+
+    /// Serialize to a pretty-printed JSON string.
+    #[cfg(feature = "json")]
+    pub fn to_json(&self) -> Result<String, ManifestError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plugin(version: &str, name: &str) -> Manifest {
+        Manifest::from_toml(&format!(
+            "[plugin]\nid = \"acme.tool\"\nname = \"{name}\"\nversion = \"{version}\"\ntype = \"extension\"\n\n[compatibility]\nplatforms = [\"linux-x86_64\"]\n\n[binary]\nname = \"tool\"\n\n[binary.checksums]\nlinux-x86_64 = \"abc\"\n"
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_from_manifests() {
+        let package = Manifest::from_toml(
+            "[package]\nid = \"acme.suite\"\nname = \"Suite\"\nversion = \"1.0.0\"\n\n[[plugins]]\nid = \"acme.part\"\nname = \"Part\"\ntype = \"extension\"\nbinary = \"part\"\n",
+        )
+        .unwrap();
+        let mut index = RegistryIndex::from_manifests([
+            plugin("1.10.0", "Tool"),
+            package,
+            plugin("1.2.0", "Old Tool"),
+        ])
+        .unwrap();
+
+        let ids: Vec<&str> = index.plugins.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, ["acme.suite", "acme.tool"]);
+        let tool = index.get("acme.tool").unwrap();
+        assert_eq!(tool.name, "Tool");
+        assert_eq!(tool.versions[0].version, "1.2.0");
+        assert_eq!(tool.versions[1].checksums["linux-x86_64"], "abc");
+        assert!(index.get("acme.suite").unwrap().package);
+
+        index.yank("acme.tool", "1.10.0").unwrap();
+        assert_eq!(
+            index.get("acme.tool").unwrap().latest().unwrap().version,
+            "1.2.0"
+        );
+        assert!(index.yank("acme.tool", "9.9.9").is_err());
+
+        let reparsed = RegistryIndex::from_toml(&index.to_toml().unwrap()).unwrap();
+        assert_eq!(reparsed, index);
+
+        let mut regenerated =
+            RegistryIndex::from_manifests([plugin("1.10.0", "Tool"), plugin("1.2.0", "Tool")])
+                .unwrap();
+        regenerated.keep_yanked(&index);
+        assert!(regenerated.get("acme.tool").unwrap().versions[1].yanked);
+
+        assert!(matches!(
+            RegistryIndex::from_manifests([plugin("1.0.0", "Tool"), plugin("1.0.0", "Tool")]),
+            Err(ManifestError::InvalidFormat(_))
+        ));
+    }
+
+    #[test]
+    fn test_from_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        for version in ["1.0.0", "2.0.0"] {
+            let path = dir.path().join(version);
+            std::fs::create_dir(&path).unwrap();
+            std::fs::write(
+                path.join("plugin.toml"),
+                plugin(version, "Tool").to_toml().unwrap(),
+            )
+            .unwrap();
+        }
+
+        let index = RegistryIndex::from_dir(dir.path()).unwrap();
+        assert_eq!(index.plugins.len(), 1);
+        assert_eq!(index.plugins[0].latest().unwrap().version, "2.0.0");
+    }
+}
@@ -0,0 +1,357 @@
+//! Dependency resolution across manifests.
+//!
+//! `PackageManifest::install_order()` only sees one package. A
+//! `DependencyResolver` takes every manifest a host knows about (single
+//! plugins and packages) and orders all of their plugins, following both
+//! plugin dependencies and service requirements:
+//!
+//! ```toml
+//! [compatibility]
+//! depends_on = ["acme.core"]      # acme.core loads first
+//!
+//! [[requires]]
+//! id = "acme.search"              # the selected provider loads first
+//! ```
+//!
+//! Every problem found is reported at once instead of stopping at the first.
+//! With an `AliasTable`, dependencies on renamed plugins resolve to their
+//! new IDs.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::aliases::{AliasTable, AliasUse};
+use crate::error::ManifestError;
+use crate::plugin::PluginManifest;
+use crate::services::{ProviderPolicy, ServiceResolution, HOST_PROVIDER};
+use crate::set::ManifestSet;
+use crate::Manifest;
+
+/// A reason the plugins in a resolver cannot be loaded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DependencyProblem {
+    /// A plugin ID is declared by more than one manifest
+    DuplicatePlugin(String),
+    /// A `depends_on` entry names a plugin that isn't available
+    MissingPlugin {
+        /// Plugin declaring the dependency
+        plugin: String,
+        /// Missing plugin ID
+        dependency: String,
+    },
+    /// A required service has no suitable provider
+    MissingService {
+        /// Plugin requiring the service
+        plugin: String,
+        /// Service ID
+        service: String,
+        /// Why no provider matched (e.g., version too low, pin not satisfied)
+        reason: String,
+    },
+    /// Plugins that depend on each other, each on the next; the first and
+    /// last entries are the same plugin
+    Cycle(Vec<String>),
+}
+
+impl fmt::Display for DependencyProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DuplicatePlugin(id) => write!(f, "plugin '{id}' is declared more than once"),
+            Self::MissingPlugin { plugin, dependency } => {
+                write!(
+                    f,
+                    "{plugin} depends on '{dependency}', which is not available"
+                )
+            }
+            Self::MissingService {
+                plugin,
+                service,
+                reason,
+            } => write!(f, "{plugin} requires '{service}': {reason}"),
+            Self::Cycle(ids) => write!(f, "circular dependency: {}", ids.join(" -> ")),
+        }
+    }
+}
+
+/// Join problems for `ManifestError::UnresolvedDependencies`.
+pub(crate) fn describe_problems(problems: &[DependencyProblem]) -> String {
+    problems
+        .iter()
+        .map(|p| p.to_string())
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Result of `DependencyResolver::resolve()`.
+#[derive(Debug, Clone)]
+pub struct LoadPlan {
+    /// Plugins in load order (dependencies first)
+    pub plugins: Vec<PluginManifest>,
+
+    /// Service bindings the order was computed from
+    pub services: ServiceResolution,
+
+    /// Aliases that `depends_on` entries were resolved through
+    pub aliases_used: Vec<AliasUse>,
+}
+
+impl LoadPlan {
+    /// Plugin IDs in load order.
+    pub fn order(&self) -> Vec<&str> {
+        self.plugins.iter().map(|p| p.plugin.id.as_str()).collect()
+    }
+}
+
+/// Computes a global load order for a set of manifests.
+#[derive(Debug, Clone, Default)]
+pub struct DependencyResolver {
+    set: ManifestSet,
+    policy: ProviderPolicy,
+    aliases: AliasTable,
+}
+
+impl DependencyResolver {
+    /// Create a resolver over a set of manifests with the default provider
+    /// policy.
+    pub fn new(set: ManifestSet) -> Self {
+        Self {
+            set,
+            policy: ProviderPolicy::default(),
+            aliases: AliasTable::default(),
+        }
+    }
+
+    /// Builder-style provider policy, used to pick between several
+    /// providers of a service and to declare host services.
+    pub fn with_policy(mut self, policy: ProviderPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Builder-style alias table, consulted for `depends_on` entries that
+    /// name no plugin in the set.
+    pub fn with_aliases(mut self, aliases: AliasTable) -> Self {
+        self.aliases = aliases;
+        self
+    }
+
+    /// Resolve every plugin in the set.
+    ///
+    /// Plugins load after their `depends_on` plugins and after the providers
+    /// selected for their requirements (optional requirements only when a
+    /// provider exists; host services impose no order). Among plugins whose
+    /// dependencies are loaded, the one added first comes first.
+    pub fn resolve(&self) -> Result<LoadPlan, ManifestError> {
+        let plugins: Vec<&PluginManifest> = self.set.plugins().collect();
+        let services = self.set.resolve_services(&self.policy);
+        let mut problems = Vec::new();
+
+        let mut index: HashMap<&str, usize> = HashMap::new();
+        for (i, plugin) in plugins.iter().enumerate() {
+            if index.insert(plugin.plugin.id.as_str(), i).is_some() {
+                problems.push(DependencyProblem::DuplicatePlugin(plugin.plugin.id.clone()));
+            }
+        }
+
+        let mut deps: Vec<Vec<usize>> = vec![Vec::new(); plugins.len()];
+        let mut aliases_used = Vec::new();
+        for (i, plugin) in plugins.iter().enumerate() {
+            for dep in &plugin.compatibility.depends_on {
+                if let Some(&d) = index.get(dep.as_str()) {
+                    deps[i].push(d);
+                    continue;
+                }
+                let aliased = self
+                    .aliases
+                    .resolve(dep)
+                    .and_then(|target| Some((target, *index.get(target)?)));
+                match aliased {
+                    Some((target, d)) => {
+                        deps[i].push(d);
+                        aliases_used.push(AliasUse {
+                            plugin: plugin.plugin.id.clone(),
+                            alias: dep.clone(),
+                            target: target.to_string(),
+                        });
+                    }
+                    None => problems.push(DependencyProblem::MissingPlugin {
+                        plugin: plugin.plugin.id.clone(),
+                        dependency: dep.clone(),
+                    }),
+                }
+            }
+        }
+        for binding in &services.bindings {
+            if binding.provider == HOST_PROVIDER || binding.provider == binding.consumer {
+                continue;
+            }
+            if let (Some(&i), Some(&d)) = (
+                index.get(binding.consumer.as_str()),
+                index.get(binding.provider.as_str()),
+            ) {
+                deps[i].push(d);
+            }
+        }
+        for unresolved in services.unresolved.iter().filter(|u| !u.optional) {
+            problems.push(DependencyProblem::MissingService {
+                plugin: unresolved.consumer.clone(),
+                service: unresolved.service.clone(),
+                reason: unresolved.reason.clone(),
+            });
+        }
+
+        let mut loaded = vec![false; plugins.len()];
+        let mut order = Vec::with_capacity(plugins.len());
+        while order.len() < plugins.len() {
+            let next =
+                (0..plugins.len()).find(|&i| !loaded[i] && deps[i].iter().all(|&d| loaded[d]));
+            match next {
+                Some(i) => {
+                    loaded[i] = true;
+                    order.push(i);
+                }
+                None => {
+                    problems.push(DependencyProblem::Cycle(cycle(&plugins, &deps, &loaded)));
+                    break;
+                }
+            }
+        }
+
+        if !problems.is_empty() {
+            return Err(ManifestError::UnresolvedDependencies(problems));
+        }
+        Ok(LoadPlan {
+            plugins: order.into_iter().map(|i| plugins[i].clone()).collect(),
+            services,
+            aliases_used,
+        })
+    }
+}
+
+impl FromIterator<Manifest> for DependencyResolver {
+    fn from_iter<I: IntoIterator<Item = Manifest>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+/// Find a cycle among the plugins not yet loaded, each depending on the next.
+fn cycle(plugins: &[&PluginManifest], deps: &[Vec<usize>], loaded: &[bool]) -> Vec<String> {
+    // Every remaining plugin waits on another remaining one; follow the
+    // chain until it loops back.
+    let mut path = Vec::new();
+    let mut current = loaded.iter().position(|done| !done).unwrap();
+    while !path.contains(&current) {
+        path.push(current);
+        current = *deps[current].iter().find(|&&d| !loaded[d]).unwrap();
+    }
+    let start = path.iter().position(|&i| i == current).unwrap();
+    path[start..]
+        .iter()
+        .chain(std::iter::once(&current))
+        .map(|&i| plugins[i].plugin.id.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plugin(id: &str, extra: &str) -> Manifest {
+        Manifest::from_toml(&format!(
+            "[plugin]\nid = \"{id}\"\nname = \"{id}\"\nversion = \"1.0.0\"\ntype = \"extension\"\n{extra}"
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_resolve_across_manifests() {
+        let package = Manifest::from_toml(
+            r#"
+[package]
+id = "acme.bundle"
+name = "Bundle"
+version = "1.0.0"
+
+[[plugins]]
+id = "acme.app"
+name = "App"
+type = "extension"
+binary = "app"
+depends_on = ["acme.core"]
+"#,
+        )
+        .unwrap();
+        let resolver: DependencyResolver = [
+            package,
+            plugin("acme.core", "[[requires]]\nid = \"acme.search\"\n"),
+            plugin(
+                "acme.indexer",
+                "[[provides]]\nid = \"acme.search\"\nversion = \"1.0.0\"\n",
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        let plan = resolver.resolve().unwrap();
+        assert_eq!(plan.order(), ["acme.indexer", "acme.core", "acme.app"]);
+    }
+
+    #[test]
+    fn test_resolve_reports_all_problems() {
+        let resolver: DependencyResolver = [
+            plugin("acme.a", "[compatibility]\ndepends_on = [\"acme.b\"]\n"),
+            plugin("acme.b", "[compatibility]\ndepends_on = [\"acme.a\"]\n"),
+            plugin(
+                "acme.c",
+                "[compatibility]\ndepends_on = [\"acme.missing\"]\n\n[[requires]]\nid = \"acme.search\"\n",
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        let Err(ManifestError::UnresolvedDependencies(problems)) = resolver.resolve() else {
+            panic!("expected unresolved dependencies");
+        };
+        assert_eq!(problems.len(), 3);
+        assert!(problems.contains(&DependencyProblem::MissingPlugin {
+            plugin: "acme.c".into(),
+            dependency: "acme.missing".into(),
+        }));
+        assert!(problems
+            .iter()
+            .any(|p| matches!(p, DependencyProblem::MissingService { service, .. } if service == "acme.search")));
+        assert!(problems.contains(&DependencyProblem::Cycle(vec![
+            "acme.a".into(),
+            "acme.b".into(),
+            "acme.a".into(),
+        ])));
+    }
+
+    #[test]
+    fn test_resolve_through_aliases() {
+        let set: ManifestSet = [
+            plugin(
+                "acme.app",
+                "[compatibility]\ndepends_on = [\"acme.search-legacy\"]\n",
+            ),
+            plugin("acme.search", ""),
+        ]
+        .into_iter()
+        .collect();
+
+        assert!(DependencyResolver::new(set.clone()).resolve().is_err());
+        let plan = DependencyResolver::new(set)
+            .with_aliases(AliasTable::new().alias("acme.search-legacy", "acme.search"))
+            .resolve()
+            .unwrap();
+        assert_eq!(plan.order(), ["acme.search", "acme.app"]);
+        assert_eq!(
+            plan.aliases_used,
+            [AliasUse {
+                plugin: "acme.app".into(),
+                alias: "acme.search-legacy".into(),
+                target: "acme.search".into(),
+            }]
+        );
+    }
+}
@@ -0,0 +1,221 @@
+//! Shared resource claims (`[resources]`).
+//!
+//! ```toml
+//! [[resources.claims]]
+//! kind = "port"
+//! name = "8080"
+//!
+//! [[resources.claims]]
+//! kind = "cache"
+//! name = "npm"
+//! ```
+//!
+//! Ports, hardware and locks are exclusive by default; caches are shared.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::plugin::PluginManifest;
+use crate::set::ManifestSet;
+use crate::validate::ValidationIssue;
+
+/// Kind of shared resource.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceKind {
+    /// TCP/UDP port (name is the port number)
+    Port,
+    /// Global cache directory
+    Cache,
+    /// Hardware device (e.g., "gpu0", "serial:/dev/ttyUSB0")
+    Hardware,
+    /// Named lock
+    Lock,
+}
+
+impl ResourceKind {
+    /// Stable name used in manifests.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ResourceKind::Port => "port",
+            ResourceKind::Cache => "cache",
+            ResourceKind::Hardware => "hardware",
+            ResourceKind::Lock => "lock",
+        }
+    }
+
+    /// Whether claims of this kind are exclusive unless stated otherwise.
+    pub fn exclusive_by_default(&self) -> bool {
+        !matches!(self, ResourceKind::Cache)
+    }
+}
+
+impl fmt::Display for ResourceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A claimed resource.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ResourceClaim {
+    /// Resource kind
+    pub kind: ResourceKind,
+
+    /// Resource name
+    pub name: String,
+
+    /// Whether no other plugin may claim it (defaults by kind)
+    #[serde(default)]
+    pub exclusive: Option<bool>,
+}
+
+impl ResourceClaim {
+    /// Whether the claim is exclusive.
+    pub fn is_exclusive(&self) -> bool {
+        self.exclusive
+            .unwrap_or_else(|| self.kind.exclusive_by_default())
+    }
+}
+
+/// Shared resources a plugin claims.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ResourcesInfo {
+    /// Claimed resources
+    #[serde(default)]
+    pub claims: Vec<ResourceClaim>,
+}
+
+impl PluginManifest {
+    /// Resources claimed by this plugin.
+    pub fn resource_claims(&self) -> &[ResourceClaim] {
+        self.resources
+            .as_ref()
+            .map(|r| r.claims.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Check resource declarations.
+    pub(crate) fn check_resources(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        for (i, claim) in self.resource_claims().iter().enumerate() {
+            let path = format!("resources.claims[{i}].name");
+            if claim.name.trim().is_empty() {
+                issues.push(ValidationIssue::error(path, "Resource name is empty"));
+            } else if claim.kind == ResourceKind::Port && claim.name.parse::<u16>().is_err() {
+                issues.push(ValidationIssue::error(
+                    path,
+                    format!("'{}' is not a valid port number", claim.name),
+                ));
+            }
+        }
+        issues
+    }
+}
+
+impl ManifestSet {
+    /// Find resources claimed by more than one plugin where at least one
+    /// claim is exclusive.
+    pub fn check_resource_conflicts(&self) -> Vec<ValidationIssue> {
+        let plugins: Vec<&PluginManifest> = self.plugins().collect();
+        // Claims of each resource, in plugin order
+        let mut claimants: HashMap<(ResourceKind, &str), Vec<(usize, &ResourceClaim)>> =
+            HashMap::new();
+        for (i, plugin) in plugins.iter().enumerate() {
+            for claim in plugin.resource_claims() {
+                claimants
+                    .entry((claim.kind, claim.name.as_str()))
+                    .or_default()
+                    .push((i, claim));
+            }
+        }
+        let mut issues = Vec::new();
+
+        for (i, plugin) in plugins.iter().enumerate() {
+            for (j, claim) in plugin.resource_claims().iter().enumerate() {
+                // Report each conflicting pair once, on the later plugin
+                let earlier = claimants[&(claim.kind, claim.name.as_str())]
+                    .iter()
+                    .take_while(|(k, _)| *k < i);
+                let mut reported = None;
+                for &(k, other) in earlier {
+                    if reported == Some(k) || !(other.is_exclusive() || claim.is_exclusive()) {
+                        continue;
+                    }
+                    reported = Some(k);
+                    issues.push(ValidationIssue::error(
+                        format!("{}.resources.claims[{j}]", plugin.plugin.id),
+                        format!(
+                            "{} '{}' is also claimed by {}",
+                            claim.kind, claim.name, plugins[k].plugin.id
+                        ),
+                    ));
+                }
+            }
+        }
+
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate::has_errors;
+    use crate::Manifest;
+
+    fn plugin(id: &str, claims: &str) -> Manifest {
+        Manifest::from_toml(&format!(
+            "[plugin]\nid = \"{id}\"\nname = \"P\"\nversion = \"1.0.0\"\ntype = \"extension\"\n{claims}"
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_resource_conflicts() {
+        let port = "[[resources.claims]]\nkind = \"port\"\nname = \"8080\"\n";
+        let cache = "[[resources.claims]]\nkind = \"cache\"\nname = \"npm\"\n";
+
+        let set = ManifestSet::new()
+            .with(plugin("acme.a", port))
+            .with(plugin("acme.b", port));
+        let issues = set.check_resource_conflicts();
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].message.contains("acme.a"));
+
+        // Several claims by the same earlier plugin are one conflict
+        let set = ManifestSet::new()
+            .with(plugin("acme.a", &format!("{port}{port}")))
+            .with(plugin("acme.b", port));
+        assert_eq!(set.check_resource_conflicts().len(), 1);
+
+        let set = ManifestSet::new()
+            .with(plugin("acme.a", cache))
+            .with(plugin("acme.b", cache));
+        assert!(set.check_resource_conflicts().is_empty());
+
+        let exclusive_cache = format!("{cache}exclusive = true\n");
+        let set = ManifestSet::new()
+            .with(plugin("acme.a", cache))
+            .with(plugin("acme.b", &exclusive_cache));
+        assert_eq!(set.check_resource_conflicts().len(), 1);
+    }
+
+    #[test]
+    fn test_invalid_port_claim() {
+        let Manifest::Single(manifest) = plugin(
+            "acme.a",
+            "[[resources.claims]]\nkind = \"port\"\nname = \"http\"\n",
+        ) else {
+            unreachable!()
+        };
+        assert!(has_errors(&manifest.validate()));
+    }
+}
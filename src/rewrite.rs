@@ -0,0 +1,194 @@
+//! Bulk rewriting of manifest trees.
+//!
+//! `migrate_tree()` walks a directory (e.g., a registry checkout), re-parses
+//! every plugin.toml and package.toml, and writes them back in the current
+//! format with `write_canonical()`, keeping their header comment. Files that
+//! already match are left alone; with `dry_run` nothing is written.
+//!
+//! Signed manifests are never rewritten: their signature covers the
+//! document as declared, so filling in fields would invalidate it.
+//!
+//! ```no_run
+//! use lib_plugin_manifest::{migrate_tree, MigrateTreeOptions};
+//! use std::path::Path;
+//!
+//! let options = MigrateTreeOptions { dry_run: true, ..Default::default() };
+//! let report = migrate_tree(Path::new("registry"), &options).unwrap();
+//! for file in report.changed() {
+//!     println!("{}", file.path.display());
+//! }
+//! ```
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::discover::collect_manifests;
+use crate::error::ManifestError;
+use crate::migrate::MigrationNote;
+use crate::options::ParseOptions;
+use crate::serialize::header_comment;
+use crate::Manifest;
+
+/// Options for `migrate_tree()`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MigrateTreeOptions {
+    /// Report what would change without writing
+    pub dry_run: bool,
+
+    /// Options used to parse each manifest. Locale and platform should be
+    /// left unset, or the rewritten files lose their conditional sections.
+    pub parse: ParseOptions,
+}
+
+/// What `migrate_tree()` did with a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RewriteStatus {
+    /// Already in the current format
+    Unchanged,
+    /// Rewritten in place
+    Rewritten,
+    /// Would be rewritten (dry run)
+    WouldRewrite,
+    /// Left alone because it is signed
+    Signed,
+    /// Could not be parsed, serialized or written
+    Failed(String),
+}
+
+/// Result for one manifest file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RewriteReport {
+    /// Manifest path
+    pub path: PathBuf,
+
+    /// Outcome
+    pub status: RewriteStatus,
+
+    /// Format migrations applied while parsing
+    pub notes: Vec<MigrationNote>,
+}
+
+/// Result of `migrate_tree()`, one entry per manifest in path order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TreeReport {
+    /// Per-file results
+    pub files: Vec<RewriteReport>,
+}
+
+impl TreeReport {
+    /// Files that were (or in a dry run, would be) rewritten.
+    pub fn changed(&self) -> impl Iterator<Item = &RewriteReport> {
+        self.files.iter().filter(|f| {
+            matches!(
+                f.status,
+                RewriteStatus::Rewritten | RewriteStatus::WouldRewrite
+            )
+        })
+    }
+
+    /// Files that could not be processed.
+    pub fn failed(&self) -> impl Iterator<Item = &RewriteReport> {
+        self.files
+            .iter()
+            .filter(|f| matches!(f.status, RewriteStatus::Failed(_)))
+    }
+}
+
+/// Re-parse and rewrite every manifest under `root`.
+///
+/// Hidden directories are skipped. Failures on individual files are
+/// reported rather than aborting the walk; only an unreadable directory is
+/// an error.
+pub fn migrate_tree(
+    root: &Path,
+    options: &MigrateTreeOptions,
+) -> Result<TreeReport, ManifestError> {
+    let files = collect_manifests(root)?
+        .into_iter()
+        .map(|path| match rewrite_file(&path, options) {
+            Ok((status, notes)) => RewriteReport {
+                path,
+                status,
+                notes,
+            },
+            Err(e) => RewriteReport {
+                path,
+                status: RewriteStatus::Failed(e.to_string()),
+                notes: Vec::new(),
+            },
+        })
+        .collect();
+    Ok(TreeReport { files })
+}
+
+fn rewrite_file(
+    path: &Path,
+    options: &MigrateTreeOptions,
+) -> Result<(RewriteStatus, Vec<MigrationNote>), ManifestError> {
+    let content = fs::read_to_string(path)?;
+    let manifest = Manifest::from_toml_with(&content, &options.parse)?;
+    let (notes, signed) = match &manifest {
+        Manifest::Single(m) => (m.origin.migration_notes.clone(), m.signature.is_some()),
+        Manifest::Package(m) => (m.origin.migration_notes.clone(), m.signature.is_some()),
+    };
+    if signed {
+        return Ok((RewriteStatus::Signed, notes));
+    }
+    let rewritten = manifest.to_canonical_toml(header_comment(&content).as_deref())?;
+
+    let status = if rewritten == content {
+        RewriteStatus::Unchanged
+    } else if options.dry_run {
+        RewriteStatus::WouldRewrite
+    } else {
+        manifest.write_canonical(path)?;
+        RewriteStatus::Rewritten
+    };
+    Ok((status, notes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrate_tree() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let legacy = root.join("acme-tool/plugin.toml");
+        let broken = root.join("broken/package.toml");
+        let signed = root.join("signed/plugin.toml");
+        for path in [&legacy, &broken, &signed] {
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+        }
+        fs::create_dir_all(root.join(".git")).unwrap();
+        let content = "# Tool plugin\n\n[plugin]\nid = \"acme.tool\"\nname = \"Tool\"\nversion = \"1.0.0\"\ntype = \"extension\"\ncli_command = \"tool\"\n";
+        let signed_content = "[plugin]\nid = \"acme.signed\"\nname = \"Signed\"\nversion = \"1.0.0\"\ntype = \"extension\"\n\n[signature]\npublic_key = \"key\"\nsignature_file = \"plugin.sig\"\n";
+        fs::write(&legacy, content).unwrap();
+        fs::write(&broken, "not toml [").unwrap();
+        fs::write(&signed, signed_content).unwrap();
+        fs::write(root.join(".git/plugin.toml"), "ignored").unwrap();
+
+        let dry_run = MigrateTreeOptions {
+            dry_run: true,
+            ..Default::default()
+        };
+        let report = migrate_tree(root, &dry_run).unwrap();
+        assert_eq!(report.files.len(), 3);
+        assert_eq!(report.failed().count(), 1);
+        assert_eq!(report.files[2].status, RewriteStatus::Signed);
+        let changed: Vec<_> = report.changed().collect();
+        assert_eq!(changed[0].status, RewriteStatus::WouldRewrite);
+        assert_eq!(changed[0].notes.len(), 1);
+        assert_eq!(fs::read_to_string(&legacy).unwrap(), content);
+
+        let report = migrate_tree(root, &MigrateTreeOptions::default()).unwrap();
+        assert_eq!(report.changed().count(), 1);
+        let report = migrate_tree(root, &MigrateTreeOptions::default()).unwrap();
+        assert_eq!(report.changed().count(), 0);
+        assert!(Manifest::from_file(&legacy).unwrap().to_toml().is_ok());
+        let rewritten = fs::read_to_string(&legacy).unwrap();
+        assert!(rewritten.starts_with("# Tool plugin\n"));
+        assert_eq!(fs::read_to_string(&signed).unwrap(), signed_content);
+    }
+}
@@ -0,0 +1,53 @@
+//! JSON Schema for plugin.toml and package.toml.
+//!
+//! The schemas are derived from the same types (and serde attributes) used
+//! for parsing. Editor tooling such as taplo or Even Better TOML can use
+//! them for completion and validation:
+//!
+//! ```toml
+//! #:schema ./plugin.schema.json
+//! [plugin]
+//! id = "vendor.plugin-name"
+//! ```
+
+use crate::package::PackageManifest;
+use crate::plugin::PluginManifest;
+
+/// JSON Schema document describing plugin.toml.
+pub fn plugin_schema() -> serde_json::Value {
+    schemars::schema_for!(PluginManifest).to_value()
+}
+
+/// JSON Schema document describing package.toml.
+pub fn package_schema() -> serde_json::Value {
+    schemars::schema_for!(PackageManifest).to_value()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plugin_schema() {
+        let schema = plugin_schema();
+        assert_eq!(schema["title"], "PluginManifest");
+        let properties = schema["properties"].as_object().unwrap();
+        assert!(properties.contains_key("plugin"));
+        assert!(properties.contains_key("compat"));
+        // skipped fields stay out of the schema
+        assert!(!properties.contains_key("defaulted_fields"));
+        let required = schema["required"].as_array().unwrap();
+        assert_eq!(required, &[serde_json::json!("plugin")]);
+
+        let meta = &schema["$defs"]["PluginMeta"]["properties"];
+        assert!(meta.get("type").is_some());
+        assert!(meta.get("plugin_type").is_none());
+    }
+
+    #[test]
+    fn test_package_schema() {
+        let schema = package_schema();
+        assert_eq!(schema["title"], "PackageManifest");
+        assert!(schema["$defs"].get("PluginDef").is_some());
+    }
+}
@@ -0,0 +1,276 @@
+//! Serialization profiles for `to_toml_with()`.
+//!
+//! `SerializeProfile::GitFriendly` is for manifests generated by tools and
+//! committed to a repository: lists whose order carries no meaning are
+//! sorted, so regenerating a manifest only changes the lines whose values
+//! changed.
+//!
+//! ```toml
+//! [compatibility]
+//! platforms = [
+//!     "darwin-aarch64",
+//!     "linux-x86_64",
+//! ]
+//! ```
+//!
+//! Map entries (e.g., `binary.checksums`) are sorted by key as well.
+//!
+//! `to_canonical_toml()` is the writer for generated manifests: the
+//! `GitFriendly` profile (sections always in declaration order) below a
+//! header comment, which `write_canonical()` carries over from the file it
+//! replaces.
+
+use std::path::Path;
+use toml_edit::{DocumentMut, Item};
+
+use crate::assets::Asset;
+use crate::error::ManifestError;
+use crate::package::PackageManifest;
+use crate::plugin::{CompatibilityInfo, PluginManifest};
+use crate::Manifest;
+
+/// How `to_toml_with()` lays out a manifest.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SerializeProfile {
+    /// Fields in declaration order, lists as written (`to_toml()`)
+    #[default]
+    Standard,
+    /// `Standard`, with unordered lists (platforms, dependencies, tags,
+    /// aliases, permission grants, metrics) and map entries sorted, and a
+    /// trailing newline
+    GitFriendly,
+}
+
+impl PluginManifest {
+    /// Serialize to TOML string with a profile.
+    pub fn to_toml_with(&self, profile: SerializeProfile) -> Result<String, ManifestError> {
+        match profile {
+            SerializeProfile::Standard => self.to_toml(),
+            SerializeProfile::GitFriendly => {
+                let mut manifest = self.clone();
+                sort_plugin_lists(&mut manifest);
+                sort_tables(&manifest.to_toml()?, &["binary", "config"])
+            }
+        }
+    }
+}
+
+impl PackageManifest {
+    /// Serialize to TOML string with a profile.
+    pub fn to_toml_with(&self, profile: SerializeProfile) -> Result<String, ManifestError> {
+        match profile {
+            SerializeProfile::Standard => self.to_toml(),
+            SerializeProfile::GitFriendly => {
+                let mut manifest = self.clone();
+                sort_compatibility_lists(&mut manifest.compatibility);
+                for plugin in &mut manifest.plugins {
+                    plugin.depends_on.sort();
+                    sort_asset_lists(&mut plugin.assets);
+                }
+                sort_tables(&manifest.to_toml()?, &["binary"])
+            }
+        }
+    }
+}
+
+impl Manifest {
+    /// Serialize to TOML string with a profile.
+    pub fn to_toml_with(&self, profile: SerializeProfile) -> Result<String, ManifestError> {
+        match self {
+            Manifest::Single(m) => m.to_toml_with(profile),
+            Manifest::Package(m) => m.to_toml_with(profile),
+        }
+    }
+
+    /// Serialize with the `GitFriendly` profile below an optional header
+    /// comment (one `#` line per header line).
+    pub fn to_canonical_toml(&self, header: Option<&str>) -> Result<String, ManifestError> {
+        let toml = self.to_toml_with(SerializeProfile::GitFriendly)?;
+        Ok(with_header(toml, header))
+    }
+
+    /// Write `to_canonical_toml()` to `path`, keeping the header comment of
+    /// the file being replaced, if any.
+    pub fn write_canonical(&self, path: &Path) -> Result<(), ManifestError> {
+        let header = match std::fs::read_to_string(path) {
+            Ok(existing) => header_comment(&existing),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e.into()),
+        };
+        std::fs::write(path, self.to_canonical_toml(header.as_deref())?)?;
+        Ok(())
+    }
+}
+
+/// The comment block at the top of a TOML document, without the `#`
+/// markers, or None if the document doesn't start with a comment.
+pub fn header_comment(toml: &str) -> Option<String> {
+    let lines: Vec<&str> = toml
+        .lines()
+        .map_while(|line| line.trim_start().strip_prefix('#'))
+        .map(|line| line.strip_prefix(' ').unwrap_or(line))
+        .collect();
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+fn with_header(toml: String, header: Option<&str>) -> String {
+    let Some(header) = header else {
+        return toml;
+    };
+    let mut out = String::new();
+    for line in header.lines() {
+        if line.is_empty() {
+            out.push_str("#\n");
+        } else {
+            out.push_str(&format!("# {line}\n"));
+        }
+    }
+    out.push('\n');
+    out + &toml
+}
+
+fn sort_plugin_lists(manifest: &mut PluginManifest) {
+    sort_compatibility_lists(&mut manifest.compatibility);
+    if let Some(cli) = &mut manifest.cli {
+        cli.aliases.sort();
+    }
+    if let Some(tags) = &mut manifest.tags {
+        tags.categories.sort();
+        tags.platforms.sort();
+    }
+    if let Some(permissions) = &mut manifest.permissions {
+        if let Some(network) = &mut permissions.network {
+            network.allow.sort();
+        }
+        if let Some(fs) = &mut permissions.fs {
+            fs.read.sort();
+            fs.write.sort();
+        }
+        if let Some(env) = &mut permissions.env {
+            env.allow.sort();
+        }
+        if let Some(subprocess) = &mut permissions.subprocess {
+            subprocess.allow.sort();
+        }
+    }
+    if let Some(observability) = &mut manifest.observability {
+        observability.metrics.sort();
+    }
+    sort_asset_lists(&mut manifest.assets);
+}
+
+fn sort_compatibility_lists(compatibility: &mut CompatibilityInfo) {
+    compatibility.platforms.sort();
+    compatibility.depends_on.sort();
+}
+
+fn sort_asset_lists(assets: &mut [Asset]) {
+    for asset in assets {
+        asset.platforms.sort();
+    }
+}
+
+/// Sort the keys of the map-valued subtables of `sections` (e.g.,
+/// `[binary.checksums]`), which serialize in arbitrary order, and ensure a
+/// trailing newline.
+fn sort_tables(toml: &str, sections: &[&str]) -> Result<String, ManifestError> {
+    let mut doc: DocumentMut = toml
+        .parse()
+        .map_err(|e| ManifestError::InvalidFormat(format!("Failed to serialize manifest: {e}")))?;
+    for section in sections {
+        let Some(table) = doc.get_mut(section).and_then(Item::as_table_like_mut) else {
+            continue;
+        };
+        for (_, item) in table.iter_mut() {
+            if let Some(map) = item.as_table_like_mut() {
+                map.sort_values();
+            }
+        }
+    }
+    let mut toml = doc.to_string();
+    if !toml.ends_with('\n') {
+        toml.push('\n');
+    }
+    Ok(toml)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_git_friendly() {
+        let manifest = PluginManifest::from_toml(
+            r#"
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.0.0"
+type = "extension"
+
+[compatibility]
+platforms = ["windows-x86_64", "darwin-aarch64", "linux-x86_64"]
+
+[binary]
+name = "tool"
+
+[binary.checksums]
+windows-x86_64 = "ccc"
+darwin-aarch64 = "aaa"
+linux-x86_64 = "bbb"
+
+[tags]
+categories = ["sync", "build"]
+"#,
+        )
+        .unwrap();
+
+        let toml = manifest
+            .to_toml_with(SerializeProfile::GitFriendly)
+            .unwrap();
+        assert!(toml.ends_with('\n'));
+        let darwin = toml.find("\"darwin-aarch64\"").unwrap();
+        let windows = toml.find("\"windows-x86_64\"").unwrap();
+        assert!(darwin < windows);
+        assert!(toml.find("darwin-aarch64 =").unwrap() < toml.find("windows-x86_64 =").unwrap());
+        assert!(toml.find("\"build\"").unwrap() < toml.find("\"sync\"").unwrap());
+
+        let reparsed = PluginManifest::from_toml(&toml).unwrap();
+        assert_eq!(
+            reparsed
+                .to_toml_with(SerializeProfile::GitFriendly)
+                .unwrap(),
+            toml
+        );
+        assert_eq!(
+            manifest.to_toml_with(SerializeProfile::Standard).unwrap(),
+            manifest.to_toml().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_write_canonical_keeps_header() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("plugin.toml");
+        std::fs::write(
+            &path,
+            "# Generated by manifest-gen.\n#\n# Do not edit.\n[plugin]\nid = \"acme.old\"\n",
+        )
+        .unwrap();
+
+        let manifest = Manifest::from_toml(
+            "[plugin]\nid = \"acme.tool\"\nname = \"Tool\"\nversion = \"1.0.0\"\ntype = \"extension\"\n",
+        )
+        .unwrap();
+        manifest.write_canonical(&path).unwrap();
+
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert!(written.starts_with("# Generated by manifest-gen.\n#\n# Do not edit.\n\n"));
+        assert_eq!(
+            header_comment(&written).as_deref(),
+            Some("Generated by manifest-gen.\n\nDo not edit.")
+        );
+        assert_eq!(Manifest::from_toml(&written).unwrap().id(), "acme.tool");
+        assert!(written.ends_with(&manifest.to_canonical_toml(None).unwrap()));
+    }
+}
@@ -0,0 +1,520 @@
+//! Service resolution across a set of manifests.
+//!
+//! Matches every `[[requires]]` entry against the `[[provides]]` entries of
+//! the other plugins in a `ManifestSet`, using `ServiceDeclaration::satisfies()`
+//! (caret-compatible versions). When several plugins provide the
+//! same service, a `ProviderPolicy` picks one and the choice is recorded.
+//! `resolve_services()` does the same for a plain slice of plugin manifests.
+//! Requirements with `provider = "host"` (or `"any"`, when no plugin
+//! provides the service) are matched against the policy's host services.
+
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::plugin::{PluginManifest, ServiceDeclaration, ServiceRequirement, ServiceSource};
+use crate::set::ManifestSet;
+use crate::validate::vendor_of;
+use crate::version::version_satisfies;
+
+/// How to choose between several providers of the same service.
+///
+/// Pins win, then (optionally) first-party providers, then the highest
+/// service version. Remaining ties go to the provider added first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProviderPolicy {
+    /// Service ID -> plugin ID that must provide it
+    pub pins: BTreeMap<String, String>,
+
+    /// Prefer providers from first-party vendors
+    pub prefer_first_party: bool,
+
+    /// Vendors considered first-party (the `vendor` in `vendor.name`)
+    pub first_party_vendors: Vec<String>,
+
+    /// Services provided by the host: service ID -> version
+    pub host_services: BTreeMap<String, String>,
+}
+
+impl Default for ProviderPolicy {
+    fn default() -> Self {
+        Self {
+            pins: BTreeMap::new(),
+            prefer_first_party: true,
+            first_party_vendors: vec!["adi".to_string()],
+            host_services: BTreeMap::new(),
+        }
+    }
+}
+
+impl ProviderPolicy {
+    /// Builder-style pin of a service to a provider.
+    pub fn pin(mut self, service: impl Into<String>, plugin: impl Into<String>) -> Self {
+        self.pins.insert(service.into(), plugin.into());
+        self
+    }
+
+    /// Builder-style declaration of a host-provided service.
+    pub fn host_service(mut self, service: impl Into<String>, version: impl Into<String>) -> Self {
+        self.host_services.insert(service.into(), version.into());
+        self
+    }
+
+    fn is_first_party(&self, plugin_id: &str) -> bool {
+        vendor_of(plugin_id).is_some_and(|v| self.first_party_vendors.iter().any(|f| f == v))
+    }
+}
+
+/// Why a provider was selected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "snake_case")]
+pub enum SelectionReason {
+    /// Only one provider matched
+    OnlyProvider,
+    /// Pinned by the policy
+    Pinned,
+    /// Preferred as first-party
+    FirstParty,
+    /// Highest service version among the candidates
+    HighestVersion,
+    /// Provided by the host
+    Host,
+}
+
+/// Provider ID used in bindings to host-provided services.
+pub const HOST_PROVIDER: &str = "host";
+
+/// A requirement bound to a provider.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ServiceBinding {
+    /// Plugin that requires the service
+    pub consumer: String,
+
+    /// Service ID
+    pub service: String,
+
+    /// Selected provider plugin ID
+    pub provider: String,
+
+    /// Version of the service the provider declares
+    pub version: String,
+
+    /// Why this provider was selected
+    pub reason: SelectionReason,
+
+    /// All providers that satisfied the requirement
+    pub candidates: Vec<String>,
+}
+
+/// A requirement no provider could satisfy.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct UnresolvedService {
+    /// Plugin that requires the service
+    pub consumer: String,
+
+    /// Service ID
+    pub service: String,
+
+    /// Whether the requirement is optional
+    pub optional: bool,
+
+    /// Human-readable explanation
+    pub reason: String,
+
+    /// Plugins providing the service at a version that doesn't satisfy the
+    /// requirement
+    pub conflicting: Vec<String>,
+}
+
+impl UnresolvedService {
+    /// Whether the service is provided, but only at incompatible versions.
+    pub fn is_version_conflict(&self) -> bool {
+        !self.conflicting.is_empty()
+    }
+}
+
+/// A requirement bound to a deprecated service.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct DeprecatedService {
+    /// Plugin that requires the service
+    pub consumer: String,
+
+    /// Service ID
+    pub service: String,
+
+    /// Provider that declared the service deprecated
+    pub provider: String,
+
+    /// ID of the replacement service, if declared
+    pub replaced_by: Option<String>,
+}
+
+impl fmt::Display for DeprecatedService {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} requires '{}', which {} deprecates",
+            self.consumer, self.service, self.provider
+        )?;
+        if let Some(replacement) = &self.replaced_by {
+            write!(f, " (use '{replacement}' instead)")?;
+        }
+        Ok(())
+    }
+}
+
+/// Result of `ManifestSet::resolve_services()`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ServiceResolution {
+    /// Resolved requirements
+    pub bindings: Vec<ServiceBinding>,
+
+    /// Requirements without a suitable provider
+    pub unresolved: Vec<UnresolvedService>,
+
+    /// Resolved requirements whose provider deprecates the service
+    pub deprecated: Vec<DeprecatedService>,
+}
+
+impl ServiceResolution {
+    /// Whether every non-optional requirement was resolved.
+    pub fn is_satisfied(&self) -> bool {
+        self.unresolved.iter().all(|u| u.optional)
+    }
+
+    /// Provider selected for a consumer's requirement.
+    pub fn provider_for(&self, consumer: &str, service: &str) -> Option<&ServiceBinding> {
+        self.bindings
+            .iter()
+            .find(|b| b.consumer == consumer && b.service == service)
+    }
+}
+
+/// A provider candidate: plugin ID and parsed service version.
+struct Candidate<'a> {
+    plugin: &'a str,
+    version: &'a str,
+    parsed: Version,
+}
+
+impl ManifestSet {
+    /// Match every service requirement in the set against the provided
+    /// services, choosing between providers with `policy`.
+    pub fn resolve_services(&self, policy: &ProviderPolicy) -> ServiceResolution {
+        resolve(self.plugins(), |service| self.providing(service), policy)
+    }
+}
+
+/// Match every service requirement in `manifests` against the services the
+/// manifests provide, with the default `ProviderPolicy`.
+///
+/// Unsatisfied requirements (including optional ones) are reported in
+/// `unresolved`; those whose service exists only at incompatible versions
+/// list the conflicting providers.
+pub fn resolve_services(manifests: &[PluginManifest]) -> ServiceResolution {
+    resolve_services_with(manifests, &ProviderPolicy::default())
+}
+
+/// `resolve_services()` with an explicit provider policy.
+pub fn resolve_services_with(
+    plugins: &[PluginManifest],
+    policy: &ProviderPolicy,
+) -> ServiceResolution {
+    let providing = |service: &str| {
+        plugins
+            .iter()
+            .filter(|p| p.provides.iter().any(|s| s.id == service))
+            .collect::<Vec<_>>()
+    };
+    resolve(plugins.iter(), providing, policy)
+}
+
+/// Resolve the requirements of `consumers`, with `providing` listing the
+/// plugins that provide a service.
+fn resolve<'a>(
+    consumers: impl Iterator<Item = &'a PluginManifest>,
+    providing: impl Fn(&str) -> Vec<&'a PluginManifest>,
+    policy: &ProviderPolicy,
+) -> ServiceResolution {
+    let mut resolution = ServiceResolution::default();
+
+    for consumer in consumers {
+        for req in &consumer.requires {
+            let providers = providing(&req.id);
+            match select_provider(&providers, req, policy) {
+                Ok(binding) => {
+                    let binding = ServiceBinding {
+                        consumer: consumer.plugin.id.clone(),
+                        ..binding
+                    };
+                    if let Some(decl) = declaration(&providers, &binding) {
+                        if decl.deprecated {
+                            resolution.deprecated.push(DeprecatedService {
+                                consumer: binding.consumer.clone(),
+                                service: binding.service.clone(),
+                                provider: binding.provider.clone(),
+                                replaced_by: decl.replaced_by.clone(),
+                            });
+                        }
+                    }
+                    resolution.bindings.push(binding);
+                }
+                Err(reason) => resolution.unresolved.push(UnresolvedService {
+                    consumer: consumer.plugin.id.clone(),
+                    service: req.id.clone(),
+                    optional: req.optional,
+                    reason,
+                    conflicting: providers
+                        .iter()
+                        .filter(|p| {
+                            p.provides
+                                .iter()
+                                .any(|s| s.id == req.id && !s.satisfies(req))
+                        })
+                        .map(|p| p.plugin.id.clone())
+                        .collect(),
+                }),
+            }
+        }
+    }
+
+    resolution
+}
+
+/// The service declaration a binding was made against.
+fn declaration<'a>(
+    providers: &[&'a PluginManifest],
+    binding: &ServiceBinding,
+) -> Option<&'a ServiceDeclaration> {
+    providers
+        .iter()
+        .copied()
+        .find(|p| p.plugin.id == binding.provider)?
+        .provides
+        .iter()
+        .find(|s| s.id == binding.service && s.version == binding.version)
+}
+
+/// Pick a provider for one requirement from the plugins providing its
+/// service. The returned binding has an empty consumer.
+fn select_provider(
+    providers: &[&PluginManifest],
+    req: &ServiceRequirement,
+    policy: &ProviderPolicy,
+) -> Result<ServiceBinding, String> {
+    if let Some(v) = &req.min_version {
+        Version::parse(v).map_err(|_| format!("min_version '{v}' is not a valid version"))?;
+    }
+    let host = policy
+        .host_services
+        .get(&req.id)
+        .filter(|_| req.provider != ServiceSource::Plugin)
+        .filter(|v| version_satisfies(v, req.min_version.as_deref()))
+        .map(|version| ServiceBinding {
+            consumer: String::new(),
+            service: req.id.clone(),
+            provider: HOST_PROVIDER.to_string(),
+            version: version.clone(),
+            reason: SelectionReason::Host,
+            candidates: vec![HOST_PROVIDER.to_string()],
+        });
+    if req.provider == ServiceSource::Host {
+        return host.ok_or_else(|| match &req.min_version {
+            Some(min) => format!("Host does not provide '{}' compatible with {min}", req.id),
+            None => format!("Host does not provide '{}'", req.id),
+        });
+    }
+
+    let candidates: Vec<Candidate> = providers
+        .iter()
+        .flat_map(|p| {
+            p.provides
+                .iter()
+                .filter(|s| s.satisfies(req))
+                .map(move |s| (p.plugin.id.as_str(), s.version.as_str()))
+        })
+        .filter_map(|(plugin, version)| {
+            Some(Candidate {
+                plugin,
+                version,
+                parsed: Version::parse(version).ok()?,
+            })
+        })
+        .collect();
+
+    if candidates.is_empty() {
+        if let Some(host) = host {
+            return Ok(host);
+        }
+        return Err(match &req.min_version {
+            Some(min) => format!("No plugin provides '{}' compatible with {min}", req.id),
+            None => format!("No plugin provides '{}'", req.id),
+        });
+    }
+
+    let (chosen, reason) = if let Some(pinned) = policy.pins.get(&req.id) {
+        let chosen = candidates
+            .iter()
+            .find(|c| c.plugin == pinned)
+            .ok_or_else(|| format!("Pinned provider '{pinned}' does not satisfy '{}'", req.id))?;
+        (chosen, SelectionReason::Pinned)
+    } else if candidates.len() == 1 {
+        (&candidates[0], SelectionReason::OnlyProvider)
+    } else {
+        let first_party: Vec<&Candidate> = candidates
+            .iter()
+            .filter(|c| policy.prefer_first_party && policy.is_first_party(c.plugin))
+            .collect();
+        let (pool, reason) = if first_party.is_empty() || first_party.len() == candidates.len() {
+            (candidates.iter().collect(), SelectionReason::HighestVersion)
+        } else {
+            (first_party, SelectionReason::FirstParty)
+        };
+        // max_by_key keeps the last maximum; reverse so the first-added wins ties
+        let chosen = pool.into_iter().rev().max_by_key(|c| &c.parsed).unwrap();
+        (chosen, reason)
+    };
+
+    Ok(ServiceBinding {
+        consumer: String::new(),
+        service: req.id.clone(),
+        provider: chosen.plugin.to_string(),
+        version: chosen.version.to_string(),
+        reason,
+        candidates: candidates.iter().map(|c| c.plugin.to_string()).collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Manifest;
+
+    fn plugin(id: &str, extra: &str) -> Manifest {
+        Manifest::from_toml(&format!(
+            "[plugin]\nid = \"{id}\"\nname = \"{id}\"\nversion = \"1.0.0\"\ntype = \"extension\"\n{extra}"
+        ))
+        .unwrap()
+    }
+
+    fn provider(id: &str, version: &str) -> Manifest {
+        plugin(
+            id,
+            &format!("[[provides]]\nid = \"search\"\nversion = \"{version}\"\n"),
+        )
+    }
+
+    fn consumer(min_version: &str) -> Manifest {
+        plugin(
+            "acme.app",
+            &format!("[[requires]]\nid = \"search\"\nmin_version = \"{min_version}\"\n"),
+        )
+    }
+
+    #[test]
+    fn test_selection_policy() {
+        let set = ManifestSet::new()
+            .with(consumer("1.0.0"))
+            .with(provider("acme.search", "1.6.0"))
+            .with(provider("adi.search", "1.4.0"))
+            .with(provider("other.search", "2.0.0"));
+
+        let resolution = set.resolve_services(&ProviderPolicy::default());
+        let binding = resolution.provider_for("acme.app", "search").unwrap();
+        assert_eq!(binding.provider, "adi.search");
+        assert_eq!(binding.reason, SelectionReason::FirstParty);
+        assert_eq!(binding.candidates, ["acme.search", "adi.search"]);
+
+        let policy = ProviderPolicy {
+            prefer_first_party: false,
+            ..ProviderPolicy::default()
+        };
+        let binding = &set.resolve_services(&policy).bindings[0];
+        assert_eq!(binding.provider, "acme.search");
+        assert_eq!(binding.reason, SelectionReason::HighestVersion);
+
+        let policy = ProviderPolicy::default().pin("search", "acme.search");
+        let binding = &set.resolve_services(&policy).bindings[0];
+        assert_eq!(binding.provider, "acme.search");
+        assert_eq!(binding.reason, SelectionReason::Pinned);
+    }
+
+    #[test]
+    fn test_unresolved() {
+        let set = ManifestSet::new()
+            .with(consumer("3.0.0"))
+            .with(provider("acme.search", "2.1.0"));
+
+        let resolution = set.resolve_services(&ProviderPolicy::default());
+        assert!(!resolution.is_satisfied());
+        assert_eq!(resolution.unresolved[0].service, "search");
+        assert_eq!(resolution.unresolved[0].conflicting, ["acme.search"]);
+
+        let plugins: Vec<PluginManifest> = ManifestSet::new()
+            .with(consumer("1.0.0"))
+            .plugins()
+            .cloned()
+            .collect();
+        let unresolved = &resolve_services(&plugins).unresolved[0];
+        assert!(!unresolved.is_version_conflict());
+
+        let policy = ProviderPolicy::default().pin("search", "acme.missing");
+        let set = ManifestSet::new()
+            .with(consumer("1.0.0"))
+            .with(provider("acme.search", "2.1.0"));
+        assert!(!set.resolve_services(&policy).is_satisfied());
+    }
+
+    #[test]
+    fn test_deprecated_service() {
+        let set = ManifestSet::new().with(consumer("1.0.0")).with(plugin(
+            "acme.search",
+            "[[provides]]\nid = \"search\"\nversion = \"1.0.0\"\ndeprecated = true\nreplaced_by = \"search2\"\n",
+        ));
+
+        let resolution = set.resolve_services(&ProviderPolicy::default());
+        assert!(resolution.is_satisfied());
+        let deprecated = &resolution.deprecated[0];
+        assert_eq!(deprecated.replaced_by.as_deref(), Some("search2"));
+        assert_eq!(
+            deprecated.to_string(),
+            "acme.app requires 'search', which acme.search deprecates (use 'search2' instead)"
+        );
+    }
+
+    #[test]
+    fn test_host_services() {
+        let app = plugin(
+            "acme.app",
+            "[[requires]]\nid = \"clipboard\"\nprovider = \"host\"\n\n[[requires]]\nid = \"search\"\nprovider = \"any\"\n",
+        );
+        let set = ManifestSet::new().with(app);
+        assert!(!set
+            .resolve_services(&ProviderPolicy::default())
+            .is_satisfied());
+
+        let policy = ProviderPolicy::default()
+            .host_service("clipboard", "1.0.0")
+            .host_service("search", "1.0.0");
+        let resolution = set.resolve_services(&policy);
+        assert!(resolution.is_satisfied());
+        let binding = resolution.provider_for("acme.app", "search").unwrap();
+        assert_eq!(binding.provider, HOST_PROVIDER);
+        assert_eq!(binding.reason, SelectionReason::Host);
+
+        // A plugin provider wins over the host for "any"
+        let set = set.with(provider("acme.search", "2.0.0"));
+        let binding = set
+            .resolve_services(&policy)
+            .provider_for("acme.app", "search")
+            .cloned()
+            .unwrap();
+        assert_eq!(binding.provider, "acme.search");
+    }
+}
@@ -0,0 +1,506 @@
+//! Sets of manifests (e.g., everything installed in a host).
+//!
+//! Checks that need to look at several manifests at once live here.
+//!
+//! The set keeps its packages expanded and indexes which plugins provide
+//! each service and which plugins depend on each plugin. Hosts that reload
+//! manifests while running call `apply_event()`, which re-indexes only the
+//! affected manifest instead of rebuilding everything.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::error::ManifestError;
+use crate::plugin::{PluginManifest, ServiceSource};
+use crate::source::{Origin, Sourced};
+use crate::validate::ValidationIssue;
+use crate::Manifest;
+
+/// A manifest together with the file it was loaded from.
+#[derive(Debug, Clone)]
+pub struct ManifestEntry {
+    /// The manifest
+    pub manifest: Manifest,
+
+    /// Source file (None for manifests not loaded from disk)
+    pub source: Option<PathBuf>,
+}
+
+/// A change to the manifests installed in a host.
+#[derive(Debug, Clone)]
+pub enum ManifestEvent {
+    /// A manifest was installed
+    Added(Manifest),
+    /// A new version of an installed manifest replaced the old one (matched
+    /// by manifest ID; the source path is kept)
+    Updated(Manifest),
+    /// The manifest with this ID was uninstalled
+    Removed(String),
+}
+
+/// A collection of plugin and package manifests.
+#[derive(Debug, Clone, Default)]
+pub struct ManifestSet {
+    entries: Vec<ManifestEntry>,
+
+    /// Plugins of each entry, with packages expanded (parallel to `entries`)
+    expanded: Vec<Vec<PluginManifest>>,
+
+    /// Service ID -> IDs of the plugins providing it
+    providers: HashMap<String, Vec<String>>,
+
+    /// Plugin ID -> IDs of the plugins listing it in `depends_on`
+    dependents: HashMap<String, Vec<String>>,
+
+    /// Plugin ID -> positions of the entries declaring it
+    plugin_entries: HashMap<String, Vec<usize>>,
+}
+
+impl ManifestSet {
+    /// Create an empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a manifest.
+    pub fn add(&mut self, manifest: Manifest) {
+        self.push(ManifestEntry {
+            manifest,
+            source: None,
+        });
+    }
+
+    /// Add a manifest loaded from `source`.
+    pub fn add_with_source(&mut self, manifest: Manifest, source: &Path) {
+        self.push(ManifestEntry {
+            manifest,
+            source: Some(source.to_path_buf()),
+        });
+    }
+
+    /// Add a parsed manifest, keeping its path when it was loaded from disk.
+    pub fn add_sourced(&mut self, sourced: Sourced<Manifest>) {
+        let source = match sourced.source.origin {
+            Origin::Path(path) => Some(path),
+            Origin::Url(_) | Origin::Inline => None,
+        };
+        self.push(ManifestEntry {
+            manifest: sourced.value,
+            source,
+        });
+    }
+
+    /// Apply an install, update or uninstall, updating the indexes for the
+    /// affected manifest only.
+    ///
+    /// Updates and removals of a manifest ID not in the set fail with
+    /// `UnknownPlugin`.
+    pub fn apply_event(&mut self, event: ManifestEvent) -> Result<(), ManifestError> {
+        match event {
+            ManifestEvent::Added(manifest) => self.add(manifest),
+            ManifestEvent::Updated(manifest) => {
+                let i = self.position(manifest.id())?;
+                self.unindex(i);
+                self.expanded[i] = self.index(i, &manifest);
+                self.entries[i].manifest = manifest;
+            }
+            ManifestEvent::Removed(id) => {
+                let i = self.position(&id)?;
+                self.unindex(i);
+                self.entries.remove(i);
+                self.expanded.remove(i);
+                for position in self.plugin_entries.values_mut().flatten() {
+                    if *position > i {
+                        *position -= 1;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// IDs of the plugins providing a service, at any version.
+    pub fn providers_of(&self, service: &str) -> &[String] {
+        self.providers.get(service).map_or(&[], Vec::as_slice)
+    }
+
+    /// IDs of the plugins listing `plugin` in their `depends_on`.
+    pub fn dependents_of(&self, plugin: &str) -> &[String] {
+        self.dependents.get(plugin).map_or(&[], Vec::as_slice)
+    }
+
+    /// The plugins providing a service, in set order, found through the
+    /// providers index.
+    pub(crate) fn providing(&self, service: &str) -> Vec<&PluginManifest> {
+        let mut positions: Vec<usize> = self
+            .providers_of(service)
+            .iter()
+            .filter_map(|id| self.plugin_entries.get(id))
+            .flatten()
+            .copied()
+            .collect();
+        positions.sort_unstable();
+        positions.dedup();
+        positions
+            .into_iter()
+            .flat_map(|i| &self.expanded[i])
+            .filter(|p| p.provides.iter().any(|s| s.id == service))
+            .collect()
+    }
+
+    fn push(&mut self, entry: ManifestEntry) {
+        let plugins = self.index(self.entries.len(), &entry.manifest);
+        self.entries.push(entry);
+        self.expanded.push(plugins);
+    }
+
+    fn position(&self, id: &str) -> Result<usize, ManifestError> {
+        self.entries
+            .iter()
+            .position(|e| e.manifest.id() == id)
+            .ok_or_else(|| ManifestError::UnknownPlugin(id.to_string()))
+    }
+
+    /// Expand the manifest of entry `i` and add its plugins to the indexes.
+    fn index(&mut self, i: usize, manifest: &Manifest) -> Vec<PluginManifest> {
+        let plugins = match manifest {
+            Manifest::Single(m) => vec![m.clone()],
+            Manifest::Package(m) => m.expand_plugins(),
+        };
+        for plugin in &plugins {
+            let id = &plugin.plugin.id;
+            self.plugin_entries.entry(id.clone()).or_default().push(i);
+            for service in &plugin.provides {
+                self.providers
+                    .entry(service.id.clone())
+                    .or_default()
+                    .push(id.clone());
+            }
+            for dep in &plugin.compatibility.depends_on {
+                self.dependents
+                    .entry(dep.clone())
+                    .or_default()
+                    .push(id.clone());
+            }
+        }
+        plugins
+    }
+
+    /// Remove the plugins of entry `i` from the indexes.
+    fn unindex(&mut self, i: usize) {
+        fn remove_one<T: PartialEq>(index: &mut HashMap<String, Vec<T>>, key: &str, value: &T) {
+            if let Some(values) = index.get_mut(key) {
+                if let Some(pos) = values.iter().position(|x| x == value) {
+                    values.remove(pos);
+                }
+                if values.is_empty() {
+                    index.remove(key);
+                }
+            }
+        }
+        for plugin in &self.expanded[i] {
+            let id = &plugin.plugin.id;
+            remove_one(&mut self.plugin_entries, id, &i);
+            for service in &plugin.provides {
+                remove_one(&mut self.providers, &service.id, id);
+            }
+            for dep in &plugin.compatibility.depends_on {
+                remove_one(&mut self.dependents, dep, id);
+            }
+        }
+    }
+
+    /// Builder-style `add()`.
+    pub fn with(mut self, manifest: Manifest) -> Self {
+        self.add(manifest);
+        self
+    }
+
+    /// All entries, in insertion order.
+    pub fn entries(&self) -> &[ManifestEntry] {
+        &self.entries
+    }
+
+    /// Number of manifests (packages count once).
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// All plugins, with packages expanded into their plugins.
+    pub fn plugins(&self) -> impl Iterator<Item = &PluginManifest> {
+        self.expanded.iter().flatten()
+    }
+
+    /// Extract the transitive dependency closure of the given plugin or
+    /// package IDs as a new set.
+    ///
+    /// Follows plugin dependencies (`depends_on`) and service requirements
+    /// (`requires`, including optional ones when a provider exists). A
+    /// package is included as a whole if any of its plugins is needed.
+    /// Entries keep their insertion order and source paths.
+    pub fn closure(&self, ids: &[&str]) -> Result<ManifestSet, ManifestError> {
+        let plugins: Vec<(usize, &PluginManifest)> = self
+            .expanded
+            .iter()
+            .enumerate()
+            .flat_map(|(i, plugins)| plugins.iter().map(move |p| (i, p)))
+            .collect();
+        let entry_of = |id: &str| {
+            plugins
+                .iter()
+                .find(|(_, p)| p.plugin.id == id)
+                .map(|(i, _)| *i)
+                .or_else(|| self.entries.iter().position(|e| e.manifest.id() == id))
+        };
+
+        let mut included = vec![false; self.entries.len()];
+        let mut pending = Vec::new();
+        for id in ids {
+            pending.push(entry_of(id).ok_or_else(|| ManifestError::UnknownPlugin(id.to_string()))?);
+        }
+
+        while let Some(entry) = pending.pop() {
+            if std::mem::replace(&mut included[entry], true) {
+                continue;
+            }
+            for (_, plugin) in plugins.iter().filter(|(i, _)| *i == entry) {
+                for dep in &plugin.compatibility.depends_on {
+                    pending.push(entry_of(dep).ok_or_else(|| {
+                        ManifestError::MissingDependency(format!(
+                            "plugin '{dep}' (required by {})",
+                            plugin.plugin.id
+                        ))
+                    })?);
+                }
+                for req in &plugin.requires {
+                    if req.provider == ServiceSource::Host {
+                        continue;
+                    }
+                    let provider = plugins
+                        .iter()
+                        .find(|(_, p)| p.provides.iter().any(|s| s.id == req.id));
+                    match provider {
+                        Some((i, _)) => pending.push(*i),
+                        // The host may provide it
+                        None if req.optional || req.provider == ServiceSource::Any => {}
+                        None => {
+                            return Err(ManifestError::MissingDependency(format!(
+                                "service '{}' (required by {})",
+                                req.id, plugin.plugin.id
+                            )))
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut closure = ManifestSet::new();
+        for (entry, _) in self.entries.iter().zip(included).filter(|(_, inc)| *inc) {
+            closure.push(entry.clone());
+        }
+        Ok(closure)
+    }
+
+    /// Report plugin IDs declared more than once across the set, e.g. as a
+    /// standalone plugin.toml and inside a package.
+    ///
+    /// Each issue lists every source path declaring the ID.
+    pub fn check_duplicate_ids(&self) -> Vec<ValidationIssue> {
+        let mut seen: Vec<(&str, Vec<String>)> = Vec::new();
+        for entry in &self.entries {
+            let source = entry
+                .source
+                .as_ref()
+                .map(|p| p.display().to_string())
+                .unwrap_or_else(|| format!("<{}>", entry.manifest.id()));
+            for id in entry.manifest.plugin_ids() {
+                match seen.iter_mut().find(|(seen_id, _)| *seen_id == id) {
+                    Some((_, sources)) => sources.push(source.clone()),
+                    None => seen.push((id, vec![source.clone()])),
+                }
+            }
+        }
+
+        seen.into_iter()
+            .filter(|(_, sources)| sources.len() > 1)
+            .map(|(id, sources)| {
+                ValidationIssue::error(
+                    id,
+                    format!(
+                        "Plugin ID '{id}' is declared {} times: {}",
+                        sources.len(),
+                        sources.join(", ")
+                    ),
+                )
+            })
+            .collect()
+    }
+}
+
+impl FromIterator<Manifest> for ManifestSet {
+    fn from_iter<I: IntoIterator<Item = Manifest>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for manifest in iter {
+            set.add(manifest);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plugins_expands_packages() {
+        let single = Manifest::from_toml(
+            "[plugin]\nid = \"acme.tool\"\nname = \"Tool\"\nversion = \"1.0.0\"\ntype = \"extension\"\n",
+        )
+        .unwrap();
+        let package = Manifest::from_toml(
+            r#"
+[package]
+id = "acme.themes"
+name = "Themes"
+version = "1.0.0"
+
+[[plugins]]
+id = "acme.theme-dark"
+name = "Dark"
+type = "theme"
+binary = "dark"
+
+[[plugins]]
+id = "acme.theme-light"
+name = "Light"
+type = "theme"
+binary = "light"
+"#,
+        )
+        .unwrap();
+
+        let set: ManifestSet = [single, package].into_iter().collect();
+        assert_eq!(set.len(), 2);
+        assert_eq!(set.plugins().count(), 3);
+    }
+
+    fn plugin(id: &str, extra: &str) -> Manifest {
+        Manifest::from_toml(&format!(
+            "[plugin]\nid = \"{id}\"\nname = \"{id}\"\nversion = \"1.0.0\"\ntype = \"extension\"\n{extra}"
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_check_duplicate_ids() {
+        let package = Manifest::from_toml(
+            r#"
+[package]
+id = "acme.bundle"
+name = "Bundle"
+version = "1.0.0"
+
+[[plugins]]
+id = "acme.tool"
+name = "Tool"
+type = "extension"
+binary = "tool"
+"#,
+        )
+        .unwrap();
+
+        let mut set = ManifestSet::new();
+        set.add_with_source(plugin("acme.tool", ""), Path::new("tool/plugin.toml"));
+        set.add_with_source(package, Path::new("bundle/package.toml"));
+        set.add(plugin("acme.other", ""));
+
+        let issues = set.check_duplicate_ids();
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].message.contains("tool/plugin.toml"));
+        assert!(issues[0].message.contains("bundle/package.toml"));
+    }
+
+    #[test]
+    fn test_closure() {
+        let set = ManifestSet::new()
+            .with(plugin(
+                "acme.app",
+                "[compatibility]\ndepends_on = [\"acme.core\"]\n\n[[requires]]\nid = \"acme.search\"\n",
+            ))
+            .with(plugin("acme.core", ""))
+            .with(plugin(
+                "acme.indexer",
+                "[[provides]]\nid = \"acme.search\"\nversion = \"1.0.0\"\n",
+            ))
+            .with(plugin("acme.unrelated", ""));
+
+        let closure = set.closure(&["acme.app"]).unwrap();
+        let ids: Vec<&str> = closure.entries().iter().map(|e| e.manifest.id()).collect();
+        assert_eq!(ids, ["acme.app", "acme.core", "acme.indexer"]);
+
+        assert!(matches!(
+            set.closure(&["acme.missing"]),
+            Err(ManifestError::UnknownPlugin(_))
+        ));
+        let broken = ManifestSet::new().with(plugin(
+            "acme.app",
+            "[compatibility]\ndepends_on = [\"acme.core\"]\n",
+        ));
+        assert!(matches!(
+            broken.closure(&["acme.app"]),
+            Err(ManifestError::MissingDependency(_))
+        ));
+    }
+
+    #[test]
+    fn test_apply_event() {
+        let provider = |version: &str| {
+            plugin(
+                "acme.indexer",
+                &format!("[[provides]]\nid = \"acme.search\"\nversion = \"{version}\"\n"),
+            )
+        };
+        let mut set = ManifestSet::new().with(provider("1.0.0")).with(plugin(
+            "acme.app",
+            "[compatibility]\ndepends_on = [\"acme.indexer\"]\n",
+        ));
+        assert_eq!(set.providers_of("acme.search"), ["acme.indexer"]);
+        assert_eq!(set.dependents_of("acme.indexer"), ["acme.app"]);
+
+        set.apply_event(ManifestEvent::Updated(plugin("acme.indexer", "")))
+            .unwrap();
+        assert!(set.providers_of("acme.search").is_empty());
+        assert_eq!(set.plugins().next().unwrap().provides.len(), 0);
+
+        set.apply_event(ManifestEvent::Added(plugin(
+            "acme.search",
+            "[[provides]]\nid = \"acme.search\"\nversion = \"2.0.0\"\n",
+        )))
+        .unwrap();
+        set.apply_event(ManifestEvent::Removed("acme.app".to_string()))
+            .unwrap();
+        assert_eq!(set.providers_of("acme.search"), ["acme.search"]);
+        assert!(set.dependents_of("acme.indexer").is_empty());
+        assert_eq!(set.len(), 2);
+
+        assert!(matches!(
+            set.apply_event(ManifestEvent::Removed("acme.app".to_string())),
+            Err(ManifestError::UnknownPlugin(_))
+        ));
+
+        // Providers come back in set order, not re-indexing order
+        set.apply_event(ManifestEvent::Updated(provider("1.1.0")))
+            .unwrap();
+        let providing: Vec<&str> = set
+            .providing("acme.search")
+            .iter()
+            .map(|p| p.plugin.id.as_str())
+            .collect();
+        assert_eq!(providing, ["acme.indexer", "acme.search"]);
+    }
+}
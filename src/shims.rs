@@ -0,0 +1,156 @@
+//! Host API shims (`[compat.shims]`).
+//!
+//! A plugin built against the current plugin API can declare that it also
+//! runs on hosts implementing an older API, through a named shim the host
+//! provides. Hosts consult the declared shims when the API versions differ.
+//!
+//! ```toml
+//! [compatibility]
+//! api_version = 3
+//!
+//! [compat.shims.api-v2]
+//! api_version = 2
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::plugin::PluginManifest;
+use crate::validate::ValidationIssue;
+
+/// Compatibility layers the plugin can run through.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CompatInfo {
+    /// Shims by name
+    #[serde(default)]
+    pub shims: BTreeMap<String, ShimInfo>,
+}
+
+/// A shim bridging the plugin to an older host API.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ShimInfo {
+    /// Host API version the shim targets
+    pub api_version: u32,
+}
+
+/// How a plugin can run against a host API version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiSupport {
+    /// The plugin implements the host API directly
+    Native,
+    /// The plugin runs through the named shim
+    Shim(String),
+    /// The plugin can't run on the host
+    Unsupported,
+}
+
+impl PluginManifest {
+    /// Name of the declared shim targeting a host API version, if any.
+    pub fn shim_for(&self, api_version: u32) -> Option<&str> {
+        self.compat
+            .iter()
+            .flat_map(|c| &c.shims)
+            .find(|(_, shim)| shim.api_version == api_version)
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Check how the plugin can run on a host with the given API version
+    /// and shims.
+    pub fn api_support(&self, api_version: u32, host_shims: &[String]) -> ApiSupport {
+        if self.compatibility.api_version == api_version {
+            return ApiSupport::Native;
+        }
+        self.compat
+            .iter()
+            .flat_map(|c| &c.shims)
+            .find(|(name, shim)| shim.api_version == api_version && host_shims.contains(name))
+            .map_or(ApiSupport::Unsupported, |(name, _)| {
+                ApiSupport::Shim(name.clone())
+            })
+    }
+
+    /// Check shim declarations.
+    pub(crate) fn check_compat(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        let mut seen = BTreeMap::new();
+        for (name, shim) in self.compat.iter().flat_map(|c| &c.shims) {
+            let path = format!("compat.shims.{name}");
+            if shim.api_version >= self.compatibility.api_version {
+                issues.push(ValidationIssue::error(
+                    format!("{path}.api_version"),
+                    format!(
+                        "Shims bridge to older API versions, but {} is not older than {}",
+                        shim.api_version, self.compatibility.api_version
+                    ),
+                ));
+            }
+            if let Some(other) = seen.insert(shim.api_version, name) {
+                issues.push(ValidationIssue::warning(
+                    path,
+                    format!(
+                        "API version {} is also covered by shim '{other}'",
+                        shim.api_version
+                    ),
+                ));
+            }
+        }
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOML: &str = r#"
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.0.0"
+type = "extension"
+
+[compatibility]
+api_version = 3
+
+[compat.shims.api-v2]
+api_version = 2
+"#;
+
+    #[test]
+    fn test_api_support() {
+        let manifest = PluginManifest::from_toml(TOML).unwrap();
+        assert_eq!(manifest.shim_for(2), Some("api-v2"));
+        assert_eq!(manifest.api_support(3, &[]), ApiSupport::Native);
+        assert_eq!(manifest.api_support(2, &[]), ApiSupport::Unsupported);
+        assert_eq!(
+            manifest.api_support(2, &["api-v2".to_string()]),
+            ApiSupport::Shim("api-v2".to_string())
+        );
+        assert_eq!(
+            manifest.api_support(1, &["api-v2".to_string()]),
+            ApiSupport::Unsupported
+        );
+    }
+
+    #[test]
+    fn test_check_compat() {
+        let manifest = PluginManifest::from_toml(TOML).unwrap();
+        assert!(manifest.check_compat().is_empty());
+
+        let toml = format!("{TOML}\n[compat.shims.api-v4]\napi_version = 4\n[compat.shims.legacy]\napi_version = 2\n");
+        let manifest = PluginManifest::from_toml(&toml).unwrap();
+        let paths: Vec<_> = manifest
+            .check_compat()
+            .into_iter()
+            .map(|i| i.path)
+            .collect();
+        assert_eq!(
+            paths,
+            ["compat.shims.api-v4.api_version", "compat.shims.legacy"]
+        );
+    }
+}
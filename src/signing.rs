@@ -0,0 +1,363 @@
+//! Signature scope.
+//!
+//! A signature covers the manifest document as declared: the parsed TOML
+//! without its `[signature]` table, serialized with all keys sorted. No
+//! defaults are filled in, so fields and defaults added in later versions
+//! of this crate don't change the signed bytes. For a package this includes
+//! every `[[plugins]]` entry and the shared checksums and sizes, so a single
+//! signature covers all of its plugins.
+//!
+//! Manifests built in code, or edited after parsing, have no declared
+//! document to sign; they are signed over the document `to_toml()` writes
+//! for them, which is what they declare once written and parsed again.
+//!
+//! Plugins expanded from a package inherit the package signature and keep a
+//! reference to the package. Verifying such a plugin verifies the package
+//! bytes and checks that the plugin is exactly what the signed package
+//! expands to, so a plugin can't borrow a signature from a package it isn't
+//! a member of.
+//!
+//! Hosts pass a `SignatureVerifier` implementing the signature algorithm;
+//! the `signature` feature provides an Ed25519 one.
+
+use serde::Serialize;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::error::ManifestError;
+use crate::options::ParseOptions;
+use crate::package::PackageManifest;
+use crate::plugin::{PluginManifest, SignatureInfo};
+use crate::substitute::TemplateContext;
+#[cfg(feature = "bundle")]
+use crate::Manifest;
+
+/// Signature algorithm supplied by the host.
+pub trait SignatureVerifier {
+    /// Check `signature` over `message` against a base64 public key.
+    fn verify(&self, public_key: &str, message: &[u8], signature: &[u8]) -> bool;
+}
+
+/// The document a manifest was parsed from.
+///
+/// Only the source is kept; the declared table and the parsed manifest it
+/// is compared against are rebuilt when a signing payload is requested.
+/// For templated manifests the source is the template as written, and the
+/// context it was rendered with is kept to rebuild the manifest.
+#[derive(Clone)]
+pub(crate) struct DeclaredDocument {
+    source: Arc<str>,
+    options: ParseOptions,
+    context: Option<TemplateContext>,
+}
+
+impl DeclaredDocument {
+    /// Record the source a manifest was parsed from with `options`.
+    pub(crate) fn new(source: &str, options: &ParseOptions) -> Arc<Self> {
+        Arc::new(Self {
+            source: source.into(),
+            options: options.clone(),
+            context: None,
+        })
+    }
+
+    /// Record the template a manifest was rendered from with `context`.
+    pub(crate) fn templated(
+        source: &str,
+        options: &ParseOptions,
+        context: &TemplateContext,
+    ) -> Arc<Self> {
+        Arc::new(Self {
+            source: source.into(),
+            options: options.clone(),
+            context: Some(context.clone()),
+        })
+    }
+
+    /// What `parse` makes of the source, rendered first if templated.
+    fn reparse<T>(
+        &self,
+        parse: impl FnOnce(&str, &ParseOptions) -> Result<T, ManifestError>,
+    ) -> Result<T, ManifestError> {
+        match &self.context {
+            Some(context) => parse(&context.render_document(&self.source)?, &self.options),
+            None => parse(&self.source, &self.options),
+        }
+    }
+
+    /// Bytes covered by a signature: the declared document if `manifest` is
+    /// still what `parse` makes of the source, otherwise its canonical
+    /// serialization.
+    fn signed_bytes<T: Serialize>(
+        &self,
+        manifest: &T,
+        parse: impl FnOnce(&str, &ParseOptions) -> Result<T, ManifestError>,
+    ) -> Result<Vec<u8>, ManifestError> {
+        let current = canonical_bytes(manifest)?;
+        if canonical_bytes(&self.reparse(parse)?)? != current {
+            return Ok(current);
+        }
+        let mut table: toml::Table = toml::from_str(&self.source)?;
+        table.remove("signature");
+        sorted_bytes(table)
+    }
+
+    /// The source, if `manifest` (signature included) is still what `parse`
+    /// makes of it.
+    #[cfg(feature = "bundle")]
+    fn source_of<T: Serialize>(
+        &self,
+        manifest: &T,
+        parse: impl FnOnce(&str, &ParseOptions) -> Result<T, ManifestError>,
+    ) -> Result<Option<&str>, ManifestError> {
+        let parsed = toml::Table::try_from(self.reparse(parse)?);
+        let current = toml::Table::try_from(manifest);
+        match (parsed, current) {
+            (Ok(parsed), Ok(current)) if parsed == current => Ok(Some(&*self.source)),
+            _ => Ok(None),
+        }
+    }
+}
+
+impl fmt::Debug for DeclaredDocument {
+    // The source may hold values `redact()` masks; keep it out of debug output
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeclaredDocument").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "bundle")]
+impl Manifest {
+    /// The document the manifest was parsed from, if it is unchanged since,
+    /// otherwise `to_toml()`. Unlike `to_toml()` this keeps comments and
+    /// what a signature covers.
+    pub(crate) fn declared_toml(&self) -> Result<String, ManifestError> {
+        let source = match self {
+            Manifest::Single(m) => match &m.origin.declared {
+                Some(declared) => declared.source_of(m, PluginManifest::from_toml_with)?,
+                None => None,
+            },
+            Manifest::Package(m) => match &m.origin.declared {
+                Some(declared) => declared.source_of(m, PackageManifest::from_toml_with)?,
+                None => None,
+            },
+        };
+        match source {
+            Some(source) => Ok(source.to_string()),
+            None => self.to_toml(),
+        }
+    }
+}
+
+/// Canonical bytes of a manifest, without its signature table.
+fn canonical_bytes<T: Serialize>(manifest: &T) -> Result<Vec<u8>, ManifestError> {
+    let mut table = toml::Table::try_from(manifest)
+        .map_err(|e| ManifestError::InvalidFormat(format!("Failed to serialize manifest: {e}")))?;
+    table.remove("signature");
+    sorted_bytes(table)
+}
+
+fn sorted_bytes(table: toml::Table) -> Result<Vec<u8>, ManifestError> {
+    let sorted = sort_keys(toml::Value::Table(table));
+    let content = toml::to_string(&sorted)
+        .map_err(|e| ManifestError::InvalidFormat(format!("Failed to serialize manifest: {e}")))?;
+    Ok(content.into_bytes())
+}
+
+/// Rebuild tables with their keys in sorted order, whatever the map type.
+fn sort_keys(value: toml::Value) -> toml::Value {
+    match value {
+        toml::Value::Table(table) => {
+            let mut entries: Vec<_> = table.into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            toml::Value::Table(
+                entries
+                    .into_iter()
+                    .map(|(k, v)| (k, sort_keys(v)))
+                    .collect(),
+            )
+        }
+        toml::Value::Array(items) => toml::Value::Array(items.into_iter().map(sort_keys).collect()),
+        other => other,
+    }
+}
+
+fn verify(
+    info: Option<&SignatureInfo>,
+    payload: &[u8],
+    signature: &[u8],
+    verifier: &dyn SignatureVerifier,
+) -> Result<(), ManifestError> {
+    let info = info.ok_or_else(|| ManifestError::Signature("manifest is not signed".into()))?;
+    if verifier.verify(&info.public_key, payload, signature) {
+        Ok(())
+    } else {
+        Err(ManifestError::Signature(
+            "signature does not match the manifest".into(),
+        ))
+    }
+}
+
+impl PackageManifest {
+    /// Bytes covered by the package signature.
+    pub fn signing_payload(&self) -> Result<Vec<u8>, ManifestError> {
+        match &self.origin.declared {
+            Some(declared) => declared.signed_bytes(self, Self::from_toml_with),
+            None => canonical_bytes(self),
+        }
+    }
+
+    /// Verify the package signature.
+    pub fn verify_signature_with(
+        &self,
+        signature: &[u8],
+        verifier: &dyn SignatureVerifier,
+    ) -> Result<(), ManifestError> {
+        let payload = self.signing_payload()?;
+        verify(self.signature.as_ref(), &payload, signature, verifier)
+    }
+}
+
+impl PluginManifest {
+    /// Bytes covered by the signature: the plugin's own canonical bytes, or
+    /// the package's for plugins expanded from a package.
+    pub fn signing_payload(&self) -> Result<Vec<u8>, ManifestError> {
+        match &self.origin.source_package {
+            Some(package) => package.signing_payload(),
+            None => match &self.origin.declared {
+                Some(declared) => declared.signed_bytes(self, Self::from_toml_with),
+                None => canonical_bytes(self),
+            },
+        }
+    }
+
+    /// Check that a plugin expanded from a package is unchanged from the
+    /// package's own expansion. Standalone plugins always pass.
+    pub fn verify_package_membership(&self) -> Result<(), ManifestError> {
+        let Some(package) = &self.origin.source_package else {
+            return Ok(());
+        };
+        let expected = package
+            .expand_plugins()
+            .into_iter()
+            .find(|p| p.plugin.id == self.plugin.id)
+            .ok_or_else(|| {
+                ManifestError::Signature(format!(
+                    "'{}' is not a member of package '{}'",
+                    self.plugin.id, package.package.id
+                ))
+            })?;
+        if canonical_bytes(&expected)? == canonical_bytes(self)? {
+            Ok(())
+        } else {
+            Err(ManifestError::Signature(format!(
+                "'{}' differs from its entry in package '{}'",
+                self.plugin.id, package.package.id
+            )))
+        }
+    }
+
+    /// Verify the signature, including package membership for plugins
+    /// expanded from a package.
+    pub fn verify_signature_with(
+        &self,
+        signature: &[u8],
+        verifier: &dyn SignatureVerifier,
+    ) -> Result<(), ManifestError> {
+        self.verify_package_membership()?;
+        let payload = self.signing_payload()?;
+        verify(self.signature.as_ref(), &payload, signature, verifier)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test "signature": the payload itself, keyed by the public key.
+    struct Echo;
+
+    impl SignatureVerifier for Echo {
+        fn verify(&self, public_key: &str, message: &[u8], signature: &[u8]) -> bool {
+            public_key == "key" && message == signature
+        }
+    }
+
+    const PACKAGE: &str = r#"
+[package]
+id = "acme.themes"
+name = "Themes"
+version = "2.0.0"
+
+[[plugins]]
+id = "acme.theme-dark"
+name = "Dark"
+type = "theme"
+binary = "dark_theme"
+
+[[plugins]]
+id = "acme.theme-light"
+name = "Light"
+type = "theme"
+binary = "light_theme"
+
+[binary.checksums]
+linux-x86_64 = "sha256:aaa"
+darwin-aarch64 = "sha256:bbb"
+windows-x86_64 = "sha256:ccc"
+
+[signature]
+public_key = "key"
+signature_file = "package.sig"
+"#;
+
+    #[test]
+    fn test_payload_is_canonical() {
+        let a = PackageManifest::from_toml(PACKAGE).unwrap();
+        let b = PackageManifest::from_toml(PACKAGE).unwrap();
+        let payload = a.signing_payload().unwrap();
+        assert_eq!(payload, b.signing_payload().unwrap());
+
+        let text = String::from_utf8(payload).unwrap();
+        assert!(text.contains("acme.theme-light"));
+        assert!(!text.contains("package.sig"));
+        // Only what the document declares, no crate defaults
+        assert!(!text.contains("api_version"));
+    }
+
+    #[test]
+    fn test_edited_manifest_signs_written_document() {
+        let mut package = PackageManifest::from_toml(PACKAGE).unwrap();
+        package
+            .binary
+            .checksums
+            .insert("linux-aarch64".into(), "sha256:ddd".into());
+        let signature = package.signing_payload().unwrap();
+        assert!(String::from_utf8_lossy(&signature).contains("sha256:ddd"));
+
+        let written = PackageManifest::from_toml(&package.to_toml().unwrap()).unwrap();
+        written.verify_signature_with(&signature, &Echo).unwrap();
+    }
+
+    #[test]
+    fn test_expanded_plugin_verification() {
+        let package = PackageManifest::from_toml(PACKAGE).unwrap();
+        let signature = package.signing_payload().unwrap();
+        package.verify_signature_with(&signature, &Echo).unwrap();
+
+        let plugins = package.expand_plugins();
+        assert_eq!(plugins[1].signing_payload().unwrap(), signature);
+        plugins[1].verify_signature_with(&signature, &Echo).unwrap();
+
+        let mut tampered = plugins[1].clone();
+        tampered.binary.name = "evil".to_string();
+        assert!(matches!(
+            tampered.verify_signature_with(&signature, &Echo),
+            Err(ManifestError::Signature(_))
+        ));
+
+        let mut standalone = plugins[0].clone();
+        standalone.origin.source_package = None;
+        assert!(standalone.verify_signature_with(&signature, &Echo).is_err());
+    }
+}
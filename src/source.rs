@@ -0,0 +1,234 @@
+//! Manifest provenance.
+//!
+//! `Sourced<T>` wraps a parsed manifest together with where it came from,
+//! the format it was parsed from, when it was parsed and a fingerprint of
+//! the source text, so diagnostics and registries can always name the
+//! origin of a manifest object.
+
+use std::fmt;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::error::ManifestError;
+use crate::options::ParseOptions;
+use crate::Manifest;
+
+/// Where a manifest was loaded from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Origin {
+    /// A file on disk
+    Path(PathBuf),
+    /// A remote location (e.g., a registry URL)
+    Url(String),
+    /// A string with no known location
+    Inline,
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Origin::Path(path) => write!(f, "{}", path.display()),
+            Origin::Url(url) => write!(f, "{url}"),
+            Origin::Inline => write!(f, "<inline>"),
+        }
+    }
+}
+
+/// Format a manifest was parsed from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceFormat {
+    /// TOML (plugin.toml / package.toml)
+    Toml,
+    /// JSON
+    Json,
+}
+
+impl fmt::Display for SourceFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SourceFormat::Toml => write!(f, "toml"),
+            SourceFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
+/// Metadata about the source of a parsed manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceInfo {
+    /// Where the manifest was loaded from
+    pub origin: Origin,
+
+    /// Format of the source text
+    pub format: SourceFormat,
+
+    /// When the manifest was parsed
+    pub parsed_at: SystemTime,
+
+    /// Fingerprint of the source text (see `fingerprint()`)
+    pub fingerprint: String,
+}
+
+impl SourceInfo {
+    /// Describe `content`, parsed now.
+    pub fn new(origin: Origin, format: SourceFormat, content: &str) -> Self {
+        Self {
+            origin,
+            format,
+            parsed_at: SystemTime::now(),
+            fingerprint: fingerprint(content),
+        }
+    }
+}
+
+impl fmt::Display for SourceInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}, {})", self.origin, self.format, self.fingerprint)
+    }
+}
+
+/// Fingerprint of manifest source text, as `fnv1a64:<hex>`.
+///
+/// Meant for telling sources apart and detecting changes, not for
+/// integrity checks; use binary checksums and signatures for those.
+pub fn fingerprint(content: &str) -> String {
+    let hash = content
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    format!("fnv1a64:{hash:016x}")
+}
+
+/// A value (usually a manifest) together with its source.
+#[derive(Debug, Clone)]
+pub struct Sourced<T> {
+    /// The parsed value
+    pub value: T,
+
+    /// Where the value came from
+    pub source: SourceInfo,
+}
+
+impl<T> Sourced<T> {
+    /// Wrap a value with its source.
+    pub fn new(value: T, source: SourceInfo) -> Self {
+        Self { value, source }
+    }
+
+    /// Drop the source metadata.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Transform the value, keeping the source.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Sourced<U> {
+        Sourced {
+            value: f(self.value),
+            source: self.source,
+        }
+    }
+}
+
+impl<T> Deref for Sourced<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl Sourced<Manifest> {
+    /// Parse a TOML manifest loaded from `origin`.
+    pub fn from_toml(content: &str, origin: Origin) -> Result<Self, ManifestError> {
+        Self::from_toml_with(content, origin, &ParseOptions::default())
+    }
+
+    /// Parse a TOML manifest loaded from `origin` with custom options.
+    pub fn from_toml_with(
+        content: &str,
+        origin: Origin,
+        options: &ParseOptions,
+    ) -> Result<Self, ManifestError> {
+        let manifest = Manifest::from_toml_with(content, options)?;
+        Ok(Self::new(
+            manifest,
+            SourceInfo::new(origin, SourceFormat::Toml, content),
+        ))
+    }
+
+    /// Parse a JSON manifest loaded from `origin`.
+    #[cfg(feature = "json")]
+    pub fn from_json(content: &str, origin: Origin) -> Result<Self, ManifestError> {
+        let manifest = Manifest::from_json(content)?;
+        Ok(Self::new(
+            manifest,
+            SourceInfo::new(origin, SourceFormat::Json, content),
+        ))
+    }
+
+    /// Parse a manifest file.
+    pub fn from_file(path: &Path) -> Result<Self, ManifestError> {
+        Self::from_file_with(path, &ParseOptions::default())
+    }
+
+    /// Parse a manifest file with custom options.
+    ///
+    /// Files with a `.json` extension are parsed as JSON when the `json`
+    /// feature is enabled; everything else is parsed as TOML.
+    pub fn from_file_with(path: &Path, options: &ParseOptions) -> Result<Self, ManifestError> {
+        let content = std::fs::read_to_string(path)?;
+        let origin = Origin::Path(path.to_path_buf());
+        #[cfg(feature = "json")]
+        if path.extension().is_some_and(|ext| ext == "json") {
+            return Self::from_json(&content, origin);
+        }
+        Self::from_toml_with(&content, origin, options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOML: &str = r#"
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.0.0"
+type = "extension"
+"#;
+
+    #[test]
+    fn test_fingerprint() {
+        assert_eq!(fingerprint(""), "fnv1a64:cbf29ce484222325");
+        assert_eq!(fingerprint(TOML), fingerprint(TOML));
+        assert_ne!(
+            fingerprint(TOML),
+            fingerprint(&TOML.replace("1.0.0", "1.0.1"))
+        );
+    }
+
+    #[test]
+    fn test_sourced_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("plugin.toml");
+        std::fs::write(&path, TOML).unwrap();
+
+        let sourced = Sourced::<Manifest>::from_file(&path).unwrap();
+        assert_eq!(sourced.id(), "acme.tool");
+        assert_eq!(sourced.source.origin, Origin::Path(path.clone()));
+        assert_eq!(sourced.source.format, SourceFormat::Toml);
+        assert_eq!(sourced.source.fingerprint, fingerprint(TOML));
+        assert!(sourced
+            .source
+            .to_string()
+            .starts_with(&path.display().to_string()));
+
+        let url = Origin::Url("https://registry.example/acme.tool".to_string());
+        let sourced = Sourced::<Manifest>::from_toml(TOML, url.clone()).unwrap();
+        let version = sourced.map(|m| m.version().to_string());
+        assert_eq!(version.value, "1.0.0");
+        assert_eq!(version.source.origin, url);
+    }
+}
@@ -0,0 +1,64 @@
+//! Compile-time manifest constants.
+
+use crate::error::ManifestError;
+use crate::plugin::PluginManifest;
+
+/// A plugin manifest embedded at compile time.
+///
+/// Created by `lib_plugin_manifest_macros::include_manifest!`. The commonly
+/// needed fields are available as `&'static str` without any parsing; the
+/// full manifest can be obtained with `parse()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StaticManifest {
+    /// Plugin ID
+    pub id: &'static str,
+
+    /// Human-readable name
+    pub name: &'static str,
+
+    /// Version string (semver, checked at compile time)
+    pub version: &'static str,
+
+    /// Plugin type
+    pub plugin_type: &'static str,
+
+    /// Author
+    pub author: &'static str,
+
+    /// Description
+    pub description: &'static str,
+
+    /// Declared API version (`DEFAULT_API_VERSION` if not declared)
+    pub api_version: u32,
+
+    /// Full manifest TOML
+    pub toml: &'static str,
+}
+
+impl StaticManifest {
+    /// Parse the full manifest.
+    pub fn parse(&self) -> Result<PluginManifest, ManifestError> {
+        PluginManifest::from_toml(self.toml)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_static_manifest() {
+        const MANIFEST: StaticManifest = StaticManifest {
+            id: "acme.tool",
+            name: "Tool",
+            version: "1.0.0",
+            plugin_type: "extension",
+            author: "",
+            description: "",
+            api_version: 0,
+            toml: "[plugin]\nid = \"acme.tool\"\nname = \"Tool\"\nversion = \"1.0.0\"\ntype = \"extension\"\n",
+        };
+        let manifest = MANIFEST.parse().unwrap();
+        assert_eq!(manifest.plugin.id, MANIFEST.id);
+    }
+}
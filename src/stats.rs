@@ -0,0 +1,224 @@
+//! Summary statistics over a set of manifests.
+//!
+//! `stats()` computes the numbers a registry dashboard shows: plugins per
+//! type, API version distribution, platform and signature coverage, and how
+//! deep `depends_on` chains go. Packages are counted once as manifests and
+//! contribute each of their plugins to the plugin counts.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::plugin::PluginManifest;
+use crate::set::ManifestSet;
+use crate::Manifest;
+
+/// Statistics computed by `stats()`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ManifestStats {
+    /// Number of manifests (packages count once)
+    pub manifests: usize,
+
+    /// Number of package manifests
+    pub packages: usize,
+
+    /// Number of plugins, with packages expanded
+    pub plugins: usize,
+
+    /// Plugins per `type`
+    pub by_type: BTreeMap<String, usize>,
+
+    /// Plugins per `compatibility.api_version`
+    pub api_versions: BTreeMap<u32, usize>,
+
+    /// Plugins declaring each platform; plugins without a platform list (or
+    /// with "all") are only counted in `all_platforms`
+    pub platforms: BTreeMap<String, usize>,
+
+    /// Plugins supporting every platform
+    pub all_platforms: usize,
+
+    /// Plugins with a `[signature]` section (own or inherited from their
+    /// package)
+    pub signed: usize,
+
+    /// Plugins with a checksum for every declared platform
+    pub complete_checksums: usize,
+
+    /// Longest `depends_on` chain per plugin: 0 for plugins without
+    /// dependencies in the set, otherwise one more than their deepest
+    /// dependency
+    pub dependency_depth: BTreeMap<String, usize>,
+}
+
+impl ManifestStats {
+    /// Fraction of plugins that are signed (1.0 for an empty set).
+    pub fn signature_coverage(&self) -> f64 {
+        if self.plugins == 0 {
+            return 1.0;
+        }
+        self.signed as f64 / self.plugins as f64
+    }
+
+    /// Deepest dependency chain in the set.
+    pub fn max_dependency_depth(&self) -> usize {
+        self.dependency_depth.values().copied().max().unwrap_or(0)
+    }
+}
+
+/// Summarize a set of manifests.
+///
+/// Dependencies outside the set are ignored, and plugins in a dependency
+/// cycle don't count the cycle towards their depth.
+pub fn stats(set: &ManifestSet) -> ManifestStats {
+    let plugins: Vec<&PluginManifest> = set.plugins().collect();
+    let mut stats = ManifestStats {
+        manifests: set.len(),
+        packages: set
+            .entries()
+            .iter()
+            .filter(|e| matches!(e.manifest, Manifest::Package(_)))
+            .count(),
+        plugins: plugins.len(),
+        ..Default::default()
+    };
+
+    for plugin in &plugins {
+        *stats
+            .by_type
+            .entry(plugin.plugin.plugin_type.to_string())
+            .or_default() += 1;
+        *stats
+            .api_versions
+            .entry(plugin.compatibility.api_version)
+            .or_default() += 1;
+
+        let declared = &plugin.compatibility.platforms;
+        if declared.is_empty() || declared.iter().any(|p| p == "all") {
+            stats.all_platforms += 1;
+        } else {
+            for platform in declared {
+                *stats.platforms.entry(platform.clone()).or_default() += 1;
+            }
+        }
+
+        if plugin.signature.is_some() {
+            stats.signed += 1;
+        }
+        if plugin.checksum_coverage().missing.is_empty() {
+            stats.complete_checksums += 1;
+        }
+    }
+
+    let index: HashMap<&str, &PluginManifest> =
+        plugins.iter().map(|p| (p.plugin.id.as_str(), *p)).collect();
+    let mut depths = HashMap::new();
+    for plugin in &plugins {
+        let depth = depth(&plugin.plugin.id, &index, &mut depths);
+        stats
+            .dependency_depth
+            .insert(plugin.plugin.id.clone(), depth);
+    }
+    stats
+}
+
+impl ManifestSet {
+    /// Summarize the set; see `stats()`.
+    pub fn stats(&self) -> ManifestStats {
+        stats(self)
+    }
+}
+
+/// Memoized dependency depth; `None` marks plugins being visited, so a cycle
+/// counts as depth 0 where it closes.
+fn depth<'a>(
+    id: &'a str,
+    index: &HashMap<&'a str, &'a PluginManifest>,
+    depths: &mut HashMap<&'a str, Option<usize>>,
+) -> usize {
+    match depths.get(id) {
+        Some(Some(depth)) => return *depth,
+        Some(None) => return 0,
+        None => {}
+    }
+    depths.insert(id, None);
+    let depth = index[id]
+        .compatibility
+        .depends_on
+        .iter()
+        .filter_map(|dep| index.get_key_value(dep.as_str()).map(|(k, _)| *k))
+        .map(|dep| depth(dep, index, depths) + 1)
+        .max()
+        .unwrap_or(0);
+    depths.insert(id, Some(depth));
+    depth
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DEFAULT_API_VERSION;
+
+    fn plugin(id: &str, extra: &str) -> Manifest {
+        Manifest::from_toml(&format!(
+            "[plugin]\nid = \"{id}\"\nname = \"{id}\"\nversion = \"1.0.0\"\ntype = \"extension\"\n{extra}"
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_stats() {
+        let package = Manifest::from_toml(
+            r#"
+[package]
+id = "acme.themes"
+name = "Themes"
+version = "1.0.0"
+
+[compatibility]
+platforms = ["linux-x86_64", "darwin-aarch64"]
+
+[[plugins]]
+id = "acme.dark"
+name = "Dark"
+type = "theme"
+binary = "dark"
+
+[[plugins]]
+id = "acme.light"
+name = "Light"
+type = "theme"
+binary = "light"
+depends_on = ["acme.app"]
+
+[signature]
+public_key = "abc"
+signature_file = "package.sig"
+"#,
+        )
+        .unwrap();
+        let set: ManifestSet = [
+            package,
+            plugin("acme.core", ""),
+            plugin(
+                "acme.app",
+                "[compatibility]\ndepends_on = [\"acme.core\"]\n",
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        let stats = stats(&set);
+        assert_eq!((stats.manifests, stats.packages, stats.plugins), (3, 1, 4));
+        assert_eq!(stats.by_type["theme"], 2);
+        assert_eq!(stats.by_type["extension"], 2);
+        assert_eq!(stats.api_versions[&DEFAULT_API_VERSION], 4);
+        assert_eq!(stats.platforms["linux-x86_64"], 2);
+        assert_eq!(stats.all_platforms, 2);
+        assert_eq!(stats.signed, 2);
+        assert_eq!(stats.signature_coverage(), 0.5);
+        assert_eq!(stats.complete_checksums, 2);
+        assert_eq!(stats.dependency_depth["acme.light"], 2);
+        assert_eq!(stats.max_dependency_depth(), 2);
+    }
+}
@@ -0,0 +1,177 @@
+//! Storage directory declarations (`[storage]`).
+//!
+//! Plugins declare which directory roles they use; the host decides where
+//! they live.
+//!
+//! ```toml
+//! [storage.data]
+//! quota_mb = 500
+//!
+//! [storage.cache]
+//! quota_mb = 2048
+//!
+//! [storage.state]
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::plugin::PluginManifest;
+use crate::validate::ValidationIssue;
+
+/// Role of a plugin storage directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StorageRole {
+    /// User data; backed up and kept across updates
+    Data,
+    /// Regenerable data; may be garbage-collected at any time
+    Cache,
+    /// Runtime state (e.g., session, locks); kept across restarts
+    State,
+}
+
+impl StorageRole {
+    /// All roles.
+    pub const ALL: [StorageRole; 3] = [StorageRole::Data, StorageRole::Cache, StorageRole::State];
+
+    /// Key used in the `[storage]` section.
+    pub fn name(&self) -> &'static str {
+        match self {
+            StorageRole::Data => "data",
+            StorageRole::Cache => "cache",
+            StorageRole::State => "state",
+        }
+    }
+
+    /// Whether hosts should include this directory in backups.
+    pub fn is_backed_up(&self) -> bool {
+        matches!(self, StorageRole::Data)
+    }
+
+    /// Whether hosts may delete this directory to reclaim space.
+    pub fn is_disposable(&self) -> bool {
+        matches!(self, StorageRole::Cache)
+    }
+}
+
+impl fmt::Display for StorageRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A declared storage directory.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct StorageDir {
+    /// Size quota in megabytes (None = host default)
+    #[serde(default)]
+    pub quota_mb: Option<u64>,
+}
+
+impl StorageDir {
+    /// Quota in bytes.
+    pub fn quota_bytes(&self) -> Option<u64> {
+        self.quota_mb.map(|mb| mb.saturating_mul(1024 * 1024))
+    }
+}
+
+/// Storage directories used by the plugin.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct StorageInfo {
+    /// User data directory
+    #[serde(default)]
+    pub data: Option<StorageDir>,
+
+    /// Cache directory
+    #[serde(default)]
+    pub cache: Option<StorageDir>,
+
+    /// State directory
+    #[serde(default)]
+    pub state: Option<StorageDir>,
+}
+
+impl StorageInfo {
+    /// Get the declaration for a role.
+    pub fn get(&self, role: StorageRole) -> Option<&StorageDir> {
+        match role {
+            StorageRole::Data => self.data.as_ref(),
+            StorageRole::Cache => self.cache.as_ref(),
+            StorageRole::State => self.state.as_ref(),
+        }
+    }
+
+    /// Iterate over declared directories.
+    pub fn iter(&self) -> impl Iterator<Item = (StorageRole, &StorageDir)> {
+        StorageRole::ALL
+            .into_iter()
+            .filter_map(|role| self.get(role).map(|dir| (role, dir)))
+    }
+}
+
+impl PluginManifest {
+    /// Check whether the plugin declares a storage role.
+    pub fn uses_storage(&self, role: StorageRole) -> bool {
+        self.storage.as_ref().is_some_and(|s| s.get(role).is_some())
+    }
+
+    /// Check storage declarations.
+    pub(crate) fn check_storage(&self) -> Vec<ValidationIssue> {
+        let Some(storage) = &self.storage else {
+            return Vec::new();
+        };
+
+        storage
+            .iter()
+            .filter(|(_, dir)| dir.quota_mb == Some(0))
+            .map(|(role, _)| {
+                ValidationIssue::error(
+                    format!("storage.{role}.quota_mb"),
+                    "Quota must be greater than zero (omit it to use the host default)",
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate::has_errors;
+
+    const BASE: &str = r#"
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.0.0"
+type = "extension"
+"#;
+
+    #[test]
+    fn test_parse_storage() {
+        let toml = format!("{BASE}\n[storage.data]\nquota_mb = 500\n\n[storage.cache]\n");
+        let manifest = PluginManifest::from_toml(&toml).unwrap();
+        let storage = manifest.storage.as_ref().unwrap();
+
+        assert!(manifest.uses_storage(StorageRole::Data));
+        assert!(!manifest.uses_storage(StorageRole::State));
+        assert_eq!(
+            storage.get(StorageRole::Data).unwrap().quota_bytes(),
+            Some(500 * 1024 * 1024)
+        );
+        assert_eq!(storage.get(StorageRole::Cache).unwrap().quota_mb, None);
+        assert_eq!(storage.iter().count(), 2);
+        assert!(manifest.validate().is_empty());
+    }
+
+    #[test]
+    fn test_zero_quota() {
+        let toml = format!("{BASE}\n[storage.cache]\nquota_mb = 0\n");
+        let manifest = PluginManifest::from_toml(&toml).unwrap();
+        assert!(has_errors(&manifest.validate()));
+    }
+}
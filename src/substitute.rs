@@ -0,0 +1,353 @@
+//! Variable substitution in manifests (`${name}`).
+//!
+//! Manifest string values may reference variables the loading side fills
+//! in:
+//!
+//! ```toml
+//! [binary]
+//! name = "acme_tool"
+//! path = "bin/${platform}"
+//!
+//! [config.defaults]
+//! cache = "${cache_dir}/acme"
+//! ```
+//!
+//! [`TemplateContext::from_environment`] detects the standard variables, so
+//! CLI tools and hosts resolve the same manifest to the same values:
+//!
+//! | Variable       | Value                                                  |
+//! |----------------|--------------------------------------------------------|
+//! | `platform`     | Platform identifier (e.g., "darwin-aarch64")           |
+//! | `os`, `arch`   | Halves of `platform`                                   |
+//! | `host_version` | Set by the host with `host_version()`                  |
+//! | `home`         | User home directory                                    |
+//! | `config_dir`   | XDG config dir, `~/Library/Application Support`, `%APPDATA%` |
+//! | `data_dir`     | XDG data dir, `~/Library/Application Support`, `%APPDATA%` |
+//! | `cache_dir`    | XDG cache dir, `~/Library/Caches`, `%LOCALAPPDATA%`    |
+//! | `temp_dir`     | System temporary directory                             |
+//!
+//! `$${` produces a literal `${`. Other `$` signs (e.g., `$HOME` in
+//! permission patterns) are left alone. Only string values are rendered,
+//! after the document is parsed, so substituted text is never read as TOML
+//! and comments are ignored.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::error::ManifestError;
+use crate::options::ParseOptions;
+use crate::platform::Platform;
+use crate::signing::DeclaredDocument;
+use crate::Manifest;
+
+/// Variables available to `${name}` substitution.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TemplateContext {
+    vars: BTreeMap<String, String>,
+}
+
+impl TemplateContext {
+    /// Create an empty context.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a context with the standard variables of the running system.
+    pub fn from_environment() -> Self {
+        let platform = Platform::current();
+        let mut context = Self::new()
+            .with("platform", platform.to_string())
+            .with("os", platform.os.as_str())
+            .with("arch", platform.arch.as_str())
+            .with("temp_dir", std::env::temp_dir().display().to_string());
+        for (name, dir) in standard_dirs(&platform.os, |name| std::env::var(name).ok()) {
+            context.set(name, dir.display().to_string());
+        }
+        context
+    }
+
+    /// Builder-style `host_version` variable.
+    pub fn host_version(self, version: impl Into<String>) -> Self {
+        self.with("host_version", version)
+    }
+
+    /// Builder-style variant of `set()`.
+    pub fn with(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.set(name, value);
+        self
+    }
+
+    /// Set a variable, returning its previous value.
+    pub fn set(&mut self, name: impl Into<String>, value: impl Into<String>) -> Option<String> {
+        self.vars.insert(name.into(), value.into())
+    }
+
+    /// Get a variable.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.vars.get(name).map(String::as_str)
+    }
+
+    /// Iterate over all variables in sorted order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.vars.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Replace every `${name}` in `text`.
+    ///
+    /// Fails on variables the context doesn't define, so a manifest never
+    /// loads with a half-resolved path.
+    pub fn render(&self, text: &str) -> Result<String, ManifestError> {
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find("${") {
+            if rest[..start].ends_with('$') {
+                out.push_str(&rest[..start - 1]);
+                out.push_str("${");
+                rest = &rest[start + 2..];
+                continue;
+            }
+            out.push_str(&rest[..start]);
+            let end = rest[start..].find('}').ok_or_else(|| {
+                ManifestError::InvalidFormat(format!("Unterminated '${{' in '{}'", &rest[start..]))
+            })?;
+            let name = &rest[start + 2..start + end];
+            let value = self
+                .get(name)
+                .ok_or_else(|| ManifestError::MissingField(format!("template variable {name}")))?;
+            out.push_str(value);
+            rest = &rest[start + end + 1..];
+        }
+        out.push_str(rest);
+        Ok(out)
+    }
+
+    /// Render every string in `value`, leaving table keys alone.
+    fn render_value(&self, value: &mut toml::Value) -> Result<(), ManifestError> {
+        match value {
+            toml::Value::String(text) => *text = self.render(text)?,
+            toml::Value::Array(items) => {
+                for item in items {
+                    self.render_value(item)?;
+                }
+            }
+            toml::Value::Table(table) => {
+                for (_, item) in table.iter_mut() {
+                    self.render_value(item)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Render every string value of a TOML document.
+    pub(crate) fn render_document(&self, content: &str) -> Result<String, ManifestError> {
+        let mut document = toml::Value::Table(toml::from_str(content)?);
+        self.render_value(&mut document)?;
+        toml::to_string(&document).map_err(|e| {
+            ManifestError::InvalidFormat(format!("Failed to serialize manifest: {e}"))
+        })
+    }
+}
+
+impl Manifest {
+    /// Parse, substituting `${name}` variables from `context` in string
+    /// values.
+    pub fn from_toml_templated(
+        content: &str,
+        context: &TemplateContext,
+    ) -> Result<Self, ManifestError> {
+        Self::from_toml_templated_with(content, context, &ParseOptions::default())
+    }
+
+    /// Parse with custom options, substituting `${name}` variables from
+    /// `context` in string values.
+    ///
+    /// The template as written stays the declared document, so a signature
+    /// over it verifies whatever the variables render to.
+    pub fn from_toml_templated_with(
+        content: &str,
+        context: &TemplateContext,
+        options: &ParseOptions,
+    ) -> Result<Self, ManifestError> {
+        options.check_size(content)?;
+        let mut manifest = Self::from_toml_with(&context.render_document(content)?, options)?;
+        let declared = Some(DeclaredDocument::templated(content, options, context));
+        match &mut manifest {
+            Manifest::Single(m) => m.origin.declared = declared,
+            Manifest::Package(m) => m.origin.declared = declared,
+        }
+        Ok(manifest)
+    }
+}
+
+/// Home, config, data and cache directories by the conventions of `os`.
+fn standard_dirs(os: &str, var: impl Fn(&str) -> Option<String>) -> Vec<(&'static str, PathBuf)> {
+    let home = match os {
+        "windows" => var("USERPROFILE").or_else(|| var("HOME")),
+        _ => var("HOME"),
+    }
+    .map(PathBuf::from);
+    let from_home = |path: &str| home.as_ref().map(|h| h.join(path));
+    let xdg =
+        |name: &str, fallback: &str| var(name).map(PathBuf::from).or_else(|| from_home(fallback));
+
+    let (config, data, cache) = match os {
+        "darwin" => (
+            from_home("Library/Application Support"),
+            from_home("Library/Application Support"),
+            from_home("Library/Caches"),
+        ),
+        "windows" => (
+            var("APPDATA").map(PathBuf::from),
+            var("APPDATA").map(PathBuf::from),
+            var("LOCALAPPDATA").map(PathBuf::from),
+        ),
+        _ => (
+            xdg("XDG_CONFIG_HOME", ".config"),
+            xdg("XDG_DATA_HOME", ".local/share"),
+            xdg("XDG_CACHE_HOME", ".cache"),
+        ),
+    };
+    [
+        ("home", home),
+        ("config_dir", config),
+        ("data_dir", data),
+        ("cache_dir", cache),
+    ]
+    .into_iter()
+    .filter_map(|(name, dir)| Some((name, dir?)))
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let context = TemplateContext::new()
+            .with("platform", "linux-x86_64")
+            .host_version("1.4.0");
+        assert_eq!(
+            context.render("bin/${platform}/v${host_version}").unwrap(),
+            "bin/linux-x86_64/v1.4.0"
+        );
+        assert_eq!(
+            context.render("$${platform} $HOME").unwrap(),
+            "${platform} $HOME"
+        );
+        assert!(matches!(
+            context.render("${missing}"),
+            Err(ManifestError::MissingField(_))
+        ));
+        assert!(context.render("${platform").is_err());
+    }
+
+    #[test]
+    fn test_standard_dirs() {
+        let env = |name: &str| match name {
+            "HOME" => Some("/home/ada".to_string()),
+            "XDG_CACHE_HOME" => Some("/var/cache/ada".to_string()),
+            _ => None,
+        };
+        let dirs: BTreeMap<_, _> = standard_dirs("linux", env).into_iter().collect();
+        assert_eq!(dirs["config_dir"], PathBuf::from("/home/ada/.config"));
+        assert_eq!(dirs["cache_dir"], PathBuf::from("/var/cache/ada"));
+        let dirs: BTreeMap<_, _> = standard_dirs("darwin", env).into_iter().collect();
+        assert_eq!(dirs["cache_dir"], PathBuf::from("/home/ada/Library/Caches"));
+        assert!(standard_dirs("windows", env)
+            .iter()
+            .all(|(n, _)| *n == "home"));
+
+        let context = TemplateContext::from_environment();
+        assert!(context.get("platform").is_some());
+        assert!(context.get("host_version").is_none());
+    }
+
+    #[test]
+    fn test_from_toml_templated() {
+        let content = r#"
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.0.0"
+type = "extension"
+
+[binary]
+name = "acme_tool"
+path = "bin/${platform}"
+
+[config.defaults]
+# ${undefined} in a comment is ignored
+cache = "${data_dir}/acme"
+"#;
+        let load = |data_dir: &str| {
+            let context = TemplateContext::new()
+                .with("data_dir", data_dir)
+                .with("platform", "linux-x86_64");
+            let manifest = Manifest::from_toml_templated(content, &context).unwrap();
+            let Manifest::Single(plugin) = manifest else {
+                panic!("expected a plugin manifest");
+            };
+            plugin
+        };
+
+        let plugin = load("/data");
+        assert_eq!(plugin.binary.path.as_deref(), Some("bin/linux-x86_64"));
+        assert_eq!(
+            plugin.config.defaults["cache"].as_str(),
+            Some("/data/acme")
+        );
+
+        // Values are substituted as strings, not spliced into the TOML
+        let plugin = load(r"C:\Users\ada\AppData\Roaming");
+        assert_eq!(
+            plugin.config.defaults["cache"].as_str(),
+            Some(r"C:\Users\ada\AppData\Roaming/acme")
+        );
+        let plugin = load("x\"\n[permissions.network]\nallow = [\"*\"]\n#");
+        assert!(plugin.permissions.is_none());
+        assert!(plugin.config.defaults["cache"]
+            .as_str()
+            .unwrap()
+            .contains("[permissions.network]"));
+    }
+
+    #[test]
+    fn test_from_toml_templated_with_keeps_options_and_template() {
+        let content = r#"
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.0.0"
+type = "widget"
+
+[binary]
+name = "acme_tool"
+path = "bin/${platform}"
+"#;
+        let render = |platform: &str, options: &ParseOptions| {
+            let context = TemplateContext::new().with("platform", platform);
+            Manifest::from_toml_templated_with(content, &context, options)
+        };
+
+        let strict = ParseOptions::new().strict(true);
+        assert!(render("linux-x86_64", &strict).is_err());
+        assert!(render("linux-x86_64", &ParseOptions::new().max_size(16)).is_err());
+
+        let options = strict.clone().plugin_type("widget");
+        let Manifest::Single(linux) = render("linux-x86_64", &options).unwrap() else {
+            panic!("expected a plugin manifest");
+        };
+        let Manifest::Single(darwin) = render("darwin-aarch64", &options).unwrap() else {
+            panic!("expected a plugin manifest");
+        };
+        assert_eq!(linux.binary.path.as_deref(), Some("bin/linux-x86_64"));
+        // The signature covers the template, not what it rendered to
+        let payload = linux.signing_payload().unwrap();
+        assert_eq!(payload, darwin.signing_payload().unwrap());
+        assert!(String::from_utf8(payload).unwrap().contains("bin/${platform}"));
+    }
+
+}
@@ -0,0 +1,131 @@
+//! Per-type manifest templates.
+
+use crate::plugin::*;
+use crate::plugin_type::PluginType;
+
+impl PluginManifest {
+    /// Create a pre-populated manifest skeleton for a plugin kind.
+    ///
+    /// The result uses placeholder IDs and names and includes the sections
+    /// the kind requires (e.g., `[translation]` for translation plugins), so
+    /// it validates once the placeholders are filled in.
+    pub fn template_for(kind: PluginType) -> Self {
+        let slug = match &kind {
+            PluginType::Custom(t) => t.clone(),
+            kind => kind.as_str().to_string(),
+        };
+
+        let mut manifest = PluginManifest {
+            plugin: PluginMeta {
+                id: format!("vendor.my-{slug}"),
+                name: format!("My {}", title_case(&slug)),
+                version: "0.1.0".to_string(),
+                plugin_type: kind.clone(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        match kind {
+            PluginType::Translation => {
+                manifest.translation = Some(TranslationInfo {
+                    translates: "vendor.plugin".to_string(),
+                    language: "en-US".to_string(),
+                    language_name: "English (United States)".to_string(),
+                    namespace: "plugin".to_string(),
+                });
+            }
+            PluginType::Lang => {
+                manifest.language = Some(LanguageInfo {
+                    id: "language".to_string(),
+                    extensions: Vec::new(),
+                });
+            }
+            PluginType::HivePlugin => {
+                manifest.plugin.id = "vendor.runner.my-runner".to_string();
+                manifest.hive = Some(HiveInfo {
+                    category: "runner".to_string(),
+                    name: "my-runner".to_string(),
+                });
+            }
+            PluginType::Core | PluginType::Extension => {
+                manifest.cli = Some(CliConfig {
+                    command: slug,
+                    description: String::new(),
+                    aliases: Vec::new(),
+                    dynamic_completions: false,
+                    args: Vec::new(),
+                    subcommands: Vec::new(),
+                });
+            }
+            PluginType::Theme => {
+                manifest.theme = Some(ThemeInfo {
+                    appearance: "dark".to_string(),
+                    extends: None,
+                });
+            }
+            PluginType::Font | PluginType::Custom(_) => {}
+        }
+
+        manifest
+    }
+}
+
+fn title_case(slug: &str) -> String {
+    slug.split('-')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate::has_errors;
+
+    #[test]
+    fn test_templates_validate() {
+        for kind in [
+            "extension",
+            "theme",
+            "font",
+            "core",
+            "hive-plugin",
+            "translation",
+            "lang",
+        ] {
+            let manifest = PluginManifest::template_for(PluginType::parse(kind));
+            assert_eq!(manifest.plugin.plugin_type.as_str(), kind);
+            assert!(
+                !has_errors(&manifest.validate()),
+                "{kind} template is invalid"
+            );
+        }
+    }
+
+    #[test]
+    fn test_template_sections() {
+        let manifest = PluginManifest::template_for(PluginType::HivePlugin);
+        assert_eq!(manifest.plugin.name, "My Hive Plugin");
+        assert!(manifest.hive.is_some());
+
+        let manifest = PluginManifest::template_for(PluginType::Theme);
+        assert_eq!(manifest.plugin.id, "vendor.my-theme");
+        assert!(manifest.cli.is_none());
+
+        // Templates serialize to a parseable plugin.toml
+        let toml = PluginManifest::template_for(PluginType::Translation)
+            .to_toml()
+            .unwrap();
+        assert!(PluginManifest::from_toml(&toml)
+            .unwrap()
+            .translation
+            .is_some());
+    }
+}
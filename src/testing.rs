@@ -0,0 +1,240 @@
+//! Property-testing support (`testing` feature).
+//!
+//! Proptest strategies generating structurally valid manifests: IDs,
+//! versions and platforms are well-formed, service requirements name
+//! services some generated plugin may provide, and package plugins only
+//! depend on plugins declared before them, so `install_order()` succeeds.
+//!
+//! ```ignore
+//! use lib_plugin_manifest::PluginManifest;
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     #[test]
+//!     fn loader_accepts_valid_manifests(manifest in any::<PluginManifest>()) {
+//!         my_host::load(&manifest.to_toml().unwrap()).unwrap();
+//!     }
+//! }
+//! ```
+
+use proptest::prelude::*;
+use proptest::sample::{select, subsequence};
+
+use crate::migrate::CURRENT_MANIFEST_VERSION;
+use crate::options::ManifestOrigin;
+use crate::package::{PackageBinaryInfo, PackageManifest, PackageMeta, PluginDef};
+use crate::platform::{KNOWN_ARCH, KNOWN_OS};
+use crate::plugin::{
+    CompatibilityInfo, PluginManifest, ServiceDeclaration, ServiceRequirement, ServiceSource,
+};
+use crate::plugin_type::{PluginType, BUILTIN_PLUGIN_TYPES};
+
+/// Service IDs drawn from, so requirements and declarations overlap.
+const SERVICE_IDS: &[&str] = &["acme.search", "acme.storage", "acme.auth", "acme.index"];
+
+/// A reverse-domain ID (e.g., "acme.tool-2").
+pub fn id() -> impl Strategy<Value = String> {
+    "[a-z]{3,8}\\.[a-z][a-z0-9]{0,6}(-[a-z0-9]{1,4})?"
+}
+
+/// A release semver version (e.g., "1.4.2").
+pub fn version() -> impl Strategy<Value = String> {
+    (0u64..5, 0u64..20, 0u64..50)
+        .prop_map(|(major, minor, patch)| format!("{major}.{minor}.{patch}"))
+}
+
+/// A list of distinct `<os>-<arch>` platforms; empty means all platforms.
+pub fn platforms() -> impl Strategy<Value = Vec<String>> {
+    let all: Vec<String> = KNOWN_OS
+        .iter()
+        .flat_map(|os| KNOWN_ARCH.iter().map(move |arch| format!("{os}-{arch}")))
+        .collect();
+    let len = all.len();
+    subsequence(all, 0..=len)
+}
+
+/// A service declaration.
+pub fn service_declaration() -> impl Strategy<Value = ServiceDeclaration> {
+    (select(SERVICE_IDS), version()).prop_map(|(id, version)| ServiceDeclaration {
+        id: id.to_string(),
+        version,
+        description: String::new(),
+        deprecated: false,
+        replaced_by: None,
+    })
+}
+
+/// A service requirement, optionally with a minimum version.
+pub fn service_requirement() -> impl Strategy<Value = ServiceRequirement> {
+    (
+        select(SERVICE_IDS),
+        proptest::option::of(version()),
+        any::<bool>(),
+        select(vec![
+            ServiceSource::Plugin,
+            ServiceSource::Host,
+            ServiceSource::Any,
+        ]),
+    )
+        .prop_map(|(id, min_version, optional, provider)| ServiceRequirement {
+            id: id.to_string(),
+            min_version,
+            optional,
+            provider,
+        })
+}
+
+/// Services provided by one plugin, without duplicate IDs.
+fn provides() -> impl Strategy<Value = Vec<ServiceDeclaration>> {
+    proptest::collection::vec(service_declaration(), 0..3).prop_map(|mut services| {
+        services.sort_by(|a, b| a.id.cmp(&b.id));
+        services.dedup_by(|a, b| a.id == b.id);
+        services
+    })
+}
+
+/// Services required by one plugin, without duplicate IDs.
+fn requires() -> impl Strategy<Value = Vec<ServiceRequirement>> {
+    proptest::collection::vec(service_requirement(), 0..3).prop_map(|mut services| {
+        services.sort_by(|a, b| a.id.cmp(&b.id));
+        services.dedup_by(|a, b| a.id == b.id);
+        services
+    })
+}
+
+/// A plugin manifest of any built-in type.
+///
+/// Starts from `PluginManifest::template_for()`, so type-specific sections
+/// (e.g., `[translation]`) are present.
+pub fn plugin_manifest() -> impl Strategy<Value = PluginManifest> {
+    (
+        select(BUILTIN_PLUGIN_TYPES),
+        id(),
+        "[A-Z][a-z]{2,10}( [A-Z][a-z]{2,10})?",
+        version(),
+        ".{0,40}",
+        platforms(),
+        provides(),
+        requires(),
+    )
+        .prop_map(
+            |(plugin_type, id, name, version, description, platforms, provides, requires)| {
+                let mut manifest = PluginManifest::template_for(PluginType::parse(plugin_type));
+                manifest.plugin.id = id;
+                manifest.plugin.name = name;
+                manifest.plugin.version = version;
+                manifest.plugin.description = description;
+                manifest.compatibility.platforms = platforms;
+                manifest.provides = provides;
+                manifest.requires = requires;
+                manifest
+            },
+        )
+}
+
+/// A plugin definition within a package, depending on some of `earlier`.
+fn plugin_def(
+    package_id: String,
+    index: usize,
+    earlier: Vec<String>,
+) -> impl Strategy<Value = PluginDef> {
+    let len = earlier.len();
+    (
+        select(BUILTIN_PLUGIN_TYPES),
+        subsequence(earlier, 0..=len),
+        provides(),
+        requires(),
+    )
+        .prop_map(
+            move |(plugin_type, depends_on, provides, requires)| PluginDef {
+                id: format!("{package_id}-p{index}"),
+                name: format!("Plugin {index}"),
+                plugin_type: PluginType::parse(plugin_type),
+                binary: format!("plugin_{index}"),
+                description: None,
+                depends_on,
+                config: None,
+                provides,
+                requires,
+                assets: Vec::new(),
+                compatibility: None,
+            },
+        )
+}
+
+/// A package manifest with one to four plugins.
+///
+/// Plugin IDs are unique and dependencies form no cycles.
+pub fn package_manifest() -> impl Strategy<Value = PackageManifest> {
+    (id(), version(), platforms(), 1usize..5)
+        .prop_flat_map(|(id, version, platforms, count)| {
+            let ids: Vec<String> = (0..count).map(|i| format!("{id}-p{i}")).collect();
+            let plugins: Vec<_> = (0..count)
+                .map(|i| plugin_def(id.clone(), i, ids[..i].to_vec()))
+                .collect();
+            (Just(id), Just(version), Just(platforms), plugins)
+        })
+        .prop_map(|(id, version, platforms, plugins)| PackageManifest {
+            manifest_version: CURRENT_MANIFEST_VERSION,
+            package: PackageMeta {
+                name: format!("Package {id}"),
+                id,
+                version,
+                author: String::new(),
+                description: String::new(),
+                license: None,
+                homepage: None,
+                repository: None,
+                license_file: None,
+            },
+            compatibility: CompatibilityInfo {
+                platforms,
+                ..Default::default()
+            },
+            plugins,
+            binary: PackageBinaryInfo::default(),
+            signature: None,
+            origin: ManifestOrigin::default(),
+        })
+}
+
+impl Arbitrary for PluginManifest {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        plugin_manifest().boxed()
+    }
+}
+
+impl Arbitrary for PackageManifest {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        package_manifest().boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate::has_errors;
+
+    proptest! {
+        #[test]
+        fn test_plugin_manifests_are_valid(manifest in any::<PluginManifest>()) {
+            prop_assert!(!has_errors(&manifest.validate()), "{:?}", manifest.validate());
+            let reparsed = PluginManifest::from_toml(&manifest.to_toml().unwrap()).unwrap();
+            prop_assert_eq!(reparsed.plugin.id, manifest.plugin.id);
+        }
+
+        #[test]
+        fn test_package_manifests_are_valid(manifest in any::<PackageManifest>()) {
+            prop_assert!(!has_errors(&manifest.validate()), "{:?}", manifest.validate());
+            prop_assert_eq!(manifest.install_order().unwrap().len(), manifest.plugins.len());
+            let reparsed = PackageManifest::from_toml(&manifest.to_toml().unwrap()).unwrap();
+            prop_assert_eq!(reparsed.plugins.len(), manifest.plugins.len());
+        }
+    }
+}
@@ -0,0 +1,311 @@
+//! Golden manifest corpus for integration tests (`testing` feature).
+//!
+//! Representative fixtures kept in sync with the schema by this crate's own
+//! tests, so host test suites don't embed TOML that drifts from it. Each
+//! fixture is available parsed and as source text:
+//!
+//! ```ignore
+//! use lib_plugin_manifest::testkit;
+//!
+//! let manifest = testkit::full_plugin();
+//! my_host::install(&manifest).unwrap();
+//! my_host::parse(testkit::FULL_PLUGIN_TOML).unwrap();
+//! ```
+
+use crate::package::PackageManifest;
+use crate::plugin::PluginManifest;
+use crate::Manifest;
+
+/// Source of `minimal_plugin()`.
+pub const MINIMAL_PLUGIN_TOML: &str = r#"[plugin]
+id = "acme.minimal"
+name = "Minimal"
+version = "0.1.0"
+type = "extension"
+"#;
+
+/// Source of `full_plugin()`.
+pub const FULL_PLUGIN_TOML: &str = r#"manifest_version = 3
+
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "2.3.1"
+type = "extension"
+author = "Acme Corp"
+description = "Builds, indexes and syncs Acme projects"
+license = "MIT"
+homepage = "https://tool.acme.dev"
+repository = "https://github.com/acme/tool"
+
+[compatibility]
+api_version = 1
+min_host_version = "0.8.0"
+platforms = ["darwin-aarch64", "linux-x86_64", "windows-x86_64"]
+depends_on = ["acme.core"]
+
+[binary]
+name = "acme_tool"
+
+[binary.checksums]
+darwin-aarch64 = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+linux-x86_64 = "60303ae22b998861bce3b28f33eec1be758a213c86c93c076dbe9f558c11c752"
+windows-x86_64 = "fd61a03af4f77d870fc21e05e7e80678095c92d808cfb3b5c279ee04c74aca13"
+
+[binary.sizes]
+darwin-aarch64 = 1048576
+linux-x86_64 = 1153433
+windows-x86_64 = 1258291
+
+[config.defaults]
+log_level = "info"
+sync_interval = 300
+
+[config.profiles.dev]
+log_level = "debug"
+
+[[provides]]
+id = "acme.tool.build"
+version = "2.3.0"
+description = "Build service"
+
+[[requires]]
+id = "acme.storage"
+min_version = "1.0.0"
+
+[[requires]]
+id = "acme.telemetry"
+optional = true
+
+[cli]
+command = "tool"
+description = "Acme tool commands"
+aliases = ["t"]
+
+[[capabilities]]
+protocol = "tasks"
+version = "1.0.0"
+
+[tags]
+categories = ["development", "build"]
+
+[storage.data]
+quota_mb = 500
+
+[storage.cache]
+
+[permissions.network]
+allow = ["api.acme.dev:443"]
+
+[permissions.fs]
+read = ["$WORKSPACE/**"]
+write = ["$CACHE/**"]
+
+[hooks.post_install]
+script = "scripts/setup.sh"
+
+[[uninstall.caches]]
+path = "index-cache"
+
+[post_install_message]
+text = "Run `tool init` to get started."
+
+[[migrations]]
+from_version = "<2.0.0"
+script = "migrations/v2.sh"
+
+[[messaging]]
+topic = "acme.build.finished"
+direction = "publish"
+
+[health]
+probe = "service_call"
+target = "acme.tool.build"
+interval_secs = 30
+
+[observability]
+metrics = ["acme_tool"]
+tracing = true
+
+[logging]
+level = "info"
+"#;
+
+/// Source of `large_package()`.
+pub const LARGE_PACKAGE_TOML: &str = r#"manifest_version = 3
+
+[package]
+id = "acme.suite"
+name = "Acme Suite"
+version = "4.0.0"
+author = "Acme Corp"
+description = "Everything Acme in one package"
+license = "Apache-2.0"
+
+[compatibility]
+api_version = 1
+platforms = ["darwin-aarch64", "darwin-x86_64", "linux-x86_64", "linux-aarch64", "windows-x86_64"]
+
+[[plugins]]
+id = "acme.core"
+name = "Core"
+type = "core"
+binary = "acme_core"
+
+[[plugins.provides]]
+id = "acme.storage"
+version = "1.2.0"
+
+[[plugins]]
+id = "acme.auth"
+name = "Auth"
+type = "extension"
+binary = "acme_auth"
+depends_on = ["acme.core"]
+
+[[plugins.provides]]
+id = "acme.auth"
+version = "1.0.0"
+
+[[plugins]]
+id = "acme.index"
+name = "Index"
+type = "extension"
+binary = "acme_index"
+depends_on = ["acme.core"]
+
+[[plugins.requires]]
+id = "acme.storage"
+min_version = "1.0.0"
+
+[[plugins]]
+id = "acme.search"
+name = "Search"
+type = "extension"
+binary = "acme_search"
+depends_on = ["acme.index"]
+
+[[plugins]]
+id = "acme.sync"
+name = "Sync"
+type = "extension"
+binary = "acme_sync"
+depends_on = ["acme.auth", "acme.core"]
+
+[plugins.config.defaults]
+interval = 300
+
+[[plugins]]
+id = "acme.theme-dark"
+name = "Dark Theme"
+type = "theme"
+binary = "acme_theme_dark"
+
+[[plugins]]
+id = "acme.font-mono"
+name = "Acme Mono"
+type = "font"
+binary = "acme_font_mono"
+
+[[plugins]]
+id = "acme.dashboard"
+name = "Dashboard"
+type = "extension"
+binary = "acme_dashboard"
+depends_on = ["acme.search", "acme.sync", "acme.theme-dark"]
+
+[binary.checksums]
+darwin-aarch64 = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+linux-x86_64 = "60303ae22b998861bce3b28f33eec1be758a213c86c93c076dbe9f558c11c752"
+"#;
+
+/// Source of `translation_plugin()`.
+pub const TRANSLATION_PLUGIN_TOML: &str = r#"[plugin]
+id = "acme.tool.de-DE"
+name = "Tool (Deutsch)"
+version = "2.3.0"
+type = "translation"
+
+[translation]
+translates = "acme.tool"
+language = "de-DE"
+language_name = "Deutsch (Deutschland)"
+namespace = "tool"
+"#;
+
+/// Source of `hive_plugin()`.
+pub const HIVE_PLUGIN_TOML: &str = r#"[plugin]
+id = "hive.runner.docker"
+name = "Docker Runner"
+version = "1.0.0"
+type = "hive-plugin"
+description = "Run workloads in Docker containers"
+
+[compatibility]
+platforms = ["linux-x86_64", "linux-aarch64"]
+
+[hive]
+category = "runner"
+name = "docker"
+
+[tags]
+categories = ["hive", "runner", "docker"]
+"#;
+
+/// A plugin with only the required fields.
+pub fn minimal_plugin() -> PluginManifest {
+    PluginManifest::from_toml(MINIMAL_PLUGIN_TOML).expect("minimal_plugin fixture")
+}
+
+/// A plugin using most sections: binary checksums, config profiles,
+/// services, CLI, storage, permissions, hooks, migrations and health.
+pub fn full_plugin() -> PluginManifest {
+    PluginManifest::from_toml(FULL_PLUGIN_TOML).expect("full_plugin fixture")
+}
+
+/// A package of eight plugins with services and a layered dependency graph.
+pub fn large_package() -> PackageManifest {
+    PackageManifest::from_toml(LARGE_PACKAGE_TOML).expect("large_package fixture")
+}
+
+/// A translation plugin for `full_plugin()`.
+pub fn translation_plugin() -> PluginManifest {
+    PluginManifest::from_toml(TRANSLATION_PLUGIN_TOML).expect("translation_plugin fixture")
+}
+
+/// A hive runner plugin.
+pub fn hive_plugin() -> PluginManifest {
+    PluginManifest::from_toml(HIVE_PLUGIN_TOML).expect("hive_plugin fixture")
+}
+
+/// Every fixture, by name.
+pub fn all() -> Vec<(&'static str, Manifest)> {
+    vec![
+        ("minimal_plugin", Manifest::Single(minimal_plugin())),
+        ("full_plugin", Manifest::Single(full_plugin())),
+        ("large_package", Manifest::Package(large_package())),
+        ("translation_plugin", Manifest::Single(translation_plugin())),
+        ("hive_plugin", Manifest::Single(hive_plugin())),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate::has_errors;
+
+    #[test]
+    fn test_fixtures_are_valid() {
+        for (name, manifest) in all() {
+            let issues = manifest.validate();
+            assert!(!has_errors(&issues), "{name}: {issues:?}");
+            let reparsed = Manifest::from_toml(&manifest.to_toml().unwrap()).unwrap();
+            assert_eq!(reparsed.id(), manifest.id(), "{name}");
+        }
+
+        let full = full_plugin();
+        assert!(full.origin.defaulted_fields.is_empty());
+        assert!(full.origin.migration_notes.is_empty());
+        assert_eq!(large_package().install_order().unwrap().len(), 8);
+    }
+}
@@ -0,0 +1,172 @@
+//! TypeScript type definitions for the manifest schema.
+//!
+//! The definitions are derived from the same types (and serde attributes)
+//! used for parsing, so they can't drift from the Rust model.
+
+use ts_rs::{Config, TS};
+
+use crate::abi::AbiRequirements;
+use crate::artifact::{ArtifactDescriptor, ArtifactKind};
+use crate::assets::{Asset, AssetKind};
+use crate::cli::{CliArg, CliArgType, CliSubcommand};
+use crate::coverage::{ChecksumCoverage, PlatformMatrix, PlatformRow, PlatformSupport};
+use crate::diff::{
+    ChangeLevel, FieldChange, FieldChangeKind, ManifestChange, ManifestDiff, ServiceChange,
+    ServiceCompatibility,
+};
+use crate::dto::{ArtifactInfo, ManifestSummaryDto, RegistryEntry, RegistryEntryDto};
+use crate::effective::LocalizedMeta;
+use crate::health::{HealthInfo, ProbeKind, RestartPolicy};
+use crate::hooks::{HookAction, HooksInfo};
+use crate::hosts::HostVersionRange;
+use crate::logging::{LogLevel, LoggingInfo};
+use crate::messaging::{MessagingTopic, TopicDirection};
+use crate::migrate::MigrationNote;
+use crate::migrations::Migration;
+use crate::narrow::CompatibilityOverride;
+use crate::observability::ObservabilityInfo;
+use crate::package::{PackageBinaryInfo, PackageManifest, PackageMeta, PluginDef};
+use crate::permissions::{
+    EnvPermissions, FsPermissions, NetworkPermissions, Permissions, SubprocessPermissions,
+};
+use crate::plan::InstallStep;
+use crate::plugin::{
+    BinaryInfo, BinaryKind, BinaryOverride, CapabilityDeclaration, CliConfig, CompatibilityInfo,
+    ConfigInfo, HiveInfo, LanguageInfo, PluginManifest, PluginMeta, RequirementsInfo,
+    ServiceDeclaration, ServiceRequirement, ServiceSource, SignatureInfo, TagsInfo, ThemeInfo,
+    TranslationInfo,
+};
+use crate::post_install::{MessageFormat, PostInstallMessage};
+use crate::preflight::{PreflightCheck, PreflightReport, PreflightStatus};
+use crate::resources::{ResourceClaim, ResourceKind, ResourcesInfo};
+use crate::services::{
+    DeprecatedService, SelectionReason, ServiceBinding, ServiceResolution, UnresolvedService,
+};
+use crate::shims::{CompatInfo, ShimInfo};
+use crate::stats::ManifestStats;
+use crate::storage::{StorageDir, StorageInfo};
+use crate::uninstall::{CleanupPath, CleanupPolicy, CleanupResource, UninstallInfo};
+
+/// Generate TypeScript declarations for all manifest types.
+///
+/// Returns a single module with one exported type per manifest struct.
+/// 64-bit integers (e.g., binary sizes) are emitted as `number`, matching
+/// what JSON consumers actually receive.
+pub fn typescript_definitions() -> String {
+    let cfg = Config::new().with_large_int("number");
+    let decls = [
+        PluginManifest::decl(&cfg),
+        PluginMeta::decl(&cfg),
+        CompatibilityInfo::decl(&cfg),
+        HostVersionRange::decl(&cfg),
+        BinaryInfo::decl(&cfg),
+        BinaryOverride::decl(&cfg),
+        BinaryKind::decl(&cfg),
+        AbiRequirements::decl(&cfg),
+        SignatureInfo::decl(&cfg),
+        ConfigInfo::decl(&cfg),
+        ServiceDeclaration::decl(&cfg),
+        ServiceRequirement::decl(&cfg),
+        ServiceSource::decl(&cfg),
+        CapabilityDeclaration::decl(&cfg),
+        CliConfig::decl(&cfg),
+        CliSubcommand::decl(&cfg),
+        CliArg::decl(&cfg),
+        CliArgType::decl(&cfg),
+        TagsInfo::decl(&cfg),
+        HiveInfo::decl(&cfg),
+        TranslationInfo::decl(&cfg),
+        LanguageInfo::decl(&cfg),
+        ThemeInfo::decl(&cfg),
+        RequirementsInfo::decl(&cfg),
+        HooksInfo::decl(&cfg),
+        HookAction::decl(&cfg),
+        UninstallInfo::decl(&cfg),
+        CleanupPath::decl(&cfg),
+        CleanupResource::decl(&cfg),
+        CleanupPolicy::decl(&cfg),
+        PostInstallMessage::decl(&cfg),
+        MessageFormat::decl(&cfg),
+        Migration::decl(&cfg),
+        MigrationNote::decl(&cfg),
+        StorageInfo::decl(&cfg),
+        StorageDir::decl(&cfg),
+        Permissions::decl(&cfg),
+        NetworkPermissions::decl(&cfg),
+        FsPermissions::decl(&cfg),
+        EnvPermissions::decl(&cfg),
+        SubprocessPermissions::decl(&cfg),
+        MessagingTopic::decl(&cfg),
+        Asset::decl(&cfg),
+        AssetKind::decl(&cfg),
+        TopicDirection::decl(&cfg),
+        ResourcesInfo::decl(&cfg),
+        ResourceClaim::decl(&cfg),
+        ResourceKind::decl(&cfg),
+        HealthInfo::decl(&cfg),
+        ProbeKind::decl(&cfg),
+        RestartPolicy::decl(&cfg),
+        ObservabilityInfo::decl(&cfg),
+        LogLevel::decl(&cfg),
+        LoggingInfo::decl(&cfg),
+        LocalizedMeta::decl(&cfg),
+        CompatInfo::decl(&cfg),
+        ShimInfo::decl(&cfg),
+        PackageManifest::decl(&cfg),
+        PackageMeta::decl(&cfg),
+        PluginDef::decl(&cfg),
+        CompatibilityOverride::decl(&cfg),
+        InstallStep::decl(&cfg),
+        ChecksumCoverage::decl(&cfg),
+        PlatformSupport::decl(&cfg),
+        PlatformRow::decl(&cfg),
+        PlatformMatrix::decl(&cfg),
+        PreflightStatus::decl(&cfg),
+        PreflightCheck::decl(&cfg),
+        PreflightReport::decl(&cfg),
+        SelectionReason::decl(&cfg),
+        ServiceBinding::decl(&cfg),
+        UnresolvedService::decl(&cfg),
+        DeprecatedService::decl(&cfg),
+        ServiceResolution::decl(&cfg),
+        ChangeLevel::decl(&cfg),
+        ServiceChange::decl(&cfg),
+        ServiceCompatibility::decl(&cfg),
+        FieldChange::decl(&cfg),
+        FieldChangeKind::decl(&cfg),
+        ManifestChange::decl(&cfg),
+        ManifestDiff::decl(&cfg),
+        PackageBinaryInfo::decl(&cfg),
+        ManifestSummaryDto::decl(&cfg),
+        RegistryEntryDto::decl(&cfg),
+        ArtifactInfo::decl(&cfg),
+        RegistryEntry::decl(&cfg),
+        ManifestStats::decl(&cfg),
+        ArtifactDescriptor::decl(&cfg),
+        ArtifactKind::decl(&cfg),
+    ];
+
+    let mut out = String::from("// Generated by lib-plugin-manifest. Do not edit.\n");
+    for decl in decls {
+        out.push_str("\nexport ");
+        out.push_str(&decl);
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_typescript_definitions() {
+        let ts = typescript_definitions();
+        assert!(ts.contains("export type PluginManifest = "));
+        assert!(ts.contains("export type PackageManifest = "));
+        // serde renames are honored
+        assert!(ts.contains("type: string"));
+        assert!(!ts.contains("plugin_type: string"));
+        assert!(!ts.contains("bigint"));
+    }
+}
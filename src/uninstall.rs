@@ -0,0 +1,202 @@
+//! Uninstall cleanup declarations (`[uninstall]`).
+//!
+//! ```toml
+//! [[uninstall.data]]
+//! path = "projects"
+//! policy = "keep_by_default"
+//!
+//! [[uninstall.caches]]
+//! path = "index-cache"
+//!
+//! [[uninstall.resources]]
+//! id = "acme.tool.scheduled-sync"
+//! ```
+//!
+//! Paths are relative to the plugin's data directory.
+
+use serde::{Deserialize, Serialize};
+
+use crate::plugin::PluginManifest;
+use crate::validate::{is_contained_path, ValidationIssue};
+
+/// What the host does with an entry on uninstall.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum CleanupPolicy {
+    /// Always removed with the plugin
+    #[default]
+    Remove,
+    /// Kept unless the user chooses "remove including data"
+    KeepByDefault,
+}
+
+/// A directory the plugin creates.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CleanupPath {
+    /// Directory path (relative to the plugin data directory)
+    pub path: String,
+
+    /// Cleanup policy
+    #[serde(default)]
+    pub policy: CleanupPolicy,
+
+    /// Human-readable description shown in the uninstall dialog
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// A resource the plugin registers with the host (e.g., a scheduled job).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CleanupResource {
+    /// Resource ID
+    pub id: String,
+
+    /// Cleanup policy
+    #[serde(default)]
+    pub policy: CleanupPolicy,
+
+    /// Human-readable description shown in the uninstall dialog
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Uninstall cleanup metadata.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct UninstallInfo {
+    /// User data directories
+    #[serde(default)]
+    pub data: Vec<CleanupPath>,
+
+    /// Cache directories
+    #[serde(default)]
+    pub caches: Vec<CleanupPath>,
+
+    /// Registered resources
+    #[serde(default)]
+    pub resources: Vec<CleanupResource>,
+}
+
+impl UninstallInfo {
+    /// Whether anything is kept by default, i.e., whether a
+    /// "remove including data" option changes anything.
+    pub fn has_kept_entries(&self) -> bool {
+        self.data
+            .iter()
+            .chain(&self.caches)
+            .map(|e| e.policy)
+            .chain(self.resources.iter().map(|r| r.policy))
+            .any(|p| p == CleanupPolicy::KeepByDefault)
+    }
+
+    /// Directories to remove (data first, then caches).
+    ///
+    /// With `include_kept`, entries marked `keep_by_default` are included.
+    pub fn paths_to_remove(&self, include_kept: bool) -> Vec<&CleanupPath> {
+        self.data
+            .iter()
+            .chain(&self.caches)
+            .filter(|e| include_kept || e.policy == CleanupPolicy::Remove)
+            .collect()
+    }
+
+    /// Resources to unregister.
+    ///
+    /// With `include_kept`, resources marked `keep_by_default` are included.
+    pub fn resources_to_remove(&self, include_kept: bool) -> Vec<&CleanupResource> {
+        self.resources
+            .iter()
+            .filter(|r| include_kept || r.policy == CleanupPolicy::Remove)
+            .collect()
+    }
+}
+
+impl PluginManifest {
+    /// Check uninstall declarations.
+    ///
+    /// Paths must stay inside the plugin data directory so that cleanup
+    /// can never remove anything else.
+    pub(crate) fn check_uninstall(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        let Some(uninstall) = &self.uninstall else {
+            return issues;
+        };
+
+        for (section, entries) in [("data", &uninstall.data), ("caches", &uninstall.caches)] {
+            for (i, entry) in entries.iter().enumerate() {
+                if !is_contained_path(&entry.path) {
+                    issues.push(ValidationIssue::error(
+                        format!("uninstall.{section}[{i}].path"),
+                        format!(
+                            "Cleanup path '{}' must be a relative path inside the plugin data directory",
+                            entry.path
+                        ),
+                    ));
+                }
+            }
+        }
+        for (i, resource) in uninstall.resources.iter().enumerate() {
+            if resource.id.trim().is_empty() {
+                issues.push(ValidationIssue::error(
+                    format!("uninstall.resources[{i}].id"),
+                    "Resource ID is empty",
+                ));
+            }
+        }
+
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate::has_errors;
+
+    const BASE: &str = r#"
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.0.0"
+type = "extension"
+"#;
+
+    #[test]
+    fn test_parse_uninstall() {
+        let toml = format!(
+            r#"{BASE}
+[[uninstall.data]]
+path = "projects"
+policy = "keep_by_default"
+
+[[uninstall.caches]]
+path = "index-cache"
+
+[[uninstall.resources]]
+id = "acme.tool.scheduled-sync"
+"#
+        );
+        let manifest = PluginManifest::from_toml(&toml).unwrap();
+        let uninstall = manifest.uninstall.as_ref().unwrap();
+
+        assert!(uninstall.has_kept_entries());
+        assert_eq!(uninstall.paths_to_remove(false).len(), 1);
+        assert_eq!(uninstall.paths_to_remove(true).len(), 2);
+        assert_eq!(uninstall.resources_to_remove(false).len(), 1);
+        assert!(manifest.validate().is_empty());
+    }
+
+    #[test]
+    fn test_escaping_cleanup_path() {
+        let toml = format!("{BASE}\n[[uninstall.caches]]\npath = \"../../home\"\n");
+        let manifest = PluginManifest::from_toml(&toml).unwrap();
+        assert!(has_errors(&manifest.validate()));
+    }
+}
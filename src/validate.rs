@@ -0,0 +1,1301 @@
+//! Manifest validation.
+//!
+//! Parsing only checks that a manifest is well-formed TOML with the expected
+//! shape. The checks in this module catch manifests that parse fine but
+//! should not be installed or published.
+
+use std::fmt;
+use std::path::{Component, Path};
+
+use crate::assets::check_assets;
+use crate::error::ManifestError;
+use crate::license::LicensePolicy;
+use crate::package::PackageManifest;
+use crate::platform::is_known_platform;
+use crate::plugin::{CompatibilityInfo, PluginManifest, ServiceDeclaration, ServiceRequirement};
+use crate::plugin_type::{PluginType, PluginTypeRegistry};
+
+/// Severity of a validation issue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Suspicious but acceptable
+    Warning,
+    /// The manifest must be rejected
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A single problem found while validating a manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationIssue {
+    /// How serious the issue is
+    pub severity: Severity,
+
+    /// Path of the offending field (e.g., "plugin.id", "plugins[2].id")
+    pub path: String,
+
+    /// Human-readable description
+    pub message: String,
+}
+
+impl ValidationIssue {
+    /// Create an error-level issue.
+    pub fn error(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Error,
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Create a warning-level issue.
+    pub fn warning(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Check if this issue is an error.
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}: {}", self.severity, self.path, self.message)
+    }
+}
+
+/// Check if any of the issues is an error.
+pub fn has_errors(issues: &[ValidationIssue]) -> bool {
+    issues.iter().any(ValidationIssue::is_error)
+}
+
+/// Fail with the error-level issues, joined into one message.
+pub(crate) fn ensure_valid(issues: &[ValidationIssue]) -> Result<(), ManifestError> {
+    let errors: Vec<String> = issues
+        .iter()
+        .filter(|i| i.is_error())
+        .map(ToString::to_string)
+        .collect();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(ManifestError::InvalidFormat(errors.join("; ")))
+    }
+}
+
+/// Get the vendor namespace of an ID (the part before the first dot).
+///
+/// Returns None for IDs that are not namespaced.
+pub fn vendor_of(id: &str) -> Option<&str> {
+    id.split_once('.')
+        .map(|(vendor, _)| vendor)
+        .filter(|vendor| !vendor.is_empty())
+}
+
+/// Vendor namespace policy for plugin and package IDs.
+///
+/// IDs are namespaced as `vendor.name`. The policy reserves namespaces for
+/// first-party plugins and, when the publisher is known (e.g., from the
+/// signing key), requires the vendor prefix to match it.
+#[derive(Debug, Clone)]
+pub struct IdPolicy {
+    /// Namespaces only first-party publishers may use (e.g., "adi", "hive")
+    pub reserved_namespaces: Vec<String>,
+
+    /// Publishers allowed to use reserved namespaces
+    pub first_party_publishers: Vec<String>,
+
+    /// Require the vendor prefix to match the signing publisher
+    pub require_publisher_match: bool,
+}
+
+impl Default for IdPolicy {
+    fn default() -> Self {
+        Self {
+            reserved_namespaces: vec!["adi".to_string(), "hive".to_string()],
+            first_party_publishers: vec!["adi".to_string()],
+            require_publisher_match: true,
+        }
+    }
+}
+
+impl IdPolicy {
+    /// Check if a namespace is reserved for first-party plugins.
+    pub fn is_reserved(&self, namespace: &str) -> bool {
+        self.reserved_namespaces.iter().any(|n| n == namespace)
+    }
+
+    /// Check if a publisher is first-party.
+    pub fn is_first_party(&self, publisher: &str) -> bool {
+        self.first_party_publishers.iter().any(|p| p == publisher)
+    }
+
+    /// Check a single ID against the policy.
+    ///
+    /// `path` is used for reporting, `publisher` is the verified publisher
+    /// of the manifest (None if unknown, e.g., unsigned).
+    pub fn check(&self, path: &str, id: &str, publisher: Option<&str>) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+
+        let vendor = match vendor_of(id) {
+            Some(v) => v,
+            None => {
+                issues.push(ValidationIssue::error(
+                    path,
+                    format!("ID '{id}' must be namespaced as 'vendor.name'"),
+                ));
+                return issues;
+            }
+        };
+
+        // First-party publishers may use every reserved namespace, but
+        // other vendors' namespaces only like anyone else
+        let first_party = publisher.is_some_and(|p| self.is_first_party(p));
+        let reserved = self.is_reserved(vendor);
+        if reserved && !first_party {
+            issues.push(ValidationIssue::error(
+                path,
+                format!("Namespace '{vendor}' is reserved for first-party plugins"),
+            ));
+            return issues;
+        }
+
+        if self.require_publisher_match {
+            match publisher {
+                Some(publisher) if publisher != vendor && !reserved => {
+                    issues.push(ValidationIssue::error(
+                        path,
+                        format!("Vendor prefix '{vendor}' does not match publisher '{publisher}'"),
+                    ));
+                }
+                Some(_) => {}
+                None => {
+                    issues.push(ValidationIssue::warning(
+                        path,
+                        format!("Vendor prefix '{vendor}' cannot be verified without a publisher"),
+                    ));
+                }
+            }
+        }
+
+        issues
+    }
+}
+
+/// Deny-list of plugin IDs the registry refuses to accept.
+#[derive(Debug, Clone)]
+pub struct Blocklist {
+    /// Words that may not appear as an ID segment (e.g., "official", "system")
+    pub reserved_words: Vec<String>,
+
+    /// Official plugin IDs protected against look-alike impersonation
+    pub protected_ids: Vec<String>,
+
+    /// Banned IDs; a trailing `.*` bans a whole namespace (e.g., "spam.*")
+    pub banned_ids: Vec<String>,
+}
+
+impl Default for Blocklist {
+    fn default() -> Self {
+        Self {
+            reserved_words: ["official", "system", "admin", "root"]
+                .iter()
+                .map(|w| w.to_string())
+                .collect(),
+            protected_ids: Vec::new(),
+            banned_ids: Vec::new(),
+        }
+    }
+}
+
+impl Blocklist {
+    /// Check IDs against the deny-list.
+    ///
+    /// Typically called with `Manifest::plugin_ids()` plus the manifest ID.
+    pub fn validate_against_blocklist(&self, ids: &[&str]) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+
+        for (i, id) in ids.iter().enumerate() {
+            let path = format!("ids[{i}]");
+
+            if self.is_banned(id) {
+                issues.push(ValidationIssue::error(
+                    &path,
+                    format!("ID '{id}' is banned"),
+                ));
+                continue;
+            }
+
+            if let Some(word) = id
+                .split('.')
+                .find(|segment| self.reserved_words.iter().any(|w| w == segment))
+            {
+                issues.push(ValidationIssue::error(
+                    &path,
+                    format!("ID '{id}' uses reserved word '{word}'"),
+                ));
+            }
+
+            let normalized = normalize_id(id);
+            if let Some(official) = self
+                .protected_ids
+                .iter()
+                .find(|p| p.as_str() != *id && normalize_id(p) == normalized)
+            {
+                issues.push(ValidationIssue::error(
+                    &path,
+                    format!("ID '{id}' impersonates official plugin '{official}'"),
+                ));
+            }
+        }
+
+        issues
+    }
+
+    fn is_banned(&self, id: &str) -> bool {
+        self.banned_ids
+            .iter()
+            .any(|banned| match banned.strip_suffix(".*") {
+                Some(namespace) => vendor_of(id) == Some(namespace),
+                None => banned == id,
+            })
+    }
+}
+
+/// Reduce an ID to a form where look-alikes compare equal
+/// ("adi.tasks", "ADI.Task_s" and "adi.ta5ks" all collapse together).
+fn normalize_id(id: &str) -> String {
+    id.chars()
+        .filter(|c| !matches!(c, '-' | '_' | '.'))
+        .map(|c| match c.to_ascii_lowercase() {
+            '0' => 'o',
+            '1' => 'l',
+            '3' => 'e',
+            '5' => 's',
+            other => other,
+        })
+        .collect()
+}
+
+/// Constraints on the shape of plugin and package IDs.
+#[derive(Debug, Clone)]
+pub struct IdConstraints {
+    /// Maximum ID length in bytes
+    pub max_length: usize,
+
+    /// Minimum number of dot-separated segments
+    pub min_segments: usize,
+
+    /// Maximum number of dot-separated segments
+    pub max_segments: usize,
+
+    /// Allow uppercase ASCII letters (e.g., "adi.workflow.en-US")
+    pub allow_uppercase: bool,
+
+    /// Punctuation allowed inside segments in addition to ASCII alphanumerics
+    pub allowed_punctuation: Vec<char>,
+}
+
+impl Default for IdConstraints {
+    fn default() -> Self {
+        Self {
+            max_length: 128,
+            min_segments: 2,
+            max_segments: 4,
+            allow_uppercase: true,
+            allowed_punctuation: vec!['-', '_'],
+        }
+    }
+}
+
+impl IdConstraints {
+    /// Check a single ID against the constraints.
+    pub fn check(&self, path: &str, id: &str) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+
+        if id.is_empty() {
+            issues.push(ValidationIssue::error(path, "ID must not be empty"));
+            return issues;
+        }
+
+        if id.len() > self.max_length {
+            issues.push(ValidationIssue::error(
+                path,
+                format!("ID '{id}' exceeds {} characters", self.max_length),
+            ));
+        }
+
+        let segments: Vec<&str> = id.split('.').collect();
+        if segments.len() < self.min_segments || segments.len() > self.max_segments {
+            issues.push(ValidationIssue::error(
+                path,
+                format!(
+                    "ID '{id}' has {} segments, expected {} to {}",
+                    segments.len(),
+                    self.min_segments,
+                    self.max_segments
+                ),
+            ));
+        }
+
+        if segments.iter().any(|s| s.is_empty()) {
+            issues.push(ValidationIssue::error(
+                path,
+                format!("ID '{id}' contains an empty segment"),
+            ));
+        }
+
+        if let Some(c) = id.chars().find(|c| !self.is_allowed_char(*c)) {
+            issues.push(ValidationIssue::error(
+                path,
+                format!("ID '{id}' contains disallowed character '{c}'"),
+            ));
+        }
+
+        issues
+    }
+
+    fn is_allowed_char(&self, c: char) -> bool {
+        c == '.'
+            || c.is_ascii_lowercase()
+            || c.is_ascii_digit()
+            || (self.allow_uppercase && c.is_ascii_uppercase())
+            || self.allowed_punctuation.contains(&c)
+    }
+}
+
+/// Policy controlling which checks `validate_with()` runs and how strict
+/// they are. Different deployments want stricter or looser rules.
+#[derive(Debug, Clone)]
+pub struct ValidationPolicy {
+    /// ID shape constraints
+    pub ids: IdConstraints,
+
+    /// Vendor namespace policy (None = not enforced)
+    pub namespace: Option<IdPolicy>,
+
+    /// Verified publisher of the manifest being validated (e.g., from the signing key)
+    pub publisher: Option<String>,
+
+    /// License allow-list policy
+    pub license: LicensePolicy,
+
+    /// URL schemes accepted in URL-typed fields
+    pub url_schemes: Vec<String>,
+
+    /// Known plugin types
+    pub plugin_types: PluginTypeRegistry,
+
+    /// Reject unknown plugin types instead of warning about them
+    pub reject_unknown_types: bool,
+
+    /// Require provided service IDs to be under the declaring plugin's vendor
+    /// namespace or one of `shared_service_namespaces`
+    pub scope_service_ids: bool,
+
+    /// Namespaces any plugin may declare services in (e.g., "shared")
+    pub shared_service_namespaces: Vec<String>,
+}
+
+impl Default for ValidationPolicy {
+    fn default() -> Self {
+        Self {
+            ids: IdConstraints::default(),
+            namespace: None,
+            publisher: None,
+            license: LicensePolicy::default(),
+            url_schemes: vec!["http".to_string(), "https".to_string()],
+            plugin_types: PluginTypeRegistry::builtin(),
+            reject_unknown_types: false,
+            scope_service_ids: false,
+            shared_service_namespaces: Vec::new(),
+        }
+    }
+}
+
+impl ValidationPolicy {
+    fn check_id(&self, path: &str, id: &str) -> Vec<ValidationIssue> {
+        let mut issues = self.ids.check(path, id);
+        if let Some(namespace) = &self.namespace {
+            issues.extend(namespace.check(path, id, self.publisher.as_deref()));
+        }
+        issues
+    }
+
+    fn check_service_ids(
+        &self,
+        prefix: &str,
+        owner: &str,
+        provides: &[ServiceDeclaration],
+    ) -> Vec<ValidationIssue> {
+        let Some(vendor) = vendor_of(owner).filter(|_| self.scope_service_ids) else {
+            return Vec::new();
+        };
+        let in_namespace = |id: &str, ns: &str| {
+            id.strip_prefix(ns)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+        };
+        provides
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| {
+                !in_namespace(&s.id, vendor)
+                    && !self
+                        .shared_service_namespaces
+                        .iter()
+                        .any(|ns| in_namespace(&s.id, ns))
+            })
+            .map(|(i, s)| {
+                ValidationIssue::error(
+                    format!("{prefix}provides[{i}].id"),
+                    format!("Service ID '{}' is outside the '{vendor}' namespace", s.id),
+                )
+            })
+            .collect()
+    }
+
+    fn check_plugin_type(&self, path: &str, plugin_type: &PluginType) -> Option<ValidationIssue> {
+        if self.plugin_types.contains(plugin_type.as_str()) {
+            return None;
+        }
+        let message = format!("Unknown plugin type '{plugin_type}'");
+        Some(if self.reject_unknown_types {
+            ValidationIssue::error(path, message)
+        } else {
+            ValidationIssue::warning(path, message)
+        })
+    }
+}
+
+/// Check that a URL has an allowed scheme and a plausible host.
+pub fn check_url(path: &str, url: &str, schemes: &[String]) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+
+    let (scheme, rest) = match url.split_once("://") {
+        Some(parts) => parts,
+        None => {
+            issues.push(ValidationIssue::error(
+                path,
+                format!("'{url}' is not a valid URL"),
+            ));
+            return issues;
+        }
+    };
+
+    if !schemes.iter().any(|s| s.eq_ignore_ascii_case(scheme)) {
+        issues.push(ValidationIssue::error(
+            path,
+            format!(
+                "URL scheme '{scheme}' is not allowed (expected one of: {})",
+                schemes.join(", ")
+            ),
+        ));
+    }
+
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or("");
+    let host = authority.rsplit('@').next().unwrap_or("");
+    let host = match host.rsplit_once(':') {
+        Some((h, port)) if port.chars().all(|c| c.is_ascii_digit()) => h,
+        _ => host,
+    };
+    let host_ok = !host.is_empty()
+        && !host.starts_with('.')
+        && !host.ends_with('.')
+        && host
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '[' | ']' | ':'));
+
+    if !host_ok || url.chars().any(char::is_whitespace) {
+        issues.push(ValidationIssue::error(
+            path,
+            format!("'{url}' is not a valid URL"),
+        ));
+    }
+
+    issues
+}
+
+/// Check that a path is relative and can't escape its base directory.
+pub(crate) fn is_contained_path(path: &str) -> bool {
+    !path.is_empty()
+        && Path::new(path)
+            .components()
+            .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+}
+
+/// Whether `name` is a single file name, without a directory part.
+pub(crate) fn is_file_name(name: &str) -> bool {
+    is_contained_path(name) && !name.contains(['/', '\\']) && name != "."
+}
+
+/// Binary names become file names inside the plugin directory, so they
+/// can't have a directory part.
+fn check_binary_name(path: &str, name: &str) -> Option<ValidationIssue> {
+    if name.trim().is_empty() {
+        return Some(ValidationIssue::error(path, "Binary name is empty"));
+    }
+    (!is_file_name(name)).then(|| {
+        ValidationIssue::error(
+            path,
+            format!("Binary name '{name}' must be a file name without a directory"),
+        )
+    })
+}
+
+/// Check that a version string is valid semver.
+fn check_version(path: &str, version: &str) -> Option<ValidationIssue> {
+    semver::Version::parse(version).err().map(|e| {
+        ValidationIssue::error(
+            path,
+            format!("'{version}' is not a valid semantic version: {e}"),
+        )
+    })
+}
+
+/// Warn about platform identifiers that aren't known `<os>-<arch>` pairs.
+fn check_platforms<'a>(
+    path: &str,
+    platforms: impl IntoIterator<Item = &'a String>,
+) -> Vec<ValidationIssue> {
+    platforms
+        .into_iter()
+        .filter(|p| !is_known_platform(p))
+        .map(|p| ValidationIssue::warning(path, format!("Unknown platform '{p}'")))
+        .collect()
+}
+
+fn check_compatibility(compat: &CompatibilityInfo) -> Vec<ValidationIssue> {
+    let mut issues: Vec<ValidationIssue> = [
+        ("compatibility.min_host_version", &compat.min_host_version),
+        ("compatibility.max_host_version", &compat.max_host_version),
+    ]
+    .into_iter()
+    .filter_map(|(path, v)| check_version(path, v.as_deref()?))
+    .collect();
+    issues.extend(check_platforms(
+        "compatibility.platforms",
+        &compat.platforms,
+    ));
+    for (host, range) in &compat.host_versions {
+        for (field, v) in [
+            ("min_host_version", &range.min_host_version),
+            ("max_host_version", &range.max_host_version),
+        ] {
+            if let Some(v) = v {
+                issues.extend(check_version(
+                    &format!("compatibility.host_versions.{host}.{field}"),
+                    v,
+                ));
+            }
+        }
+    }
+    issues.extend(compat.check_hosts());
+    issues
+}
+
+fn check_services(
+    prefix: &str,
+    provides: &[ServiceDeclaration],
+    requires: &[ServiceRequirement],
+) -> Vec<ValidationIssue> {
+    let provided = provides
+        .iter()
+        .enumerate()
+        .filter_map(|(i, s)| check_version(&format!("{prefix}provides[{i}].version"), &s.version));
+    let required = requires.iter().enumerate().filter_map(|(i, r)| {
+        check_version(
+            &format!("{prefix}requires[{i}].min_version"),
+            r.min_version.as_deref()?,
+        )
+    });
+    provided.chain(required).collect()
+}
+
+impl PluginManifest {
+    /// Validate the manifest with the default policy.
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        self.validate_with(&ValidationPolicy::default())
+    }
+
+    /// Validate the manifest with a custom policy.
+    pub fn validate_with(&self, policy: &ValidationPolicy) -> Vec<ValidationIssue> {
+        let mut issues = policy.check_id("plugin.id", &self.plugin.id);
+        issues.extend(policy.check_plugin_type("plugin.type", &self.plugin.plugin_type));
+        issues.extend(check_version("plugin.version", &self.plugin.version));
+        issues.extend(check_compatibility(&self.compatibility));
+        issues.extend(check_services("", &self.provides, &self.requires));
+        issues.extend(policy.check_service_ids("", &self.plugin.id, &self.provides));
+        let binary_names = std::iter::once(("binary.name".to_string(), Some(&self.binary.name)))
+            .chain(self.binary.platform.iter().map(|(platform, o)| {
+                (format!("binary.platform.{platform}.name"), o.name.as_ref())
+            }));
+        for (path, name) in binary_names {
+            issues.extend(name.and_then(|name| check_binary_name(&path, name)));
+        }
+        issues.extend(check_platforms(
+            "binary.checksums",
+            self.binary.checksums.keys(),
+        ));
+        issues.extend(check_platforms("binary.sizes", self.binary.sizes.keys()));
+        issues.extend(check_platforms(
+            "binary.platform",
+            self.binary.platform.keys(),
+        ));
+        let binary_paths = std::iter::once(("binary.path".to_string(), &self.binary.path)).chain(
+            self.binary
+                .platform
+                .iter()
+                .map(|(platform, o)| (format!("binary.platform.{platform}.path"), &o.path)),
+        );
+        for (path, dir) in binary_paths {
+            if let Some(dir) = dir.as_ref().filter(|d| !is_contained_path(d)) {
+                issues.push(ValidationIssue::error(
+                    path,
+                    format!("Binary path '{dir}' must be a relative path inside the plugin"),
+                ));
+            }
+        }
+        if let Some(abi) = &self.binary.abi_requirements {
+            issues.extend(abi.validate());
+        }
+        if let Some(cli) = &self.cli {
+            issues.extend(cli.check_tree());
+        }
+        issues.extend(self.check_type_sections());
+        issues.extend(self.check_capabilities());
+        issues.extend(self.check_hooks());
+        issues.extend(self.check_uninstall());
+        issues.extend(self.check_post_install_message());
+        issues.extend(self.check_migrations());
+        issues.extend(self.check_storage());
+        issues.extend(self.check_permissions());
+        issues.extend(self.check_messaging());
+        issues.extend(check_assets("", &self.assets));
+        issues.extend(self.check_resources());
+        issues.extend(self.check_health());
+        issues.extend(self.check_observability());
+        issues.extend(self.check_logging());
+        issues.extend(self.check_effective());
+        issues.extend(self.check_compat());
+        if let Some(license) = &self.plugin.license {
+            issues.extend(policy.license.check("plugin.license", license));
+        }
+        for (path, url) in [
+            ("plugin.homepage", &self.plugin.homepage),
+            ("plugin.repository", &self.plugin.repository),
+        ] {
+            if let Some(url) = url {
+                issues.extend(check_url(path, url, &policy.url_schemes));
+            }
+        }
+        issues
+    }
+
+    /// Check that capabilities reference services the plugin provides.
+    fn check_capabilities(&self) -> Vec<ValidationIssue> {
+        self.capabilities
+            .iter()
+            .enumerate()
+            .filter_map(|(i, cap)| {
+                let service = cap.implements.as_ref()?;
+                if self.provides.iter().any(|s| s.id == *service) {
+                    return None;
+                }
+                Some(ValidationIssue::error(
+                    format!("capabilities[{i}].implements"),
+                    format!(
+                        "Capability '{}' implements '{service}', which is not in provides",
+                        cap.protocol
+                    ),
+                ))
+            })
+            .collect()
+    }
+
+    /// Check that special-purpose plugin types carry their sections.
+    fn check_type_sections(&self) -> Vec<ValidationIssue> {
+        let (section, present) = match self.plugin.kind() {
+            PluginType::Translation => ("translation", self.translation.is_some()),
+            PluginType::Lang => ("language", self.language.is_some()),
+            PluginType::HivePlugin => ("hive", self.hive.is_some()),
+            PluginType::Theme => ("theme", self.theme.is_some()),
+            _ => return Vec::new(),
+        };
+
+        if present {
+            Vec::new()
+        } else if section == "theme" {
+            // [theme] is newer than theme plugins, so a missing one only warns
+            vec![ValidationIssue::warning(
+                section,
+                "Theme plugins should have a [theme] section",
+            )]
+        } else {
+            vec![ValidationIssue::error(
+                section,
+                format!(
+                    "Plugins of type '{}' require a [{section}] section",
+                    self.plugin.plugin_type
+                ),
+            )]
+        }
+    }
+
+    /// Check the plugin ID against a vendor namespace policy.
+    pub fn check_id_policy(
+        &self,
+        policy: &IdPolicy,
+        publisher: Option<&str>,
+    ) -> Vec<ValidationIssue> {
+        policy.check("plugin.id", &self.plugin.id, publisher)
+    }
+}
+
+impl PackageManifest {
+    /// Validate the manifest with the default policy.
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        self.validate_with(&ValidationPolicy::default())
+    }
+
+    /// Validate the manifest with a custom policy.
+    pub fn validate_with(&self, policy: &ValidationPolicy) -> Vec<ValidationIssue> {
+        let mut issues = policy.check_id("package.id", &self.package.id);
+        issues.extend(check_version("package.version", &self.package.version));
+        issues.extend(check_compatibility(&self.compatibility));
+        issues.extend(check_platforms(
+            "binary.checksums",
+            self.binary.checksums.keys(),
+        ));
+        issues.extend(check_platforms("binary.sizes", self.binary.sizes.keys()));
+        if let Some(license) = &self.package.license {
+            issues.extend(policy.license.check("package.license", license));
+        }
+        for (path, url) in [
+            ("package.homepage", &self.package.homepage),
+            ("package.repository", &self.package.repository),
+        ] {
+            if let Some(url) = url {
+                issues.extend(check_url(path, url, &policy.url_schemes));
+            }
+        }
+        for (i, plugin) in self.plugins.iter().enumerate() {
+            issues.extend(policy.check_id(&format!("plugins[{i}].id"), &plugin.id));
+            issues.extend(
+                policy.check_plugin_type(&format!("plugins[{i}].type"), &plugin.plugin_type),
+            );
+            if self.plugins[..i].iter().any(|p| p.id == plugin.id) {
+                issues.push(ValidationIssue::error(
+                    format!("plugins[{i}].id"),
+                    format!("Duplicate plugin ID '{}'", plugin.id),
+                ));
+            }
+            issues.extend(check_binary_name(
+                &format!("plugins[{i}].binary"),
+                &plugin.binary,
+            ));
+            for dep in &plugin.depends_on {
+                if !self.plugins.iter().any(|p| p.id == *dep) {
+                    issues.push(ValidationIssue::error(
+                        format!("plugins[{i}].depends_on"),
+                        format!("'{dep}' is not a plugin in this package"),
+                    ));
+                }
+            }
+            issues.extend(check_services(
+                &format!("plugins[{i}]."),
+                &plugin.provides,
+                &plugin.requires,
+            ));
+            if let Some(o) = &plugin.compatibility {
+                issues.extend(
+                    o.validate(&format!("plugins[{i}].compatibility"), &self.compatibility),
+                );
+            }
+            issues.extend(policy.check_service_ids(
+                &format!("plugins[{i}]."),
+                &plugin.id,
+                &plugin.provides,
+            ));
+            issues.extend(check_assets(&format!("plugins[{i}]."), &plugin.assets));
+        }
+        issues
+    }
+
+    /// Check the package ID and all contained plugin IDs against a vendor
+    /// namespace policy.
+    pub fn check_id_policy(
+        &self,
+        policy: &IdPolicy,
+        publisher: Option<&str>,
+    ) -> Vec<ValidationIssue> {
+        let mut issues = policy.check("package.id", &self.package.id, publisher);
+        for (i, plugin) in self.plugins.iter().enumerate() {
+            issues.extend(policy.check(&format!("plugins[{i}].id"), &plugin.id, publisher));
+        }
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::ThemeInfo;
+
+    #[test]
+    fn test_vendor_of() {
+        assert_eq!(vendor_of("adi.tasks"), Some("adi"));
+        assert_eq!(vendor_of("hive.runner.docker"), Some("hive"));
+        assert_eq!(vendor_of("tasks"), None);
+        assert_eq!(vendor_of(".tasks"), None);
+    }
+
+    #[test]
+    fn test_reserved_namespace() {
+        let policy = IdPolicy::default();
+        let issues = policy.check("plugin.id", "adi.tasks", Some("evil"));
+        assert!(has_errors(&issues));
+        let issues = policy.check("plugin.id", "hive.runner.docker", None);
+        assert!(has_errors(&issues));
+        assert!(policy
+            .check("plugin.id", "adi.tasks", Some("adi"))
+            .is_empty());
+        assert!(policy
+            .check("plugin.id", "hive.runner.docker", Some("adi"))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_first_party_keeps_vendor_match() {
+        let policy = IdPolicy::default();
+        let issues = policy.check("plugin.id", "acme.tool", Some("adi"));
+        assert!(has_errors(&issues));
+        assert!(issues[0].message.contains("does not match publisher 'adi'"));
+
+        let relaxed = IdPolicy {
+            require_publisher_match: false,
+            ..IdPolicy::default()
+        };
+        assert!(relaxed
+            .check("plugin.id", "acme.tool", Some("adi"))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_publisher_mismatch() {
+        let policy = IdPolicy::default();
+        let issues = policy.check("plugin.id", "acme.tool", Some("evil"));
+        assert!(has_errors(&issues));
+        assert!(policy
+            .check("plugin.id", "acme.tool", Some("acme"))
+            .is_empty());
+
+        // Unknown publisher can't be verified
+        let issues = policy.check("plugin.id", "acme.tool", None);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn test_blocklist_banned_and_reserved() {
+        let blocklist = Blocklist {
+            banned_ids: vec!["acme.malware".into(), "spam.*".into()],
+            ..Blocklist::default()
+        };
+        let issues =
+            blocklist.validate_against_blocklist(&["acme.malware", "spam.anything", "acme.system"]);
+        assert_eq!(issues.len(), 3);
+        assert_eq!(issues[1].path, "ids[1]");
+        assert!(blocklist
+            .validate_against_blocklist(&["acme.tool"])
+            .is_empty());
+    }
+
+    #[test]
+    fn test_blocklist_impersonation() {
+        let blocklist = Blocklist {
+            protected_ids: vec!["adi.tasks".into()],
+            ..Blocklist::default()
+        };
+        assert!(blocklist
+            .validate_against_blocklist(&["adi.tasks"])
+            .is_empty());
+        assert!(has_errors(
+            &blocklist.validate_against_blocklist(&["adi.ta5ks"])
+        ));
+        assert!(has_errors(
+            &blocklist.validate_against_blocklist(&["ADI.Task_s"])
+        ));
+    }
+
+    #[test]
+    fn test_package_id_policy() {
+        let toml = r#"
+[package]
+id = "acme.pack"
+name = "Pack"
+version = "1.0.0"
+
+[[plugins]]
+id = "acme.one"
+name = "One"
+type = "extension"
+binary = "one"
+
+[[plugins]]
+id = "adi.fake"
+name = "Fake"
+type = "extension"
+binary = "fake"
+"#;
+        let manifest = PackageManifest::from_toml(toml).unwrap();
+        let issues = manifest.check_id_policy(&IdPolicy::default(), Some("acme"));
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].path, "plugins[1].id");
+    }
+
+    #[test]
+    fn test_id_constraints_default() {
+        let constraints = IdConstraints::default();
+        assert!(constraints.check("plugin.id", "adi.tasks").is_empty());
+        assert!(constraints
+            .check("plugin.id", "adi.workflow.en-US")
+            .is_empty());
+        assert!(has_errors(&constraints.check("plugin.id", "tasks")));
+        assert!(has_errors(&constraints.check("plugin.id", "adi..tasks")));
+        assert!(has_errors(&constraints.check("plugin.id", "adi.ta sks")));
+    }
+
+    #[test]
+    fn test_id_constraints_custom() {
+        let constraints = IdConstraints {
+            max_length: 12,
+            max_segments: 2,
+            allow_uppercase: false,
+            allowed_punctuation: Vec::new(),
+            ..IdConstraints::default()
+        };
+        assert!(constraints.check("plugin.id", "acme.tool").is_empty());
+        assert!(has_errors(&constraints.check("plugin.id", "acme.my-tool")));
+        assert!(has_errors(&constraints.check("plugin.id", "acme.Tool")));
+        assert!(has_errors(&constraints.check("plugin.id", "acme.tool.sub")));
+        assert!(has_errors(
+            &constraints.check("plugin.id", "acme.toolbox12")
+        ));
+    }
+
+    #[test]
+    fn test_validate_with_policy() {
+        let toml = r#"
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.0.0"
+type = "extension"
+"#;
+        let manifest = PluginManifest::from_toml(toml).unwrap();
+        assert!(manifest.validate().is_empty());
+
+        let policy = ValidationPolicy {
+            namespace: Some(IdPolicy::default()),
+            publisher: Some("other".into()),
+            ..ValidationPolicy::default()
+        };
+        assert!(has_errors(&manifest.validate_with(&policy)));
+    }
+
+    #[test]
+    fn test_check_url() {
+        let schemes = ValidationPolicy::default().url_schemes;
+        assert!(check_url("plugin.homepage", "https://adi.dev/plugins", &schemes).is_empty());
+        assert!(check_url("plugin.homepage", "http://localhost:8080", &schemes).is_empty());
+        assert!(has_errors(&check_url(
+            "plugin.homepage",
+            "adi.dev",
+            &schemes
+        )));
+        assert!(has_errors(&check_url(
+            "plugin.homepage",
+            "https://",
+            &schemes
+        )));
+        assert!(has_errors(&check_url(
+            "plugin.homepage",
+            "https://adi dev",
+            &schemes
+        )));
+        assert!(has_errors(&check_url(
+            "plugin.homepage",
+            "ftp://adi.dev",
+            &schemes
+        )));
+    }
+
+    #[test]
+    fn test_url_fields_validated() {
+        let toml = r#"
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.0.0"
+type = "extension"
+homepage = "https://acme.dev"
+repository = "git://github.com/acme/tool"
+"#;
+        let manifest = PluginManifest::from_toml(toml).unwrap();
+        let issues = manifest.validate();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].path, "plugin.repository");
+
+        let mut policy = ValidationPolicy::default();
+        policy.url_schemes.push("git".into());
+        assert!(manifest.validate_with(&policy).is_empty());
+    }
+
+    #[test]
+    fn test_unknown_plugin_type() {
+        let toml = r#"
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.0.0"
+type = "extenson"
+"#;
+        let manifest = PluginManifest::from_toml(toml).unwrap();
+        let issues = manifest.validate();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, Severity::Warning);
+
+        let policy = ValidationPolicy {
+            reject_unknown_types: true,
+            ..ValidationPolicy::default()
+        };
+        assert!(has_errors(&manifest.validate_with(&policy)));
+
+        let policy = ValidationPolicy {
+            plugin_types: PluginTypeRegistry::builtin().with_type("extenson"),
+            reject_unknown_types: true,
+            ..ValidationPolicy::default()
+        };
+        assert!(manifest.validate_with(&policy).is_empty());
+    }
+
+    #[test]
+    fn test_type_specific_sections() {
+        let toml = r#"
+[plugin]
+id = "adi.lang.rust"
+name = "Rust"
+version = "1.0.0"
+type = "lang"
+"#;
+        let manifest = PluginManifest::from_toml(toml).unwrap();
+        let issues = manifest.validate();
+        assert!(has_errors(&issues));
+        assert_eq!(issues[0].path, "language");
+
+        let toml = r#"
+[plugin]
+id = "hive.runner.docker"
+name = "Docker Runner"
+version = "0.1.0"
+type = "hive-plugin"
+
+[hive]
+category = "runner"
+name = "docker"
+"#;
+        let manifest = PluginManifest::from_toml(toml).unwrap();
+        assert!(manifest.validate().is_empty());
+
+        let toml = r#"
+[plugin]
+id = "adi.theme-dark"
+name = "Dark"
+version = "1.0.0"
+type = "theme"
+"#;
+        let mut manifest = PluginManifest::from_toml(toml).unwrap();
+        let issues = manifest.validate();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].path, "theme");
+        assert_eq!(issues[0].severity, Severity::Warning);
+
+        manifest.theme = Some(ThemeInfo {
+            appearance: "dark".into(),
+            extends: None,
+        });
+        assert!(manifest.validate().is_empty());
+    }
+
+    #[test]
+    fn test_versions_and_platforms() {
+        let toml = r#"
+[plugin]
+id = "adi.tasks"
+name = "Tasks"
+version = "1.0"
+type = "extension"
+
+[compatibility]
+platforms = ["linux-x86_64", "macos-arm64"]
+
+[binary]
+name = ""
+"#;
+        let manifest = PluginManifest::from_toml(toml).unwrap();
+        let issues = manifest.validate();
+        let paths: Vec<_> = issues.iter().map(|i| i.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            ["plugin.version", "compatibility.platforms", "binary.name"]
+        );
+        assert_eq!(issues[1].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn test_binary_paths_contained() {
+        let toml = r#"
+[plugin]
+id = "adi.tasks"
+name = "Tasks"
+version = "1.0.0"
+type = "extension"
+
+[binary]
+name = "tasks"
+path = "../../../etc"
+
+[binary.platform.linux-x86_64]
+path = "/usr/lib"
+
+[binary.platform.darwin-aarch64]
+path = "lib/macos"
+"#;
+        let manifest = PluginManifest::from_toml(toml).unwrap();
+        let issues = manifest.validate();
+        assert!(issues.iter().all(|i| i.severity == Severity::Error));
+        let paths: Vec<_> = issues.into_iter().map(|i| i.path).collect();
+        assert_eq!(paths, ["binary.path", "binary.platform.linux-x86_64.path"]);
+    }
+
+    #[test]
+    fn test_binary_names_are_file_names() {
+        let toml = r#"
+[plugin]
+id = "adi.tasks"
+name = "Tasks"
+version = "1.0.0"
+type = "extension"
+
+[binary]
+name = "../../bin/sh"
+kind = "script"
+
+[binary.platform.linux-x86_64]
+name = "/bin/sh"
+
+[binary.platform.windows-x86_64]
+name = "..\\tasks"
+
+[binary.platform.darwin-aarch64]
+name = "tasks_macos"
+"#;
+        let manifest = PluginManifest::from_toml(toml).unwrap();
+        let paths: Vec<_> = manifest.validate().into_iter().map(|i| i.path).collect();
+        assert_eq!(
+            paths,
+            [
+                "binary.name",
+                "binary.platform.linux-x86_64.name",
+                "binary.platform.windows-x86_64.name"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_package_plugin_references() {
+        let toml = r#"
+[package]
+id = "adi.suite"
+name = "Suite"
+version = "1.0.0"
+
+[[plugins]]
+id = "adi.core"
+name = "Core"
+type = "extension"
+binary = "core"
+
+[[plugins]]
+id = "adi.core"
+name = "Core Again"
+type = "extension"
+binary = ""
+depends_on = ["adi.missing"]
+"#;
+        let manifest = PackageManifest::from_toml(toml).unwrap();
+        let paths: Vec<_> = manifest.validate().into_iter().map(|i| i.path).collect();
+        assert_eq!(
+            paths,
+            [
+                "plugins[1].id",
+                "plugins[1].binary",
+                "plugins[1].depends_on"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_service_id_namespace() {
+        let toml = r#"
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.0.0"
+type = "extension"
+
+[[provides]]
+id = "acme.tool.api"
+version = "1.0.0"
+
+[[provides]]
+id = "adi.core.tasks"
+version = "1.0.0"
+
+[[provides]]
+id = "shared.clipboard"
+version = "1.0.0"
+"#;
+        let manifest = PluginManifest::from_toml(toml).unwrap();
+        assert!(manifest.validate().is_empty());
+
+        let mut policy = ValidationPolicy {
+            scope_service_ids: true,
+            ..ValidationPolicy::default()
+        };
+        let paths: Vec<_> = manifest
+            .validate_with(&policy)
+            .into_iter()
+            .map(|i| i.path)
+            .collect();
+        assert_eq!(paths, ["provides[1].id", "provides[2].id"]);
+
+        policy.shared_service_namespaces = vec!["shared".to_string()];
+        let issues = manifest.validate_with(&policy);
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].message.contains("adi.core.tasks"));
+    }
+}
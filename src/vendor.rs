@@ -0,0 +1,147 @@
+//! Vendor allow-list for the plugins inside a package.
+//!
+//! A package may only ship plugins under its own vendor namespace, so
+//! `evil.pack` can't install a plugin claiming to be `acme.tool`:
+//!
+//! ```toml
+//! [package]
+//! id = "acme.suite"
+//!
+//! [[plugins]]
+//! id = "acme.tool"        # ok: same vendor
+//!
+//! [[plugins]]
+//! id = "other.tool"       # rejected unless the policy allows "other"
+//! ```
+//!
+//! `PackageManifest::expand_plugins_with()` is `expand_plugins()` with this
+//! check applied first.
+
+use std::collections::BTreeMap;
+
+use crate::error::ManifestError;
+use crate::package::PackageManifest;
+use crate::plugin::PluginManifest;
+use crate::validate::{ensure_valid, vendor_of, ValidationIssue};
+
+/// Which vendors a package may ship plugins for, besides its own.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VendorPolicy {
+    /// Vendors any package may ship plugins for (e.g., "community")
+    pub allowed_vendors: Vec<String>,
+
+    /// Additional vendors per package vendor (e.g., "acme" may also ship
+    /// "acmelabs" plugins)
+    pub affiliates: BTreeMap<String, Vec<String>>,
+}
+
+impl VendorPolicy {
+    /// Builder-style addition of a vendor any package may use.
+    pub fn allow_vendor(mut self, vendor: impl Into<String>) -> Self {
+        self.allowed_vendors.push(vendor.into());
+        self
+    }
+
+    /// Builder-style addition of an affiliate vendor for a package vendor.
+    pub fn affiliate(mut self, vendor: impl Into<String>, affiliate: impl Into<String>) -> Self {
+        self.affiliates
+            .entry(vendor.into())
+            .or_default()
+            .push(affiliate.into());
+        self
+    }
+
+    /// Check whether a package from `package_vendor` may ship a plugin
+    /// under `plugin_vendor`.
+    pub fn allows(&self, package_vendor: &str, plugin_vendor: &str) -> bool {
+        package_vendor == plugin_vendor
+            || self.allowed_vendors.iter().any(|v| v == plugin_vendor)
+            || self
+                .affiliates
+                .get(package_vendor)
+                .is_some_and(|a| a.iter().any(|v| v == plugin_vendor))
+    }
+}
+
+impl PackageManifest {
+    /// Check that every plugin ID uses a vendor the package may ship.
+    ///
+    /// IDs without a vendor prefix are left to `validate()`.
+    pub fn check_plugin_vendors(&self, policy: &VendorPolicy) -> Vec<ValidationIssue> {
+        let Some(package_vendor) = vendor_of(&self.package.id) else {
+            return Vec::new();
+        };
+        self.plugins
+            .iter()
+            .enumerate()
+            .filter_map(|(i, plugin)| {
+                let vendor = vendor_of(&plugin.id)?;
+                if policy.allows(package_vendor, vendor) {
+                    return None;
+                }
+                Some(ValidationIssue::error(
+                    format!("plugins[{i}].id"),
+                    format!(
+                        "Plugin '{}' uses vendor '{vendor}', but package '{}' may only ship '{package_vendor}' plugins",
+                        plugin.id, self.package.id
+                    ),
+                ))
+            })
+            .collect()
+    }
+
+    /// Expand into plugin manifests, failing if any plugin uses a vendor
+    /// the policy doesn't allow for this package.
+    pub fn expand_plugins_with(
+        &self,
+        policy: &VendorPolicy,
+    ) -> Result<Vec<PluginManifest>, ManifestError> {
+        ensure_valid(&self.check_plugin_vendors(policy))?;
+        Ok(self.expand_plugins())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PACKAGE: &str = r#"
+[package]
+id = "acme.suite"
+name = "Suite"
+version = "1.0.0"
+
+[[plugins]]
+id = "acme.tool"
+name = "Tool"
+type = "extension"
+binary = "tool"
+
+[[plugins]]
+id = "acmelabs.preview"
+name = "Preview"
+type = "extension"
+binary = "preview"
+"#;
+
+    #[test]
+    fn test_plugin_vendors() {
+        let package = PackageManifest::from_toml(PACKAGE).unwrap();
+
+        let strict = VendorPolicy::default();
+        let issues = package.check_plugin_vendors(&strict);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].path, "plugins[1].id");
+        assert!(matches!(
+            package.expand_plugins_with(&strict),
+            Err(ManifestError::InvalidFormat(_))
+        ));
+
+        let affiliated = VendorPolicy::default().affiliate("acme", "acmelabs");
+        assert_eq!(package.expand_plugins_with(&affiliated).unwrap().len(), 2);
+        assert!(VendorPolicy::default()
+            .allow_vendor("acmelabs")
+            .allows("other", "acmelabs"));
+        assert!(!affiliated.allows("other", "acmelabs"));
+    }
+}
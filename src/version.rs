@@ -0,0 +1,352 @@
+//! Semantic versions and version ranges.
+//!
+//! Manifest version fields are plain strings; `Version` and `VersionRange`
+//! parse them with semver rules. Errors name the offending field:
+//!
+//! ```
+//! use lib_plugin_manifest::{Version, VersionRange};
+//!
+//! let v: Version = "0.9.3".parse().unwrap();
+//! assert!(VersionRange::parse("^0.9").unwrap().matches(&v));
+//! assert!(v < Version::parse("0.10.0").unwrap());
+//! ```
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::ManifestError;
+use crate::plugin::{
+    CompatibilityInfo, PluginManifest, PluginMeta, ServiceDeclaration, ServiceRequirement,
+};
+
+/// A semantic version (e.g., "1.2.3", "2.0.0-beta.1").
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Version(semver::Version);
+
+impl Version {
+    /// Parse a version.
+    pub fn parse(s: &str) -> Result<Self, ManifestError> {
+        semver::Version::parse(s)
+            .map(Version)
+            .map_err(|e| ManifestError::InvalidVersion(format!("'{s}': {e}")))
+    }
+
+    /// Parse a version read from a manifest field, naming the field in errors.
+    pub fn parse_field(field: &str, s: &str) -> Result<Self, ManifestError> {
+        semver::Version::parse(s)
+            .map(Version)
+            .map_err(|e| ManifestError::InvalidVersion(format!("{field} '{s}': {e}")))
+    }
+
+    /// Check whether the version matches a range such as "^1.2", ">=0.8" or "~1.4.2".
+    pub fn satisfies(&self, range: &str) -> Result<bool, ManifestError> {
+        Ok(VersionRange::parse(range)?.matches(self))
+    }
+
+    /// Borrow the underlying semver version.
+    pub fn as_semver(&self) -> &semver::Version {
+        &self.0
+    }
+
+    /// Major version.
+    pub fn major(&self) -> u64 {
+        self.0.major
+    }
+
+    /// Minor version.
+    pub fn minor(&self) -> u64 {
+        self.0.minor
+    }
+
+    /// Patch version.
+    pub fn patch(&self) -> u64 {
+        self.0.patch
+    }
+
+    /// Check if this is a pre-release version.
+    pub fn is_prerelease(&self) -> bool {
+        !self.0.pre.is_empty()
+    }
+}
+
+impl FromStr for Version {
+    type Err = ManifestError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl From<semver::Version> for Version {
+    fn from(v: semver::Version) -> Self {
+        Version(v)
+    }
+}
+
+impl Serialize for Version {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Version {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Version::parse(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+/// A version range (e.g., "^1.2", ">=0.8, <2", "~1.4.2").
+///
+/// A bare version ("1.2.3") means "^1.2.3", as in Cargo.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionRange(semver::VersionReq);
+
+impl VersionRange {
+    /// Parse a range.
+    pub fn parse(s: &str) -> Result<Self, ManifestError> {
+        semver::VersionReq::parse(s)
+            .map(VersionRange)
+            .map_err(|e| ManifestError::InvalidVersion(format!("range '{s}': {e}")))
+    }
+
+    /// Check whether a version is in the range.
+    pub fn matches(&self, version: &Version) -> bool {
+        self.0.matches(&version.0)
+    }
+}
+
+impl FromStr for VersionRange {
+    type Err = ManifestError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for VersionRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl PluginMeta {
+    /// Parse `plugin.version`.
+    pub fn parsed_version(&self) -> Result<Version, ManifestError> {
+        Version::parse_field("plugin.version", &self.version)
+    }
+}
+
+impl ServiceDeclaration {
+    /// Parse the provided service version.
+    pub fn parsed_version(&self) -> Result<Version, ManifestError> {
+        Version::parse_field(&format!("provides.{}.version", self.id), &self.version)
+    }
+
+    /// Check whether this service satisfies a requirement.
+    ///
+    /// The IDs must match and the version must be caret-compatible with
+    /// `min_version` ("1.2.0" accepts 1.x from 1.2.0, "0.3.1" accepts
+    /// 0.3.x from 0.3.1). Pre-release versions only satisfy a requirement
+    /// naming a pre-release of the same major.minor.patch. Unparsable
+    /// versions never match.
+    pub fn satisfies(&self, req: &ServiceRequirement) -> bool {
+        self.id == req.id && version_satisfies(&self.version, req.min_version.as_deref())
+    }
+}
+
+/// Caret matching of a provided version against an optional minimum; the
+/// rule behind `ServiceDeclaration::satisfies()`.
+pub(crate) fn version_satisfies(version: &str, min_version: Option<&str>) -> bool {
+    let Ok(version) = Version::parse(version) else {
+        return false;
+    };
+    let range = match min_version {
+        Some(min) => match Version::parse(min) {
+            Ok(min) => VersionRange(semver::VersionReq {
+                comparators: vec![semver::Comparator {
+                    op: semver::Op::Caret,
+                    major: min.major(),
+                    minor: Some(min.minor()),
+                    patch: Some(min.patch()),
+                    pre: min.0.pre,
+                }],
+            }),
+            Err(_) => return false,
+        },
+        None => VersionRange(semver::VersionReq::STAR),
+    };
+    range.matches(&version)
+}
+
+impl ServiceRequirement {
+    /// Parse the minimum required version, if any.
+    pub fn parsed_min_version(&self) -> Result<Option<Version>, ManifestError> {
+        self.min_version
+            .as_deref()
+            .map(|v| Version::parse_field(&format!("requires.{}.min_version", self.id), v))
+            .transpose()
+    }
+}
+
+impl CompatibilityInfo {
+    /// Parse `min_host_version`, if declared.
+    pub fn parsed_min_host_version(&self) -> Result<Option<Version>, ManifestError> {
+        self.min_host_version
+            .as_deref()
+            .map(|v| Version::parse_field("compatibility.min_host_version", v))
+            .transpose()
+    }
+
+    /// Parse `max_host_version`, if declared.
+    pub fn parsed_max_host_version(&self) -> Result<Option<Version>, ManifestError> {
+        self.max_host_version
+            .as_deref()
+            .map(|v| Version::parse_field("compatibility.max_host_version", v))
+            .transpose()
+    }
+
+    /// Check whether a host version lies within `min_host_version` and
+    /// `max_host_version` (both inclusive).
+    pub fn satisfies_host(&self, host_version: &str) -> Result<bool, ManifestError> {
+        let host = Version::parse_field("host version", host_version)?;
+        Ok(self.compatibility_summary(&[host])?[0].compatible)
+    }
+
+    /// Whether each host version lies within `min_host_version` and
+    /// `max_host_version`, in the order given (e.g., for version-support
+    /// badges).
+    pub fn compatibility_summary(
+        &self,
+        host_versions: &[Version],
+    ) -> Result<Vec<HostSupport>, ManifestError> {
+        let min = self.parsed_min_host_version()?;
+        let max = self.parsed_max_host_version()?;
+        Ok(host_versions
+            .iter()
+            .map(|host| HostSupport {
+                host_version: host.clone(),
+                compatible: min.as_ref().is_none_or(|min| host >= min)
+                    && max.as_ref().is_none_or(|max| host <= max),
+            })
+            .collect())
+    }
+}
+
+impl PluginManifest {
+    /// Whether each host version is supported; see
+    /// `CompatibilityInfo::compatibility_summary()`.
+    pub fn compatibility_summary(
+        &self,
+        host_versions: &[Version],
+    ) -> Result<Vec<HostSupport>, ManifestError> {
+        self.compatibility.compatibility_summary(host_versions)
+    }
+}
+
+/// Whether a plugin supports one host version.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HostSupport {
+    /// Host version
+    pub host_version: Version,
+
+    /// Within the plugin's `min_host_version`/`max_host_version` range
+    pub compatible: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::{PluginManifest, ServiceSource};
+
+    #[test]
+    fn test_ranges() {
+        let v = Version::parse("1.4.2").unwrap();
+        assert!(v.satisfies("^1.2").unwrap());
+        assert!(v.satisfies(">=1.4.0").unwrap());
+        assert!(v.satisfies("~1.4").unwrap());
+        assert!(!v.satisfies("~1.3").unwrap());
+        assert!(!v.satisfies("1.5").unwrap());
+        assert!(Version::parse("2.0.0-beta.1").unwrap() < Version::parse("2.0.0").unwrap());
+        assert!(v.satisfies("not a range").is_err());
+    }
+
+    #[test]
+    fn test_satisfies_host() {
+        let manifest = PluginManifest::from_toml(
+            r#"
+[plugin]
+id = "acme.tool"
+name = "Tool"
+version = "1.0"
+type = "extension"
+
+[compatibility]
+min_host_version = "0.8.0"
+max_host_version = "0.9.5"
+"#,
+        )
+        .unwrap();
+
+        assert!(manifest.compatibility.satisfies_host("0.9.3").unwrap());
+        assert!(!manifest.compatibility.satisfies_host("0.7.9").unwrap());
+        assert!(!manifest.compatibility.satisfies_host("1.0.0").unwrap());
+
+        let hosts: Vec<Version> = ["0.7.0", "0.8.0", "0.9.5", "0.10.0"]
+            .iter()
+            .map(|v| v.parse().unwrap())
+            .collect();
+        let summary = manifest.compatibility_summary(&hosts).unwrap();
+        let compatible: Vec<bool> = summary.iter().map(|s| s.compatible).collect();
+        assert_eq!(compatible, [false, true, true, false]);
+        assert_eq!(summary[1].host_version.to_string(), "0.8.0");
+
+        let err = manifest.plugin.parsed_version().unwrap_err();
+        assert!(
+            matches!(&err, ManifestError::InvalidVersion(msg) if msg.starts_with("plugin.version"))
+        );
+    }
+
+    #[test]
+    fn test_service_satisfies() {
+        let service = |version: &str| ServiceDeclaration {
+            id: "search".to_string(),
+            version: version.to_string(),
+            description: String::new(),
+            deprecated: false,
+            replaced_by: None,
+        };
+        let req = |min_version: Option<&str>| ServiceRequirement {
+            id: "search".to_string(),
+            min_version: min_version.map(String::from),
+            optional: false,
+            provider: ServiceSource::Plugin,
+        };
+
+        assert!(service("1.4.0").satisfies(&req(Some("1.2.0"))));
+        assert!(!service("1.1.0").satisfies(&req(Some("1.2.0"))));
+        assert!(!service("2.0.0").satisfies(&req(Some("1.2.0"))));
+        assert!(service("0.3.4").satisfies(&req(Some("0.3.1"))));
+        assert!(!service("0.4.0").satisfies(&req(Some("0.3.1"))));
+        assert!(service("2.0.0").satisfies(&req(None)));
+
+        assert!(!service("2.0.0-beta.1").satisfies(&req(None)));
+        assert!(!service("2.0.0-beta.1").satisfies(&req(Some("1.0.0"))));
+        assert!(service("2.0.0-beta.2").satisfies(&req(Some("2.0.0-beta.1"))));
+        assert!(service("2.0.0").satisfies(&req(Some("2.0.0-beta.1"))));
+
+        assert!(!service("not a version").satisfies(&req(None)));
+        let other = ServiceRequirement {
+            id: "other".to_string(),
+            ..req(None)
+        };
+        assert!(!service("1.0.0").satisfies(&other));
+    }
+}